@@ -1,4 +1,16 @@
-import {init, Vector2, World} from "../builds/2d-deterministic/pkg";
+import {
+    ColliderDesc,
+    ConvexPolygon,
+    Cuboid,
+    init,
+    Polyline,
+    RigidBodyDesc,
+    ShapeType,
+    TilemapMode,
+    TriMesh,
+    Vector2,
+    World,
+} from "../builds/2d-deterministic/pkg";
 
 describe("2d/World", () => {
     let world: World;
@@ -20,4 +32,92 @@ describe("2d/World", () => {
     test("constructor", () => {
         expect(world.colliders.len()).toBe(0);
     });
+
+    test("polygons are triangulated and decomposed into convex parts", () => {
+        // An L-shaped polygon, made of three unit squares.
+        const vertices = new Float64Array([0, 0, 2, 0, 2, 1, 1, 1, 1, 2, 0, 2]);
+        // The signed area of the polygon with the given vertices.
+        const area = (points: ArrayLike<number>, indices: number[]) =>
+            indices.reduce((sum, i, k) => {
+                const j = indices[(k + 1) % indices.length];
+                return (
+                    sum +
+                    (points[2 * i] * points[2 * j + 1] -
+                        points[2 * j] * points[2 * i + 1]) /
+                        2.0
+                );
+            }, 0.0);
+
+        const mesh = TriMesh.fromPolygon(vertices);
+        expect(mesh.indices.length).toBe(12);
+        let meshArea = 0.0;
+        for (let i = 0; i < mesh.indices.length; i += 3) {
+            const triangle = Array.from(mesh.indices.subarray(i, i + 3));
+            // The triangles are counter-clockwise.
+            expect(area(vertices, triangle)).toBeGreaterThan(0.0);
+            meshArea += area(vertices, triangle);
+        }
+        expect(meshArea).toBeCloseTo(3.0);
+
+        const parts = ConvexPolygon.decomposePolygon(vertices);
+        expect(parts.length).toBeGreaterThanOrEqual(2);
+        expect(parts.length).toBeLessThanOrEqual(4);
+        let partsArea = 0.0;
+        for (const part of parts) {
+            const indices = Array.from(
+                {length: part.vertices.length / 2},
+                (_, i) => i,
+            );
+            partsArea += area(part.vertices, indices);
+            world.createCollider(new ColliderDesc(part));
+        }
+        expect(partsArea).toBeCloseTo(3.0);
+        expect(world.colliders.len()).toBe(parts.length);
+    });
+
+    test("tilemaps are built from merged colliders", () => {
+        // Three filled tiles on the first row, one on the second row.
+        const occupancy = new Uint8Array([1, 1, 1, 1, 0, 0]);
+        const tileSize = new Vector2(2.0, 1.0);
+
+        const cuboids = ColliderDesc.tilemap(
+            occupancy,
+            3,
+            2,
+            tileSize,
+            TilemapMode.Cuboids,
+        );
+        expect(cuboids.length).toBe(2);
+        expect((cuboids[0].shape as Cuboid).halfExtents).toEqual({
+            x: 3.0,
+            y: 0.5,
+        });
+        expect(cuboids[0].translation).toEqual({x: 3.0, y: 0.5});
+        expect((cuboids[1].shape as Cuboid).halfExtents).toEqual({
+            x: 1.0,
+            y: 0.5,
+        });
+        expect(cuboids[1].translation).toEqual({x: 1.0, y: 1.5});
+
+        // The outline of the L shape is made of six segments, one per side.
+        const outline = ColliderDesc.tilemap(occupancy, 3, 2, tileSize);
+        expect(outline.length).toBe(1);
+        expect(outline[0].shape.type).toBe(ShapeType.Polyline);
+        expect((outline[0].shape as Polyline).indices.length).toBe(12);
+
+        // A ball dropped on the long side of the outline rests on it.
+        world.createCollider(outline[0]);
+        const ball = world.createRigidBody(
+            RigidBodyDesc.dynamic().setTranslation(3.0, -2.0),
+        );
+        world.createCollider(ColliderDesc.ball(0.5), ball);
+        for (let i = 0; i < 120; i++) {
+            world.step();
+        }
+        expect(ball.translation().y).toBeCloseTo(-0.5, 1);
+
+        expect(
+            ColliderDesc.tilemap(new Uint8Array(6), 3, 2, tileSize),
+        ).toEqual([]);
+    });
 });
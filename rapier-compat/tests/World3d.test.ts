@@ -1,4 +1,20 @@
-import {init, Vector3, World} from "../builds/3d-deterministic/pkg";
+import {
+    ActiveEvents,
+    ActiveHooks,
+    CoefficientCombineRule,
+    ColliderDesc,
+    EventQueue,
+    init,
+    JointData,
+    Ray,
+    RevoluteImpulseJoint,
+    RigidBody,
+    RigidBodyDesc,
+    SolverFlags,
+    Vector3,
+    WheelShape,
+    World,
+} from "../builds/3d-deterministic/pkg";
 
 describe("3d/World", () => {
     let world: World;
@@ -20,4 +36,1546 @@ describe("3d/World", () => {
     test("constructor", () => {
         expect(world.colliders.len()).toBe(0);
     });
+
+    test("shape-cast vehicle wheels don't fall into gaps", () => {
+        // Two slabs with a gap narrower than the wheel under the chassis.
+        for (const x of [-5.05, 5.05]) {
+            world.createCollider(
+                ColliderDesc.cuboid(5.0, 0.5, 5.0).setTranslation(x, -0.5, 0.0),
+            );
+        }
+        const chassis = world.createRigidBody(
+            RigidBodyDesc.dynamic().setTranslation(0.0, 1.0, 0.0),
+        );
+        world.createCollider(ColliderDesc.cuboid(0.5, 0.1, 0.5), chassis);
+        world.updateSceneQueries();
+
+        const vehicle = world.createVehicleController(chassis);
+        vehicle.addWheel(
+            new Vector3(0.0, 0.0, 0.0),
+            new Vector3(0.0, -1.0, 0.0),
+            new Vector3(0.0, 0.0, 1.0),
+            0.8,
+            0.3,
+        );
+
+        vehicle.updateVehicle(1.0 / 60.0);
+        expect(vehicle.wheelIsInContact(0)).toBe(false);
+
+        vehicle.setWheelShape(WheelShape.Ball);
+        expect(vehicle.wheelShape()).toBe(WheelShape.Ball);
+        vehicle.updateVehicle(1.0 / 60.0);
+        expect(vehicle.wheelIsInContact(0)).toBe(true);
+        expect(vehicle.wheelSuspensionLength(0)).toBeLessThan(0.8);
+        expect(vehicle.wheelSuspensionForce(0)).toBeGreaterThan(0.0);
+
+        // The engine drives the chassis through the swept contact.
+        vehicle.setWheelEngineForce(0, 10.0);
+        vehicle.updateVehicle(1.0 / 60.0);
+        expect(vehicle.wheelForwardImpulse(0)).toBeGreaterThan(0.0);
+        world.removeVehicleController(vehicle);
+    });
+
+    test("rigid-body centers of mass", () => {
+        // Rotated by 180 degrees around the z axis.
+        const body = world.createRigidBody(
+            RigidBodyDesc.dynamic()
+                .setTranslation(1.0, 2.0, 3.0)
+                .setRotation({x: 0.0, y: 0.0, z: 1.0, w: 0.0}),
+        );
+        world.createCollider(
+            ColliderDesc.ball(0.5).setTranslation(1.0, 0.0, 0.0),
+            body,
+        );
+
+        const localCom = body.localCom();
+        expect(localCom.x).toBeCloseTo(1.0);
+        expect(localCom.y).toBeCloseTo(0.0);
+        expect(localCom.z).toBeCloseTo(0.0);
+
+        const worldCom = body.worldCom();
+        expect(worldCom.x).toBeCloseTo(0.0);
+        expect(worldCom.y).toBeCloseTo(2.0);
+        expect(worldCom.z).toBeCloseTo(3.0);
+    });
+
+    test("dynamics state round-trip", () => {
+        const body = world.createRigidBody(
+            RigidBodyDesc.dynamic().setLinvel(1.0, 2.0, 3.0),
+        );
+        for (let i = 0; i < 5; ++i) {
+            world.step();
+        }
+
+        const saved = world.saveDynamicsState();
+        const savedTranslation = body.translation();
+        const savedLinvel = body.linvel();
+        for (let i = 0; i < 10; ++i) {
+            world.step();
+        }
+        const expected = body.translation();
+
+        expect(world.restoreDynamicsState(saved)).toBe(true);
+        expect(body.translation()).toEqual(savedTranslation);
+        expect(body.linvel()).toEqual(savedLinvel);
+
+        // Resimulating from the restored state gives the same result.
+        for (let i = 0; i < 10; ++i) {
+            world.step();
+        }
+        expect(body.translation()).toEqual(expected);
+    });
+
+    test("dynamics states with invalid counts are rejected", () => {
+        world.createRigidBody(RigidBodyDesc.dynamic());
+        const saved = world.saveDynamicsState();
+
+        // Counts whose implied lengths overflow.
+        for (const counts of [
+            [2 ** 40, 0],
+            [0, 2 ** 40],
+            [2 ** 40, 2 ** 40],
+        ]) {
+            const data = new Float64Array(saved);
+            data.set(counts);
+            expect(world.restoreDynamicsState(data)).toBe(false);
+        }
+        expect(world.restoreDynamicsState(saved.subarray(1))).toBe(false);
+        expect(world.restoreDynamicsState(saved)).toBe(true);
+    });
+
+    test("compressed snapshot round-trip", () => {
+        for (let i = 0; i < 20; ++i) {
+            const body = world.createRigidBody(
+                RigidBodyDesc.dynamic().setTranslation(i * 2.0, 1.0, -3.0),
+            );
+            world.createCollider(ColliderDesc.ball(0.5), body);
+        }
+        world.step();
+
+        const snapshot = world.takeSnapshot();
+        const compressed = world.takeSnapshot(true);
+        expect(compressed.length).toBeLessThan(snapshot.length);
+
+        const restored = World.restoreSnapshot(compressed, true);
+        expect(restored.bodies.len()).toBe(world.bodies.len());
+        expect(restored.colliders.len()).toBe(world.colliders.len());
+        world.bodies.forEach((body) => {
+            const restoredBody = restored.getRigidBody(body.handle);
+            expect(restoredBody.translation()).toEqual(body.translation());
+            expect(restoredBody.linvel()).toEqual(body.linvel());
+        });
+        restored.free();
+    });
+
+    test("partial substeps match full steps", () => {
+        const other = new World(new Vector3(0, 9.8, 0));
+        const bodies = [world, other].map((w) => {
+            w.numSubsteps = 4;
+            const body = w.createRigidBody(
+                RigidBodyDesc.dynamic().setLinvel(1.0, 0.0, 2.0),
+            );
+            w.createCollider(ColliderDesc.ball(0.5), body);
+            return body;
+        });
+
+        world.step();
+        other.stepSubsteps(1);
+        expect(other.substepIndex).toBe(1);
+        other.stepSubsteps(2);
+        expect(other.substepIndex).toBe(3);
+        other.stepSingleSubstep();
+        expect(other.substepIndex).toBe(0);
+        expect(bodies[1].translation()).toEqual(bodies[0].translation());
+        expect(bodies[1].linvel()).toEqual(bodies[0].linvel());
+
+        // A partial step is completed by the next call to `step`.
+        world.step();
+        world.step();
+        other.stepSubsteps(6);
+        expect(other.substepIndex).toBe(2);
+        other.step();
+        expect(other.substepIndex).toBe(0);
+        expect(bodies[1].translation()).toEqual(bodies[0].translation());
+        expect(bodies[1].linvel()).toEqual(bodies[0].linvel());
+        expect(other.simulationTime).toBe(world.simulationTime);
+        other.free();
+    });
+
+    test("substep tags of the collision events", () => {
+        world.numSubsteps = 4;
+        world.createCollider(
+            ColliderDesc.cuboid(10.0, 0.1, 10.0).setTranslation(0.0, -1.0, 0.0),
+        );
+        const body = world.createRigidBody(
+            RigidBodyDesc.dynamic().setLinvel(0.0, -10.0, 0.0),
+        );
+        world.createCollider(
+            ColliderDesc.ball(0.5).setActiveEvents(
+                ActiveEvents.COLLISION_EVENTS,
+            ),
+            body,
+        );
+        const eventQueue = new EventQueue(true);
+        eventQueue.setSubstepTagsEnabled(true);
+        const substepDt = world.timestep / 4;
+
+        let numEvents = 0;
+        for (let i = 0; i < 60 && numEvents === 0; ++i) {
+            const stepStart = world.simulationTime;
+            world.step(eventQueue);
+            eventQueue.drainCollisionEvents(
+                (handle1, handle2, started, substep, time) => {
+                    expect(started).toBe(true);
+                    expect(substep).toBeGreaterThanOrEqual(0);
+                    expect(substep).toBeLessThan(4);
+                    expect(time).toBeCloseTo(
+                        stepStart + (substep + 1) * substepDt,
+                        10,
+                    );
+                    numEvents += 1;
+                },
+            );
+        }
+
+        expect(numEvents).toBe(1);
+        eventQueue.free();
+    });
+
+    test("pair filter rules don't call the user hooks", () => {
+        const createBall = (x: number, memberships: number) => {
+            const body = world.createRigidBody(
+                RigidBodyDesc.dynamic().setTranslation(x, 0.0, 0.0),
+            );
+            return world.createCollider(
+                ColliderDesc.ball(0.5).setCollisionGroups(
+                    (memberships << 16) | 0xffff,
+                ),
+                body,
+            );
+        };
+        let numCalls = 0;
+        const hooks = {
+            filterContactPair: () => {
+                numCalls += 1;
+                return SolverFlags.COMPUTE_IMPULSE;
+            },
+            filterIntersectionPair: () => {
+                numCalls += 1;
+                return true;
+            },
+        };
+
+        const ball1 = createBall(0.0, 0x1);
+        const ball2 = createBall(0.5, 0x1);
+        world.addPairFilterRule(0x1, 0x1);
+        expect(ball1.activeHooks()).toBe(ActiveHooks.NONE);
+
+        world.step(undefined, hooks);
+        expect(numCalls).toBe(0);
+        let numManifolds = 0;
+        world.contactPair(ball1, ball2, () => (numManifolds += 1));
+        expect(numManifolds).toBe(0);
+
+        // The hooks are still called for the colliders where the user set them.
+        const ball3 = createBall(-0.5, 0x2);
+        ball3.setActiveHooks(ActiveHooks.FILTER_CONTACT_PAIRS);
+        world.step(undefined, hooks);
+        expect(numCalls).toBeGreaterThan(0);
+    });
+
+    test("collision pair exclusions don't call the user hooks", () => {
+        const [ball1, ball2] = [0.0, 0.5].map((x) => {
+            const body = world.createRigidBody(
+                RigidBodyDesc.dynamic().setTranslation(x, 0.0, 0.0),
+            );
+            return world.createCollider(ColliderDesc.ball(0.5), body);
+        });
+        let numCalls = 0;
+        const hooks = {
+            filterContactPair: () => {
+                numCalls += 1;
+                return SolverFlags.COMPUTE_IMPULSE;
+            },
+            filterIntersectionPair: () => {
+                numCalls += 1;
+                return true;
+            },
+        };
+
+        world.excludeCollisionPair(ball1, ball2);
+        expect(world.isCollisionPairExcluded(ball2, ball1)).toBe(true);
+        world.step(undefined, hooks);
+        let numManifolds = 0;
+        world.contactPair(ball1, ball2, () => (numManifolds += 1));
+        expect(numManifolds).toBe(0);
+
+        world.removeExclusion(ball1, ball2);
+        world.step(undefined, hooks);
+        world.contactPair(ball1, ball2, () => (numManifolds += 1));
+        expect(numManifolds).toBeGreaterThan(0);
+        expect(numCalls).toBe(0);
+    });
+
+    test("triangle materials don't show in the active hooks", () => {
+        const collider = world.createCollider(
+            ColliderDesc.trimesh(
+                new Float64Array([0, 0, 0, 1, 0, 0, 0, 0, 1]),
+                new Uint32Array([0, 1, 2]),
+            ),
+        );
+        const setMaterials = () =>
+            collider.setTriangleMaterials(
+                new Uint32Array([0]),
+                new Float64Array([0.5]),
+                new Float64Array([0.0]),
+            );
+
+        expect(setMaterials()).toBe(true);
+        expect(collider.activeHooks()).toBe(ActiveHooks.NONE);
+        collider.clearTriangleMaterials();
+        expect(collider.activeHooks()).toBe(ActiveHooks.NONE);
+
+        // The hooks set by the user are kept when the materials are removed.
+        expect(setMaterials()).toBe(true);
+        collider.setActiveHooks(ActiveHooks.FILTER_CONTACT_PAIRS);
+        expect(collider.activeHooks()).toBe(ActiveHooks.FILTER_CONTACT_PAIRS);
+        collider.clearTriangleMaterials();
+        expect(collider.activeHooks()).toBe(ActiveHooks.FILTER_CONTACT_PAIRS);
+    });
+
+    test("prefab instances map the handles", () => {
+        world.addPairFilterRule(0x1, 0x1);
+        const root = world.createRigidBody(RigidBodyDesc.dynamic());
+        const child = world.createRigidBody(
+            RigidBodyDesc.dynamic().setTranslation(1.0, 0.0, 0.0),
+        );
+        world.createCollider(
+            ColliderDesc.ball(0.5).setCollisionGroups(0x1ffff),
+            root,
+        );
+        world.createImpulseJoint(
+            JointData.spherical(
+                new Vector3(0.5, 0.0, 0.0),
+                new Vector3(-0.5, 0.0, 0.0),
+            ),
+            root,
+            child,
+            true,
+        );
+        const data = world.serializeBodyTree(root.handle);
+
+        const other = new World(new Vector3(0, 9.8, 0));
+        other.createRigidBody(RigidBodyDesc.fixed());
+        const mapping = other.instantiatePrefab(
+            data,
+            new Vector3(5.0, 0.0, 0.0),
+            {x: 0.0, y: 0.0, z: 0.0, w: 1.0},
+        );
+        expect(Array.from(mapping.oldBodies)).toEqual([
+            root.handle,
+            child.handle,
+        ]);
+        expect(mapping.oldImpulseJoints.length).toBe(1);
+        const [newRoot, newChild] = Array.from(mapping.newBodies).map(
+            (handle) => other.getRigidBody(handle),
+        );
+        expect(newRoot.translation()).toEqual({x: 5.0, y: 0.0, z: 0.0});
+        expect(newChild.translation()).toEqual({x: 6.0, y: 0.0, z: 0.0});
+        const joint = other.getImpulseJoint(mapping.newImpulseJoints[0]);
+        expect(joint.body1().handle).toBe(newRoot.handle);
+        expect(joint.body2().handle).toBe(newChild.handle);
+
+        // The hooks set natively for the pair filter rule aren't copied.
+        const collider = other.getCollider(mapping.newColliders[0]);
+        expect(collider.parent().handle).toBe(newRoot.handle);
+        expect(collider.activeHooks()).toBe(ActiveHooks.NONE);
+        other.free();
+    });
+
+    test("snapshots keep the triangle materials", () => {
+        const collider = world.createCollider(
+            ColliderDesc.trimesh(
+                new Float64Array([0, 0, 0, 1, 0, 0, 0, 0, 1, 1, 0, 1]),
+                new Uint32Array([0, 1, 2, 1, 3, 2]),
+            ),
+        );
+        collider.setTriangleMaterials(
+            new Uint32Array([1, 0]),
+            new Float64Array([0.5, 0.25]),
+            new Float64Array([0.0, 0.75]),
+        );
+
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        const restoredCollider = restored.getCollider(collider.handle);
+        expect(restoredCollider.triangleMaterial(0)).toBe(1);
+        expect(restoredCollider.triangleMaterial(1)).toBe(0);
+        expect(restoredCollider.activeHooks()).toBe(ActiveHooks.NONE);
+        restored.free();
+    });
+
+    test("contact pair ids persist across steps and snapshots", () => {
+        world.contactPairIdsEnabled = true;
+        const ground = world.createCollider(ColliderDesc.cuboid(5.0, 0.5, 5.0));
+        // The balls are pushed against the ground by the gravity along +y.
+        const createBall = (w: World, x: number) =>
+            w.createCollider(
+                ColliderDesc.ball(0.5),
+                w.createRigidBody(
+                    RigidBodyDesc.dynamic().setTranslation(x, -0.9, 0.0),
+                ),
+            );
+
+        const ball1 = createBall(world, 0.0);
+        world.step();
+        const id = world.contactPairId(ground, ball1);
+        expect(id).not.toBeNull();
+        world.step();
+        expect(world.contactPairId(ball1, ground)).toBe(id);
+
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        const restoredGround = restored.getCollider(ground.handle);
+        const restoredBall1 = restored.getCollider(ball1.handle);
+        expect(restored.contactPairIdsEnabled).toBe(true);
+        expect(restored.contactPairId(restoredGround, restoredBall1)).toBe(id);
+
+        // The new pairs get the same ids in both worlds.
+        const ball2 = createBall(world, 2.0);
+        const restoredBall2 = createBall(restored, 2.0);
+        world.step();
+        restored.step();
+        const id2 = world.contactPairId(ground, ball2);
+        expect(id2).not.toBeNull();
+        expect(id2).not.toBe(id);
+        expect(restored.contactPairId(restoredGround, restoredBall2)).toBe(id2);
+        expect(restored.contactPairId(restoredGround, restoredBall1)).toBe(id);
+        restored.free();
+    });
+
+    test("snapshots keep the impacts of the last step", () => {
+        world.impactTrackingEnabled = true;
+        const ground = world.createCollider(ColliderDesc.cuboid(5.0, 0.5, 5.0));
+        const ball = world.createCollider(
+            ColliderDesc.ball(0.5),
+            world.createRigidBody(
+                RigidBodyDesc.dynamic()
+                    .setTranslation(0.0, -1.0, 0.0)
+                    .setLinvel(0.0, 5.0, 0.0),
+            ),
+        );
+        const impacts = (w: World) => {
+            const res: number[][] = [];
+            w.forEachImpact((collider1, collider2, impulse, velocity) =>
+                res.push([
+                    collider1.handle,
+                    collider2.handle,
+                    impulse,
+                    velocity,
+                ]),
+            );
+            return res;
+        };
+
+        world.step();
+        expect(impacts(world).length).toBe(1);
+        expect(impacts(world)[0].slice(0, 2).sort()).toEqual(
+            [ground.handle, ball.handle].sort(),
+        );
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        expect(restored.impactTrackingEnabled).toBe(true);
+        expect(impacts(restored)).toEqual(impacts(world));
+
+        // The pair still touching isn't reported again.
+        world.step();
+        restored.step();
+        expect(impacts(restored)).toEqual([]);
+        expect(impacts(world)).toEqual([]);
+        expect(restored.getCollider(ball.handle).translation()).toEqual(
+            ball.translation(),
+        );
+        restored.free();
+    });
+
+    test("snapshots keep the pair filter rules", () => {
+        const [ball1, ball2] = [0.0, 0.5].map((x) =>
+            world.createCollider(
+                ColliderDesc.ball(0.5).setCollisionGroups(0x1ffff),
+                world.createRigidBody(
+                    RigidBodyDesc.dynamic().setTranslation(x, 0.0, 0.0),
+                ),
+            ),
+        );
+        const id = world.addPairFilterRule(0x1, 0x1);
+
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        const restoredBall1 = restored.getCollider(ball1.handle);
+        const restoredBall2 = restored.getCollider(ball2.handle);
+        expect(restoredBall1.activeHooks()).toBe(ActiveHooks.NONE);
+        restored.step();
+        let numManifolds = 0;
+        restored.contactPair(
+            restoredBall1,
+            restoredBall2,
+            () => (numManifolds += 1),
+        );
+        expect(numManifolds).toBe(0);
+
+        // The rule ids aren't reused.
+        expect(restored.addPairFilterRule(0x2, 0x2)).not.toBe(id);
+        restored.removePairFilterRule(id);
+        restored.step();
+        restored.contactPair(
+            restoredBall1,
+            restoredBall2,
+            () => (numManifolds += 1),
+        );
+        expect(numManifolds).toBeGreaterThan(0);
+        restored.free();
+    });
+
+    test("snapshots keep the collision pair exclusions", () => {
+        const [ball1, ball2] = [0.0, 0.5].map((x) =>
+            world.createCollider(
+                ColliderDesc.ball(0.5),
+                world.createRigidBody(
+                    RigidBodyDesc.dynamic().setTranslation(x, 0.0, 0.0),
+                ),
+            ),
+        );
+        world.excludeCollisionPair(ball1, ball2);
+
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        const restoredBall1 = restored.getCollider(ball1.handle);
+        const restoredBall2 = restored.getCollider(ball2.handle);
+        expect(
+            restored.isCollisionPairExcluded(restoredBall2, restoredBall1),
+        ).toBe(true);
+        expect(restoredBall1.activeHooks()).toBe(ActiveHooks.NONE);
+        restored.step();
+        let numManifolds = 0;
+        restored.contactPair(
+            restoredBall1,
+            restoredBall2,
+            () => (numManifolds += 1),
+        );
+        expect(numManifolds).toBe(0);
+        restored.free();
+    });
+
+    test("snapshots keep the pending warm-start resets", () => {
+        world.createCollider(ColliderDesc.cuboid(5.0, 0.5, 5.0));
+        const body = world.createRigidBody(
+            RigidBodyDesc.dynamic().setTranslation(0.0, -1.0, 0.0),
+        );
+        world.createCollider(ColliderDesc.cuboid(0.5, 0.5, 0.5), body);
+        for (let i = 0; i < 10; ++i) {
+            world.step();
+        }
+
+        world.resetWarmstart();
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        world.step();
+        restored.step();
+        const restoredBody = restored.getRigidBody(body.handle);
+        expect(restoredBody.linvel()).toEqual(body.linvel());
+        expect(restoredBody.translation()).toEqual(body.translation());
+        restored.free();
+    });
+
+    test("snapshots keep the number of substeps", () => {
+        world.numSubsteps = 4;
+
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        expect(restored.numSubsteps).toBe(4);
+        restored.step();
+        expect(restored.lastNumSubsteps).toBe(4);
+        restored.free();
+    });
+
+    test("automatic substepping rejects invalid parameters", () => {
+        for (const [ratio, maxSubsteps] of [
+            [0.0, 8],
+            [-0.5, 8],
+            [NaN, 8],
+            [0.5, 0],
+            [0.5, -1],
+            [0.5, NaN],
+            [0.5, 1.5],
+        ]) {
+            expect(world.enableAutoSubstepping(ratio, maxSubsteps)).toBe(false);
+            expect(world.autoSubsteppingEnabled).toBe(false);
+        }
+
+        expect(world.enableAutoSubstepping(0.5, 8)).toBe(true);
+        expect(world.autoSubsteppingEnabled).toBe(true);
+        expect(world.enableAutoSubstepping(0.0, 16)).toBe(false);
+        expect(world.autoSubsteppingEnabled).toBe(true);
+    });
+
+    test("snapshots keep the automatic substepping", () => {
+        world.enableAutoSubstepping(0.5, 8);
+        const body = world.createRigidBody(
+            RigidBodyDesc.dynamic().setLinvel(45.0, 0.0, 0.0),
+        );
+        world.createCollider(ColliderDesc.ball(0.5), body);
+        world.step();
+        expect(world.lastNumSubsteps).toBe(2);
+
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        expect(restored.autoSubsteppingEnabled).toBe(true);
+        expect(restored.lastNumSubsteps).toBe(2);
+        world.step();
+        restored.step();
+        expect(restored.lastNumSubsteps).toBe(world.lastNumSubsteps);
+        expect(restored.getRigidBody(body.handle).translation()).toEqual(
+            body.translation(),
+        );
+        restored.free();
+    });
+
+    test("snapshots keep the time scale", () => {
+        world.timeScale = 0.25;
+        const body = world.createRigidBody(
+            RigidBodyDesc.dynamic().setLinvel(1.0, 0.0, 0.0),
+        );
+
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        expect(restored.timeScale).toBe(0.25);
+        world.step();
+        restored.step();
+        expect(restored.getRigidBody(body.handle).translation()).toEqual(
+            body.translation(),
+        );
+        restored.free();
+    });
+
+    test("joint friction slows the joints down", () => {
+        const base = world.createRigidBody(RigidBodyDesc.fixed());
+        const wheel = world.createRigidBody(
+            RigidBodyDesc.dynamic().setAngvel(new Vector3(0.0, 0.0, 5.0)),
+        );
+        world.createCollider(ColliderDesc.ball(0.5), wheel);
+        const origin = new Vector3(0.0, 0.0, 0.0);
+        const joint = world.createImpulseJoint(
+            JointData.revolute(origin, origin, new Vector3(0.0, 0.0, 1.0)),
+            base,
+            wheel,
+            true,
+        ) as RevoluteImpulseJoint;
+        joint.setFriction(0.1);
+        expect(joint.friction()).toBe(0.1);
+
+        world.step();
+        const angvel = wheel.angvel().z;
+        expect(angvel).toBeLessThan(5.0);
+        expect(angvel).toBeGreaterThan(0.0);
+
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        const restoredJoint = restored.getImpulseJoint(
+            joint.handle,
+        ) as RevoluteImpulseJoint;
+        expect(restoredJoint.friction()).toBe(0.1);
+        world.step();
+        restored.step();
+        expect(wheel.angvel().z).toBeLessThan(angvel);
+        expect(restored.getRigidBody(wheel.handle).angvel()).toEqual(
+            wheel.angvel(),
+        );
+        restored.free();
+    });
+
+    test("snapshots keep the rigid-body collision pair exclusions", () => {
+        const [body1, body2] = [0.0, 0.5].map((x) => {
+            const body = world.createRigidBody(
+                RigidBodyDesc.dynamic().setTranslation(x, 0.0, 0.0),
+            );
+            world.createCollider(ColliderDesc.ball(0.5), body);
+            return body;
+        });
+        world.excludeBodyCollisionPair(body1, body2);
+
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        const restoredBody1 = restored.getRigidBody(body1.handle);
+        const restoredBody2 = restored.getRigidBody(body2.handle);
+        expect(
+            restored.isBodyCollisionPairExcluded(restoredBody2, restoredBody1),
+        ).toBe(true);
+        restored.step();
+        let numManifolds = 0;
+        restored.contactPair(
+            restoredBody1.collider(0),
+            restoredBody2.collider(0),
+            () => (numManifolds += 1),
+        );
+        expect(numManifolds).toBe(0);
+        restored.free();
+    });
+
+    test("snapshots keep the proximity events of the last step", () => {
+        world.proximityEventsEnabled = true;
+        // The AABBs of the balls overlap, but not the balls.
+        const ball1 = world.createCollider(ColliderDesc.ball(0.5));
+        const ball2 = world.createCollider(
+            ColliderDesc.ball(0.5),
+            world.createRigidBody(
+                RigidBodyDesc.dynamic()
+                    .setTranslation(0.8, 0.8, 0.0)
+                    .setGravityScale(0.0),
+            ),
+        );
+        const events = (w: World) => {
+            const res: [number, number, boolean][] = [];
+            w.forEachProximityEvent((collider1, collider2, started) =>
+                res.push([collider1.handle, collider2.handle, started]),
+            );
+            return res;
+        };
+
+        world.step();
+        const started = events(world);
+        expect(started.length).toBe(1);
+        expect(started[0].slice(0, 2).sort()).toEqual(
+            [ball1.handle, ball2.handle].sort(),
+        );
+        expect(started[0][2]).toBe(true);
+
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        expect(restored.proximityEventsEnabled).toBe(true);
+        expect(events(restored)).toEqual(started);
+
+        // The pair still overlapping isn't reported again.
+        restored.step();
+        expect(events(restored)).toEqual([]);
+        restored.free();
+    });
+
+    test("rolling resistance slows the rolling balls down", () => {
+        world.gravity = new Vector3(0.0, -9.81, 0.0);
+        // Two balls rolling on two grounds, only one of them resisting.
+        const ground = world.createCollider(
+            ColliderDesc.cuboid(50.0, 0.5, 2.0)
+                .setRollingResistance(0.05)
+                .setRollingResistanceCombineRule(CoefficientCombineRule.Max),
+        );
+        world.createCollider(
+            ColliderDesc.cuboid(50.0, 0.5, 2.0).setTranslation(0.0, 0.0, 10.0),
+        );
+        const createBall = (z: number) => {
+            const body = world.createRigidBody(
+                RigidBodyDesc.dynamic()
+                    .setTranslation(0.0, 1.0, z)
+                    .setLinvel(2.0, 0.0, 0.0)
+                    .setAngvel(new Vector3(0.0, 0.0, -4.0)),
+            );
+            world.createCollider(ColliderDesc.ball(0.5), body);
+            return body;
+        };
+        const resisted = createBall(0.0);
+        const free = createBall(10.0);
+
+        for (let i = 0; i < 60; i++) {
+            world.step();
+        }
+        expect(resisted.linvel().x).toBeLessThan(free.linvel().x - 0.1);
+
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        const restoredGround = restored.getCollider(ground.handle);
+        expect(restoredGround.rollingResistance()).toBeCloseTo(0.05);
+        expect(restoredGround.rollingResistanceCombineRule()).toBe(
+            CoefficientCombineRule.Max,
+        );
+
+        for (let i = 0; i < 30; i++) {
+            world.step();
+            restored.step();
+        }
+        expect(restored.getRigidBody(resisted.handle).linvel()).toEqual(
+            resisted.linvel(),
+        );
+        restored.free();
+    });
+
+    test("snapshots keep the local linear damping", () => {
+        const body = world.createRigidBody(
+            RigidBodyDesc.dynamic()
+                .setGravityScale(0.0)
+                .setLinvel(1.0, 0.0, 1.0),
+        );
+        world.createCollider(ColliderDesc.ball(0.5), body);
+        body.setLocalLinearDamping(new Vector3(0.0, 0.0, 5.0));
+
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        const restoredBody = restored.getRigidBody(body.handle);
+        expect(restoredBody.localLinearDamping()).toEqual(
+            new Vector3(0.0, 0.0, 5.0),
+        );
+
+        for (let i = 0; i < 10; i++) {
+            world.step();
+            restored.step();
+        }
+        expect(restoredBody.linvel().z).toBeLessThan(0.5);
+        expect(restoredBody.linvel()).toEqual(body.linvel());
+        restored.free();
+    });
+
+    test("snapshots keep the aerodynamic drag", () => {
+        const body = world.createRigidBody(RigidBodyDesc.dynamic());
+        const collider = world.createCollider(
+            ColliderDesc.cuboid(1.0, 0.01, 1.0).setDragCoefficient(1.2),
+            body,
+        );
+        world.airDensity = 2.0;
+
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        expect(restored.airDensity).toBe(2.0);
+        expect(restored.getCollider(collider.handle).dragCoefficient()).toBe(
+            1.2,
+        );
+
+        for (let i = 0; i < 30; i++) {
+            world.step();
+            restored.step();
+        }
+        // The drag keeps the plate well below the free-fall velocity.
+        expect(body.linvel().y).toBeLessThan(9.8 * 0.5 * 0.5);
+        expect(restored.getRigidBody(body.handle).linvel()).toEqual(
+            body.linvel(),
+        );
+        restored.free();
+    });
+
+    test("snapshots keep the forces added in the local frame", () => {
+        // Rotated by 90 degrees around z: the local x axis is the world y axis.
+        const body = world.createRigidBody(
+            RigidBodyDesc.dynamic().setRotation({
+                x: 0.0,
+                y: 0.0,
+                z: Math.SQRT1_2,
+                w: Math.SQRT1_2,
+            }),
+        );
+        world.createCollider(ColliderDesc.ball(0.5), body);
+        body.addForceLocal(new Vector3(2.0, 0.0, 0.0), true);
+        body.addTorqueLocal(new Vector3(0.0, 3.0, 0.0), true);
+
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        const restoredBody = restored.getRigidBody(body.handle);
+        const force = restoredBody.userForce();
+        const torque = restoredBody.userTorque();
+        expect(force.x).toBeCloseTo(0.0);
+        expect(force.y).toBeCloseTo(2.0);
+        expect(torque.x).toBeCloseTo(-3.0);
+        expect(torque.y).toBeCloseTo(0.0);
+        expect(force).toEqual(body.userForce());
+        expect(torque).toEqual(body.userTorque());
+        restored.free();
+    });
+
+    test("snapshots keep the gravity compensation", () => {
+        const body = world.createRigidBody(RigidBodyDesc.dynamic());
+        world.createCollider(ColliderDesc.ball(0.5), body);
+        body.setGravityCompensation(0.75);
+
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        const restoredBody = restored.getRigidBody(body.handle);
+        expect(restoredBody.gravityCompensation()).toBe(0.75);
+
+        for (let i = 0; i < 60; i++) {
+            world.step();
+            restored.step();
+        }
+        // Only a quarter of the gravity is left after one second.
+        expect(restoredBody.linvel().y).toBeCloseTo(9.8 * 0.25, 3);
+        expect(restoredBody.linvel()).toEqual(body.linvel());
+        restored.free();
+    });
+
+    test("upright constraints straighten the rigid-bodies up", () => {
+        // Tilted by 45 degrees around z.
+        const body = world.createRigidBody(
+            RigidBodyDesc.dynamic()
+                .setGravityScale(0.0)
+                .setRotation({
+                    x: 0.0,
+                    y: 0.0,
+                    z: Math.sin(Math.PI / 8.0),
+                    w: Math.cos(Math.PI / 8.0),
+                }),
+        );
+        world.createCollider(ColliderDesc.cuboid(0.5, 1.0, 0.5), body);
+        body.setUprightConstraint(
+            new Vector3(0.0, 1.0, 0.0),
+            new Vector3(0.0, 1.0, 0.0),
+            100.0,
+            20.0,
+        );
+        // The y component of the local y axis in the world frame.
+        const upY = (rot: {x: number; z: number}) =>
+            1.0 - 2.0 * (rot.x * rot.x + rot.z * rot.z);
+
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        const restoredBody = restored.getRigidBody(body.handle);
+        expect(restoredBody.hasUprightConstraint()).toBe(true);
+
+        for (let i = 0; i < 120; i++) {
+            world.step();
+            restored.step();
+        }
+        expect(upY(body.rotation())).toBeCloseTo(1.0, 3);
+        expect(restoredBody.rotation()).toEqual(body.rotation());
+
+        body.removeUprightConstraint();
+        expect(body.hasUprightConstraint()).toBe(false);
+        restored.free();
+    });
+
+    test("pose tracking drives the rigid-bodies to their target", () => {
+        const body = world.createRigidBody(
+            RigidBodyDesc.dynamic().setGravityScale(0.0),
+        );
+        world.createCollider(ColliderDesc.ball(0.5), body);
+        // A rotation of 90 degrees around y.
+        const target = {x: 0.0, y: Math.SQRT1_2, z: 0.0, w: Math.SQRT1_2};
+        body.setPoseTracking(
+            new Vector3(2.0, 0.0, 0.0),
+            target,
+            50.0,
+            15.0,
+            50.0,
+            15.0,
+        );
+
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        const restoredBody = restored.getRigidBody(body.handle);
+        expect(restoredBody.hasPoseTracking()).toBe(true);
+
+        for (let i = 0; i < 180; i++) {
+            world.step();
+            restored.step();
+        }
+        expect(body.translation().x).toBeCloseTo(2.0, 3);
+        expect(body.rotation().y).toBeCloseTo(target.y, 3);
+        expect(body.rotation().w).toBeCloseTo(target.w, 3);
+        expect(restoredBody.translation()).toEqual(body.translation());
+        expect(restoredBody.rotation()).toEqual(body.rotation());
+        restored.free();
+    });
+
+    test("drag constraints pull the grabbed point to the target", () => {
+        const body = world.createRigidBody(
+            RigidBodyDesc.dynamic().setGravityScale(0.0),
+        );
+        world.createCollider(ColliderDesc.cuboid(0.5, 0.5, 0.5), body);
+        // Grabs the top of the cube, and pulls it up.
+        body.setDragConstraint(
+            new Vector3(0.0, 0.5, 0.0),
+            new Vector3(0.0, 1.5, 0.0),
+            50.0,
+            15.0,
+        );
+
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        const restoredBody = restored.getRigidBody(body.handle);
+        expect(restoredBody.hasDragConstraint()).toBe(true);
+
+        for (let i = 0; i < 180; i++) {
+            world.step();
+            restored.step();
+        }
+        expect(body.translation().y).toBeCloseTo(1.0, 2);
+        expect(restoredBody.translation()).toEqual(body.translation());
+
+        // Moving the target moves the rigid-body along.
+        body.setDragTarget(new Vector3(1.0, 1.5, 0.0));
+        for (let i = 0; i < 180; i++) {
+            world.step();
+        }
+        // The cube may have swung around the grabbed point.
+        const pos = body.translation();
+        const dist = Math.hypot(pos.x - 1.0, pos.y - 1.5, pos.z);
+        expect(dist).toBeCloseTo(0.5, 2);
+        restored.free();
+    });
+
+    test("welded rigid-bodies move together until unwelded", () => {
+        world.gravity = new Vector3(0.0, 0.0, 0.0);
+        const createBox = (x: number) => {
+            const body = world.createRigidBody(
+                RigidBodyDesc.dynamic().setTranslation(x, 0.0, 0.0),
+            );
+            const collider = world.createCollider(
+                ColliderDesc.cuboid(0.5, 0.5, 0.5),
+                body,
+            );
+            return [body, collider] as const;
+        };
+        const [body1, collider1] = createBox(0.0);
+        const [body2, collider2] = createBox(2.0);
+
+        // Merged: the colliders of the second rigid-body move to the first one.
+        const merge = world.weldBodies(body1, body2, true);
+        expect(merge.merged).toBe(true);
+        expect(merge.joint).toBeNull();
+        expect(collider2.parent().handle).toBe(body1.handle);
+        expect(body2.isEnabled()).toBe(false);
+
+        body1.setLinvel(new Vector3(0.0, 0.0, 1.0), true);
+        for (let i = 0; i < 30; i++) {
+            world.step();
+        }
+        expect(body1.mass()).toBeCloseTo(2.0);
+        expect(collider2.translation().x).toBeCloseTo(2.0);
+        expect(collider2.translation().z).toBeCloseTo(0.5);
+
+        world.unweldBodies(merge);
+        expect(merge.merged).toBe(false);
+        expect(collider2.parent().handle).toBe(body2.handle);
+        expect(body2.isEnabled()).toBe(true);
+        expect(body2.translation().x).toBeCloseTo(2.0);
+        expect(body2.translation().z).toBeCloseTo(0.5);
+        expect(body2.linvel().z).toBeCloseTo(1.0);
+        world.step();
+        expect(body1.mass()).toBeCloseTo(1.0);
+
+        // Jointed: the rigid-bodies keep their relative pose.
+        const weld = world.weldBodies(body1, body2);
+        expect(weld.merged).toBe(false);
+        body1.setLinvel(new Vector3(0.0, 1.0, 0.0), true);
+        body2.setLinvel(new Vector3(0.0, 0.0, 0.0), true);
+        for (let i = 0; i < 60; i++) {
+            world.step();
+        }
+        const dx = body2.translation().x - body1.translation().x;
+        const dy = body2.translation().y - body1.translation().y;
+        expect(dx).toBeCloseTo(2.0, 2);
+        expect(dy).toBeCloseTo(0.0, 2);
+        expect(collider1.parent().handle).toBe(body1.handle);
+
+        world.unweldBodies(weld);
+        expect(world.getImpulseJoint(weld.joint.handle)).toBeNull();
+    });
+
+    test("trigger sets report the enter, stay, and exit of colliders", () => {
+        world.gravity = new Vector3(0.0, 0.0, 0.0);
+        const sensor = world.createCollider(
+            ColliderDesc.cuboid(1.0, 1.0, 1.0).setSensor(true),
+        );
+        const body = world.createRigidBody(
+            RigidBodyDesc.dynamic().setTranslation(5.0, 0.0, 0.0),
+        );
+        const ball = world.createCollider(ColliderDesc.ball(0.5), body);
+        const triggers = world.createTriggerSet();
+        triggers.addSensor(sensor);
+        expect(triggers.contains(sensor)).toBe(true);
+        expect(triggers.len()).toBe(1);
+
+        const state = () => ({
+            overlapping: Array.from(triggers.overlapping(sensor.handle)),
+            entered: Array.from(triggers.entered(sensor.handle)),
+            stayed: Array.from(triggers.stayed(sensor.handle)),
+            exited: Array.from(triggers.exited(sensor.handle)),
+        });
+        const none = {overlapping: [], entered: [], stayed: [], exited: []};
+
+        world.step();
+        expect(state()).toEqual(none);
+
+        body.setTranslation(new Vector3(0.0, 0.0, 0.0), true);
+        world.step();
+        expect(state()).toEqual({
+            ...none,
+            overlapping: [ball.handle],
+            entered: [ball.handle],
+        });
+
+        world.step();
+        expect(state()).toEqual({
+            ...none,
+            overlapping: [ball.handle],
+            stayed: [ball.handle],
+        });
+
+        body.setTranslation(new Vector3(5.0, 0.0, 0.0), true);
+        world.step();
+        expect(state()).toEqual({...none, exited: [ball.handle]});
+
+        world.step();
+        expect(state()).toEqual(none);
+
+        triggers.removeSensor(sensor);
+        expect(triggers.contains(sensor)).toBe(false);
+        world.removeTriggerSet(triggers);
+    });
+
+    test("intersection durations count the completed steps", () => {
+        world.gravity = new Vector3(0.0, 0.0, 0.0);
+        world.intersectionDurationsEnabled = true;
+        const sensor = world.createCollider(
+            ColliderDesc.cuboid(1.0, 1.0, 1.0).setSensor(true),
+        );
+        const ball = world.createCollider(
+            ColliderDesc.ball(0.5),
+            world.createRigidBody(RigidBodyDesc.dynamic()),
+        );
+        expect(world.intersectionDuration(sensor, ball)).toBeUndefined();
+
+        // The step where the intersection started doesn't count.
+        world.step();
+        expect(world.intersectionDuration(sensor, ball)).toBe(0.0);
+
+        world.step();
+        world.step();
+        expect(world.intersectionDuration(sensor, ball)).toBeCloseTo(
+            2.0 * world.timestep,
+        );
+    });
+
+    test("snapshots keep the intersection durations", () => {
+        world.gravity = new Vector3(0.0, 0.0, 0.0);
+        world.intersectionDurationsEnabled = true;
+        const sensor = world.createCollider(
+            ColliderDesc.cuboid(1.0, 1.0, 1.0).setSensor(true),
+        );
+        const ball = world.createCollider(
+            ColliderDesc.ball(0.5),
+            world.createRigidBody(RigidBodyDesc.dynamic()),
+        );
+        for (let i = 0; i < 3; i++) {
+            world.step();
+        }
+
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        expect(restored.intersectionDurationsEnabled).toBe(true);
+        const duration = (w: World) =>
+            w.intersectionDuration(
+                w.getCollider(sensor.handle),
+                w.getCollider(ball.handle),
+            );
+        expect(duration(restored)).toBe(duration(world));
+
+        world.step();
+        restored.step();
+        expect(duration(restored)).toBeCloseTo(3.0 * world.timestep);
+        expect(duration(restored)).toBe(duration(world));
+        restored.free();
+    });
+
+    test("snapshots keep the contact loads of the last step", () => {
+        world.contactLoadsEnabled = true;
+        const ground = world.createCollider(ColliderDesc.cuboid(5.0, 0.5, 5.0));
+        const body = world.createRigidBody(
+            RigidBodyDesc.dynamic().setTranslation(0.0, -0.99, 0.0),
+        );
+        const ball = world.createCollider(ColliderDesc.ball(0.5), body);
+        for (let i = 0; i < 60; i++) {
+            world.step();
+        }
+        // At rest, the ground supports the weight of the ball.
+        const weight = body.mass() * 9.8 * world.timestep;
+        expect(world.contactLoad(ground, ball)).toBeCloseTo(weight, 2);
+
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        expect(restored.contactLoadsEnabled).toBe(true);
+        expect(
+            restored.contactLoad(
+                restored.getCollider(ground.handle),
+                restored.getCollider(ball.handle),
+            ),
+        ).toBe(world.contactLoad(ground, ball));
+        expect(
+            restored.bodyContactLoad(restored.getRigidBody(body.handle)),
+        ).toBe(world.bodyContactLoad(body));
+        restored.free();
+    });
+
+    test("snapshots keep the simulation layers", () => {
+        const createBall = (x: number) => {
+            const body = world.createRigidBody(
+                RigidBodyDesc.dynamic().setTranslation(x, 0.0, 0.0),
+            );
+            world.createCollider(ColliderDesc.ball(0.5), body);
+            return body;
+        };
+        const active = createBall(0.0);
+        const paused = createBall(5.0);
+        paused.setSimulationLayers(0b10);
+        world.activeLayers = 0b01;
+
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        expect(restored.activeLayers).toBe(0b01);
+        const restoredActive = restored.getRigidBody(active.handle);
+        const restoredPaused = restored.getRigidBody(paused.handle);
+        expect(restoredPaused.simulationLayers()).toBe(0b10);
+
+        for (let i = 0; i < 10; i++) {
+            restored.step();
+        }
+        expect(restoredActive.translation().y).toBeGreaterThan(0.0);
+        expect(restoredPaused.translation().y).toBe(0.0);
+
+        restored.activeLayers = 0b11;
+        restored.step();
+        expect(restoredPaused.translation().y).toBeGreaterThan(0.0);
+        restored.free();
+    });
+
+    test("regions are extracted and inserted back", () => {
+        const createBall = (x: number) => {
+            const body = world.createRigidBody(
+                RigidBodyDesc.dynamic().setTranslation(x, 0.0, 0.0),
+            );
+            world.createCollider(ColliderDesc.ball(0.5), body);
+            return body;
+        };
+        const joint = (body1: RigidBody, body2: RigidBody) =>
+            world.createImpulseJoint(
+                JointData.spherical(
+                    new Vector3(0.5, 0.0, 0.0),
+                    new Vector3(-0.5, 0.0, 0.0),
+                ),
+                body1,
+                body2,
+                true,
+            );
+        const inside1 = createBall(0.0);
+        const inside2 = createBall(1.0);
+        const outside = createBall(10.0);
+        joint(inside1, inside2);
+        joint(inside2, outside);
+        const ground = world.createCollider(
+            ColliderDesc.cuboid(1.0, 0.1, 1.0).setTranslation(0.0, 1.5, 0.0),
+        );
+
+        const data = world.extractRegion(
+            new Vector3(-2.0, -2.0, -2.0),
+            new Vector3(2.0, 2.0, 2.0),
+        );
+        expect(world.bodies.len()).toBe(1);
+        expect(world.getRigidBody(outside.handle)).not.toBeNull();
+        expect(world.colliders.len()).toBe(1);
+        // The joint crossing the region boundary is removed.
+        expect(world.impulseJoints.len()).toBe(0);
+
+        const other = new World(new Vector3(0, 9.8, 0));
+        other.createRigidBody(RigidBodyDesc.fixed());
+        const mapping = other.insertRegion(data);
+        expect(Array.from(mapping.oldBodies).sort()).toEqual(
+            [inside1.handle, inside2.handle].sort(),
+        );
+        expect(mapping.newBodies.length).toBe(2);
+        expect(mapping.newColliders.length).toBe(3);
+        expect(Array.from(mapping.oldColliders)).toContain(ground.handle);
+        expect(mapping.newImpulseJoints.length).toBe(1);
+        expect(other.bodies.len()).toBe(3);
+
+        const newBody = (oldHandle: number) =>
+            other.getRigidBody(
+                mapping.newBodies[
+                    Array.from(mapping.oldBodies).indexOf(oldHandle)
+                ],
+            );
+        expect(newBody(inside2.handle).translation()).toEqual({
+            x: 1.0,
+            y: 0.0,
+            z: 0.0,
+        });
+        const newJoint = other.getImpulseJoint(mapping.newImpulseJoints[0]);
+        expect(newJoint.body1().handle).toBe(newBody(inside1.handle).handle);
+        expect(newJoint.body2().handle).toBe(newBody(inside2.handle).handle);
+        expect(other.insertRegion(new Uint8Array([1, 2, 3]))).toBeNull();
+        other.free();
+    });
+
+    test("snapshots keep the remote poses", () => {
+        const body = world.createRigidBody(
+            RigidBodyDesc.kinematicPositionBased(),
+        );
+        world.createCollider(ColliderDesc.ball(0.5), body);
+        const identity = {x: 0.0, y: 0.0, z: 0.0, w: 1.0};
+        body.pushRemotePose(0.0, new Vector3(0.0, 0.0, 0.0), identity);
+        body.pushRemotePose(1.0, new Vector3(10.0, 0.0, 0.0), identity);
+        world.remotePoseDelay = 0.0;
+        world.maxRemotePoseExtrapolation = 0.5;
+
+        // The rigid-body follows the interpolated poses.
+        for (let i = 0; i < 30; i++) {
+            world.step();
+        }
+        expect(body.translation().x).toBeCloseTo(5.0);
+
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        expect(restored.remotePoseTime).toBe(world.remotePoseTime);
+        expect(restored.remotePoseDelay).toBe(0.0);
+        expect(restored.maxRemotePoseExtrapolation).toBe(0.5);
+
+        for (let i = 0; i < 15; i++) {
+            world.step();
+            restored.step();
+        }
+        const restoredBody = restored.getRigidBody(body.handle);
+        expect(restoredBody.translation().x).toBeCloseTo(7.5);
+        expect(restoredBody.translation()).toEqual(body.translation());
+        restored.free();
+    });
+
+    test("snapshots keep the collider tags", () => {
+        const front = world.createCollider(
+            ColliderDesc.cuboid(0.5, 0.5, 0.5).setTags(0b01),
+        );
+        const back = world.createCollider(
+            ColliderDesc.cuboid(0.5, 0.5, 0.5).setTranslation(3.0, 0.0, 0.0),
+        );
+        back.setTags(0b10);
+        world.step();
+
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        expect(restored.getCollider(front.handle).tags()).toBe(0b01);
+        expect(restored.getCollider(back.handle).tags()).toBe(0b10);
+
+        const ray = new Ray(
+            new Vector3(-5.0, 0.0, 0.0),
+            new Vector3(1.0, 0.0, 0.0),
+        );
+        const castRay = (requireTags?: number, excludeTags?: number) =>
+            restored.castRay(
+                ray,
+                100.0,
+                true,
+                undefined,
+                undefined,
+                undefined,
+                undefined,
+                undefined,
+                requireTags,
+                excludeTags,
+            ).collider.handle;
+        expect(castRay()).toBe(front.handle);
+        expect(castRay(undefined, 0b01)).toBe(back.handle);
+        expect(castRay(0b10)).toBe(back.handle);
+        restored.free();
+    });
+
+    test("kinematic targets set between substeps are reached", () => {
+        world.numSubsteps = 4;
+        const body = world.createRigidBody(
+            RigidBodyDesc.kinematicPositionBased(),
+        );
+        body.setNextKinematicTranslation(new Vector3(4.0, 0.0, 0.0));
+
+        world.stepSubsteps(2);
+        expect(body.translation().x).toBeCloseTo(2.0);
+
+        // The new target is reached over the remaining substeps.
+        body.setNextKinematicTranslation(new Vector3(10.0, 0.0, 0.0));
+        world.stepSingleSubstep();
+        expect(body.translation().x).toBeCloseTo(6.0);
+        world.stepSingleSubstep();
+        expect(body.translation().x).toBeCloseTo(10.0);
+        expect(world.substepIndex).toBe(0);
+    });
+
+    test("snapshots keep the partially executed steps", () => {
+        world.numSubsteps = 4;
+        const kinematic = world.createRigidBody(
+            RigidBodyDesc.kinematicPositionBased(),
+        );
+        kinematic.setNextKinematicTranslation(new Vector3(4.0, 0.0, 0.0));
+        // A rigid-body frozen during the step.
+        const paused = world.createRigidBody(
+            RigidBodyDesc.dynamic()
+                .setTranslation(10.0, 0.0, 0.0)
+                .setLinvel(1.0, 0.0, 0.0)
+                .setGravityScale(0.0),
+        );
+        world.createCollider(ColliderDesc.ball(0.5), paused);
+        paused.setSimulationLayers(0b10);
+        world.activeLayers = 0b01;
+
+        world.stepSingleSubstep();
+        expect(kinematic.translation().x).toBeCloseTo(1.0);
+        const snapshot = world.takeSnapshot();
+
+        const restored = World.restoreSnapshot(snapshot);
+        expect(restored.substepIndex).toBe(1);
+        restored.step();
+        world.step();
+        expect(restored.substepIndex).toBe(0);
+        const restoredKinematic = restored.getRigidBody(kinematic.handle);
+        expect(restoredKinematic.translation().x).toBeCloseTo(4.0);
+        expect(restoredKinematic.translation()).toEqual(
+            kinematic.translation(),
+        );
+
+        // The frozen rigid-body gets its velocity back after the step.
+        const restoredPaused = restored.getRigidBody(paused.handle);
+        expect(restoredPaused.translation().x).toBe(10.0);
+        expect(restoredPaused.linvel()).toEqual(paused.linvel());
+        expect(restoredPaused.linvel().x).toBe(1.0);
+        restored.free();
+
+        // The rigid-bodies inserted from the snapshot aren't frozen.
+        const other = new World(new Vector3(0, 9.8, 0));
+        const mapping = other.mergeSnapshot(snapshot);
+        const inserted = other.getRigidBody(
+            mapping.newBodies[
+                Array.from(mapping.oldBodies).indexOf(paused.handle)
+            ],
+        );
+        expect(inserted.linvel().x).toBe(1.0);
+        other.step();
+        expect(inserted.translation().x).toBeGreaterThan(10.0);
+        other.free();
+    });
+
+    test("snapshots keep the simulation time", () => {
+        world.numSubsteps = 2;
+        for (let i = 0; i < 10; i++) {
+            world.step();
+        }
+        expect(world.simulationTime).toBeCloseTo(10.0 * world.timestep);
+
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        expect(restored.simulationTime).toBe(world.simulationTime);
+        world.step();
+        restored.step();
+        expect(restored.simulationTime).toBe(world.simulationTime);
+        restored.free();
+    });
+
+    test("kinematic rigid-bodies move evenly across the substeps", () => {
+        world.numSubsteps = 4;
+        const body = world.createRigidBody(
+            RigidBodyDesc.kinematicPositionBased(),
+        );
+        body.setNextKinematicTranslation(new Vector3(4.0, 0.0, 0.0));
+
+        world.stepSingleSubstep();
+        expect(body.translation().x).toBeCloseTo(1.0);
+        world.stepSingleSubstep();
+        expect(body.translation().x).toBeCloseTo(2.0);
+
+        // It goes on in a world restored in the middle of the step.
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+        const restoredBody = restored.getRigidBody(body.handle);
+        restored.stepSingleSubstep();
+        expect(restoredBody.translation().x).toBeCloseTo(3.0);
+        restored.stepSingleSubstep();
+        expect(restoredBody.translation().x).toBeCloseTo(4.0);
+        restored.free();
+    });
+
+    test("sources at the listener position aren’t occluded", () => {
+        world.createCollider(ColliderDesc.cuboid(1.0, 1.0, 1.0));
+        world.step();
+
+        // The listener is inside the cuboid.
+        const listener = new Vector3(0.0, 0.0, 0.0);
+        const sources = new Float64Array([0.0, 0.0, 0.0, 5.0, 0.0, 0.0]);
+        expect(Array.from(world.occlusion(listener, sources))).toEqual([0, 1]);
+    });
+
+    describe("network state", () => {
+        let other: World;
+
+        beforeEach(() => {
+            other = new World(new Vector3(0, 9.8, 0));
+        });
+
+        afterEach(() => {
+            other.free();
+        });
+
+        // Creates a rigid-body in both worlds, with the same handle.
+        function createBodies() {
+            const body = world.createRigidBody(
+                RigidBodyDesc.dynamic()
+                    .setTranslation(1.2345, -2.5, 3.75)
+                    .setRotation({x: 0.0, y: 0.6, z: 0.0, w: 0.8})
+                    .setLinvel(1.5, -0.25, 3.0),
+            );
+            const otherBody = other.createRigidBody(RigidBodyDesc.dynamic());
+            expect(otherBody.handle).toBe(body.handle);
+            return [body, otherBody];
+        }
+
+        test("round-trip within the quantization error", () => {
+            const [body, otherBody] = createBodies();
+            const data = world.encodeNetworkState([body.handle], 0.01);
+
+            expect(other.applyNetworkState(data)).toBe(true);
+
+            const t = otherBody.translation();
+            expect(t.x).toBeCloseTo(1.2345, 2);
+            expect(t.y).toBeCloseTo(-2.5, 2);
+            expect(t.z).toBeCloseTo(3.75, 2);
+            const r = otherBody.rotation();
+            expect(r.y).toBeCloseTo(0.6, 3);
+            expect(r.w).toBeCloseTo(0.8, 3);
+            const v = otherBody.linvel();
+            expect(v.x).toBeCloseTo(1.5, 2);
+            expect(v.y).toBeCloseTo(-0.25, 2);
+            expect(v.z).toBeCloseTo(3.0, 2);
+        });
+
+        test("smoothing", () => {
+            const [body, otherBody] = createBodies();
+            const data = world.encodeNetworkState([body.handle], 0.001);
+
+            expect(other.applyNetworkState(data, 0.5)).toBe(true);
+
+            const t = otherBody.translation();
+            expect(t.x).toBeCloseTo(1.2345 / 2, 2);
+            expect(t.y).toBeCloseTo(-2.5 / 2, 2);
+            expect(t.z).toBeCloseTo(3.75 / 2, 2);
+        });
+
+        test("angular velocities and sleep states are applied", () => {
+            const [body, otherBody] = createBodies();
+            body.setAngvel(new Vector3(0.5, -2.0, 1.0), true);
+            const sleeping = world.createRigidBody(RigidBodyDesc.dynamic());
+            const otherSleeping = other.createRigidBody(
+                RigidBodyDesc.dynamic(),
+            );
+            sleeping.sleep();
+            // The removed rigid-bodies are skipped.
+            const removed = world.createRigidBody(RigidBodyDesc.dynamic());
+            world.removeRigidBody(removed);
+            const data = world.encodeNetworkState([
+                body.handle,
+                removed.handle,
+                sleeping.handle,
+            ]);
+
+            expect(other.applyNetworkState(data)).toBe(true);
+
+            const w = otherBody.angvel();
+            expect(w.x).toBeCloseTo(0.5, 2);
+            expect(w.y).toBeCloseTo(-2.0, 2);
+            expect(w.z).toBeCloseTo(1.0, 2);
+            expect(otherBody.isSleeping()).toBe(false);
+            expect(otherSleeping.isSleeping()).toBe(true);
+        });
+
+        test("truncated buffers are rejected", () => {
+            const [body, otherBody] = createBodies();
+            const data = world.encodeNetworkState([body.handle]);
+
+            const truncated = data.slice(0, data.length - 1);
+            expect(other.applyNetworkState(truncated)).toBe(false);
+            expect(other.applyNetworkState(data.slice(0, 4))).toBe(false);
+            // Nothing is applied from a rejected buffer.
+            expect(otherBody.translation().x).toBe(0);
+        });
+
+        test("invalid precisions are rejected", () => {
+            const [body] = createBodies();
+
+            for (const precision of [0, -0.1, NaN, Infinity]) {
+                expect(
+                    world.encodeNetworkState([body.handle], precision),
+                ).toBeNull();
+            }
+        });
+    });
 });
@@ -95,10 +95,22 @@ export class KinematicCharacterController {
         rawVect.free();
     }
 
+    /**
+     * Does the character push the dynamic rigid-bodies it collides with?
+     */
     public applyImpulsesToDynamicBodies(): boolean {
         return this._applyImpulsesToDynamicBodies;
     }
 
+    /**
+     * Sets whether the character pushes the dynamic rigid-bodies it collides with.
+     *
+     * If enabled, `this.computeColliderMovement` applies to every dynamic rigid-body hit during
+     * the movement an impulse based on the character mass (see `this.setCharacterMass`), so
+     * the character can shove them instead of being stopped by them.
+     *
+     * @param enabled - If `true`, impulses are applied to the dynamic bodies hit by the character.
+     */
     public setApplyImpulsesToDynamicBodies(enabled: boolean) {
         this._applyImpulsesToDynamicBodies = enabled;
     }
@@ -330,6 +342,61 @@ export class KinematicCharacterController {
         rawTranslationDelta.free();
     }
 
+    /**
+     * Resizes the capsule shape of the given character collider, e.g., to crouch or stand up.
+     *
+     * The lowest point of the capsule along the up direction of this controller is preserved,
+     * so the character keeps its feet on the ground. If the resized capsule would overlap other
+     * colliders (e.g. when standing up under a low ceiling), the resize is blocked, unless
+     * `depenetrate` is `true`.
+     *
+     * @param collider - The character collider, which must have a capsule shape.
+     * @param halfHeight - The new half-height of the capsule, excluding its rounded caps.
+     * @param radius - The new radius of the capsule.
+     * @param depenetrate - If `true`, the resize is never blocked by overlaps. The translation
+     *                      needed to push the character out of the overlapping colliders is
+     *                      returned instead, and must be applied by the caller.
+     * @param filterFlags - Flags for excluding whole subsets of colliders from the obstacles taken into account.
+     * @param filterGroups - Groups for excluding colliders with incompatible collision groups from the obstacles
+     *                       taken into account.
+     * @param filterPredicate - Any collider for which this closure returns `false` will be excluded from the
+     *                          obstacles taken into account.
+     * @returns `null` if the collider isn't a capsule or if the resize was blocked, in which case the shape
+     *          is left unchanged. Otherwise, the translation to apply to the character to resolve the
+     *          overlaps (zero if there were none).
+     */
+    public resizeCapsule(
+        collider: Collider,
+        halfHeight: number,
+        radius: number,
+        depenetrate: boolean = false,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterPredicate?: (collider: Collider) => boolean,
+    ): Vector | null {
+        let translation = VectorOps.fromRaw(
+            this.raw.resizeCapsule(
+                this.broadPhase.raw,
+                this.narrowPhase.raw,
+                this.bodies.raw,
+                this.colliders.raw,
+                collider.handle,
+                halfHeight,
+                radius,
+                depenetrate,
+                filterFlags,
+                filterGroups,
+                this.colliders.castClosure(filterPredicate),
+            ),
+        );
+
+        if (!!translation) {
+            collider.clearShapeCache();
+        }
+
+        return translation;
+    }
+
     /**
      * The movement computed by the last call to `this.computeColliderMovement`.
      */
@@ -352,6 +419,20 @@ export class KinematicCharacterController {
         return this.raw.numComputedCollisions();
     }
 
+    /**
+     * All the collisions against obstacles detected along the path of the last call to
+     * `this.computeColliderMovement`, flattened into a single array.
+     *
+     * This avoids calling `this.computedCollision` for each collision. Each collision is
+     * described by consecutive values: the handle of the collider hit, the time of impact,
+     * the world-space normal of the collider hit, and the translation applied to the character
+     * before the collision. The normal and the translation have 2 components in 2D and 3 in 3D,
+     * i.e., there are 6 values per collision in 2D and 8 in 3D.
+     */
+    public computedCollisionsFlat(): Float64Array {
+        return this.raw.computedCollisionsFlat();
+    }
+
     /**
      * Returns the collision against one of the obstacles detected along the path of the last
      * call to `this.computeColliderMovement`.
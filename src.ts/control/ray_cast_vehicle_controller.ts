@@ -10,6 +10,24 @@ import {
 import {QueryFilterFlags} from "../pipeline";
 import {RigidBody, RigidBodyHandle, RigidBodySet} from "../dynamics";
 
+/**
+ * The shape swept along the suspension of the wheels of a vehicle to detect the ground.
+ */
+export enum WheelShape {
+    /**
+     * A single ray cast from the center of each wheel.
+     */
+    Ray = 0,
+    /**
+     * A ball with the radius of each wheel.
+     */
+    Ball = 1,
+    /**
+     * A cylinder with the radius of each wheel, aligned with its axle.
+     */
+    Cylinder = 2,
+}
+
 /**
  * A character controller to simulate vehicles using ray-casting for the wheels.
  */
@@ -115,6 +133,30 @@ export class DynamicRayCastVehicleController {
         this.raw.set_index_forward_axis(axis);
     }
 
+    /**
+     * The shape swept along the suspension of the wheels to detect the ground.
+     */
+    public wheelShape(): WheelShape {
+        return this.raw.wheel_shape();
+    }
+
+    /**
+     * Sets the shape swept along the suspension of the wheels to detect the ground
+     * (default: `WheelShape.Ray`).
+     *
+     * With a ball or a cylinder, the wheels are still ray-cast, but their shape is also swept
+     * along their suspension: if it touches the ground before the ray, e.g., because the ray falls
+     * into a gap narrower than the wheel or slips past a mesh edge, its contact replaces the
+     * ray-cast one and the suspension and friction forces are computed again from the swept
+     * contact point and normal.
+     *
+     * @param shape - The shape of the wheels.
+     * @param width - The width of the wheels, only used by `WheelShape.Cylinder`.
+     */
+    public setWheelShape(shape: WheelShape, width?: number) {
+        this.raw.set_wheel_shape(shape, width ?? 0.0);
+    }
+
     /**
      * Adds a new wheel attached to this vehicle.
      * @param chassisConnectionCs  - The position of the wheel relative to the chassis.
@@ -445,7 +487,8 @@ export class DynamicRayCastVehicleController {
     }
 
     /**
-     *  The (world-space) point hit by the wheel’s ray-cast for the i-th wheel.
+     *  The (world-space) point hit by the wheel’s ray-cast (or shape-cast, see
+     *  `this.setWheelShape`) for the i-th wheel.
      */
     public wheelContactPoint(i: number): Vector | null {
         return VectorOps.fromRaw(this.raw.wheel_contact_point_ws(i));
@@ -473,7 +516,38 @@ export class DynamicRayCastVehicleController {
     }
 
     /**
-     *  The collider hit by the ray-cast for the i-th wheel.
+     *  How much the suspension of the i-th wheel is compressed, i.e., its rest length minus its
+     *  current length.
+     */
+    public wheelCompression(i: number): number | null {
+        return this.raw.wheel_compression(i);
+    }
+
+    /**
+     *  The longitudinal slip ratio of the i-th wheel, computed by the last call to `this.updateVehicle`.
+     *
+     *  This is the difference between the rolling speed of the wheel and its forward speed relative to
+     *  the ground, divided by that forward speed. It is positive when the wheel spins faster than the
+     *  ground moves under it, and zero when the wheel isn’t in contact with the ground.
+     */
+    public wheelSlipRatio(i: number): number | null {
+        return this.raw.wheel_slip_ratio(i);
+    }
+
+    /**
+     *  The lateral slip angle of the i-th wheel, in radians, computed by the last call to
+     *  `this.updateVehicle`.
+     *
+     *  This is the angle between the rolling direction of the wheel and its velocity relative to the
+     *  ground. It is zero when the wheel isn’t in contact with the ground.
+     */
+    public wheelSlipAngle(i: number): number | null {
+        return this.raw.wheel_slip_angle(i);
+    }
+
+    /**
+     *  The collider hit by the ray-cast (or shape-cast, see `this.setWheelShape`) for the i-th
+     *  wheel.
      */
     public wheelGroundObject(i: number): Collider | null {
         return this.colliders.get(this.raw.wheel_ground_object(i));
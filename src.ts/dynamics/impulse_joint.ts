@@ -210,6 +210,63 @@ export class ImpulseJoint {
         rawPoint.free();
     }
 
+    /**
+     * Sets the rotation of the joint's local frame relative to the first rigid-body it is
+     * attached to, e.g., to change the joint axes at runtime.
+     */
+    public setLocalFrame1(newRot: Rotation) {
+        const rawRot = RotationOps.intoRaw(newRot);
+        this.rawSet.jointSetLocalFrame1(this.handle, rawRot);
+        rawRot.free();
+    }
+
+    /**
+     * Sets the rotation of the joint's local frame relative to the second rigid-body it is
+     * attached to, e.g., to change the joint axes at runtime.
+     */
+    public setLocalFrame2(newRot: Rotation) {
+        const rawRot = RotationOps.intoRaw(newRot);
+        this.rawSet.jointSetLocalFrame2(this.handle, rawRot);
+        rawRot.free();
+    }
+
+    /**
+     * Indicates if this joint is enabled.
+     */
+    public isEnabled(): boolean {
+        return this.rawSet.jointIsEnabled(this.handle);
+    }
+
+    /**
+     * Enables or disables this joint.
+     *
+     * A disabled joint doesn't constrain the rigid-bodies it is attached to but keeps its
+     * configuration, e.g., to blend in a ragdoll or detach a trailer temporarily.
+     *
+     * @param enabled - Set to `false` to suspend this joint.
+     */
+    public setEnabled(enabled: boolean) {
+        this.rawSet.jointSetEnabled(this.handle, enabled);
+    }
+
+    /**
+     * The axes constrained together through their norm by this joint.
+     */
+    public coupledAxes(): JointAxesMask {
+        return this.rawSet.jointCoupledAxes(this.handle);
+    }
+
+    /**
+     * Sets the axes constrained together through their norm instead of independently.
+     *
+     * The limits and motor of the coupled axes are configured on their first axis.
+     *
+     * @param axes - The coupled axes, combined with a bitwise OR.
+     */
+    public setCoupledAxes(axes: JointAxesMask) {
+        this.rawSet.jointSetCoupledAxes(this.handle, axes);
+    }
+
     /**
      * Controls whether contacts are computed between colliders attached
      * to the rigid-bodies linked by this joint.
@@ -264,6 +321,52 @@ export class UnitImpulseJoint extends ImpulseJoint {
         this.rawSet.jointSetLimits(this.handle, this.rawAxis(), min, max);
     }
 
+    /**
+     * The current position along the free axis of this joint, i.e., the displacement of a
+     * prismatic joint or the angle of a revolute joint, e.g., to know how open a door is.
+     */
+    public currentPosition(): number {
+        return this.rawSet.jointCurrentPosition(
+            this.bodySet.raw,
+            this.handle,
+            this.rawAxis(),
+        );
+    }
+
+    /**
+     * The current velocity along the free axis of this joint, i.e., the linear velocity of a
+     * prismatic joint or the angular velocity of a revolute joint.
+     */
+    public currentVelocity(): number {
+        return this.rawSet.jointCurrentVelocity(
+            this.bodySet.raw,
+            this.handle,
+            this.rawAxis(),
+        );
+    }
+
+    /**
+     * The dry friction force (or torque for a revolute joint) along the free axis of this
+     * joint.
+     */
+    public friction(): number {
+        return this.rawSet.jointFriction(this.handle, this.rawAxis());
+    }
+
+    /**
+     * Sets the dry friction along the free axis of this joint.
+     *
+     * The friction opposes the relative motion of the attached rigid-bodies with a force (or
+     * torque for a revolute joint) up to the given value, without fighting the motor of the
+     * joint, unlike a velocity motor with a zero target.
+     *
+     * @param friction - The maximum friction force or torque. Set it to 0 to disable the
+     *   friction.
+     */
+    public setFriction(friction: number) {
+        this.rawSet.jointSetFriction(this.handle, this.rawAxis(), friction);
+    }
+
     public configureMotorModel(model: MotorModel) {
         this.rawSet.jointConfigureMotorModel(
             this.handle,
@@ -316,7 +419,53 @@ export class FixedImpulseJoint extends ImpulseJoint {}
 
 export class RopeImpulseJoint extends ImpulseJoint {}
 
-export class SpringImpulseJoint extends ImpulseJoint {}
+/**
+ * A spring-damper joint pulling the anchors of its rigid-bodies toward a rest distance.
+ */
+export class SpringImpulseJoint extends ImpulseJoint {
+    /**
+     * The distance between the anchors at which this spring applies no force.
+     */
+    public restLength(): number {
+        return this.rawSet.jointMotorTargetPos(this.handle, RawJointAxis.LinX);
+    }
+
+    /**
+     * The stiffness of this spring.
+     */
+    public stiffness(): number {
+        return this.rawSet.jointMotorStiffness(this.handle, RawJointAxis.LinX);
+    }
+
+    /**
+     * The damping of this spring.
+     */
+    public damping(): number {
+        return this.rawSet.jointMotorDamping(this.handle, RawJointAxis.LinX);
+    }
+
+    /**
+     * Sets the rest length, stiffness, and damping of this spring, e.g., to reel a winch in
+     * or out.
+     *
+     * @param restLength - The distance between the anchors at which the spring applies no force.
+     * @param stiffness - The stiffness of the spring.
+     * @param damping - The damping of the spring.
+     */
+    public configureSpring(
+        restLength: number,
+        stiffness: number,
+        damping: number,
+    ) {
+        this.rawSet.jointConfigureMotorPosition(
+            this.handle,
+            RawJointAxis.LinX,
+            restLength,
+            stiffness,
+            damping,
+        );
+    }
+}
 
 export class PrismaticImpulseJoint extends UnitImpulseJoint {
     public rawAxis(): RawJointAxis {
@@ -367,6 +516,14 @@ export class JointData {
     limitsEnabled: boolean;
     limits: Array<number>;
     axesMask: JointAxesMask;
+    /**
+     * The axes constrained together through their norm instead of independently, e.g., the
+     * linear axes of spring and rope joints, which constrain the distance between the anchors.
+     *
+     * The limits and motor of the coupled axes are configured on their first axis. Coupling
+     * with a ratio between axes, like in screw joints, isn't supported by the physics engine.
+     */
+    coupledAxes?: JointAxesMask;
     stiffness: number;
     damping: number;
     length: number;
@@ -401,6 +558,21 @@ export class JointData {
         return res;
     }
 
+    /**
+     * Creates a new joint descriptor that builds a spring-damper joint.
+     *
+     * A spring joint pulls its anchors toward each other (or pushes them apart) so that their
+     * distance gets closer to the rest length, with a force proportional to the stiffness and
+     * damping, e.g., for soft attachments, winches, or bungees.
+     *
+     * @param rest_length - The distance between the anchors at which the spring applies no force.
+     * @param stiffness - The stiffness of the spring.
+     * @param damping - The damping of the spring.
+     * @param anchor1 - Point where the joint is attached on the first rigid-body affected by this joint. Expressed in the
+     *                  local-space of the rigid-body.
+     * @param anchor2 - Point where the joint is attached on the second rigid-body affected by this joint. Expressed in the
+     *                  local-space of the rigid-body.
+     */
     public static spring(
         rest_length: number,
         stiffness: number,
@@ -673,6 +845,10 @@ export class JointData {
             // #endif
         }
 
+        if (!!result && this.coupledAxes !== undefined) {
+            result.setCoupledAxes(this.coupledAxes);
+        }
+
         rawA1.free();
         rawA2.free();
 
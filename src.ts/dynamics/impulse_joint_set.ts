@@ -87,6 +87,33 @@ export class ImpulseJointSet {
         return joint;
     }
 
+    /**
+     * Attaches two rigid-bodies with a fixed joint at their current relative pose.
+     *
+     * The joint frame is located at the origin of the second rigid-body.
+     *
+     * @param bodies - The set of rigid-bodies containing the rigid-bodies to attach.
+     * @param parent1 - The handle of the first rigid-body.
+     * @param parent2 - The handle of the second rigid-body.
+     * @param wakeUp - Should the attached rigid-bodies be woken-up?
+     * @returns The created joint, or `null` if one of the rigid-bodies doesn’t exist.
+     */
+    public weld(
+        bodies: RigidBodySet,
+        parent1: RigidBodyHandle,
+        parent2: RigidBodyHandle,
+        wakeUp: boolean,
+    ): ImpulseJoint | null {
+        const handle = this.raw.weld(bodies.raw, parent1, parent2, wakeUp);
+        if (handle == null) {
+            return null;
+        }
+
+        let joint = ImpulseJoint.newTyped(this.raw, bodies, handle);
+        this.map.set(handle, joint);
+        return joint;
+    }
+
     /**
      * Remove a joint from this set.
      *
@@ -110,6 +137,21 @@ export class ImpulseJointSet {
         this.raw.forEachJointAttachedToRigidBody(handle, f);
     }
 
+    /**
+     * Gets the impulse joints attached to both given rigid-bodies.
+     *
+     * @param body1 - The handle of the first rigid-body.
+     * @param body2 - The handle of the second rigid-body.
+     */
+    public jointsBetween(
+        body1: RigidBodyHandle,
+        body2: RigidBodyHandle,
+    ): ImpulseJoint[] {
+        return Array.from(this.raw.jointsBetween(body1, body2), (handle) =>
+            this.get(handle),
+        );
+    }
+
     /**
      * Internal function, do not call directly.
      * @param handle
@@ -118,6 +160,14 @@ export class ImpulseJointSet {
         this.map.delete(handle);
     }
 
+    /**
+     * Internal function, do not call directly.
+     * @param handle
+     */
+    public mapInserted(handle: ImpulseJointHandle, bodies: RigidBodySet) {
+        this.map.set(handle, ImpulseJoint.newTyped(this.raw, bodies, handle));
+    }
+
     /**
      * The number of joints on this set.
      */
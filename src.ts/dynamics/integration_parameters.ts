@@ -32,6 +32,30 @@ export class IntegrationParameters {
         return this.raw.contact_erp;
     }
 
+    /**
+     * The Error Reduction Parameter applied to the joints, derived from
+     * `jointNaturalFrequency`, `jointDampingRatio` and the timestep length.
+     */
+    get jointErp(): number {
+        return this.raw.jointErp;
+    }
+
+    /**
+     * The natural frequency of the spring used to regularize the joints
+     * (default: `1.0e6`). Lower values make all the joints softer.
+     */
+    get jointNaturalFrequency(): number {
+        return this.raw.jointNaturalFrequency;
+    }
+
+    /**
+     * The damping ratio of the spring used to regularize the joints
+     * (default: `1.0`).
+     */
+    get jointDampingRatio(): number {
+        return this.raw.jointDampingRatio;
+    }
+
     get lengthUnit(): number {
         return this.raw.lengthUnit;
     }
@@ -90,6 +114,14 @@ export class IntegrationParameters {
         this.raw.contact_natural_frequency = value;
     }
 
+    set jointNaturalFrequency(value: number) {
+        this.raw.jointNaturalFrequency = value;
+    }
+
+    set jointDampingRatio(value: number) {
+        this.raw.jointDampingRatio = value;
+    }
+
     set lengthUnit(value: number) {
         this.raw.lengthUnit = value;
     }
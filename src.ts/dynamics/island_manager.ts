@@ -1,5 +1,43 @@
 import {RawIslandManager} from "../raw";
 import {RigidBodyHandle} from "./rigid_body";
+import {RigidBodySet} from "./rigid_body_set";
+import {ImpulseJointSet} from "./impulse_joint_set";
+import {MultibodyJointSet} from "./multibody_joint_set";
+import {ColliderSet, NarrowPhase} from "../geometry";
+
+/**
+ * A simulation island: a group of dynamic rigid-bodies connected by contacts or joints.
+ */
+export class Island {
+    /**
+     * The handles of the rigid-bodies of this island.
+     */
+    bodies: RigidBodyHandle[];
+    /**
+     * Are all the rigid-bodies of this island asleep?
+     */
+    sleeping: boolean;
+    /**
+     * The number of contacts given to the constraints solver for the rigid-bodies of this island.
+     */
+    numContacts: number;
+    /**
+     * The number of impulse and multibody joints attached to the rigid-bodies of this island.
+     */
+    numJoints: number;
+
+    constructor(
+        bodies: RigidBodyHandle[],
+        sleeping: boolean,
+        numContacts: number,
+        numJoints: number,
+    ) {
+        this.bodies = bodies;
+        this.sleeping = sleeping;
+        this.numContacts = numContacts;
+        this.numJoints = numJoints;
+    }
+}
 
 /**
  * The CCD solver responsible for resolving Continuous Collision Detection.
@@ -34,4 +72,41 @@ export class IslandManager {
     public forEachActiveRigidBodyHandle(f: (handle: RigidBodyHandle) => void) {
         this.raw.forEachActiveRigidBodyHandle(f);
     }
+
+    /**
+     * Computes the simulation islands, i.e., the groups of dynamic rigid-bodies connected by
+     * contacts or joints.
+     *
+     * This is meant for diagnostics, e.g., to find out why a pile of objects doesn't fall asleep,
+     * or which island dominates the solver time. Fixed and kinematic rigid-bodies don't connect
+     * islands together.
+     */
+    public computeIslands(
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        narrowPhase: NarrowPhase,
+        impulseJoints: ImpulseJointSet,
+        multibodyJoints: MultibodyJointSet,
+    ): Island[] {
+        let rawIslands = this.raw.computeIslands(
+            bodies.raw,
+            colliders.raw,
+            narrowPhase.raw,
+            impulseJoints.raw,
+            multibodyJoints.raw,
+        );
+        let result = [];
+        for (let i = 0; i < rawIslands.numIslands(); ++i) {
+            result.push(
+                new Island(
+                    Array.from(rawIslands.bodies(i)),
+                    rawIslands.isSleeping(i),
+                    rawIslands.numContacts(i),
+                    rawIslands.numJoints(i),
+                ),
+            );
+        }
+        rawIslands.free();
+        return result;
+    }
 }
@@ -1,3 +1,4 @@
+import {Rotation, RotationOps, Vector, VectorOps} from "../math";
 import {
     RawImpulseJointSet,
     RawJointAxis,
@@ -121,6 +122,50 @@ export class MultibodyJoint {
     //     return VectorOps.fromRaw(this.rawSet.jointAnchor2(this.handle));
     // }
 
+    /**
+     * Sets the position of the first anchor of this joint.
+     *
+     * The first anchor gives the position of the application point on the
+     * local frame of the first rigid-body it is attached to.
+     */
+    public setAnchor1(newPos: Vector) {
+        const rawPoint = VectorOps.intoRaw(newPos);
+        this.rawSet.jointSetAnchor1(this.handle, rawPoint);
+        rawPoint.free();
+    }
+
+    /**
+     * Sets the position of the second anchor of this joint.
+     *
+     * The second anchor gives the position of the application point on the
+     * local frame of the second rigid-body it is attached to.
+     */
+    public setAnchor2(newPos: Vector) {
+        const rawPoint = VectorOps.intoRaw(newPos);
+        this.rawSet.jointSetAnchor2(this.handle, rawPoint);
+        rawPoint.free();
+    }
+
+    /**
+     * Sets the rotation of the joint's local frame relative to the first rigid-body it is
+     * attached to, e.g., to change the joint axes at runtime.
+     */
+    public setLocalFrame1(newRot: Rotation) {
+        const rawRot = RotationOps.intoRaw(newRot);
+        this.rawSet.jointSetLocalFrame1(this.handle, rawRot);
+        rawRot.free();
+    }
+
+    /**
+     * Sets the rotation of the joint's local frame relative to the second rigid-body it is
+     * attached to, e.g., to change the joint axes at runtime.
+     */
+    public setLocalFrame2(newRot: Rotation) {
+        const rawRot = RotationOps.intoRaw(newRot);
+        this.rawSet.jointSetLocalFrame2(this.handle, rawRot);
+        rawRot.free();
+    }
+
     /**
      * Controls whether contacts are computed between colliders attached
      * to the rigid-bodies linked by this joint.
@@ -1,5 +1,6 @@
 import {RawMultibodyJointSet} from "../raw";
 import {Coarena} from "../coarena";
+import {Vector, VectorOps} from "../math";
 import {RigidBodySet} from "./rigid_body_set";
 import {
     MultibodyJoint,
@@ -146,6 +147,162 @@ export class MultibodyJointSet {
         this.raw.forEachJointAttachedToRigidBody(handle, f);
     }
 
+    /**
+     * The handle of the rigid-body at the root of the multibody containing the given joint.
+     *
+     * @param handle - The handle of any joint of the multibody.
+     */
+    public root(handle: MultibodyJointHandle): RigidBodyHandle | null {
+        return this.raw.multibodyRoot(handle) ?? null;
+    }
+
+    /**
+     * Makes the root of the multibody containing the given joint kinematic (position-based)
+     * or dynamic, while the rest of the multibody remains dynamic.
+     *
+     * @param bodies - The set of rigid-bodies.
+     * @param handle - The handle of any joint of the multibody.
+     * @param kinematic - `true` to animate the root from its next kinematic position.
+     */
+    public setRootKinematic(
+        bodies: RigidBodySet,
+        handle: MultibodyJointHandle,
+        kinematic: boolean,
+    ) {
+        this.raw.multibodySetRootKinematic(bodies.raw, handle, kinematic);
+    }
+
+    /**
+     * Indicates if the root of the multibody containing the given joint is kinematic.
+     *
+     * @param bodies - The set of rigid-bodies.
+     * @param handle - The handle of any joint of the multibody.
+     */
+    public isRootKinematic(
+        bodies: RigidBodySet,
+        handle: MultibodyJointHandle,
+    ): boolean {
+        return this.raw.multibodyIsRootKinematic(bodies.raw, handle);
+    }
+
+    /**
+     * Indicates if contacts are enabled between the links of the multibody containing the
+     * given joint.
+     *
+     * @param handle - The handle of any joint of the multibody.
+     */
+    public selfContactsEnabled(handle: MultibodyJointHandle): boolean {
+        return this.raw.multibodySelfContactsEnabled(handle);
+    }
+
+    /**
+     * Enables or disables the contacts between all the links of the multibody containing the
+     * given joint. Contacts between adjacent links are additionally controlled by
+     * `MultibodyJoint.setContactsEnabled`.
+     *
+     * @param handle - The handle of any joint of the multibody.
+     * @param enabled - `false` to prevent all the links from colliding with each other.
+     */
+    public setSelfContactsEnabled(
+        handle: MultibodyJointHandle,
+        enabled: boolean,
+    ) {
+        this.raw.multibodySetSelfContactsEnabled(handle, enabled);
+    }
+
+    /**
+     * The number of target positions of a pose of the multibody containing the given joint,
+     * i.e., the number of free coordinates of its joints, excluding its root.
+     *
+     * @param handle - The handle of any joint of the multibody.
+     */
+    public numPoseCoordinates(handle: MultibodyJointHandle): number {
+        return this.raw.multibodyNumPoseCoordinates(handle);
+    }
+
+    /**
+     * Configures the position motors of all the joints of the multibody containing the given
+     * joint at once, to drive it toward a target pose.
+     *
+     * @param bodies - The set of rigid-bodies, used to wake up the multibody.
+     * @param handle - The handle of any joint of the multibody.
+     * @param targets - The target positions, for each link after the root in the order of the
+     *   links, and for each free axis of its joint in the order of the axes (linear axes first).
+     * @param stiffness - The stiffness shared by all the motors.
+     * @param damping - The damping shared by all the motors.
+     * @param maxForce - The maximum force shared by all the motors.
+     * @returns `false`, without modifying any motor, if the number of target positions doesn't
+     *   match `numPoseCoordinates`.
+     */
+    public configurePoseTarget(
+        bodies: RigidBodySet,
+        handle: MultibodyJointHandle,
+        targets: ArrayLike<number>,
+        stiffness: number,
+        damping: number,
+        maxForce: number = Infinity,
+    ): boolean {
+        return this.raw.multibodyConfigurePoseTarget(
+            bodies.raw,
+            handle,
+            new Float64Array(targets),
+            stiffness,
+            damping,
+            maxForce,
+        );
+    }
+
+    /**
+     * The number of degrees of freedom of the multibody containing the given joint, including
+     * the free degrees of freedom of its root if it is dynamic.
+     *
+     * @param handle - The handle of any joint of the multibody.
+     */
+    public numDofs(handle: MultibodyJointHandle): number {
+        return this.raw.multibodyNumDofs(handle);
+    }
+
+    /**
+     * The generalized mass matrix of the multibody containing the given joint, as computed
+     * during the last simulation step.
+     *
+     * This is a square matrix with `numDofs` rows, in row-major order. It includes the
+     * contributions of the joint damping, like the matrix used by the solver.
+     *
+     * @param handle - The handle of any joint of the multibody.
+     */
+    public massMatrix(handle: MultibodyJointHandle): Float64Array {
+        return this.raw.multibodyMassMatrix(handle);
+    }
+
+    /**
+     * The Jacobian of a point attached to the link of the given joint, as computed during
+     * the last simulation step, e.g., for operational-space or computed-torque controllers.
+     *
+     * The Jacobian maps the generalized velocities of the multibody to the velocity of the
+     * point. It is in row-major order, with `numDofs` columns, and 2 rows (3 in 3D) for the
+     * linear velocity of the point followed by 1 row (3 in 3D) for the angular velocity of
+     * the link.
+     *
+     * @param bodies - The set of rigid-bodies.
+     * @param handle - The handle of the joint attaching the link to its parent.
+     * @param point - The point attached to the link, in world-space.
+     */
+    public pointJacobian(
+        bodies: RigidBodySet,
+        handle: MultibodyJointHandle,
+        point: Vector,
+    ): Float64Array {
+        const rawPoint = VectorOps.intoRaw(point);
+        const result = this.raw.multibodyPointJacobian(
+            bodies.raw,
+            handle,
+            rawPoint,
+        );
+        rawPoint.free();
+        return result;
+    }
+
     /**
      * Gets all joints in the list.
      *
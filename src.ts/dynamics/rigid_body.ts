@@ -3,7 +3,12 @@ import {Rotation, RotationOps, Vector, VectorOps} from "../math";
 // #if DIM3
 import {SdpMatrix3, SdpMatrix3Ops} from "../math";
 // #endif
-import {Collider, ColliderSet} from "../geometry";
+import {
+    Collider,
+    ColliderSet,
+    InteractionGroups,
+    MassProperties,
+} from "../geometry";
 
 /**
  * The integer identifier of a collider added to a `ColliderSet`.
@@ -43,6 +48,27 @@ export enum RigidBodyType {
     KinematicVelocityBased,
 }
 
+/**
+ * The pose of a rigid-body, i.e., its translation and orientation.
+ */
+export interface RigidBodyPose {
+    translation: Vector;
+    rotation: Rotation;
+}
+
+/**
+ * The linear and angular velocities of a rigid-body.
+ */
+export interface RigidBodyVelocity {
+    linvel: Vector;
+    // #if DIM2
+    angvel: number;
+    // #endif
+    // #if DIM3
+    angvel: Vector;
+    // #endif
+}
+
 /**
  * A rigid-body.
  */
@@ -133,7 +159,24 @@ export class RigidBody {
         enableY: boolean,
         wakeUp: boolean,
     ) {
-        this.setEnabledTranslations(enableX, enableX, wakeUp);
+        this.setEnabledTranslations(enableX, enableY, wakeUp);
+    }
+
+    /**
+     * Whether this rigid-body can translate along each coordinate axis.
+     *
+     * @returns The enabled states of the translations along the X and Y coordinate axes.
+     */
+    public enabledTranslations(): [boolean, boolean] {
+        const locked = this.rawSet.rbLockedAxes(this.handle);
+        return [(locked & 0b001) == 0, (locked & 0b010) == 0];
+    }
+
+    /**
+     * Whether this rigid-body can rotate.
+     */
+    public isRotationEnabled(): boolean {
+        return (this.rawSet.rbLockedAxes(this.handle) & 0b100000) == 0;
     }
 
     // #endif
@@ -220,8 +263,87 @@ export class RigidBody {
         this.setEnabledRotations(enableX, enableY, enableZ, wakeUp);
     }
 
+    /**
+     * Whether this rigid-body can translate along each coordinate axis.
+     *
+     * @returns The enabled states of the translations along the X, Y, and Z coordinate axes.
+     */
+    public enabledTranslations(): [boolean, boolean, boolean] {
+        const locked = this.rawSet.rbLockedAxes(this.handle);
+        return [
+            (locked & 0b001) == 0,
+            (locked & 0b010) == 0,
+            (locked & 0b100) == 0,
+        ];
+    }
+
+    /**
+     * Whether this rigid-body can rotate about each coordinate axis.
+     *
+     * @returns The enabled states of the rotations about the X, Y, and Z coordinate axes.
+     */
+    public enabledRotations(): [boolean, boolean, boolean] {
+        const locked = this.rawSet.rbLockedAxes(this.handle);
+        return [
+            (locked & 0b001000) == 0,
+            (locked & 0b010000) == 0,
+            (locked & 0b100000) == 0,
+        ];
+    }
+
     // #endif
 
+    /**
+     * The simulation layers of this rigid-body, as a bit mask (default: `0x0001`).
+     */
+    public simulationLayers(): number {
+        return this.rawSet.rbSimulationLayers(this.handle);
+    }
+
+    /**
+     * Sets the simulation layers of this rigid-body.
+     *
+     * This rigid-body is only simulated if any of its layers is active, see
+     * `World.activeLayers`.
+     *
+     * @param layers - The bit mask of the simulation layers of this rigid-body.
+     */
+    public setSimulationLayers(layers: number) {
+        this.rawSet.rbSetSimulationLayers(this.handle, layers);
+    }
+
+    /**
+     * Adds a timestamped pose, e.g. received from the network, to the buffer driving the
+     * kinematic target of this position-based kinematic rigid-body.
+     *
+     * At each step, the next kinematic position of this rigid-body is interpolated from its
+     * buffered poses at the time `World.remotePoseTime - World.remotePoseDelay`. Poses older
+     * than the last one buffered are ignored.
+     *
+     * @param time - The timestamp of the pose, on the same clock as `World.remotePoseTime`.
+     * @param translation - The translation of the rigid-body at that time.
+     * @param rotation - The rotation of the rigid-body at that time.
+     */
+    public pushRemotePose(
+        time: number,
+        translation: Vector,
+        rotation: Rotation,
+    ) {
+        let rawTra = VectorOps.intoRaw(translation);
+        let rawRot = RotationOps.intoRaw(rotation);
+        this.rawSet.rbPushRemotePose(this.handle, time, rawTra, rawRot);
+        rawTra.free();
+        rawRot.free();
+    }
+
+    /**
+     * Removes all the buffered poses of this rigid-body, which stops driving its kinematic
+     * target.
+     */
+    public clearRemotePoses() {
+        this.rawSet.rbClearRemotePoses(this.handle);
+    }
+
     /**
      * The dominance group, in [-127, +127] this rigid-body is part of.
      */
@@ -331,6 +453,25 @@ export class RigidBody {
         return RotationOps.fromRaw(res);
     }
 
+    /**
+     * The world-space pose this rigid-body would have after integrating its current velocity and
+     * forces for a time `dt`, without running a simulation step.
+     *
+     * Contacts and joints are ignored, and the forces are those applied since the last step,
+     * including the gravity computed by the last step.
+     *
+     * @param dt - The time to integrate for, e.g., `world.timestep`.
+     */
+    public predictPose(dt: number): RigidBodyPose {
+        let raw = this.rawSet.rbPredictPose(this.handle, dt);
+        let result = {
+            translation: VectorOps.fromRaw(raw.translation()),
+            rotation: RotationOps.fromRaw(raw.rotation()),
+        };
+        raw.free();
+        return result;
+    }
+
     /**
      * Sets the translation of this rigid-body.
      *
@@ -379,6 +520,27 @@ export class RigidBody {
         this.rawSet.rbSetGravityScale(this.handle, factor, wakeUp);
     }
 
+    /**
+     * The fraction of the gravity cancelled for this rigid-body.
+     */
+    public gravityCompensation(): number {
+        return this.rawSet.rbGravityCompensation(this.handle);
+    }
+
+    /**
+     * Sets the fraction of the gravity cancelled for this rigid-body, e.g., `1.0` for
+     * a hovering body.
+     *
+     * The compensation applies on top of the gravity scale, cancelling the given fraction
+     * of the scaled gravity at each substep. Values greater than 1 make the rigid-body rise.
+     *
+     * @param fraction - The fraction of the gravity to cancel. Set it to 0 to disable the
+     *   compensation.
+     */
+    public setGravityCompensation(fraction: number) {
+        this.rawSet.rbSetGravityCompensation(this.handle, fraction);
+    }
+
     // #if DIM3
     /**
      * Sets the rotation quaternion of this rigid-body.
@@ -507,6 +669,31 @@ export class RigidBody {
         return VectorOps.fromRaw(this.rawSet.rbLinvel(this.handle));
     }
 
+    /**
+     * The linear and angular velocities the solver uses for this rigid-body during the next
+     * timestep.
+     *
+     * For a position-based kinematic rigid-body, these are inferred from its current pose and its
+     * next kinematic pose. For the other rigid-bodies, these are equal to `this.linvel()` and
+     * `this.angvel()`.
+     *
+     * @param dt - The length of the next timestep, e.g., `world.timestep`.
+     */
+    public effectiveKinematicVelocity(dt: number): RigidBodyVelocity {
+        let raw = this.rawSet.rbEffectiveKinematicVelocity(this.handle, dt);
+        let result = {
+            linvel: VectorOps.fromRaw(raw.linvel()),
+            // #if DIM2
+            angvel: raw.angvel(),
+            // #endif
+            // #if DIM3
+            angvel: VectorOps.fromRaw(raw.angvel()),
+            // #endif
+        };
+        raw.free();
+        return result;
+    }
+
     /**
      * The velocity of the given world-space point on this rigid-body.
      */
@@ -730,6 +917,36 @@ export class RigidBody {
         return this.colliderSet.get(this.rawSet.rbCollider(this.handle, i));
     }
 
+    /**
+     * Sets the collision groups of all the colliders attached to this rigid-body.
+     *
+     * See `Collider.setCollisionGroups` for details.
+     *
+     * @param groups - The collision groups to set.
+     */
+    public setCollisionGroups(groups: InteractionGroups) {
+        this.rawSet.rbSetCollisionGroups(
+            this.handle,
+            this.colliderSet.raw,
+            groups,
+        );
+    }
+
+    /**
+     * Sets the solver groups of all the colliders attached to this rigid-body.
+     *
+     * See `Collider.setSolverGroups` for details.
+     *
+     * @param groups - The solver groups to set.
+     */
+    public setSolverGroups(groups: InteractionGroups) {
+        this.rawSet.rbSetSolverGroups(
+            this.handle,
+            this.colliderSet.raw,
+            groups,
+        );
+    }
+
     /**
      * Sets whether this rigid-body is enabled or not.
      *
@@ -813,6 +1030,39 @@ export class RigidBody {
         return this.rawSet.rbAngularDamping(this.handle);
     }
 
+    /**
+     * The additional mass of this rigid-body, set with `this.setAdditionalMass` or
+     * `this.setAdditionalMassProperties`, or zero if none was set.
+     */
+    public additionalMass(): number {
+        return this.rawSet.rbAdditionalMass(this.handle);
+    }
+
+    /**
+     * The additional mass-properties of this rigid-body, expressed in its local-space.
+     *
+     * Returns `null` if no additional mass-properties were set with
+     * `this.setAdditionalMassProperties`, including if only an additional mass was set
+     * with `this.setAdditionalMass`.
+     */
+    public additionalMassProperties(): MassProperties | null {
+        const raw = this.rawSet.rbAdditionalMassProperties(this.handle);
+        if (!raw) return null;
+
+        const result = {
+            mass: raw.mass(),
+            centerOfMass: VectorOps.fromRaw(raw.centerOfMass()),
+            // #if DIM2
+            angularInertia: raw.angularInertia(),
+            // #endif
+            // #if DIM3
+            angularInertia: SdpMatrix3Ops.fromRaw(raw.angularInertia()),
+            // #endif
+        };
+        raw.free();
+        return result;
+    }
+
     /**
      * Sets the linear damping factor applied to this rigid-body.
      *
@@ -822,6 +1072,212 @@ export class RigidBody {
         this.rawSet.rbSetLinearDamping(this.handle, factor);
     }
 
+    /**
+     * The linear damping coefficients of this rigid-body along each of its local axes.
+     */
+    public localLinearDamping(): Vector {
+        let res = this.rawSet.rbLocalLinearDamping(this.handle);
+        return VectorOps.fromRaw(res);
+    }
+
+    /**
+     * Sets the linear damping coefficients applied to this rigid-body along each of its local
+     * axes, in addition to its linear damping.
+     *
+     * This allows anisotropic drag, e.g., a strong lateral damping for an arrow or a boat.
+     *
+     * @param damping - The damping coefficient along each local axis. Negative values are
+     *                  treated as 0.
+     */
+    public setLocalLinearDamping(damping: Vector) {
+        let rawDamping = VectorOps.intoRaw(damping);
+        this.rawSet.rbSetLocalLinearDamping(this.handle, rawDamping);
+        rawDamping.free();
+    }
+
+    /**
+     * Keeps the given local axis of this rigid-body aligned with the given world-space axis,
+     * e.g., for hover vehicles or characters simulated as dynamic rigid-bodies.
+     *
+     * At each substep, an angular impulse pulls the local axis toward the target axis like a
+     * spring-damper. The rotation around the target axis is left free.
+     *
+     * @param localAxis - The axis, in the local frame of this rigid-body, to keep aligned.
+     * @param targetAxis - The world-space axis the local axis is pulled toward.
+     * @param stiffness - The angular acceleration per radian of tilt.
+     * @param damping - The angular acceleration per radian per second of tilt velocity.
+     */
+    public setUprightConstraint(
+        localAxis: Vector,
+        targetAxis: Vector,
+        stiffness: number,
+        damping: number,
+    ) {
+        const rawLocalAxis = VectorOps.intoRaw(localAxis);
+        const rawTargetAxis = VectorOps.intoRaw(targetAxis);
+        this.rawSet.rbSetUprightConstraint(
+            this.handle,
+            rawLocalAxis,
+            rawTargetAxis,
+            stiffness,
+            damping,
+        );
+        rawLocalAxis.free();
+        rawTargetAxis.free();
+    }
+
+    /**
+     * Removes the upright constraint of this rigid-body, if it has one.
+     */
+    public removeUprightConstraint() {
+        this.rawSet.rbRemoveUprightConstraint(this.handle);
+    }
+
+    /**
+     * Does this rigid-body have an upright constraint?
+     */
+    public hasUprightConstraint(): boolean {
+        return this.rawSet.rbHasUprightConstraint(this.handle);
+    }
+
+    /**
+     * Drives this rigid-body toward a target pose with a PD controller evaluated natively at
+     * each substep, e.g., for physically-animated characters.
+     *
+     * The stiffnesses and dampings are accelerations, so they don’t depend on the mass of the
+     * rigid-body. The target can be updated each frame with `setPoseTrackingTarget`.
+     *
+     * @param targetTranslation - The world-space translation the rigid-body is driven toward.
+     * @param targetRotation - The world-space rotation the rigid-body is driven toward.
+     * @param linStiffness - The linear acceleration per unit of position error.
+     * @param linDamping - The linear acceleration per unit of linear velocity.
+     * @param angStiffness - The angular acceleration per radian of rotation error.
+     * @param angDamping - The angular acceleration per unit of angular velocity.
+     * @param maxForce - The maximum force applied by the controller.
+     * @param maxTorque - The maximum torque applied by the controller.
+     */
+    public setPoseTracking(
+        targetTranslation: Vector,
+        targetRotation: Rotation,
+        linStiffness: number,
+        linDamping: number,
+        angStiffness: number,
+        angDamping: number,
+        maxForce: number = Infinity,
+        maxTorque: number = Infinity,
+    ) {
+        const rawTranslation = VectorOps.intoRaw(targetTranslation);
+        const rawRotation = RotationOps.intoRaw(targetRotation);
+        this.rawSet.rbSetPoseTracking(
+            this.handle,
+            rawTranslation,
+            rawRotation,
+            linStiffness,
+            linDamping,
+            angStiffness,
+            angDamping,
+            maxForce,
+            maxTorque,
+        );
+        rawTranslation.free();
+        rawRotation.free();
+    }
+
+    /**
+     * Sets the target pose of the PD controller of this rigid-body, if it has one.
+     *
+     * @param targetTranslation - The world-space translation the rigid-body is driven toward.
+     * @param targetRotation - The world-space rotation the rigid-body is driven toward.
+     */
+    public setPoseTrackingTarget(
+        targetTranslation: Vector,
+        targetRotation: Rotation,
+    ) {
+        const rawTranslation = VectorOps.intoRaw(targetTranslation);
+        const rawRotation = RotationOps.intoRaw(targetRotation);
+        this.rawSet.rbSetPoseTrackingTarget(
+            this.handle,
+            rawTranslation,
+            rawRotation,
+        );
+        rawTranslation.free();
+        rawRotation.free();
+    }
+
+    /**
+     * Removes the PD controller of this rigid-body, if it has one.
+     */
+    public removePoseTracking() {
+        this.rawSet.rbRemovePoseTracking(this.handle);
+    }
+
+    /**
+     * Is this rigid-body driven toward a target pose?
+     */
+    public hasPoseTracking(): boolean {
+        return this.rawSet.rbHasPoseTracking(this.handle);
+    }
+
+    /**
+     * Pulls a point of this rigid-body toward a world-space target point with a spring-damper,
+     * e.g., to grab the rigid-body with the mouse or an editor gizmo.
+     *
+     * The stiffness and damping are accelerations of the grabbed point, so they don’t depend on
+     * the mass of the rigid-body. Update the target each frame with `setDragTarget`. This replaces
+     * the previous drag constraint of this rigid-body, if any.
+     *
+     * @param localPoint - The grabbed point, in the local frame of this rigid-body.
+     * @param target - The world-space point the grabbed point is pulled toward.
+     * @param stiffness - The acceleration of the grabbed point per unit of distance to the target.
+     * @param damping - The acceleration of the grabbed point per unit of velocity.
+     * @param maxForce - The maximum force applied to this rigid-body.
+     */
+    public setDragConstraint(
+        localPoint: Vector,
+        target: Vector,
+        stiffness: number,
+        damping: number,
+        maxForce: number = Infinity,
+    ) {
+        const rawLocalPoint = VectorOps.intoRaw(localPoint);
+        const rawTarget = VectorOps.intoRaw(target);
+        this.rawSet.rbSetDragConstraint(
+            this.handle,
+            rawLocalPoint,
+            rawTarget,
+            stiffness,
+            damping,
+            maxForce,
+        );
+        rawLocalPoint.free();
+        rawTarget.free();
+    }
+
+    /**
+     * Sets the world-space target point of the drag constraint of this rigid-body, if it has one.
+     *
+     * @param target - The world-space point the grabbed point is pulled toward.
+     */
+    public setDragTarget(target: Vector) {
+        const rawTarget = VectorOps.intoRaw(target);
+        this.rawSet.rbSetDragTarget(this.handle, rawTarget);
+        rawTarget.free();
+    }
+
+    /**
+     * Removes the drag constraint of this rigid-body, if it has one.
+     */
+    public removeDragConstraint() {
+        this.rawSet.rbRemoveDragConstraint(this.handle);
+    }
+
+    /**
+     * Does this rigid-body have a drag constraint?
+     */
+    public hasDragConstraint(): boolean {
+        return this.rawSet.rbHasDragConstraint(this.handle);
+    }
+
     /**
      * Recompute the mass-properties of this rigid-bodies based on its currently attached colliders.
      */
@@ -986,6 +1442,32 @@ export class RigidBody {
         rawImpulse.free();
     }
 
+    /**
+     * Adds a force, expressed in the local frame of this rigid-body, at its center-of-mass.
+     *
+     * This avoids rotating the force by the rigid-body orientation, e.g., for thrusters.
+     *
+     * @param force - the local-space force to add to the rigid-body.
+     * @param wakeUp - should the rigid-body be automatically woken-up?
+     */
+    public addForceLocal(force: Vector, wakeUp: boolean) {
+        const rawForce = VectorOps.intoRaw(force);
+        this.rawSet.rbAddForceLocal(this.handle, rawForce, wakeUp);
+        rawForce.free();
+    }
+
+    /**
+     * Applies an impulse, expressed in the local frame of this rigid-body, at its center-of-mass.
+     *
+     * @param impulse - the local-space impulse to apply on the rigid-body.
+     * @param wakeUp - should the rigid-body be automatically woken-up?
+     */
+    public applyImpulseLocal(impulse: Vector, wakeUp: boolean) {
+        const rawImpulse = VectorOps.intoRaw(impulse);
+        this.rawSet.rbApplyImpulseLocal(this.handle, rawImpulse, wakeUp);
+        rawImpulse.free();
+    }
+
     // #if DIM2
     /**
      * Adds a torque at the center-of-mass of this rigid-body.
@@ -1040,6 +1522,18 @@ export class RigidBody {
         rawTorqueImpulse.free();
     }
 
+    /**
+     * Adds a torque, expressed in the local frame of this rigid-body, at its center-of-mass.
+     *
+     * @param torque - the local-space torque to add to the rigid-body.
+     * @param wakeUp - should the rigid-body be automatically woken-up?
+     */
+    public addTorqueLocal(torque: Vector, wakeUp: boolean) {
+        const rawTorque = VectorOps.intoRaw(torque);
+        this.rawSet.rbAddTorqueLocal(this.handle, rawTorque, wakeUp);
+        rawTorque.free();
+    }
+
     // #endif
 
     /**
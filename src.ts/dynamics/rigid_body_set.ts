@@ -177,6 +177,14 @@ export class RigidBodySet {
         this.map.delete(handle);
     }
 
+    /**
+     * Internal function, do not call directly.
+     * @param handle
+     */
+    public mapInserted(handle: RigidBodyHandle, colliderSet: ColliderSet) {
+        this.map.set(handle, new RigidBody(this.raw, colliderSet, handle));
+    }
+
     /**
      * The number of rigid-bodies on this set.
      */
@@ -232,6 +240,20 @@ export class RigidBodySet {
      *
      * @returns rigid-bodies list.
      */
+    /**
+     * Gets the dynamic rigid-bodies that are awake and were moving at the end of the last step.
+     *
+     * This lets renderers and network layers iterate only on the rigid-bodies that changed.
+     *
+     * @param islands - The island manager of the physics world.
+     */
+    public activeDynamicBodies(islands: IslandManager): RigidBody[] {
+        return Array.from(
+            this.raw.rbActiveDynamicBodies(islands.raw),
+            (handle) => this.get(handle),
+        );
+    }
+
     public getAll(): RigidBody[] {
         return this.map.getAll();
     }
@@ -0,0 +1,46 @@
+import {RawBodyMerge} from "../raw";
+import {RigidBody} from "./rigid_body";
+import {ImpulseJoint} from "./impulse_joint";
+
+/**
+ * Two rigid-bodies attached by `World.weldBodies`, either with a fixed joint or by merging the
+ * colliders of the second rigid-body into the first one.
+ *
+ * Pass it to `World.unweldBodies` to detach the rigid-bodies.
+ */
+export class BodyWeld {
+    /**
+     * The first rigid-body of the weld.
+     */
+    readonly body1: RigidBody;
+    /**
+     * The second rigid-body of the weld. It is disabled while merged into the first one.
+     */
+    readonly body2: RigidBody;
+    /**
+     * The fixed joint attaching the rigid-bodies, or `null` if they are merged.
+     */
+    readonly joint: ImpulseJoint | null;
+    /** @internal */
+    rawMerge: RawBodyMerge | null;
+
+    /** @internal */
+    constructor(
+        body1: RigidBody,
+        body2: RigidBody,
+        joint: ImpulseJoint | null,
+        rawMerge: RawBodyMerge | null,
+    ) {
+        this.body1 = body1;
+        this.body2 = body2;
+        this.joint = joint;
+        this.rawMerge = rawMerge;
+    }
+
+    /**
+     * Are the colliders of the second rigid-body merged into the first one?
+     */
+    get merged(): boolean {
+        return !!this.rawMerge;
+    }
+}
@@ -1,13 +1,28 @@
-import {RawBroadPhase, RawRayColliderIntersection} from "../raw";
-import {RigidBodyHandle, RigidBodySet} from "../dynamics";
+import {
+    RawBroadPhase,
+    RawColliderShapeCastHit,
+    RawPointColliderProjection,
+    RawRayColliderIntersection,
+} from "../raw";
+import {
+    IntegrationParameters,
+    RigidBodyHandle,
+    RigidBodySet,
+} from "../dynamics";
 import {ColliderSet} from "./collider_set";
-import {Ray, RayColliderHit, RayColliderIntersection} from "./ray";
+import {
+    PickHit,
+    Ray,
+    RayColliderHit,
+    RayColliderIntersection,
+} from "./ray";
 import {InteractionGroups} from "./interaction_groups";
 import {ColliderHandle} from "./collider";
 import {Rotation, RotationOps, Vector, VectorOps} from "../math";
 import {Shape} from "./shape";
 import {PointColliderProjection} from "./point";
 import {ColliderShapeCastHit} from "./toi";
+import {ColliderShapeContact, ShapeDepenetration} from "./contact";
 import {QueryFilterFlags} from "../pipeline";
 import {NarrowPhase} from "./narrow_phase";
 
@@ -59,6 +74,8 @@ export class BroadPhase {
         filterExcludeCollider?: ColliderHandle,
         filterExcludeRigidBody?: RigidBodyHandle,
         filterPredicate?: (collider: ColliderHandle) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
     ): RayColliderHit | null {
         let rawOrig = VectorOps.intoRaw(ray.origin);
         let rawDir = VectorOps.intoRaw(ray.dir);
@@ -77,6 +94,8 @@ export class BroadPhase {
                 filterExcludeCollider,
                 filterExcludeRigidBody,
                 filterPredicate,
+                filterRequireTags,
+                filterExcludeTags,
             ),
         );
 
@@ -111,6 +130,8 @@ export class BroadPhase {
         filterExcludeCollider?: ColliderHandle,
         filterExcludeRigidBody?: RigidBodyHandle,
         filterPredicate?: (collider: ColliderHandle) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
     ): RayColliderIntersection | null {
         let rawOrig = VectorOps.intoRaw(ray.origin);
         let rawDir = VectorOps.intoRaw(ray.dir);
@@ -129,6 +150,8 @@ export class BroadPhase {
                 filterExcludeCollider,
                 filterExcludeRigidBody,
                 filterPredicate,
+                filterRequireTags,
+                filterExcludeTags,
             ),
         );
 
@@ -165,6 +188,8 @@ export class BroadPhase {
         filterExcludeCollider?: ColliderHandle,
         filterExcludeRigidBody?: RigidBodyHandle,
         filterPredicate?: (collider: ColliderHandle) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
     ) {
         let rawOrig = VectorOps.intoRaw(ray.origin);
         let rawDir = VectorOps.intoRaw(ray.dir);
@@ -188,12 +213,114 @@ export class BroadPhase {
             filterExcludeCollider,
             filterExcludeRigidBody,
             filterPredicate,
+            filterRequireTags,
+            filterExcludeTags,
         );
 
         rawOrig.free();
         rawDir.free();
     }
 
+    /**
+     * Finds the closest collider hit by a ray, e.g., under the mouse cursor.
+     *
+     * @param colliders - The set of colliders taking part in this pipeline.
+     * @param origin - The origin of the ray.
+     * @param dir - The direction of the ray. It doesn't need to be normalized.
+     * @param maxDistance - The maximum distance of the hit from the origin of the ray.
+     */
+    public pick(
+        narrowPhase: NarrowPhase,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        origin: Vector,
+        dir: Vector,
+        maxDistance: number,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: ColliderHandle,
+        filterExcludeRigidBody?: RigidBodyHandle,
+        filterPredicate?: (collider: ColliderHandle) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
+    ): PickHit | null {
+        let rawOrig = VectorOps.intoRaw(origin);
+        let rawDir = VectorOps.intoRaw(dir);
+        let result = PickHit.fromRawArray(
+            colliders,
+            this.raw.pick(
+                narrowPhase.raw,
+                bodies.raw,
+                colliders.raw,
+                rawOrig,
+                rawDir,
+                maxDistance,
+                filterFlags,
+                filterGroups,
+                filterExcludeCollider,
+                filterExcludeRigidBody,
+                filterPredicate,
+                filterRequireTags,
+                filterExcludeTags,
+            ),
+        );
+
+        rawOrig.free();
+        rawDir.free();
+
+        return result.length > 0 ? result[0] : null;
+    }
+
+    /**
+     * Finds all the colliders hit by a ray, sorted by increasing distance.
+     *
+     * @param colliders - The set of colliders taking part in this pipeline.
+     * @param origin - The origin of the ray.
+     * @param dir - The direction of the ray. It doesn't need to be normalized.
+     * @param maxDistance - The maximum distance of the hits from the origin of the ray.
+     */
+    public pickAll(
+        narrowPhase: NarrowPhase,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        origin: Vector,
+        dir: Vector,
+        maxDistance: number,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: ColliderHandle,
+        filterExcludeRigidBody?: RigidBodyHandle,
+        filterPredicate?: (collider: ColliderHandle) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
+    ): PickHit[] {
+        let rawOrig = VectorOps.intoRaw(origin);
+        let rawDir = VectorOps.intoRaw(dir);
+        let result = PickHit.fromRawArray(
+            colliders,
+            this.raw.pickAll(
+                narrowPhase.raw,
+                bodies.raw,
+                colliders.raw,
+                rawOrig,
+                rawDir,
+                maxDistance,
+                filterFlags,
+                filterGroups,
+                filterExcludeCollider,
+                filterExcludeRigidBody,
+                filterPredicate,
+                filterRequireTags,
+                filterExcludeTags,
+            ),
+        );
+
+        rawOrig.free();
+        rawDir.free();
+
+        return result;
+    }
+
     /**
      * Gets the handle of up to one collider intersecting the given shape.
      *
@@ -216,6 +343,8 @@ export class BroadPhase {
         filterExcludeCollider?: ColliderHandle,
         filterExcludeRigidBody?: RigidBodyHandle,
         filterPredicate?: (collider: ColliderHandle) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
     ): ColliderHandle | null {
         let rawPos = VectorOps.intoRaw(shapePos);
         let rawRot = RotationOps.intoRaw(shapeRot);
@@ -232,6 +361,8 @@ export class BroadPhase {
             filterExcludeCollider,
             filterExcludeRigidBody,
             filterPredicate,
+            filterRequireTags,
+            filterExcludeTags,
         );
 
         rawPos.free();
@@ -241,6 +372,65 @@ export class BroadPhase {
         return result;
     }
 
+    /**
+     * Find the projections of a point on the `count` closest colliders, sorted by increasing distance.
+     *
+     * @param colliders - The set of colliders taking part in this pipeline.
+     * @param point - The point to project.
+     * @param count - The maximum number of colliders to report.
+     * @param maxDist - Colliders further than this distance from the point are ignored.
+     * @param solid - If this is set to `true` then the collider shapes are considered to
+     *   be plain (if the point is located inside of a plain shape, its projection is the point
+     *   itself). If it is set to `false` the collider shapes are considered to be hollow
+     *   (if the point is located inside of an hollow shape, it is projected on the shape's
+     *   boundary).
+     */
+    public nearestColliders(
+        narrowPhase: NarrowPhase,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        point: Vector,
+        count: number,
+        maxDist: number,
+        solid: boolean,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: ColliderHandle,
+        filterExcludeRigidBody?: RigidBodyHandle,
+        filterPredicate?: (collider: ColliderHandle) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
+    ): PointColliderProjection[] {
+        let rawPoint = VectorOps.intoRaw(point);
+        let result: PointColliderProjection[] = [];
+        let rawCallback = (rawProj: RawPointColliderProjection) => {
+            result.push(PointColliderProjection.fromRaw(colliders, rawProj));
+            return true;
+        };
+
+        this.raw.nearestColliders(
+            narrowPhase.raw,
+            bodies.raw,
+            colliders.raw,
+            rawPoint,
+            count,
+            maxDist,
+            solid,
+            rawCallback,
+            filterFlags,
+            filterGroups,
+            filterExcludeCollider,
+            filterExcludeRigidBody,
+            filterPredicate,
+            filterRequireTags,
+            filterExcludeTags,
+        );
+
+        rawPoint.free();
+
+        return result;
+    }
+
     /**
      * Find the projection of a point on the closest collider.
      *
@@ -265,6 +455,8 @@ export class BroadPhase {
         filterExcludeCollider?: ColliderHandle,
         filterExcludeRigidBody?: RigidBodyHandle,
         filterPredicate?: (collider: ColliderHandle) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
     ): PointColliderProjection | null {
         let rawPoint = VectorOps.intoRaw(point);
         let result = PointColliderProjection.fromRaw(
@@ -280,6 +472,8 @@ export class BroadPhase {
                 filterExcludeCollider,
                 filterExcludeRigidBody,
                 filterPredicate,
+                filterRequireTags,
+                filterExcludeTags,
             ),
         );
 
@@ -306,6 +500,8 @@ export class BroadPhase {
         filterExcludeCollider?: ColliderHandle,
         filterExcludeRigidBody?: RigidBodyHandle,
         filterPredicate?: (collider: ColliderHandle) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
     ): PointColliderProjection | null {
         let rawPoint = VectorOps.intoRaw(point);
         let result = PointColliderProjection.fromRaw(
@@ -320,6 +516,8 @@ export class BroadPhase {
                 filterExcludeCollider,
                 filterExcludeRigidBody,
                 filterPredicate,
+                filterRequireTags,
+                filterExcludeTags,
             ),
         );
 
@@ -328,6 +526,62 @@ export class BroadPhase {
         return result;
     }
 
+    /**
+     * Find the collider closest to the given shape, e.g., for clearance checks.
+     *
+     * @param colliders - The set of colliders taking part in this pipeline.
+     * @param shapePos - The position of the shape.
+     * @param shapeRot - The orientation of the shape.
+     * @param shape - The shape.
+     * @param maxDist - Colliders further than this distance from the shape are ignored.
+     */
+    public projectShape(
+        narrowPhase: NarrowPhase,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        shapePos: Vector,
+        shapeRot: Rotation,
+        shape: Shape,
+        maxDist: number,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: ColliderHandle,
+        filterExcludeRigidBody?: RigidBodyHandle,
+        filterPredicate?: (collider: ColliderHandle) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
+    ): ColliderShapeContact | null {
+        let rawPos = VectorOps.intoRaw(shapePos);
+        let rawRot = RotationOps.intoRaw(shapeRot);
+        let rawShape = shape.intoRaw();
+
+        let result = ColliderShapeContact.fromRaw(
+            colliders,
+            this.raw.projectShape(
+                narrowPhase.raw,
+                bodies.raw,
+                colliders.raw,
+                rawPos,
+                rawRot,
+                rawShape,
+                maxDist,
+                filterFlags,
+                filterGroups,
+                filterExcludeCollider,
+                filterExcludeRigidBody,
+                filterPredicate,
+                filterRequireTags,
+                filterExcludeTags,
+            ),
+        );
+
+        rawPos.free();
+        rawRot.free();
+        rawShape.free();
+
+        return result;
+    }
+
     /**
      * Find all the colliders containing the given point.
      *
@@ -349,6 +603,8 @@ export class BroadPhase {
         filterExcludeCollider?: ColliderHandle,
         filterExcludeRigidBody?: RigidBodyHandle,
         filterPredicate?: (collider: ColliderHandle) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
     ) {
         let rawPoint = VectorOps.intoRaw(point);
 
@@ -363,6 +619,8 @@ export class BroadPhase {
             filterExcludeCollider,
             filterExcludeRigidBody,
             filterPredicate,
+            filterRequireTags,
+            filterExcludeTags,
         );
 
         rawPoint.free();
@@ -404,6 +662,8 @@ export class BroadPhase {
         filterExcludeCollider?: ColliderHandle,
         filterExcludeRigidBody?: RigidBodyHandle,
         filterPredicate?: (collider: ColliderHandle) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
     ): ColliderShapeCastHit | null {
         let rawPos = VectorOps.intoRaw(shapePos);
         let rawRot = RotationOps.intoRaw(shapeRot);
@@ -428,9 +688,179 @@ export class BroadPhase {
                 filterExcludeCollider,
                 filterExcludeRigidBody,
                 filterPredicate,
+                filterRequireTags,
+                filterExcludeTags,
+            ),
+        );
+
+        rawPos.free();
+        rawRot.free();
+        rawVel.free();
+        rawShape.free();
+
+        return result;
+    }
+
+    /**
+     * Casts a shape with both a linear and an angular velocity, and retrieve the first collider
+     * it hits.
+     *
+     * @param colliders - The set of colliders taking part in this pipeline.
+     * @param shapePos - The initial position of the shape to cast.
+     * @param shapeRot - The initial rotation of the shape to cast.
+     * @param shapeLinvel - The constant linear velocity of the shape to cast.
+     * @param shapeAngvel - The constant angular velocity of the shape to cast, around its center
+     *   of mass.
+     * @param shape - The shape to cast.
+     * @param maxToi - The maximum time-of-impact that can be reported by this cast.
+     * @param stopAtPenetration - If set to `false`, the shape-cast won’t immediately stop if
+     *   the shape is penetrating another shape at its starting point **and** its trajectory is such
+     *   that it’s on a path to exit that penetration state.
+     * @param filterFlags - Flags for excluding whole sets of colliders from the cast.
+     * @param filterGroups - Only colliders with collision groups compatible with this one will
+     *   be hit.
+     * @param filterExcludeCollider - A collider to exclude from the cast.
+     * @param filterExcludeRigidBody - A rigid-body whose colliders are excluded from the cast.
+     * @param filterPredicate - Any collider for which this closure returns `false` will be excluded.
+     */
+    public castShapeNonlinear(
+        narrowPhase: NarrowPhase,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        shapePos: Vector,
+        shapeRot: Rotation,
+        shapeLinvel: Vector,
+        // #if DIM2
+        shapeAngvel: number,
+        // #endif
+        // #if DIM3
+        shapeAngvel: Vector,
+        // #endif
+        shape: Shape,
+        maxToi: number,
+        stopAtPenetration: boolean,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: ColliderHandle,
+        filterExcludeRigidBody?: RigidBodyHandle,
+        filterPredicate?: (collider: ColliderHandle) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
+    ): ColliderShapeCastHit | null {
+        let rawPos = VectorOps.intoRaw(shapePos);
+        let rawRot = RotationOps.intoRaw(shapeRot);
+        let rawLinvel = VectorOps.intoRaw(shapeLinvel);
+        // #if DIM2
+        let rawAngvel = shapeAngvel;
+        // #endif
+        // #if DIM3
+        let rawAngvel = VectorOps.intoRaw(shapeAngvel);
+        // #endif
+        let rawShape = shape.intoRaw();
+
+        let result = ColliderShapeCastHit.fromRaw(
+            colliders,
+            this.raw.castShapeNonlinear(
+                narrowPhase.raw,
+                bodies.raw,
+                colliders.raw,
+                rawPos,
+                rawRot,
+                rawLinvel,
+                rawAngvel,
+                rawShape,
+                maxToi,
+                stopAtPenetration,
+                filterFlags,
+                filterGroups,
+                filterExcludeCollider,
+                filterExcludeRigidBody,
+                filterPredicate,
+                filterRequireTags,
+                filterExcludeTags,
             ),
         );
 
+        rawPos.free();
+        rawRot.free();
+        rawLinvel.free();
+        // #if DIM3
+        rawAngvel.free();
+        // #endif
+        rawShape.free();
+
+        return result;
+    }
+
+    /**
+     * Casts a shape at a constant linear velocity and retrieve all the colliders it hits, sorted
+     * by increasing time of impact.
+     *
+     * @param colliders - The set of colliders taking part in this pipeline.
+     * @param shapePos - The initial position of the shape to cast.
+     * @param shapeRot - The initial rotation of the shape to cast.
+     * @param shapeVel - The constant velocity of the shape to cast (i.e. the cast direction).
+     * @param shape - The shape to cast.
+     * @param targetDistance − If the shape moves closer to this distance from a collider, a hit
+     *                         will be returned.
+     * @param maxToi - The maximum time-of-impact that can be reported by this cast. This effectively
+     *   limits the distance traveled by the shape to `shapeVel.norm() * maxToi`.
+     * @param stopAtPenetration - If set to `false`, the linear shape-cast won’t immediately stop if
+     *   the shape is penetrating another shape at its starting point **and** its trajectory is such
+     *   that it’s on a path to exit that penetration state.
+     * @param maxHits - The maximum number of hits to report.
+     */
+    public castShapeAll(
+        narrowPhase: NarrowPhase,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        shapePos: Vector,
+        shapeRot: Rotation,
+        shapeVel: Vector,
+        shape: Shape,
+        targetDistance: number,
+        maxToi: number,
+        stopAtPenetration: boolean,
+        maxHits?: number,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: ColliderHandle,
+        filterExcludeRigidBody?: RigidBodyHandle,
+        filterPredicate?: (collider: ColliderHandle) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
+    ): ColliderShapeCastHit[] {
+        let rawPos = VectorOps.intoRaw(shapePos);
+        let rawRot = RotationOps.intoRaw(shapeRot);
+        let rawVel = VectorOps.intoRaw(shapeVel);
+        let rawShape = shape.intoRaw();
+        let result: ColliderShapeCastHit[] = [];
+        let rawCallback = (rawHit: RawColliderShapeCastHit) => {
+            result.push(ColliderShapeCastHit.fromRaw(colliders, rawHit));
+            return maxHits == undefined || result.length < maxHits;
+        };
+
+        this.raw.castShapeAll(
+            narrowPhase.raw,
+            bodies.raw,
+            colliders.raw,
+            rawPos,
+            rawRot,
+            rawVel,
+            rawShape,
+            targetDistance,
+            maxToi,
+            stopAtPenetration,
+            rawCallback,
+            filterFlags,
+            filterGroups,
+            filterExcludeCollider,
+            filterExcludeRigidBody,
+            filterPredicate,
+            filterRequireTags,
+            filterExcludeTags,
+        );
+
         rawPos.free();
         rawRot.free();
         rawVel.free();
@@ -463,6 +893,8 @@ export class BroadPhase {
         filterExcludeCollider?: ColliderHandle,
         filterExcludeRigidBody?: RigidBodyHandle,
         filterPredicate?: (collider: ColliderHandle) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
     ) {
         let rawPos = VectorOps.intoRaw(shapePos);
         let rawRot = RotationOps.intoRaw(shapeRot);
@@ -481,11 +913,299 @@ export class BroadPhase {
             filterExcludeCollider,
             filterExcludeRigidBody,
             filterPredicate,
+            filterRequireTags,
+            filterExcludeTags,
+        );
+
+        rawPos.free();
+        rawRot.free();
+        rawShape.free();
+    }
+
+    /**
+     * Retrieve the handles of all the colliders intersecting the given shape.
+     *
+     * This is similar to `intersectionsWithShape` but returns all the handles at once instead of
+     * invoking a callback for each of them.
+     *
+     * @param colliders - The set of colliders taking part in this pipeline.
+     * @param shapePos - The position of the shape to test.
+     * @param shapeRot - The orientation of the shape to test.
+     * @param shape - The shape to test.
+     * @returns The handles of all the colliders intersecting the `shape`.
+     */
+    public intersectionsWithShapeCollect(
+        narrowPhase: NarrowPhase,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        shapePos: Vector,
+        shapeRot: Rotation,
+        shape: Shape,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: ColliderHandle,
+        filterExcludeRigidBody?: RigidBodyHandle,
+        filterPredicate?: (collider: ColliderHandle) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
+    ): Float64Array {
+        let rawPos = VectorOps.intoRaw(shapePos);
+        let rawRot = RotationOps.intoRaw(shapeRot);
+        let rawShape = shape.intoRaw();
+
+        let result = this.raw.intersectionsWithShapeCollect(
+            narrowPhase.raw,
+            bodies.raw,
+            colliders.raw,
+            rawPos,
+            rawRot,
+            rawShape,
+            filterFlags,
+            filterGroups,
+            filterExcludeCollider,
+            filterExcludeRigidBody,
+            filterPredicate,
+            filterRequireTags,
+            filterExcludeTags,
+        );
+
+        rawPos.free();
+        rawRot.free();
+        rawShape.free();
+
+        return result;
+    }
+
+    /**
+     * Retrieves the handles of the colliders preventing the given shape from being placed
+     * at the given position, i.e., penetrating it by more than `tolerance`.
+     *
+     * @param shapePos - The position of the shape to place.
+     * @param shapeRot - The orientation of the shape to place.
+     * @param shape - The shape to place.
+     * @param tolerance - The penetration depth allowed without blocking the placement.
+     */
+    public placementBlockers(
+        narrowPhase: NarrowPhase,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        shapePos: Vector,
+        shapeRot: Rotation,
+        shape: Shape,
+        tolerance: number,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: ColliderHandle,
+        filterExcludeRigidBody?: RigidBodyHandle,
+        filterPredicate?: (collider: ColliderHandle) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
+    ): Float64Array {
+        let rawPos = VectorOps.intoRaw(shapePos);
+        let rawRot = RotationOps.intoRaw(shapeRot);
+        let rawShape = shape.intoRaw();
+
+        let result = this.raw.placementBlockers(
+            narrowPhase.raw,
+            bodies.raw,
+            colliders.raw,
+            rawPos,
+            rawRot,
+            rawShape,
+            tolerance,
+            filterFlags,
+            filterGroups,
+            filterExcludeCollider,
+            filterExcludeRigidBody,
+            filterPredicate,
+            filterRequireTags,
+            filterExcludeTags,
+        );
+
+        rawPos.free();
+        rawRot.free();
+        rawShape.free();
+
+        return result;
+    }
+
+    /**
+     * Computes a position where the given shape no longer penetrates the colliders of the
+     * scene by more than `tolerance`.
+     *
+     * @param shapePos - The initial position of the shape.
+     * @param shapeRot - The orientation of the shape, preserved by the depenetration.
+     * @param shape - The shape to depenetrate.
+     * @param maxIterations - The maximum number of depenetration iterations.
+     * @param tolerance - The penetration depth left unresolved.
+     */
+    public depenetrateShape(
+        narrowPhase: NarrowPhase,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        shapePos: Vector,
+        shapeRot: Rotation,
+        shape: Shape,
+        maxIterations: number,
+        tolerance: number,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: ColliderHandle,
+        filterExcludeRigidBody?: RigidBodyHandle,
+        filterPredicate?: (collider: ColliderHandle) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
+    ): ShapeDepenetration {
+        let rawPos = VectorOps.intoRaw(shapePos);
+        let rawRot = RotationOps.intoRaw(shapeRot);
+        let rawShape = shape.intoRaw();
+
+        let result = ShapeDepenetration.fromRaw(
+            this.raw.depenetrateShape(
+                narrowPhase.raw,
+                bodies.raw,
+                colliders.raw,
+                rawPos,
+                rawRot,
+                rawShape,
+                maxIterations,
+                tolerance,
+                filterFlags,
+                filterGroups,
+                filterExcludeCollider,
+                filterExcludeRigidBody,
+                filterPredicate,
+                filterRequireTags,
+                filterExcludeTags,
+            ),
         );
 
         rawPos.free();
         rawRot.free();
         rawShape.free();
+
+        return result;
+    }
+
+    /**
+     * Counts, for each sound source, the colliders crossed by the segment between the listener
+     * and the source.
+     *
+     * @param listener - The position of the listener.
+     * @param sources - The flattened positions of the sources.
+     * @returns The number of colliders crossed for each source.
+     */
+    public occlusion(
+        narrowPhase: NarrowPhase,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        listener: Vector,
+        sources: Float64Array,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: ColliderHandle,
+        filterExcludeRigidBody?: RigidBodyHandle,
+        filterPredicate?: (collider: ColliderHandle) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
+    ): Uint32Array {
+        let rawListener = VectorOps.intoRaw(listener);
+        let result = this.raw.occlusion(
+            narrowPhase.raw,
+            bodies.raw,
+            colliders.raw,
+            rawListener,
+            sources,
+            filterFlags,
+            filterGroups,
+            filterExcludeCollider,
+            filterExcludeRigidBody,
+            filterPredicate,
+            filterRequireTags,
+            filterExcludeTags,
+        );
+        rawListener.free();
+
+        return result;
+    }
+
+    /**
+     * Tests the visibility of each target point from the origin.
+     *
+     * @param origin - The point the targets are seen from.
+     * @param targets - The flattened positions of the targets.
+     * @returns A bit mask with the bit `i % 32` of the element `i / 32` set if the target `i` is
+     *          visible.
+     */
+    public lineOfSight(
+        narrowPhase: NarrowPhase,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        origin: Vector,
+        targets: Float64Array,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: ColliderHandle,
+        filterExcludeRigidBody?: RigidBodyHandle,
+        filterPredicate?: (collider: ColliderHandle) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
+    ): Uint32Array {
+        let rawOrigin = VectorOps.intoRaw(origin);
+        let result = this.raw.lineOfSight(
+            narrowPhase.raw,
+            bodies.raw,
+            colliders.raw,
+            rawOrigin,
+            targets,
+            filterFlags,
+            filterGroups,
+            filterExcludeCollider,
+            filterExcludeRigidBody,
+            filterPredicate,
+            filterRequireTags,
+            filterExcludeTags,
+        );
+        rawOrigin.free();
+
+        return result;
+    }
+
+    /**
+     * Finds the handles of all the colliders with an AABB intersecting the convex region bounded
+     * by the given planes (typically the planes of a camera frustum).
+     *
+     * @param planes - The flattened plane equations `[a, b, c, d]` (`[a, b, c]` in 2D) of each plane.
+     *                 A point `x` is inside of the region if `dot([a, b, c], x) + d >= 0` for every plane,
+     *                 i.e., the plane normals point toward the inside of the region.
+     * @returns The handles of the colliders with an AABB intersecting the region.
+     */
+    public collidersInFrustum(
+        narrowPhase: NarrowPhase,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        planes: Float64Array,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: ColliderHandle,
+        filterExcludeRigidBody?: RigidBodyHandle,
+        filterPredicate?: (collider: ColliderHandle) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
+    ): Float64Array {
+        return this.raw.collidersInFrustum(
+            narrowPhase.raw,
+            bodies.raw,
+            colliders.raw,
+            planes,
+            filterFlags,
+            filterGroups,
+            filterExcludeCollider,
+            filterExcludeRigidBody,
+            filterPredicate,
+            filterRequireTags,
+            filterExcludeTags,
+        );
     }
 
     /**
@@ -517,4 +1237,26 @@ export class BroadPhase {
         rawCenter.free();
         rawHalfExtents.free();
     }
+
+    /**
+     * Refits the scene-query acceleration structure to the current positions of the given colliders.
+     *
+     * Collision pairs (and their events) are only updated by the next simulation step.
+     *
+     * @param integrationParameters - The integration parameters of the simulation.
+     * @param colliders - The set of colliders taking part in this pipeline.
+     * @param handles - The handles of the colliders that changed since the last update. If not provided,
+     *                  all the colliders are updated.
+     */
+    public updateWithColliders(
+        integrationParameters: IntegrationParameters,
+        colliders: ColliderSet,
+        handles?: ColliderHandle[],
+    ) {
+        this.raw.updateWithColliders(
+            integrationParameters.raw,
+            colliders.raw,
+            handles ? new Float64Array(handles) : undefined,
+        );
+    }
 }
@@ -1,5 +1,8 @@
-import {RawColliderSet} from "../raw";
+import {RawColliderSet, RawShape} from "../raw";
 import {Rotation, RotationOps, Vector, VectorOps} from "../math";
+// #if DIM3
+import {SdpMatrix3, SdpMatrix3Ops} from "../math";
+// #endif
 import {
     CoefficientCombineRule,
     RigidBody,
@@ -41,7 +44,7 @@ import {
 import {Ray, RayIntersection} from "./ray";
 import {PointProjection} from "./point";
 import {ColliderShapeCastHit, ShapeCastHit} from "./toi";
-import {ShapeContact} from "./contact";
+import {ShapeContact, ShapePenetration} from "./contact";
 import {ColliderSet} from "./collider_set";
 
 /**
@@ -103,6 +106,32 @@ export enum ActiveCollisionTypes {
  */
 export type ColliderHandle = number;
 
+/**
+ * A mass, a center of mass, and an angular inertia.
+ */
+export interface MassProperties {
+    /**
+     * The mass.
+     */
+    mass: number;
+    /**
+     * The center of mass.
+     */
+    centerOfMass: Vector;
+    // #if DIM2
+    /**
+     * The angular inertia, relative to the center of mass.
+     */
+    angularInertia: number;
+    // #endif
+    // #if DIM3
+    /**
+     * The angular inertia tensor, relative to the center of mass.
+     */
+    angularInertia: SdpMatrix3;
+    // #endif
+}
+
 /**
  * A geometric entity that can be attached to a body so it can be affected
  * by contacts and proximity queries.
@@ -271,6 +300,168 @@ export class Collider {
         this.colliderSet.raw.coSetFriction(this.handle, friction);
     }
 
+    /**
+     * Assigns a material to each triangle of this collider (typically a triangle mesh).
+     *
+     * For any contact involving a triangle, the friction and restitution coefficients of its
+     * material are used instead of the collider’s. They are then combined with the other collider’s
+     * coefficients with the usual combine rules.
+     *
+     * @param triangleMaterials - The material index of each triangle.
+     * @param friction - The friction coefficient of each material.
+     * @param restitution - The restitution coefficient of each material.
+     * @returns `false`, leaving the collider unchanged, if it isn't a triangle mesh, if the number of
+     *          material indices doesn't match its number of triangles, or if a material index is out
+     *          of bounds of `friction` or `restitution`.
+     */
+    public setTriangleMaterials(
+        triangleMaterials: Uint32Array,
+        friction: Float64Array,
+        restitution: Float64Array,
+    ): boolean {
+        return this.colliderSet.raw.coSetTriangleMaterials(
+            this.handle,
+            triangleMaterials,
+            friction,
+            restitution,
+        );
+    }
+
+    /**
+     * Removes the per-triangle materials of this collider.
+     */
+    public clearTriangleMaterials() {
+        this.colliderSet.raw.coClearTriangleMaterials(this.handle);
+    }
+
+    /**
+     * The material index of a triangle of this collider.
+     *
+     * @param triangle - The triangle index (e.g. `TempContactManifold.subshape1()`), or the face
+     *                   feature id of a ray intersection.
+     * @returns The material index, or `null` if this triangle has no material.
+     */
+    public triangleMaterial(triangle: number): number | null {
+        let material = this.colliderSet.raw.coTriangleMaterial(
+            this.handle,
+            triangle,
+        );
+        return material === undefined ? null : material;
+    }
+
+    // #if DIM3
+    /**
+     * Enables or disables the correction of the contacts with the internal edges of this collider, if it
+     * is a triangle mesh or a heightfield.
+     *
+     * This is the same as the `FIX_INTERNAL_EDGES` flag given when creating the shape, but can be toggled
+     * at runtime. It prevents objects sliding on flat surfaces from snagging on the edges between
+     * triangles.
+     *
+     * @param enabled - Whether the internal edges are fixed.
+     * @returns `false` if this collider isn’t a triangle mesh or a heightfield, or if the topology of the
+     *          triangle mesh needed for the correction couldn’t be computed.
+     */
+    public setFixInternalEdges(enabled: boolean): boolean {
+        const result = this.colliderSet.raw.coSetFixInternalEdges(
+            this.handle,
+            enabled,
+        );
+        // We modified the shape, invalidate it to keep our cache
+        // up-to-date the next time the user requests the shape data.
+        this._shape = null;
+        return result;
+    }
+
+    /**
+     * Is the correction of the contacts with the internal edges of this collider enabled?
+     *
+     * @returns `null` if this collider isn’t a triangle mesh or a heightfield.
+     */
+    public fixInternalEdges(): boolean | null {
+        const enabled = this.colliderSet.raw.coFixInternalEdges(this.handle);
+        return enabled === undefined ? null : enabled;
+    }
+    // #endif
+
+    /**
+     * The rolling resistance coefficient of this collider.
+     */
+    public rollingResistance(): number {
+        return this.colliderSet.raw.coRollingResistance(this.handle);
+    }
+
+    /**
+     * Sets the rolling resistance coefficient of this collider.
+     *
+     * See the documentation of `ColliderDesc.setRollingResistance` for additional details.
+     *
+     * @param rollingResistance - The rolling resistance coefficient. Must be greater or equal to 0.
+     */
+    public setRollingResistance(rollingResistance: number) {
+        this.colliderSet.raw.coSetRollingResistance(
+            this.handle,
+            rollingResistance,
+        );
+    }
+
+    /**
+     * Gets the rule used to combine the rolling resistance coefficients of two colliders
+     * involved in a contact.
+     */
+    public rollingResistanceCombineRule(): CoefficientCombineRule {
+        return this.colliderSet.raw.coRollingResistanceCombineRule(this.handle);
+    }
+
+    /**
+     * Sets the rule used to combine the rolling resistance coefficients of two colliders
+     * involved in a contact.
+     *
+     * @param rule − The combine rule to apply.
+     */
+    public setRollingResistanceCombineRule(rule: CoefficientCombineRule) {
+        this.colliderSet.raw.coSetRollingResistanceCombineRule(
+            this.handle,
+            rule,
+        );
+    }
+
+    /**
+     * The aerodynamic drag coefficient of this collider.
+     */
+    public dragCoefficient(): number {
+        return this.colliderSet.raw.coDragCoefficient(this.handle);
+    }
+
+    /**
+     * Sets the aerodynamic drag coefficient of this collider.
+     *
+     * See the documentation of `ColliderDesc.setDragCoefficient` for additional details.
+     *
+     * @param dragCoefficient - The drag coefficient. Set it to 0 to disable the drag.
+     */
+    public setDragCoefficient(dragCoefficient: number) {
+        this.colliderSet.raw.coSetDragCoefficient(this.handle, dragCoefficient);
+    }
+
+    /**
+     * The user tags of this collider, as a 32-bit mask (default: `0`).
+     */
+    public tags(): number {
+        return this.colliderSet.raw.coTags(this.handle);
+    }
+
+    /**
+     * Sets the user tags of this collider.
+     *
+     * See the documentation of `ColliderDesc.setTags` for additional details.
+     *
+     * @param tags - The 32-bit mask of the tags of this collider.
+     */
+    public setTags(tags: number) {
+        this.colliderSet.raw.coSetTags(this.handle, tags);
+    }
+
     /**
      * Gets the rule used to combine the friction coefficients of two colliders
      * colliders involved in a contact.
@@ -354,6 +545,10 @@ export class Collider {
 
     /**
      * Get the physics hooks active for this collider.
+     *
+     * The hooks set automatically for the pair filter rules and exclusions (see
+     * `World.addPairFilterRule`) and for the per-triangle materials (see
+     * `setTriangleMaterials`) aren't included.
      */
     public activeHooks(): ActiveHooks {
         return this.colliderSet.raw.coActiveHooks(this.handle);
@@ -611,6 +806,46 @@ export class Collider {
 
     // #endif
 
+    /**
+     * Sets both the world-space translation and rotation of this collider.
+     *
+     * This is equivalent to, but cheaper than, calling `setTranslation`
+     * followed by `setRotation`: the broad-phase is only updated once.
+     *
+     * @param tra - The world-space position of the collider.
+     * @param rot - The world-space orientation of the collider.
+     */
+    public setPosition(tra: Vector, rot: Rotation) {
+        let rawTra = VectorOps.intoRaw(tra);
+        let rawRot = RotationOps.intoRaw(rot);
+        this.colliderSet.raw.coSetPosition(this.handle, rawTra, rawRot);
+        rawTra.free();
+        rawRot.free();
+    }
+
+    /**
+     * Sets both the translation and rotation of this collider relative to its
+     * parent rigid-body.
+     *
+     * Does nothing if this collider isn't attached to a rigid-body. This is
+     * equivalent to, but cheaper than, calling `setTranslationWrtParent`
+     * followed by `setRotationWrtParent`.
+     *
+     * @param tra - The new translation of the collider relative to its parent.
+     * @param rot - The new rotation of the collider relative to its parent.
+     */
+    public setPositionWrtParent(tra: Vector, rot: Rotation) {
+        let rawTra = VectorOps.intoRaw(tra);
+        let rawRot = RotationOps.intoRaw(rot);
+        this.colliderSet.raw.coSetPositionWrtParent(
+            this.handle,
+            rawTra,
+            rawRot,
+        );
+        rawTra.free();
+        rawRot.free();
+    }
+
     /**
      * The type of the shape of this collider.
      */
@@ -687,6 +922,67 @@ export class Collider {
         this.colliderSet.raw.coSetHalfHeight(this.handle, newHalfheight);
     }
 
+    /**
+     * If this collider has a Voxels shape, the range of grid coordinates
+     * covered by its voxels.
+     *
+     * The voxels outside of the range between `mins` (included) and `maxs`
+     * (excluded) are guaranteed to be empty. Returns `null` if this collider
+     * doesn’t have a Voxels shape.
+     */
+    public voxelDomain(): {mins: Vector; maxs: Vector} | null {
+        const domain = this.colliderSet.raw.coVoxelDomain(this.handle);
+        if (!domain) {
+            return null;
+        }
+
+        // #if DIM2
+        return {
+            mins: VectorOps.new(domain[0], domain[1]),
+            maxs: VectorOps.new(domain[2], domain[3]),
+        };
+        // #endif
+        // #if DIM3
+        return {
+            mins: VectorOps.new(domain[0], domain[1], domain[2]),
+            maxs: VectorOps.new(domain[3], domain[4], domain[5]),
+        };
+        // #endif
+    }
+
+    /**
+     * If this collider has a Voxels shape, checks if the voxel at the given
+     * grid coordinates is filled.
+     *
+     * Returns `false` if this collider doesn’t have a Voxels shape.
+     */
+    public isVoxelFilled(
+        ix: number,
+        iy: number,
+        // #if DIM3
+        iz: number,
+        // #endif
+    ): boolean {
+        return this.colliderSet.raw.coVoxelIsFilled(
+            this.handle,
+            ix,
+            iy,
+            // #if DIM3
+            iz,
+            // #endif
+        );
+    }
+
+    /**
+     * If this collider has a Voxels shape, the number of its filled voxels.
+     *
+     * Returns `null` if this collider doesn’t have a Voxels shape.
+     */
+    public numFilledVoxels(): number | null {
+        const count = this.colliderSet.raw.coNumFilledVoxels(this.handle);
+        return count === undefined ? null : count;
+    }
+
     /**
      * If this collider has a Voxels shape, this will mark the voxel at the
      * given grid coordinates as filled or empty (depending on the `filled`
@@ -774,6 +1070,39 @@ export class Collider {
         this._shape = null;
     }
 
+    /**
+     * Same as `propagateVoxelChange`, for several voxels of `this` modified
+     * with `setVoxel`, e.g., all the modified voxels on the boundary shared
+     * by two chunks of a voxel terrain.
+     *
+     * @param voxels - The integer coordinates of the modified voxels in the
+     *   local coordinate frame of `this`, stored consecutively: `[ix0, iy0,
+     *   ix1, iy1, ...]` in 2D, or `[ix0, iy0, iz0, ix1, iy1, iz1, ...]` in 3D.
+     */
+    public propagateVoxelChanges(
+        voxels2: Collider,
+        voxels: Int32Array,
+        shift_x: number,
+        shift_y: number,
+        // #if DIM3
+        shift_z: number,
+        // #endif
+    ) {
+        this.colliderSet.raw.coPropagateVoxelChanges(
+            this.handle,
+            voxels2.handle,
+            voxels,
+            shift_x,
+            shift_y,
+            // #if DIM3
+            shift_z,
+            // #endif
+        );
+        // We modified the shape, invalidate it to keep our cache
+        // up-to-date the next time the user requests the shape data.
+        this._shape = null;
+    }
+
     /**
      * If this and `voxels2` are voxel colliders, this will ensure that a
      * moving object transitioning across the boundaries of these colliders
@@ -839,6 +1168,29 @@ export class Collider {
         return this.colliderSet.raw.coHeightfieldHeights(this.handle);
     }
 
+    /**
+     * If this collider has a heightfield shape, this replaces its heights,
+     * e.g., after they were modified by a terrain editor, keeping its scale
+     * and its removed cells.
+     *
+     * The heights have the same layout as the ones returned by
+     * `heightfieldHeights`: in 3D, the height matrix is provided in
+     * column-major order. Returns `false`, leaving the collider unchanged, if
+     * it isn't a heightfield or if the number of heights doesn't match.
+     *
+     * @param heights - The new heights of the heightfield.
+     */
+    public setHeightfieldHeights(heights: Float64Array): boolean {
+        const changed = this.colliderSet.raw.coSetHeightfieldHeights(
+            this.handle,
+            heights,
+        );
+        // We modified the shape, invalidate it to keep our cache
+        // up-to-date the next time the user requests the shape data.
+        this._shape = null;
+        return changed;
+    }
+
     /**
      * If this collider has a heightfield shape, this returns the scale
      * applied to it.
@@ -848,6 +1200,32 @@ export class Collider {
         return VectorOps.fromRaw(scale);
     }
 
+    /**
+     * If this collider has a heightfield shape, this returns its collision
+     * surface: a polyline in 2D, with two indices per segment, or a triangle
+     * mesh in 3D, with three indices per triangle.
+     *
+     * This can be handed to a renderer or a navmesh baker.
+     */
+    public heightfieldToTrimesh(): {
+        vertices: Float64Array;
+        indices: Uint32Array;
+    } | null {
+        const rawMesh = this.colliderSet.raw.coHeightfieldToTrimesh(
+            this.handle,
+        );
+        if (!rawMesh) {
+            return null;
+        }
+
+        const result = {
+            vertices: rawMesh.vertices(),
+            indices: rawMesh.indices(),
+        };
+        rawMesh.free();
+        return result;
+    }
+
     // #if DIM3
     /**
      * If this collider has a heightfield shape, this returns the number of
@@ -902,6 +1280,28 @@ export class Collider {
         return this.colliderSet.raw.coMass(this.handle);
     }
 
+    /**
+     * The mass, world-space center of mass, and angular inertia this collider contributes to
+     * its parent rigid-body.
+     *
+     * This is useful to check how the mass of a rigid-body is distributed among its colliders.
+     */
+    public massProperties(): MassProperties {
+        const raw = this.colliderSet.raw.coMassProperties(this.handle);
+        const result = {
+            mass: raw.mass(),
+            centerOfMass: VectorOps.fromRaw(raw.centerOfMass()),
+            // #if DIM2
+            angularInertia: raw.angularInertia(),
+            // #endif
+            // #if DIM3
+            angularInertia: SdpMatrix3Ops.fromRaw(raw.angularInertia()),
+            // #endif
+        };
+        raw.free();
+        return result;
+    }
+
     /**
      * The volume of this collider.
      */
@@ -916,6 +1316,18 @@ export class Collider {
         return this.colliderSet.raw.coCollisionGroups(this.handle);
     }
 
+    /**
+     * Is this collider a member of the given collision group?
+     *
+     * @param group - The index of the group, in `[0, 16)`.
+     */
+    public collisionGroupsContain(group: number): boolean {
+        return this.colliderSet.raw.coCollisionGroupsContain(
+            this.handle,
+            group,
+        );
+    }
+
     /**
      * The solver groups of this collider.
      */
@@ -961,6 +1373,26 @@ export class Collider {
         return result;
     }
 
+    /**
+     * Find the projection of a point on the boundary of this collider, as well as the
+     * geometric feature (vertex, edge, face) the point was projected on.
+     *
+     * @param point - The point to project.
+     */
+    public projectPointAndGetFeature(point: Vector): PointProjection | null {
+        let rawPoint = VectorOps.intoRaw(point);
+        let result = PointProjection.fromRaw(
+            this.colliderSet.raw.coProjectPointAndGetFeature(
+                this.handle,
+                rawPoint,
+            ),
+        );
+
+        rawPoint.free();
+
+        return result;
+    }
+
     /**
      * Tests if this collider intersects the given ray.
      *
@@ -1165,6 +1597,56 @@ export class Collider {
         return result;
     }
 
+    /**
+     * Computes the penetration depth and the minimum translation vector needed to separate
+     * this collider from the given shape.
+     *
+     * @param shape2 - The second shape.
+     * @param shape2Pos - The position of the second shape.
+     * @param shape2Rot - The rotation of the second shape.
+     * @returns `null` if the shapes don't overlap, otherwise the penetration details, in world-space.
+     */
+    penetrationShape(
+        shape2: Shape,
+        shape2Pos: Vector,
+        shape2Rot: Rotation,
+    ): ShapePenetration | null {
+        let rawPos2 = VectorOps.intoRaw(shape2Pos);
+        let rawRot2 = RotationOps.intoRaw(shape2Rot);
+        let rawShape2 = shape2.intoRaw();
+
+        let result = ShapePenetration.fromRaw(
+            this.colliderSet.raw.coPenetrationShape(
+                this.handle,
+                rawShape2,
+                rawPos2,
+                rawRot2,
+            ),
+        );
+
+        rawPos2.free();
+        rawRot2.free();
+        rawShape2.free();
+
+        return result;
+    }
+
+    /**
+     * Computes the penetration depth and the minimum translation vector needed to separate
+     * this collider from the given collider.
+     *
+     * @param collider2 - The second collider.
+     * @returns `null` if the colliders don't overlap, otherwise the penetration details, in world-space.
+     */
+    penetrationCollider(collider2: Collider): ShapePenetration | null {
+        return ShapePenetration.fromRaw(
+            this.colliderSet.raw.coPenetrationCollider(
+                this.handle,
+                collider2.handle,
+            ),
+        );
+    }
+
     /**
      * Find the closest intersection between a ray and this collider.
      *
@@ -1235,6 +1717,23 @@ export enum MassPropsMode {
     MassProps,
 }
 
+// #if DIM2
+/**
+ * How the colliders of a tilemap are built by `ColliderDesc.tilemap`.
+ */
+export enum TilemapMode {
+    /**
+     * A single polyline separating the filled tiles from the empty ones, without
+     * internal edges at tile seams.
+     */
+    Outline,
+    /**
+     * Cuboids covering the filled tiles, each merging as many tiles as possible.
+     */
+    Cuboids,
+}
+// #endif
+
 export class ColliderDesc {
     enabled: boolean;
     shape: Shape;
@@ -1259,6 +1758,10 @@ export class ColliderDesc {
     solverGroups: InteractionGroups;
     frictionCombineRule: CoefficientCombineRule;
     restitutionCombineRule: CoefficientCombineRule;
+    rollingResistance: number;
+    rollingResistanceCombineRule: CoefficientCombineRule;
+    dragCoefficient: number;
+    tags: number;
     activeEvents: ActiveEvents;
     activeHooks: ActiveHooks;
     activeCollisionTypes: ActiveCollisionTypes;
@@ -1284,6 +1787,10 @@ export class ColliderDesc {
         this.solverGroups = 0xffff_ffff;
         this.frictionCombineRule = CoefficientCombineRule.Average;
         this.restitutionCombineRule = CoefficientCombineRule.Average;
+        this.rollingResistance = 0.0;
+        this.rollingResistanceCombineRule = CoefficientCombineRule.Average;
+        this.dragCoefficient = 0.0;
+        this.tags = 0;
         this.activeCollisionTypes = ActiveCollisionTypes.DEFAULT;
         this.activeEvents = ActiveEvents.NONE;
         this.activeHooks = ActiveHooks.NONE;
@@ -1520,6 +2027,105 @@ export class ColliderDesc {
         return new ColliderDesc(shape);
     }
 
+    /**
+     * Creates the collider descriptors of a tilemap, merging its tiles so that
+     * tile-based levels don’t need one collider per tile.
+     *
+     * The tile `(x, y)` covers the area from `(x * tileSize.x, y * tileSize.y)`
+     * to `((x + 1) * tileSize.x, (y + 1) * tileSize.y)`. With
+     * `TilemapMode.Outline`, flat surfaces are made of a single segment, so
+     * objects sliding on them don’t snag on tile seams.
+     *
+     * @param occupancy - The `width * height` tiles, stored row by row. Any
+     *   non-zero value is a filled tile.
+     * @param width - The number of tiles along the `x` axis.
+     * @param height - The number of tiles along the `y` axis.
+     * @param tileSize - The dimensions of each tile.
+     * @param mode - How the colliders are built.
+     */
+    public static tilemap(
+        occupancy: Uint8Array,
+        width: number,
+        height: number,
+        tileSize: Vector,
+        mode: TilemapMode = TilemapMode.Outline,
+    ): ColliderDesc[] {
+        if (mode == TilemapMode.Outline) {
+            let rawTileSize = VectorOps.intoRaw(tileSize);
+            const rawMesh = RawShape.tilemapOutline(
+                occupancy,
+                width,
+                height,
+                rawTileSize,
+            );
+            rawTileSize.free();
+            const vertices = rawMesh.vertices();
+            const indices = rawMesh.indices();
+            rawMesh.free();
+            return indices.length == 0
+                ? []
+                : [ColliderDesc.polyline(vertices, indices)];
+        }
+
+        const rects = RawShape.tilemapRectangles(occupancy, width, height);
+        const result = [];
+        for (let i = 0; i < rects.length; i += 4) {
+            const hx = (rects[i + 2] * tileSize.x) / 2;
+            const hy = (rects[i + 3] * tileSize.y) / 2;
+            result.push(
+                ColliderDesc.cuboid(hx, hy).setTranslation(
+                    rects[i] * tileSize.x + hx,
+                    rects[i + 1] * tileSize.y + hy,
+                ),
+            );
+        }
+        return result;
+    }
+
+    /**
+     * Creates the collider descriptors of a polyline with a thickness, e.g., a
+     * hand-drawn stroke or the edge of a platform.
+     *
+     * Each segment of the polyline becomes a capsule with the given radius,
+     * positioned and oriented along the segment. Consecutive capsules overlap
+     * at their shared vertex, so the polyline has rounded joints without
+     * internal edges. The descriptors can be attached to the same rigid-body.
+     *
+     * @param vertices - The coordinates of the polyline's vertices.
+     * @param radius - The half-thickness of the polyline.
+     * @param indices - The indices of the vertices of each segment. If not
+     *   provided, consecutive vertices are linked by a segment.
+     */
+    public static roundPolyline(
+        vertices: Float64Array,
+        radius: number,
+        indices?: Uint32Array | null,
+    ): ColliderDesc[] {
+        const numVertices = vertices.length / 2;
+        const segments = [];
+        if (!!indices) {
+            for (let i = 0; i + 1 < indices.length; i += 2) {
+                segments.push([indices[i], indices[i + 1]]);
+            }
+        } else {
+            for (let i = 0; i + 1 < numVertices; i++) {
+                segments.push([i, i + 1]);
+            }
+        }
+
+        return segments.map(([ia, ib]) => {
+            const ax = vertices[ia * 2];
+            const ay = vertices[ia * 2 + 1];
+            const dx = vertices[ib * 2] - ax;
+            const dy = vertices[ib * 2 + 1] - ay;
+            const halfLength = Math.sqrt(dx * dx + dy * dy) / 2;
+            // The capsule axis is its local `y` axis.
+            return ColliderDesc.capsule(halfLength, radius)
+                .setTranslation(ax + dx / 2, ay + dy / 2)
+                .setRotation(Math.atan2(dy, dx) - Math.PI / 2);
+        });
+    }
+
     // #endif
 
     // #if DIM3
@@ -1908,6 +2514,66 @@ export class ColliderDesc {
         return this;
     }
 
+    /**
+     * Sets the rolling resistance coefficient of the collider to be created.
+     *
+     * Rolling resistance opposes the rolling of the rigid-bodies in contact with the collider,
+     * so balls and wheels slow down and stop naturally. The coefficient is the maximum resisting
+     * torque per unit of normal force, and has the dimension of a length: a small fraction of the
+     * radius of the rolling body is typical.
+     *
+     * @param rollingResistance - The rolling resistance coefficient. Must be greater or equal to 0.
+     */
+    public setRollingResistance(rollingResistance: number): ColliderDesc {
+        this.rollingResistance = rollingResistance;
+        return this;
+    }
+
+    /**
+     * Sets the rule used to combine the rolling resistance coefficients of two colliders
+     * involved in a contact.
+     *
+     * @param rule − The combine rule to apply.
+     */
+    public setRollingResistanceCombineRule(
+        rule: CoefficientCombineRule,
+    ): ColliderDesc {
+        this.rollingResistanceCombineRule = rule;
+        return this;
+    }
+
+    /**
+     * Sets the aerodynamic drag coefficient of the collider to be created.
+     *
+     * If it is positive, a quadratic drag force `0.5 * airDensity * dragCoefficient * area * speed²`
+     * opposing the velocity of the collider is applied to its rigid-body at each substep, where `area`
+     * is the cross-section of the collider’s bounding box along its velocity. This lets falling leaves
+     * or parachutes slow down plausibly. The air density is set with `World.airDensity`.
+     *
+     * @param dragCoefficient - The drag coefficient, typically between 0.5 and 1.5. Set it to 0 to
+     *                          disable the drag.
+     */
+    public setDragCoefficient(dragCoefficient: number): ColliderDesc {
+        this.dragCoefficient = dragCoefficient;
+        return this;
+    }
+
+    /**
+     * Sets the user tags of the collider to be created, as a 32-bit mask.
+     *
+     * Unlike the collision groups, the tags don’t affect the contacts. They are meant to
+     * categorize colliders for gameplay, e.g., to only hit enemies with a raycast: the scene
+     * queries of the `World` only consider the colliders with all the tags given by their
+     * `filterRequireTags` argument, and none of the tags given by their `filterExcludeTags`
+     * argument.
+     *
+     * @param tags - The 32-bit mask of the tags of the collider.
+     */
+    public setTags(tags: number): ColliderDesc {
+        this.tags = tags;
+        return this;
+    }
+
     /**
      * Sets the collision groups used by this collider.
      *
@@ -2,8 +2,14 @@ import {RawColliderSet} from "../raw";
 import {Coarena} from "../coarena";
 import {RotationOps, VectorOps} from "../math";
 import {Collider, ColliderDesc, ColliderHandle} from "./collider";
-import {ImpulseJointHandle, IslandManager, RigidBodyHandle} from "../dynamics";
-import {RigidBodySet} from "../dynamics";
+import {InteractionGroups} from "./interaction_groups";
+import {
+    ImpulseJointHandle,
+    IntegrationParameters,
+    IslandManager,
+    RigidBodyHandle,
+} from "../dynamics";
+import {CoefficientCombineRule, RigidBodySet} from "../dynamics";
 
 /**
  * A set of rigid bodies that can be handled by a physics pipeline.
@@ -137,6 +143,25 @@ export class ColliderSet {
         rawInertiaFrame.free();
         // #endif
 
+        if (
+            desc.rollingResistance != 0 ||
+            desc.rollingResistanceCombineRule != CoefficientCombineRule.Average
+        ) {
+            this.raw.coSetRollingResistance(handle, desc.rollingResistance);
+            this.raw.coSetRollingResistanceCombineRule(
+                handle,
+                desc.rollingResistanceCombineRule,
+            );
+        }
+
+        if (desc.dragCoefficient != 0) {
+            this.raw.coSetDragCoefficient(handle, desc.dragCoefficient);
+        }
+
+        if (desc.tags != 0) {
+            this.raw.coSetTags(handle, desc.tags);
+        }
+
         let parent = hasParent ? bodies.get(parentHandle) : null;
         let collider = new Collider(this, handle, parent, desc.shape);
         this.map.set(handle, collider);
@@ -168,6 +193,16 @@ export class ColliderSet {
         this.map.delete(handle);
     }
 
+    /**
+     * Internal function, do not call directly.
+     * @param handle
+     */
+    public mapInserted(handle: ColliderHandle, bodies: RigidBodySet) {
+        let collider = new Collider(this, handle, null);
+        collider.finalizeDeserialization(bodies);
+        this.map.set(handle, collider);
+    }
+
     /**
      * Gets the rigid-body with the given handle.
      *
@@ -184,6 +219,21 @@ export class ColliderSet {
         return this.map.len();
     }
 
+    /**
+     * The density of the fluid the colliders with an aerodynamic drag coefficient move through.
+     */
+    get airDensity(): number {
+        return this.raw.airDensity();
+    }
+
+    /**
+     * Sets the density of the fluid the colliders with an aerodynamic drag coefficient move
+     * through, e.g., `1.225` for air at sea level (the default) or `1000` for water, in kg/m³.
+     */
+    set airDensity(density: number) {
+        this.raw.setAirDensity(density);
+    }
+
     /**
      * Does this set contain a collider with the given handle?
      *
@@ -193,6 +243,74 @@ export class ColliderSet {
         return this.get(handle) != null;
     }
 
+    /**
+     * Sets the same collision groups to all the given colliders, in a single call.
+     *
+     * @param handles - The handles of the colliders to modify.
+     * @param groups - The collision groups to set.
+     */
+    public setCollisionGroups(
+        handles: ColliderHandle[],
+        groups: InteractionGroups,
+    ) {
+        this.raw.coSetCollisionGroupsBatch(new Float64Array(handles), groups);
+    }
+
+    /**
+     * Remaps the collision group bits of all the colliders, in a single pass.
+     *
+     * Both the memberships and the filters are remapped. For example, the table
+     * `[1, 0]` swaps the groups 0 and 1 of every collider, e.g., to switch two teams.
+     *
+     * @param table - The bit `i` of the groups is moved to the bit `table[i]`. The bits
+     *                without an entry in the table are kept in place, and the bits mapped
+     *                to an index greater than 15 are cleared.
+     * @param remapSolverGroups - If `true`, the solver groups are remapped too.
+     */
+    public remapCollisionGroups(table: number[], remapSolverGroups?: boolean) {
+        this.raw.remapCollisionGroups(
+            new Uint8Array(table),
+            !!remapSolverGroups,
+        );
+    }
+
+    /**
+     * The distance below which contacts are generated between two colliders, i.e.,
+     * the sum of their contact skins and of the prediction distance of the integration
+     * parameters, extended by their relative motion if soft-CCD is enabled.
+     *
+     * @param handle1 - The handle of the first collider.
+     * @param handle2 - The handle of the second collider.
+     * @param params - The integration parameters of the simulation.
+     * @param bodies - The set of rigid-bodies the colliders are attached to.
+     */
+    public effectivePredictionDistance(
+        handle1: ColliderHandle,
+        handle2: ColliderHandle,
+        params: IntegrationParameters,
+        bodies: RigidBodySet,
+    ): number {
+        return this.raw.coEffectivePredictionDistance(
+            handle1,
+            handle2,
+            params.raw,
+            bodies.raw,
+        );
+    }
+
+    /**
+     * Gets the shape types and primary shape parameters of all the colliders in a single call,
+     * e.g., to populate debug UIs and editors.
+     *
+     * For each collider, the array contains, in this order: its handle, its `ShapeType`, the
+     * half-extents of its cuboid (2 values in 2D, 3 in 3D), its radius, its half height, and
+     * the radius of its round edges. The parameters that don't apply to the shape of the
+     * collider are set to `NaN`.
+     */
+    public shapeSummaries(): Float64Array {
+        return this.raw.coShapeSummaries();
+    }
+
     /**
      * Applies the given closure to each collider contained by this set.
      *
@@ -1,5 +1,12 @@
 import {Vector, VectorOps} from "../math";
-import {RawShapeContact} from "../raw";
+import {
+    RawColliderShapeContact,
+    RawShapeContact,
+    RawShapeDepenetration,
+    RawShapePenetration,
+} from "../raw";
+import {Collider} from "./collider";
+import {ColliderSet} from "./collider_set";
 
 /**
  * The contact info between two shapes.
@@ -60,3 +67,130 @@ export class ShapeContact {
         return result;
     }
 }
+
+/**
+ * The contact info between a shape and the collider closest to it.
+ */
+export class ColliderShapeContact extends ShapeContact {
+    /**
+     * The collider closest to the shape.
+     */
+    collider: Collider;
+
+    constructor(
+        collider: Collider,
+        dist: number,
+        point1: Vector,
+        point2: Vector,
+        normal1: Vector,
+        normal2: Vector,
+    ) {
+        super(dist, point1, point2, normal1, normal2);
+        this.collider = collider;
+    }
+
+    public static fromRaw(
+        colliderSet: ColliderSet,
+        raw: RawColliderShapeContact,
+    ): ColliderShapeContact {
+        if (!raw) return null;
+
+        const result = new ColliderShapeContact(
+            colliderSet.get(raw.colliderHandle()),
+            raw.distance(),
+            VectorOps.fromRaw(raw.point1()),
+            VectorOps.fromRaw(raw.point2()),
+            VectorOps.fromRaw(raw.normal1()),
+            VectorOps.fromRaw(raw.normal2()),
+        );
+        raw.free();
+        return result;
+    }
+}
+
+/**
+ * The penetration between two overlapping shapes.
+ */
+export class ShapePenetration {
+    /**
+     * The penetration depth, i.e., the distance the first shape must be moved
+     * by to no longer overlap the second one.
+     */
+    depth: number;
+
+    /**
+     * The minimum translation vector: the smallest translation to apply to the
+     * first shape to separate it from the second one.
+     */
+    mtv: Vector;
+
+    constructor(depth: number, mtv: Vector) {
+        this.depth = depth;
+        this.mtv = mtv;
+    }
+
+    public static fromRaw(raw: RawShapePenetration): ShapePenetration {
+        if (!raw) return null;
+
+        const result = new ShapePenetration(
+            raw.depth(),
+            VectorOps.fromRaw(raw.mtv()),
+        );
+        raw.free();
+        return result;
+    }
+}
+
+/**
+ * The result of a placement test, telling if a shape can be placed without
+ * overlapping the existing colliders.
+ */
+export class ShapePlacement {
+    /**
+     * The handles of the colliders preventing the placement.
+     */
+    blockers: Float64Array;
+
+    constructor(blockers: Float64Array) {
+        this.blockers = blockers;
+    }
+
+    /**
+     * Can the shape be placed, i.e., is it blocked by no collider?
+     */
+    get canPlace(): boolean {
+        return this.blockers.length == 0;
+    }
+}
+
+/**
+ * The result of the depenetration of a shape from the colliders of the scene.
+ */
+export class ShapeDepenetration {
+    /**
+     * The corrected position of the shape.
+     */
+    position: Vector;
+
+    /**
+     * Were all the penetrations resolved? If `false`, `position` is the best
+     * position found within the allowed number of iterations.
+     */
+    resolved: boolean;
+
+    constructor(position: Vector, resolved: boolean) {
+        this.position = position;
+        this.resolved = resolved;
+    }
+
+    public static fromRaw(raw: RawShapeDepenetration): ShapeDepenetration {
+        if (!raw) return null;
+
+        const result = new ShapeDepenetration(
+            VectorOps.fromRaw(raw.position()),
+            raw.resolved(),
+        );
+        raw.free();
+        return result;
+    }
+}
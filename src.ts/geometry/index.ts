@@ -9,3 +9,4 @@ export * from "./point";
 export * from "./toi";
 export * from "./interaction_groups";
 export * from "./contact";
+export * from "./trigger_set";
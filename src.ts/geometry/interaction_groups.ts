@@ -16,3 +16,59 @@
  * ```
  */
 export type InteractionGroups = number;
+
+function groupBits(groups: number[]): number {
+    let bits = 0;
+    for (const group of groups) {
+        if (!Number.isInteger(group) || group < 0 || group >= 16) {
+            throw RangeError(
+                "Interaction group indices must be integers in [0, 16).",
+            );
+        }
+        bits |= 1 << group;
+    }
+    return bits;
+}
+
+function groupIndices(bits: number): number[] {
+    let result = [];
+    for (let group = 0; group < 16; ++group) {
+        if ((bits & (1 << group)) != 0) {
+            result.push(group);
+        }
+    }
+    return result;
+}
+
+/**
+ * Builds a packed `InteractionGroups` value from lists of group indices.
+ *
+ * @param memberships - The indices, in `[0, 16)`, of the groups the collider is part of.
+ * @param filter - The indices, in `[0, 16)`, of the groups the collider can interact with.
+ */
+export function interactionGroups(
+    memberships: number[],
+    filter: number[],
+): InteractionGroups {
+    return ((groupBits(memberships) << 16) | groupBits(filter)) >>> 0;
+}
+
+/**
+ * The indices of the groups the given interaction groups are part of.
+ *
+ * @param groups - The packed interaction groups.
+ */
+export function interactionGroupsMemberships(
+    groups: InteractionGroups,
+): number[] {
+    return groupIndices(groups >>> 16);
+}
+
+/**
+ * The indices of the groups the given interaction groups can interact with.
+ *
+ * @param groups - The packed interaction groups.
+ */
+export function interactionGroupsFilter(groups: InteractionGroups): number[] {
+    return groupIndices(groups & 0xffff);
+}
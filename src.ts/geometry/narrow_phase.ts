@@ -1,5 +1,9 @@
 import {RawNarrowPhase, RawContactManifold} from "../raw";
 import {ColliderHandle} from "./collider";
+import {ColliderSet} from "./collider_set";
+import {BroadPhase} from "./broad_phase";
+import {InteractionGroups} from "./interaction_groups";
+import {RigidBodyHandle, RigidBodySet} from "../dynamics";
 import {Vector, VectorOps} from "../math";
 
 /**
@@ -27,6 +31,287 @@ export class NarrowPhase {
         this.tempManifold = new TempContactManifold(null);
     }
 
+    /**
+     * Is the tracking of stable contact pair ids enabled?
+     */
+    get contactPairIdsEnabled(): boolean {
+        return this.raw.contactPairIdsEnabled();
+    }
+
+    /**
+     * Enables or disables the tracking of stable contact pair ids (disabled by default).
+     *
+     * When enabled, each pair of colliders that starts touching is assigned a unique id that stays
+     * the same across simulation steps until the colliders stop touching. The ids are updated at the
+     * end of each simulation step.
+     */
+    set contactPairIdsEnabled(enabled: boolean) {
+        this.raw.setContactPairIdsEnabled(enabled);
+    }
+
+    /**
+     * The stable id of the pair formed by two colliders in contact (or intersecting).
+     *
+     * The id of a pair that stopped touching during the last step can still be retrieved until the next step.
+     *
+     * @param collider1 - The first collider involved in the contact.
+     * @param collider2 - The second collider involved in the contact.
+     * @returns The pair id, or `null` if the colliders are not touching or if contact pair ids are disabled.
+     */
+    public contactPairId(
+        collider1: ColliderHandle,
+        collider2: ColliderHandle,
+    ): number | null {
+        let id = this.raw.contactPairId(collider1, collider2);
+        return id === undefined ? null : id;
+    }
+
+    /**
+     * Is the recording of impacts enabled?
+     */
+    get impactTrackingEnabled(): boolean {
+        return this.raw.impactTrackingEnabled();
+    }
+
+    /**
+     * Enables or disables the recording of impacts (disabled by default).
+     *
+     * When enabled, each pair of colliders that starts being in contact during a simulation step
+     * gets its impact recorded. Impacts can be read with `NarrowPhase.forEachImpact` until the next step.
+     */
+    set impactTrackingEnabled(enabled: boolean) {
+        this.raw.setImpactTrackingEnabled(enabled);
+    }
+
+    /**
+     * Applies the given closure to each impact recorded during the last simulation step.
+     *
+     * @param f - The closure called with the handles of the two colliders that started being in contact, the
+     *            sum of the normal impulses applied at their contact points, and their relative velocity
+     *            along the contact normal before the impact (negative when the colliders approach each other).
+     */
+    public forEachImpact(
+        f: (
+            collider1: ColliderHandle,
+            collider2: ColliderHandle,
+            normalImpulse: number,
+            relativeNormalVelocity: number,
+        ) => void,
+    ) {
+        this.raw.forEachImpact(f);
+    }
+
+    /**
+     * Is the accumulation of contact loads enabled?
+     */
+    get contactLoadsEnabled(): boolean {
+        return this.raw.contactLoadsEnabled();
+    }
+
+    /**
+     * Enables or disables the accumulation of contact loads (disabled by default).
+     *
+     * When enabled, the normal impulses applied between each pair of colliders in contact are summed
+     * over each simulation step, including all its substeps. They can be read with
+     * `NarrowPhase.contactLoad` and `NarrowPhase.bodyContactLoad` until the next step.
+     */
+    set contactLoadsEnabled(enabled: boolean) {
+        this.raw.setContactLoadsEnabled(enabled);
+    }
+
+    /**
+     * The total normal impulse applied between the two colliders during the last simulation step.
+     *
+     * Returns `undefined` if they weren’t in contact, or if the contact loads are disabled.
+     *
+     * @param collider1 - The first collider.
+     * @param collider2 - The second collider.
+     */
+    public contactLoad(
+        collider1: ColliderHandle,
+        collider2: ColliderHandle,
+    ): number | undefined {
+        return this.raw.contactLoad(collider1, collider2);
+    }
+
+    /**
+     * Applies the given closure to each pair of colliders in contact during the last simulation step.
+     *
+     * @param f - The closure called with the handles of the two colliders, and the total normal impulse
+     *            applied between them during the last step.
+     */
+    public forEachContactLoad(
+        f: (
+            collider1: ColliderHandle,
+            collider2: ColliderHandle,
+            normalImpulse: number,
+        ) => void,
+    ) {
+        this.raw.forEachContactLoad(f);
+    }
+
+    /**
+     * The sum of the normal impulses applied by all the contacts of the given rigid-body during the
+     * last simulation step.
+     *
+     * @param body - The handle of the rigid-body.
+     */
+    public bodyContactLoad(body: RigidBodyHandle): number {
+        return this.raw.bodyContactLoad(body);
+    }
+
+    /**
+     * Applies the given closure to each rigid-body in contact during the last simulation step.
+     *
+     * @param f - The closure called with the handle of the rigid-body, and the sum of the normal
+     *            impulses applied by its contacts during the last step.
+     */
+    public forEachBodyContactLoad(
+        f: (body: RigidBodyHandle, normalImpulse: number) => void,
+    ) {
+        this.raw.forEachBodyContactLoad(f);
+    }
+
+    // #if DIM3
+    /**
+     * Applies the given closure to each contact point corrected by the internal-edge correction of a
+     * triangle mesh or heightfield.
+     *
+     * A contact is reported if it lies on an edge or a vertex of a triangle, and its normal was aligned
+     * with the face normal of the triangle. The contacts with colliders without internal-edge correction
+     * aren’t reported.
+     *
+     * @param colliders - The set of colliders.
+     * @param f - The closure called with the handles of the triangle mesh or heightfield and of the other
+     *            collider, and the indices of the contact manifold and of the contact point in the
+     *            contact pair.
+     */
+    public forEachCorrectedContact(
+        colliders: ColliderSet,
+        f: (
+            meshCollider: ColliderHandle,
+            otherCollider: ColliderHandle,
+            manifoldIndex: number,
+            contactIndex: number,
+        ) => void,
+    ) {
+        this.raw.forEachCorrectedContact(colliders.raw, f);
+    }
+    // #endif
+
+    /**
+     * Is the recording of proximity events enabled?
+     */
+    get proximityEventsEnabled(): boolean {
+        return this.raw.proximityEventsEnabled();
+    }
+
+    /**
+     * Enables or disables the recording of proximity events (disabled by default).
+     *
+     * When enabled, each pair of colliders whose AABBs start or stop overlapping during a simulation step
+     * gets recorded. Events can be read with `NarrowPhase.forEachProximityEvent` until the next step.
+     */
+    set proximityEventsEnabled(enabled: boolean) {
+        this.raw.setProximityEventsEnabled(enabled);
+    }
+
+    /**
+     * Is the tracking of intersection durations enabled?
+     */
+    get intersectionDurationsEnabled(): boolean {
+        return this.raw.intersectionDurationsEnabled();
+    }
+
+    /**
+     * Enables or disables the tracking of the simulation time elapsed since each pair of colliders
+     * started intersecting (disabled by default).
+     *
+     * When enabled, the durations are reported by `NarrowPhase.intersectionPairsWith` and
+     * `NarrowPhase.intersectionDuration`.
+     */
+    set intersectionDurationsEnabled(enabled: boolean) {
+        this.raw.setIntersectionDurationsEnabled(enabled);
+    }
+
+    /**
+     * The simulation time elapsed since the two colliders started intersecting.
+     *
+     * The time is counted from the end of the substep during which the intersection started, so
+     * it is `0` after the step where the colliders started intersecting.
+     *
+     * Returns `undefined` if they aren’t intersecting, or if the intersection durations are disabled.
+     *
+     * @param collider1 - The first collider.
+     * @param collider2 - The second collider.
+     */
+    public intersectionDuration(
+        collider1: ColliderHandle,
+        collider2: ColliderHandle,
+    ): number | undefined {
+        return this.raw.intersectionDuration(collider1, collider2);
+    }
+
+    /**
+     * The handles of the rigid-bodies connected to the given rigid-body through the current contact
+     * graph, including itself.
+     *
+     * Two rigid-bodies are connected if any of their colliders have an active contact. The traversal
+     * doesn’t continue past the non-dynamic rigid-bodies other than the starting one, but these
+     * rigid-bodies are still part of the result.
+     *
+     * @param bodies - The set of rigid-bodies.
+     * @param colliders - The set of colliders.
+     * @param body - The handle of the rigid-body to start from.
+     * @param filterGroups - If set, only the contacts between colliders compatible with these
+     *   collision groups are traversed.
+     */
+    public connectedBodies(
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        body: RigidBodyHandle,
+        filterGroups?: InteractionGroups,
+    ): RigidBodyHandle[] {
+        return Array.from(
+            this.raw.connectedBodies(
+                bodies.raw,
+                colliders.raw,
+                body,
+                filterGroups,
+            ),
+        );
+    }
+
+    /**
+     * Applies the given closure to each proximity event recorded during the last simulation step.
+     *
+     * @param f - The closure called with the handles of the two colliders, and `true` if their AABBs
+     *            started overlapping, `false` if they stopped.
+     */
+    public forEachProximityEvent(
+        f: (
+            collider1: ColliderHandle,
+            collider2: ColliderHandle,
+            started: boolean,
+        ) => void,
+    ) {
+        this.raw.forEachProximityEvent(f);
+    }
+
+    /**
+     * The current contact points and normals, as line segments for debug-rendering.
+     *
+     * This is much cheaper than the full debug render pipeline. Each segment starts at a contact
+     * point given to the constraints solver and extends along the contact normal. Like
+     * `DebugRenderBuffers.vertices`, the result contains the two consecutive endpoints of each
+     * segment, each with two (in 2D) or three (in 3D) coordinates.
+     *
+     * @param normalLength - The length of the segments.
+     */
+    public contactNormalLines(normalLength: number = 1.0): Float64Array {
+        return this.raw.contactNormalLines(normalLength);
+    }
+
     /**
      * Enumerates all the colliders potentially in contact with the given collider.
      *
@@ -43,10 +328,15 @@ export class NarrowPhase {
     /**
      * Enumerates all the colliders intersecting the given colliders, assuming one of them
      * is a sensor.
+     *
+     * @param collider1 - The collider to check.
+     * @param f - Closure called with each collider intersecting `collider1`, and the simulation time
+     *            elapsed since they started intersecting if `NarrowPhase.intersectionDurationsEnabled`
+     *            is set.
      */
     public intersectionPairsWith(
         collider1: ColliderHandle,
-        f: (collider2: ColliderHandle) => void,
+        f: (collider2: ColliderHandle, duration?: number) => void,
     ) {
         this.raw.intersection_pairs_with(collider1, f);
     }
@@ -86,6 +376,49 @@ export class NarrowPhase {
         }
     }
 
+    /**
+     * Computes the contacts between the colliders at their current positions, without updating
+     * this narrow-phase nor advancing the simulation.
+     *
+     * @param broadPhase - The broad-phase used to find the pairs of colliders to test.
+     * @param bodies - The set of rigid-bodies taking part in the simulation.
+     * @param colliders - The set of colliders taking part in the simulation.
+     * @param prediction - The distance below which two colliders are considered in contact.
+     * @param f - Closure that will be called on each contact manifold, with the handles of the
+     *            two colliders. The manifold is only valid during the call.
+     */
+    public previewContacts(
+        broadPhase: BroadPhase,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        prediction: number,
+        f: (
+            collider1: ColliderHandle,
+            collider2: ColliderHandle,
+            manifold: TempContactManifold,
+        ) => void,
+    ) {
+        this.raw.previewContacts(
+            broadPhase.raw,
+            bodies.raw,
+            colliders.raw,
+            prediction,
+            (
+                collider1: ColliderHandle,
+                collider2: ColliderHandle,
+                rawManifold: RawContactManifold,
+            ) => {
+                this.tempManifold.raw = rawManifold;
+                f(collider1, collider2, this.tempManifold);
+
+                // SAFETY: The RawContactManifold stores a raw pointer that is only valid
+                //         during this call. So we must be sure to free it here to avoid
+                //         unsoundness in the Rust code.
+                this.tempManifold.free();
+            },
+        );
+    }
+
     /**
      * Returns `true` if `collider1` and `collider2` intersect and at least one of them is a sensor.
      * @param collider1 − The first collider involved in the intersection.
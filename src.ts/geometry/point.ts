@@ -21,9 +21,26 @@ export class PointProjection {
      */
     isInside: boolean;
 
-    constructor(point: Vector, isInside: boolean) {
+    /**
+     * The type of the geometric feature the point was projected on.
+     */
+    featureType = FeatureType.Unknown;
+
+    /**
+     * The id of the geometric feature the point was projected on.
+     */
+    featureId: number | undefined = undefined;
+
+    constructor(
+        point: Vector,
+        isInside: boolean,
+        featureType?: FeatureType,
+        featureId?: number,
+    ) {
         this.point = point;
         this.isInside = isInside;
+        if (featureId !== undefined) this.featureId = featureId;
+        if (featureType !== undefined) this.featureType = featureType;
     }
 
     public static fromRaw(raw: RawPointProjection): PointProjection {
@@ -32,6 +49,8 @@ export class PointProjection {
         const result = new PointProjection(
             VectorOps.fromRaw(raw.point()),
             raw.isInside(),
+            raw.featureType() as number as FeatureType,
+            raw.featureId(),
         );
         raw.free();
         return result;
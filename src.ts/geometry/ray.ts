@@ -7,6 +7,7 @@ import {
 } from "../raw";
 import {Collider} from "./collider";
 import {FeatureType} from "./feature";
+import {RigidBodyHandle} from "../dynamics";
 import {ColliderSet} from "./collider_set";
 
 /**
@@ -124,18 +125,32 @@ export class RayColliderIntersection {
      */
     featureId: number | undefined = undefined;
 
+    /**
+     * The material index of the triangle hit by the ray, if the collider has
+     * per-triangle materials.
+     */
+    material: number | null = null;
+
+    /**
+     * The handle of the rigid-body the hit collider is attached to, or `null` if it has no
+     * parent.
+     */
+    parentHandle: RigidBodyHandle | null = null;
+
     constructor(
         collider: Collider,
         timeOfImpact: number,
         normal: Vector,
         featureType?: FeatureType,
         featureId?: number,
+        material?: number,
     ) {
         this.collider = collider;
         this.timeOfImpact = timeOfImpact;
         this.normal = normal;
         if (featureId !== undefined) this.featureId = featureId;
         if (featureType !== undefined) this.featureType = featureType;
+        if (material !== undefined) this.material = material;
     }
 
     public static fromRaw(
@@ -144,13 +159,22 @@ export class RayColliderIntersection {
     ): RayColliderIntersection {
         if (!raw) return null;
 
+        const handle = raw.colliderHandle();
+        const featureType = raw.featureType() as number as FeatureType;
+        const featureId = raw.featureId();
+        const material =
+            featureType == FeatureType.Face
+                ? colliderSet.raw.coTriangleMaterial(handle, featureId)
+                : undefined;
         const result = new RayColliderIntersection(
-            colliderSet.get(raw.colliderHandle()),
+            colliderSet.get(handle),
             raw.time_of_impact(),
             VectorOps.fromRaw(raw.normal()),
-            raw.featureType() as number as FeatureType,
-            raw.featureId(),
+            featureType,
+            featureId,
+            material,
         );
+        result.parentHandle = raw.parentHandle() ?? null;
         raw.free();
         return result;
     }
@@ -170,6 +194,11 @@ export class RayColliderHit {
      * The hit point is obtained from the ray's origin and direction: `origin + dir * timeOfImpact`.
      */
     timeOfImpact: number;
+    /**
+     * The handle of the rigid-body the hit collider is attached to, or `null` if it has no
+     * parent.
+     */
+    parentHandle: RigidBodyHandle | null = null;
 
     constructor(collider: Collider, timeOfImpact: number) {
         this.collider = collider;
@@ -186,7 +215,89 @@ export class RayColliderHit {
             colliderSet.get(raw.colliderHandle()),
             raw.timeOfImpact(),
         );
+        result.parentHandle = raw.parentHandle() ?? null;
         raw.free();
         return result;
     }
 }
+
+/**
+ * A collider picked by a ray, e.g., under the mouse cursor.
+ */
+export class PickHit {
+    /**
+     * The collider hit by the ray.
+     */
+    collider: Collider;
+    /**
+     * The handle of the rigid-body the hit collider is attached to, or `null` if it has no
+     * parent.
+     */
+    parentHandle: RigidBodyHandle | null;
+    /**
+     * The hit point, in world-space.
+     */
+    point: Vector;
+    /**
+     * The normal of the collider at the hit point.
+     */
+    normal: Vector;
+    /**
+     * The distance between the origin of the ray and the hit point.
+     */
+    distance: number;
+
+    constructor(
+        collider: Collider,
+        parentHandle: RigidBodyHandle | null,
+        point: Vector,
+        normal: Vector,
+        distance: number,
+    ) {
+        this.collider = collider;
+        this.parentHandle = parentHandle;
+        this.point = point;
+        this.normal = normal;
+        this.distance = distance;
+    }
+
+    /**
+     * Reads the hits stored one after the other in the given array, as returned by the raw
+     * picking queries.
+     */
+    public static fromRawArray(
+        colliderSet: ColliderSet,
+        data: Float64Array,
+    ): PickHit[] {
+        const hits: PickHit[] = [];
+        // #if DIM2
+        const stride = 7;
+        // #endif
+        // #if DIM3
+        const stride = 9;
+        // #endif
+
+        for (let i = 0; i + stride <= data.length; i += stride) {
+            const parent = data[i + 1];
+            // #if DIM2
+            const point = VectorOps.new(data[i + 2], data[i + 3]);
+            const normal = VectorOps.new(data[i + 4], data[i + 5]);
+            // #endif
+            // #if DIM3
+            const point = VectorOps.new(data[i + 2], data[i + 3], data[i + 4]);
+            const normal = VectorOps.new(data[i + 5], data[i + 6], data[i + 7]);
+            // #endif
+            hits.push(
+                new PickHit(
+                    colliderSet.get(data[i]),
+                    isNaN(parent) ? null : parent,
+                    point,
+                    normal,
+                    data[i + stride - 1],
+                ),
+            );
+        }
+
+        return hits;
+    }
+}
@@ -1,6 +1,6 @@
 import {Vector, VectorOps, Rotation, RotationOps} from "../math";
 import {RawColliderSet, RawShape, RawShapeType} from "../raw";
-import {ShapeContact} from "./contact";
+import {ShapeContact, ShapePenetration} from "./contact";
 import {PointProjection} from "./point";
 import {Ray, RayIntersection} from "./ray";
 import {ShapeCastHit} from "./toi";
@@ -365,6 +365,53 @@ export abstract class Shape {
         return result;
     }
 
+    /**
+     * Computes the penetration depth and the minimum translation vector needed to separate
+     * this shape from another one.
+     *
+     * @param shapePos1 - The position of this shape.
+     * @param shapeRot1 - The rotation of this shape.
+     * @param shape2 - The second shape.
+     * @param shapePos2 - The position of the second shape.
+     * @param shapeRot2 - The rotation of the second shape.
+     * @returns `null` if the shapes don't overlap, otherwise the penetration details, in world-space.
+     */
+    penetrationShape(
+        shapePos1: Vector,
+        shapeRot1: Rotation,
+        shape2: Shape,
+        shapePos2: Vector,
+        shapeRot2: Rotation,
+    ): ShapePenetration | null {
+        let rawPos1 = VectorOps.intoRaw(shapePos1);
+        let rawRot1 = RotationOps.intoRaw(shapeRot1);
+        let rawPos2 = VectorOps.intoRaw(shapePos2);
+        let rawRot2 = RotationOps.intoRaw(shapeRot2);
+
+        let rawShape1 = this.intoRaw();
+        let rawShape2 = shape2.intoRaw();
+
+        let result = ShapePenetration.fromRaw(
+            rawShape1.penetrationShape(
+                rawPos1,
+                rawRot1,
+                rawShape2,
+                rawPos2,
+                rawRot2,
+            ),
+        );
+
+        rawPos1.free();
+        rawRot1.free();
+        rawPos2.free();
+        rawRot2.free();
+
+        rawShape1.free();
+        rawShape2.free();
+
+        return result;
+    }
+
     containsPoint(
         shapePos: Vector,
         shapeRot: Rotation,
@@ -408,6 +455,28 @@ export abstract class Shape {
         return result;
     }
 
+    projectPointAndGetFeature(
+        shapePos: Vector,
+        shapeRot: Rotation,
+        point: Vector,
+    ): PointProjection {
+        let rawPos = VectorOps.intoRaw(shapePos);
+        let rawRot = RotationOps.intoRaw(shapeRot);
+        let rawPoint = VectorOps.intoRaw(point);
+        let rawShape = this.intoRaw();
+
+        let result = PointProjection.fromRaw(
+            rawShape.projectPointAndGetFeature(rawPos, rawRot, rawPoint),
+        );
+
+        rawPos.free();
+        rawRot.free();
+        rawPoint.free();
+        rawShape.free();
+
+        return result;
+    }
+
     intersectsRay(
         ray: Ray,
         shapePos: Vector,
@@ -1050,6 +1119,73 @@ export class Voxels extends Shape {
         this.voxelSize = voxelSize;
     }
 
+    // #if DIM2
+    /**
+     * Creates a new shape made of the voxels filling the space under the surface
+     * of a heightfield, down to its lowest point.
+     *
+     * A voxel is filled if its center is under the surface of the heightfield.
+     *
+     * @param heights - The heights of the heightfield, like for `Heightfield`.
+     * @param scale - The scale factor applied to the heightfield.
+     * @param voxelSize - The size of each voxel.
+     */
+    public static fromHeightfield(
+        heights: Float64Array,
+        scale: Vector,
+        voxelSize: Vector,
+    ): Voxels {
+        let rawScale = VectorOps.intoRaw(scale);
+        let rawVoxelSize = VectorOps.intoRaw(voxelSize);
+        const data = RawShape.voxelsFromHeightfield(
+            heights,
+            rawScale,
+            rawVoxelSize,
+        );
+        rawScale.free();
+        rawVoxelSize.free();
+        return new Voxels(data, voxelSize);
+    }
+    // #endif
+
+    // #if DIM3
+    /**
+     * Creates a new shape made of the voxels filling the space under the surface
+     * of a heightfield, down to its lowest point.
+     *
+     * A voxel is filled if its center is under the surface of the heightfield.
+     *
+     * @param nrows − The number of rows in the heights matrix.
+     * @param ncols - The number of columns in the heights matrix.
+     * @param heights - The heights of the heightfield, like for `Heightfield`.
+     * @param scale - The dimensions of the heightfield's local `x,z` plane.
+     * @param voxelSize - The size of each voxel.
+     * @param flags - The flags of the heightfield.
+     */
+    public static fromHeightfield(
+        nrows: number,
+        ncols: number,
+        heights: Float64Array,
+        scale: Vector,
+        voxelSize: Vector,
+        flags?: HeightFieldFlags,
+    ): Voxels {
+        let rawScale = VectorOps.intoRaw(scale);
+        let rawVoxelSize = VectorOps.intoRaw(voxelSize);
+        const data = RawShape.voxelsFromHeightfield(
+            nrows,
+            ncols,
+            heights,
+            rawScale,
+            flags,
+            rawVoxelSize,
+        );
+        rawScale.free();
+        rawVoxelSize.free();
+        return new Voxels(data, voxelSize);
+    }
+    // #endif
+
     public intoRaw(): RawShape {
         let voxelSize = VectorOps.intoRaw(this.voxelSize);
 
@@ -1106,6 +1242,25 @@ export class TriMesh extends Shape {
     public intoRaw(): RawShape {
         return RawShape.trimesh(this.vertices, this.indices, this.flags);
     }
+
+    // #if DIM2
+    /**
+     * Triangulates a simple polygon by ear-clipping, e.g., a piece of level
+     * geometry drawn as an outline.
+     *
+     * @param vertices - The coordinates of the polygon's vertices, in clockwise
+     *   or counter-clockwise order.
+     * @returns The triangle mesh, or `null` if the polygon isn’t simple or has a
+     *   zero area.
+     */
+    public static fromPolygon(
+        vertices: Float64Array,
+        flags?: TriMeshFlags,
+    ): TriMesh | null {
+        const indices = RawShape.polygonTriangulation(vertices);
+        return !!indices ? new TriMesh(vertices, indices, flags) : null;
+    }
+    // #endif
 }
 
 // #if DIM2
@@ -1139,6 +1294,35 @@ export class ConvexPolygon extends Shape {
         this.skipConvexHullComputation = !!skipConvexHullComputation;
     }
 
+    /**
+     * Decomposes a simple polygon into convex polygons, e.g., a piece of level
+     * geometry drawn as an outline.
+     *
+     * The polygon is triangulated, then its triangles are merged as long as they
+     * stay convex. The resulting convex polygons can be attached to the same
+     * rigid-body as separate colliders.
+     *
+     * @param vertices - The coordinates of the polygon's vertices, in clockwise
+     *   or counter-clockwise order.
+     * @returns The convex polygons, or `null` if the polygon isn’t simple or has
+     *   a zero area.
+     */
+    public static decomposePolygon(
+        vertices: Float64Array,
+    ): ConvexPolygon[] | null {
+        const rawParts = RawShape.polygonDecomposition(vertices);
+        if (!rawParts) {
+            return null;
+        }
+
+        const parts = [];
+        for (let i = 0; i < rawParts.numParts(); i++) {
+            parts.push(new ConvexPolygon(rawParts.part(i), true));
+        }
+        rawParts.free();
+        return parts;
+    }
+
     public intoRaw(): RawShape {
         if (this.skipConvexHullComputation) {
             return RawShape.convexPolyline(this.vertices);
@@ -1235,6 +1419,23 @@ export class Heightfield extends Shape {
         rawScale.free();
         return rawShape;
     }
+
+    /**
+     * The segments of this heightfield, as a polyline.
+     *
+     * @returns The coordinates of the polyline vertices, and two indices per segment.
+     */
+    public toTrimesh(): {vertices: Float64Array; indices: Uint32Array} {
+        let rawScale = VectorOps.intoRaw(this.scale);
+        let rawMesh = RawShape.heightfieldToTrimesh(this.heights, rawScale);
+        rawScale.free();
+        const result = {
+            vertices: rawMesh.vertices(),
+            indices: rawMesh.indices(),
+        };
+        rawMesh.free();
+        return result;
+    }
 }
 
 // #endif
@@ -1403,6 +1604,28 @@ export class Heightfield extends Shape {
         rawScale.free();
         return rawShape;
     }
+
+    /**
+     * The triangles of this heightfield, as a triangle mesh.
+     *
+     * @returns The coordinates of the mesh vertices, and three indices per triangle.
+     */
+    public toTrimesh(): {vertices: Float64Array; indices: Uint32Array} {
+        let rawScale = VectorOps.intoRaw(this.scale);
+        let rawMesh = RawShape.heightfieldToTrimesh(
+            this.nrows,
+            this.ncols,
+            this.heights,
+            rawScale,
+        );
+        rawScale.free();
+        const result = {
+            vertices: rawMesh.vertices(),
+            indices: rawMesh.indices(),
+        };
+        rawMesh.free();
+        return result;
+    }
 }
 
 /**
@@ -2,6 +2,8 @@ import {Collider} from "./collider";
 import {Vector, VectorOps} from "../math";
 import {RawShapeCastHit, RawColliderShapeCastHit} from "../raw";
 import {ColliderSet} from "./collider_set";
+import {FeatureType} from "./feature";
+import {RigidBodyHandle} from "../dynamics";
 
 /**
  * The intersection between a ray and a collider.
@@ -31,6 +33,22 @@ export class ShapeCastHit {
      * the time of impact.
      */
     normal2: Vector;
+    /**
+     * The type of the geometric feature of the first shape touched at the time of impact.
+     */
+    featureType1 = FeatureType.Unknown;
+    /**
+     * The id of the geometric feature of the first shape touched at the time of impact.
+     */
+    featureId1: number | undefined = undefined;
+    /**
+     * The type of the geometric feature of the second shape touched at the time of impact.
+     */
+    featureType2 = FeatureType.Unknown;
+    /**
+     * The id of the geometric feature of the second shape touched at the time of impact.
+     */
+    featureId2: number | undefined = undefined;
 
     constructor(
         time_of_impact: number,
@@ -38,12 +56,20 @@ export class ShapeCastHit {
         witness2: Vector,
         normal1: Vector,
         normal2: Vector,
+        featureType1?: FeatureType,
+        featureId1?: number,
+        featureType2?: FeatureType,
+        featureId2?: number,
     ) {
         this.time_of_impact = time_of_impact;
         this.witness1 = witness1;
         this.witness2 = witness2;
         this.normal1 = normal1;
         this.normal2 = normal2;
+        if (featureType1 !== undefined) this.featureType1 = featureType1;
+        if (featureId1 !== undefined) this.featureId1 = featureId1;
+        if (featureType2 !== undefined) this.featureType2 = featureType2;
+        if (featureId2 !== undefined) this.featureId2 = featureId2;
     }
 
     public static fromRaw(
@@ -58,6 +84,10 @@ export class ShapeCastHit {
             VectorOps.fromRaw(raw.witness2()),
             VectorOps.fromRaw(raw.normal1()),
             VectorOps.fromRaw(raw.normal2()),
+            raw.featureType1() as number as FeatureType,
+            raw.featureId1(),
+            raw.featureType2() as number as FeatureType,
+            raw.featureId2(),
         );
         raw.free();
         return result;
@@ -72,6 +102,31 @@ export class ColliderShapeCastHit extends ShapeCastHit {
      * The handle of the collider hit by the ray.
      */
     collider: Collider;
+    /**
+     * The contact point on the collider hit, at the time of impact, in
+     * its local-space.
+     */
+    localWitness1: Vector;
+    /**
+     * The contact point on the cast shape, at the time of impact, in
+     * its local-space.
+     */
+    localWitness2: Vector;
+    /**
+     * The outward normal on the collider hit, at the time of impact, in
+     * its local-space.
+     */
+    localNormal1: Vector;
+    /**
+     * The outward normal on the cast shape, at the time of impact, in
+     * its local-space.
+     */
+    localNormal2: Vector;
+    /**
+     * The handle of the rigid-body the hit collider is attached to, or `null` if it has no
+     * parent.
+     */
+    parentHandle: RigidBodyHandle | null = null;
 
     constructor(
         collider: Collider,
@@ -80,9 +135,35 @@ export class ColliderShapeCastHit extends ShapeCastHit {
         witness2: Vector,
         normal1: Vector,
         normal2: Vector,
+        localWitness1?: Vector,
+        localWitness2?: Vector,
+        localNormal1?: Vector,
+        localNormal2?: Vector,
+        featureType1?: FeatureType,
+        featureId1?: number,
+        featureType2?: FeatureType,
+        featureId2?: number,
     ) {
-        super(time_of_impact, witness1, witness2, normal1, normal2);
+        super(
+            time_of_impact,
+            witness1,
+            witness2,
+            normal1,
+            normal2,
+            featureType1,
+            featureId1,
+            featureType2,
+            featureId2,
+        );
         this.collider = collider;
+        this.localWitness1 =
+            localWitness1 !== undefined ? localWitness1 : witness1;
+        this.localWitness2 =
+            localWitness2 !== undefined ? localWitness2 : witness2;
+        this.localNormal1 =
+            localNormal1 !== undefined ? localNormal1 : normal1;
+        this.localNormal2 =
+            localNormal2 !== undefined ? localNormal2 : normal2;
     }
 
     public static fromRaw(
@@ -98,7 +179,16 @@ export class ColliderShapeCastHit extends ShapeCastHit {
             VectorOps.fromRaw(raw.witness2()),
             VectorOps.fromRaw(raw.normal1()),
             VectorOps.fromRaw(raw.normal2()),
+            VectorOps.fromRaw(raw.localWitness1()),
+            VectorOps.fromRaw(raw.localWitness2()),
+            VectorOps.fromRaw(raw.localNormal1()),
+            VectorOps.fromRaw(raw.localNormal2()),
+            raw.featureType1() as number as FeatureType,
+            raw.featureId1(),
+            raw.featureType2() as number as FeatureType,
+            raw.featureId2(),
         );
+        result.parentHandle = raw.parentHandle() ?? null;
         raw.free();
         return result;
     }
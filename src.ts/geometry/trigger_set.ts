@@ -0,0 +1,122 @@
+import {RawTriggerSet} from "../raw";
+import {Collider, ColliderHandle} from "./collider";
+import {ColliderSet} from "./collider_set";
+import {NarrowPhase} from "./narrow_phase";
+
+/**
+ * A set of sensors tracking the colliders overlapping each of them.
+ *
+ * After each update, the colliders that started overlapping a sensor, stopped overlapping it,
+ * or kept overlapping it since the previous update can be listed, without having to accumulate
+ * intersection events. The trigger sets created with `World.createTriggerSet` are updated
+ * automatically at the end of each `World.step`.
+ *
+ * To avoid leaking WASM resources, this MUST be freed manually with `triggerSet.free()`
+ * once you are done using it, unless it was created by the physics world.
+ */
+export class TriggerSet {
+    raw: RawTriggerSet;
+
+    /**
+     * Release the WASM memory occupied by this trigger set.
+     */
+    public free() {
+        if (!!this.raw) {
+            this.raw.free();
+        }
+        this.raw = undefined;
+    }
+
+    constructor(raw?: RawTriggerSet) {
+        this.raw = raw || new RawTriggerSet();
+    }
+
+    /**
+     * Registers a sensor (or any collider) to track.
+     *
+     * The colliders already overlapping it are reported as entered at the next update.
+     *
+     * @param sensor - The collider to track.
+     */
+    public addSensor(sensor: Collider) {
+        this.raw.addSensor(sensor.handle);
+    }
+
+    /**
+     * Stops tracking the given collider.
+     *
+     * @param sensor - The collider to stop tracking.
+     */
+    public removeSensor(sensor: Collider) {
+        this.raw.removeSensor(sensor.handle);
+    }
+
+    /**
+     * Is the given collider tracked by this trigger set?
+     *
+     * @param sensor - The collider to check.
+     */
+    public contains(sensor: Collider): boolean {
+        return this.raw.contains(sensor.handle);
+    }
+
+    /**
+     * The number of sensors tracked by this trigger set.
+     */
+    public len(): number {
+        return this.raw.len();
+    }
+
+    /**
+     * Updates the colliders overlapping each sensor from the current intersections and active
+     * contacts of the narrow-phase.
+     *
+     * The sensors removed from the collider set since the previous update report all their
+     * colliders as exited, and are then unregistered.
+     *
+     * @param narrowPhase - The narrow-phase of the physics world.
+     * @param colliders - The set of colliders of the physics world.
+     */
+    public update(narrowPhase: NarrowPhase, colliders: ColliderSet) {
+        this.raw.update(narrowPhase.raw, colliders.raw);
+    }
+
+    /**
+     * The handles of the colliders currently overlapping the given sensor.
+     *
+     * @param sensor - The handle of the sensor.
+     */
+    public overlapping(sensor: ColliderHandle): Float64Array {
+        return this.raw.overlapping(sensor);
+    }
+
+    /**
+     * The handles of the colliders that started overlapping the given sensor during the last
+     * update.
+     *
+     * @param sensor - The handle of the sensor.
+     */
+    public entered(sensor: ColliderHandle): Float64Array {
+        return this.raw.entered(sensor);
+    }
+
+    /**
+     * The handles of the colliders that stopped overlapping the given sensor during the last
+     * update. They may have been removed from the physics world.
+     *
+     * @param sensor - The handle of the sensor.
+     */
+    public exited(sensor: ColliderHandle): Float64Array {
+        return this.raw.exited(sensor);
+    }
+
+    /**
+     * The handles of the colliders that kept overlapping the given sensor during the last
+     * update.
+     *
+     * @param sensor - The handle of the sensor.
+     */
+    public stayed(sensor: ColliderHandle): Float64Array {
+        return this.raw.stayed(sensor);
+    }
+}
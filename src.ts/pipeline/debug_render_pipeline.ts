@@ -1,4 +1,4 @@
-import {RawDebugRenderPipeline} from "../raw";
+import {RawDebugRenderPipeline, RawRigidBodyType} from "../raw";
 import {Vector, VectorOps} from "../math";
 import {
     IntegrationParameters,
@@ -6,10 +6,46 @@ import {
     ImpulseJointSet,
     MultibodyJointSet,
     RigidBodySet,
+    RigidBodyType,
 } from "../dynamics";
 import {BroadPhase, Collider, ColliderSet, NarrowPhase} from "../geometry";
 import {QueryFilterFlags} from "./query_pipeline";
 
+/**
+ * Flags selecting the categories of objects rendered by the debug render pipeline.
+ */
+export enum DebugRenderCategory {
+    NONE = 0,
+    /**
+     * The shapes of the non-sensor colliders.
+     */
+    COLLIDERS = 1 << 0,
+    /**
+     * The shapes of the sensor colliders.
+     */
+    SENSORS = 1 << 1,
+    /**
+     * The impulse and multibody joints.
+     */
+    JOINTS = 1 << 2,
+    /**
+     * The contact points and normals.
+     */
+    CONTACTS = 1 << 3,
+    /**
+     * The AABBs of the colliders.
+     */
+    AABBS = 1 << 4,
+    /**
+     * The local axes of the rigid-bodies.
+     */
+    RIGID_BODY_AXES = 1 << 5,
+    /**
+     * The contacts given to the constraints solver.
+     */
+    SOLVER_CONTACTS = 1 << 6,
+}
+
 /**
  * The vertex and color buffers for debug-redering the physics scene.
  */
@@ -61,6 +97,81 @@ export class DebugRenderPipeline {
         this.raw = raw || new RawDebugRenderPipeline();
     }
 
+    /**
+     * The categories of objects being rendered.
+     *
+     * Defaults to `COLLIDERS | SENSORS | JOINTS | RIGID_BODY_AXES`.
+     */
+    get categories(): DebugRenderCategory {
+        return this.raw.categories();
+    }
+
+    /**
+     * Sets the categories of objects to render, e.g.,
+     * `DebugRenderCategory.COLLIDERS | DebugRenderCategory.CONTACTS`.
+     */
+    set categories(categories: DebugRenderCategory) {
+        this.raw.setCategories(categories);
+    }
+
+    /**
+     * Renders the colliders member of at least one of the given collision groups, and their AABBs,
+     * with the given color.
+     *
+     * Style overrides are checked in insertion order, and the first matching one applies.
+     *
+     * @param groups - Bit mask of the 16 collision groups, i.e., the memberships part of an
+     *                 `InteractionGroups` without the 16-bits shift.
+     * @param r - The red component of the color, in `[0, 1]`.
+     * @param g - The green component of the color, in `[0, 1]`.
+     * @param b - The blue component of the color, in `[0, 1]`.
+     * @param a - The alpha component of the color, in `[0, 1]`.
+     */
+    public addStyleOverrideForGroups(
+        groups: number,
+        r: number,
+        g: number,
+        b: number,
+        a: number = 1.0,
+    ) {
+        this.raw.addStyleOverrideForGroups(groups, r, g, b, a);
+    }
+
+    /**
+     * Renders the rigid-bodies of the given type, the colliders attached to them, and their AABBs,
+     * with the given color.
+     *
+     * Style overrides are checked in insertion order, and the first matching one applies.
+     *
+     * @param bodyType - The type of rigid-bodies to render with this color.
+     * @param r - The red component of the color, in `[0, 1]`.
+     * @param g - The green component of the color, in `[0, 1]`.
+     * @param b - The blue component of the color, in `[0, 1]`.
+     * @param a - The alpha component of the color, in `[0, 1]`.
+     */
+    public addStyleOverrideForBodyType(
+        bodyType: RigidBodyType,
+        r: number,
+        g: number,
+        b: number,
+        a: number = 1.0,
+    ) {
+        this.raw.addStyleOverrideForBodyType(
+            bodyType as number as RawRigidBodyType,
+            r,
+            g,
+            b,
+            a,
+        );
+    }
+
+    /**
+     * Removes all the style overrides.
+     */
+    public clearStyleOverrides() {
+        this.raw.clearStyleOverrides();
+    }
+
     public render(
         bodies: RigidBodySet,
         colliders: ColliderSet,
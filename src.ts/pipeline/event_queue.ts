@@ -1,6 +1,6 @@
 import {RawContactForceEvent, RawEventQueue} from "../raw";
 import {RigidBodyHandle} from "../dynamics";
-import {Collider, ColliderHandle} from "../geometry";
+import {Collider, ColliderHandle, NarrowPhase} from "../geometry";
 import {Vector, VectorOps} from "../math";
 
 /**
@@ -114,6 +114,118 @@ export class EventQueue {
         this.raw = undefined;
     }
 
+    /**
+     * Only report the events involving the given collider, in addition to the other registered interests.
+     *
+     * If no interest is registered, the events involving any collider are reported. Filtering happens
+     * before the events are collected, so uninteresting events never reach JavaScript.
+     *
+     * @param collider - The handle of the collider of interest.
+     */
+    public addInterestCollider(collider: ColliderHandle) {
+        this.raw.addInterestCollider(collider);
+    }
+
+    /**
+     * Removes a collider from the colliders of interest.
+     *
+     * @param collider - The handle of the collider to remove.
+     */
+    public removeInterestCollider(collider: ColliderHandle) {
+        this.raw.removeInterestCollider(collider);
+    }
+
+    /**
+     * Only report the events involving a collider member of at least one of the given collision groups,
+     * in addition to the other registered interests.
+     *
+     * @param groups - Bit mask of the collision groups of interest, `0` to disable this interest.
+     */
+    public setInterestGroups(groups: number) {
+        this.raw.setInterestGroups(groups);
+    }
+
+    /**
+     * The bit mask of the collision groups of interest.
+     */
+    public interestGroups(): number {
+        return this.raw.interestGroups();
+    }
+
+    /**
+     * Removes all the registered interests, so that all the events are reported again.
+     */
+    public clearInterests() {
+        this.raw.clearInterests();
+    }
+
+    /**
+     * Adds a contact force event threshold for the pairs of colliders members of `groups1` and
+     * `groups2`, in addition to the thresholds of the colliders.
+     *
+     * The contact force events of these pairs are only reported if the total force magnitude reaches
+     * `threshold`. Contact force events are only generated above the smallest threshold of the two
+     * colliders, so this can only make the pairs less sensitive. If several thresholds apply to a
+     * pair, the largest one is used.
+     *
+     * @param groups1 - Bit mask of collision groups.
+     * @param groups2 - Bit mask of collision groups.
+     * @param threshold - The minimum total force magnitude of the reported events.
+     * @returns The id of the threshold, to remove it with `removeContactForceThreshold`.
+     */
+    public addContactForceThreshold(
+        groups1: number,
+        groups2: number,
+        threshold: number,
+    ): number {
+        return this.raw.addContactForceThreshold(groups1, groups2, threshold);
+    }
+
+    /**
+     * Removes a contact force event threshold.
+     *
+     * @param id - The id returned by `addContactForceThreshold`.
+     */
+    public removeContactForceThreshold(id: number) {
+        this.raw.removeContactForceThreshold(id);
+    }
+
+    /**
+     * Removes all the contact force event thresholds per pair of collision groups.
+     */
+    public clearContactForceThresholds() {
+        this.raw.clearContactForceThresholds();
+    }
+
+    /**
+     * The number of contact force event thresholds per pair of collision groups.
+     */
+    public numContactForceThresholds(): number {
+        return this.raw.numContactForceThresholds();
+    }
+
+    /**
+     * Enables the tagging of the events with the substep during which they were generated
+     * (disabled by default).
+     *
+     * When enabled, the closures given to the drain methods also receive the index of the
+     * substep in its step, and the simulation time at the end of that substep. This allows
+     * ordering the events of pairs of colliders that start and stop touching during the same
+     * step. The events already in the queue are removed when this is changed.
+     *
+     * @param enabled - Whether the events are tagged with their substep.
+     */
+    public setSubstepTagsEnabled(enabled: boolean) {
+        this.raw.setSubstepTagsEnabled(enabled);
+    }
+
+    /**
+     * Are the events tagged with the substep during which they were generated?
+     */
+    public substepTagsEnabled(): boolean {
+        return this.raw.substepTagsEnabled();
+    }
+
     /**
      * Applies the given javascript closure on each collision event of this collector, then clear
      * the internal collision event buffer.
@@ -121,32 +233,74 @@ export class EventQueue {
      * @param f - JavaScript closure applied to each collision event. The
      * closure must take three arguments: two integers representing the handles of the colliders
      * involved in the collision, and a boolean indicating if the collision started (true) or stopped
-     * (false).
+     * (false). If substep tags are enabled, it also receives the index of the substep during which
+     * the event was generated, and the simulation time at the end of that substep.
      */
     public drainCollisionEvents(
         f: (
             handle1: ColliderHandle,
             handle2: ColliderHandle,
             started: boolean,
+            substep?: number,
+            time?: number,
         ) => void,
     ) {
         this.raw.drainCollisionEvents(f);
     }
 
+    /**
+     * Applies the given javascript closure on each collision event of this collector, then clear
+     * the internal collision event buffer.
+     *
+     * This is similar to `drainCollisionEvents` except that the closure also receives the stable id
+     * of the pair of colliders. This requires `narrowPhase.contactPairIdsEnabled` to be set.
+     *
+     * @param narrowPhase - The narrow-phase of the world that generated the events.
+     * @param f - JavaScript closure applied to each collision event. The
+     * closure must take four arguments: two integers representing the handles of the colliders
+     * involved in the collision, a boolean indicating if the collision started (true) or stopped
+     * (false), and the id of the pair of colliders (`undefined` if contact pair ids are disabled).
+     * If substep tags are enabled, it also receives the index of the substep during which the event
+     * was generated, and the simulation time at the end of that substep.
+     */
+    public drainCollisionEventsWithPairIds(
+        narrowPhase: NarrowPhase,
+        f: (
+            handle1: ColliderHandle,
+            handle2: ColliderHandle,
+            started: boolean,
+            pairId: number | undefined,
+            substep?: number,
+            time?: number,
+        ) => void,
+    ) {
+        this.raw.drainCollisionEventsWithPairIds(narrowPhase.raw, f);
+    }
+
     /**
      * Applies the given javascript closure on each contact force event of this collector, then clear
      * the internal collision event buffer.
      *
      * @param f - JavaScript closure applied to each collision event. The
-     *            closure must take one `TempContactForceEvent` argument.
+     *            closure must take one `TempContactForceEvent` argument. If substep tags are
+     *            enabled, it also receives the index of the substep during which the event was
+     *            generated, and the simulation time at the end of that substep.
      */
-    public drainContactForceEvents(f: (event: TempContactForceEvent) => void) {
+    public drainContactForceEvents(
+        f: (
+            event: TempContactForceEvent,
+            substep?: number,
+            time?: number,
+        ) => void,
+    ) {
         let event = new TempContactForceEvent();
-        this.raw.drainContactForceEvents((raw: RawContactForceEvent) => {
-            event.raw = raw;
-            f(event);
-            event.free();
-        });
+        this.raw.drainContactForceEvents(
+            (raw: RawContactForceEvent, substep?: number, time?: number) => {
+                event.raw = raw;
+                f(event, substep, time);
+                event.free();
+            },
+        );
     }
 
     /**
@@ -18,6 +18,22 @@ import {
 import {EventQueue} from "./event_queue";
 import {PhysicsHooks} from "./physics_hooks";
 
+/**
+ * A function called before each substep to compute forces applied during that substep only.
+ *
+ * @param substep - The index of the substep, starting at 0.
+ * @param dt - The timestep of the substep.
+ * @param states - For each awake dynamic rigid-body, its handle followed by its state with
+ *   the same layout as the rigid-body entries of `World.saveDynamicsState`.
+ * @returns For each rigid-body to push, its handle followed by the force and torque to add
+ *   to its user forces during the substep, or nothing. The torque is a scalar in 2D.
+ */
+export type SubstepCallback = (
+    substep: number,
+    dt: number,
+    states: Float64Array,
+) => Float64Array | number[] | void;
+
 export class PhysicsPipeline {
     raw: RawPhysicsPipeline;
 
@@ -32,6 +48,302 @@ export class PhysicsPipeline {
         this.raw = raw || new RawPhysicsPipeline();
     }
 
+    /**
+     * Adds a rule, evaluated natively, preventing the colliders members of `groups1` from
+     * generating contacts or intersections with the colliders members of `groups2`, as long as
+     * the relative linear velocity of their rigid-bodies is smaller than `velocityThreshold`.
+     *
+     * The `ActiveHooks.FILTER_CONTACT_PAIRS` and `ActiveHooks.FILTER_INTERSECTION_PAIR` active
+     * hooks are set automatically on the colliders members of `groups1` or `groups2`, and removed
+     * once no rule or exclusion applies to them, unless they were already set.
+     *
+     * @param colliders - The set of colliders.
+     * @param groups1 - Bit mask of the first set of collision groups.
+     * @param groups2 - Bit mask of the second set of collision groups.
+     * @param velocityThreshold - The relative velocity above which the rule no longer applies.
+     * @returns The id of the rule, to be given to `removePairFilterRule`.
+     */
+    public addPairFilterRule(
+        colliders: ColliderSet,
+        groups1: number,
+        groups2: number,
+        velocityThreshold: number = Infinity,
+    ): number {
+        return this.raw.addPairFilterRule(
+            colliders.raw,
+            groups1,
+            groups2,
+            velocityThreshold,
+        );
+    }
+
+    /**
+     * Removes the pair filter rule with the given id.
+     */
+    public removePairFilterRule(colliders: ColliderSet, id: number) {
+        this.raw.removePairFilterRule(colliders.raw, id);
+    }
+
+    /**
+     * Removes all the pair filter rules.
+     */
+    public clearPairFilterRules(colliders: ColliderSet) {
+        this.raw.clearPairFilterRules(colliders.raw);
+    }
+
+    /**
+     * The number of pair filter rules.
+     */
+    public numPairFilterRules(): number {
+        return this.raw.numPairFilterRules();
+    }
+
+    /**
+     * Prevents the two given colliders from generating contacts or intersections
+     * with each other, until `removeExclusion` is called.
+     *
+     * The filtering active hooks are set automatically on both colliders, and
+     * removed by `removeExclusion` unless they were already set.
+     */
+    public excludeCollisionPair(
+        colliders: ColliderSet,
+        handle1: ColliderHandle,
+        handle2: ColliderHandle,
+    ) {
+        this.raw.excludeCollisionPair(colliders.raw, handle1, handle2);
+    }
+
+    /**
+     * Allows the two given colliders to interact with each other again.
+     */
+    public removeExclusion(
+        colliders: ColliderSet,
+        handle1: ColliderHandle,
+        handle2: ColliderHandle,
+    ) {
+        this.raw.removeExclusion(colliders.raw, handle1, handle2);
+    }
+
+    /**
+     * Are the two given colliders prevented from interacting with each other?
+     */
+    public isCollisionPairExcluded(
+        handle1: ColliderHandle,
+        handle2: ColliderHandle,
+    ): boolean {
+        return this.raw.isCollisionPairExcluded(handle1, handle2);
+    }
+
+    /**
+     * Prevents the colliders attached to the two given rigid-bodies from
+     * generating contacts or intersections with each other, until
+     * `removeBodyExclusion` is called.
+     *
+     * The filtering active hooks are set automatically on the colliders of
+     * both rigid-bodies, and removed by `removeBodyExclusion` unless they were
+     * already set.
+     */
+    public excludeBodyCollisionPair(
+        colliders: ColliderSet,
+        handle1: RigidBodyHandle,
+        handle2: RigidBodyHandle,
+    ) {
+        this.raw.excludeBodyCollisionPair(colliders.raw, handle1, handle2);
+    }
+
+    /**
+     * Allows the colliders of the two given rigid-bodies to interact with each
+     * other again.
+     */
+    public removeBodyExclusion(
+        colliders: ColliderSet,
+        handle1: RigidBodyHandle,
+        handle2: RigidBodyHandle,
+    ) {
+        this.raw.removeBodyExclusion(colliders.raw, handle1, handle2);
+    }
+
+    /**
+     * Are the colliders of the two given rigid-bodies prevented from
+     * interacting with each other?
+     */
+    public isBodyCollisionPairExcluded(
+        handle1: RigidBodyHandle,
+        handle2: RigidBodyHandle,
+    ): boolean {
+        return this.raw.isBodyCollisionPairExcluded(handle1, handle2);
+    }
+
+    /**
+     * Removes all the collision pair exclusions, between colliders and between
+     * rigid-bodies.
+     */
+    public clearExclusions(colliders: ColliderSet) {
+        this.raw.clearExclusions(colliders.raw);
+    }
+
+    /**
+     * Discards the warm-start impulses of all the contacts and joints at the next simulation step.
+     *
+     * Warm-starting reuses the impulses computed at the previous step to speed up the solver
+     * convergence, but these impulses may cause visible pops after large state changes.
+     */
+    public resetWarmstart() {
+        this.raw.resetWarmstart();
+    }
+
+    /**
+     * Discards the warm-start impulses of the contacts and impulse joints involving the given
+     * rigid-body, e.g., after teleporting it.
+     *
+     * The contact impulses are discarded at the next simulation step, for which the
+     * `ActiveHooks.MODIFY_SOLVER_CONTACTS` active hook is set automatically on the colliders of
+     * the rigid-body.
+     */
+    public resetBodyWarmstart(
+        impulseJoints: ImpulseJointSet,
+        handle: RigidBodyHandle,
+    ) {
+        this.raw.resetBodyWarmstart(impulseJoints.raw, handle);
+    }
+
+    /**
+     * Discards, at the next simulation step, the warm-start impulses of the contacts between
+     * the two given colliders.
+     *
+     * The `ActiveHooks.MODIFY_SOLVER_CONTACTS` active hook is set automatically on both colliders
+     * for that step.
+     */
+    public resetPairWarmstart(
+        handle1: ColliderHandle,
+        handle2: ColliderHandle,
+    ) {
+        this.raw.resetPairWarmstart(handle1, handle2);
+    }
+
+    /**
+     * The ratio between the time simulated by a step and the timestep (default: 1).
+     */
+    get timeScale(): number {
+        return this.raw.timeScale();
+    }
+
+    /**
+     * Sets the ratio between the time simulated by a step and the timestep, e.g., `0.2` for
+     * a slow-motion effect. A time scale of 0 pauses the simulation.
+     */
+    set timeScale(factor: number) {
+        this.raw.setTimeScale(factor);
+    }
+
+    /**
+     * The bit mask of the active simulation layers (default: all the layers).
+     */
+    get activeLayers(): number {
+        return this.raw.activeLayers();
+    }
+
+    /**
+     * Sets the bit mask of the active simulation layers.
+     *
+     * Only the rigid-bodies with any of their simulation layers active are simulated.
+     */
+    set activeLayers(mask: number) {
+        this.raw.setActiveLayers(mask);
+    }
+
+    /**
+     * The number of substeps each simulation step is split into (default: 1).
+     */
+    get numSubsteps(): number {
+        return this.raw.numSubsteps();
+    }
+
+    /**
+     * Sets the number of substeps each simulation step is split into.
+     *
+     * Each substep runs a full pipeline step with a timestep equal to `dt / numSubsteps`.
+     */
+    set numSubsteps(numSubsteps: number) {
+        this.raw.setNumSubsteps(numSubsteps);
+    }
+
+    /**
+     * Enables the automatic increase of the number of substeps when a rigid-body moves fast
+     * relative to the size of its colliders.
+     *
+     * @param maxTravelRatio - The maximum distance an awake dynamic rigid-body may travel
+     *   during a substep, relative to the smallest extent of its colliders.
+     * @param maxSubsteps - The maximum number of substeps of a step.
+     * @returns `false`, leaving the substepping unchanged, if `maxTravelRatio` isn't a positive
+     *   number or `maxSubsteps` isn't a positive integer.
+     */
+    public enableAutoSubstepping(
+        maxTravelRatio: number,
+        maxSubsteps: number,
+    ): boolean {
+        if (!Number.isInteger(maxSubsteps) || maxSubsteps < 1) {
+            return false;
+        }
+
+        return this.raw.enableAutoSubstepping(maxTravelRatio, maxSubsteps);
+    }
+
+    public disableAutoSubstepping() {
+        this.raw.disableAutoSubstepping();
+    }
+
+    get autoSubsteppingEnabled(): boolean {
+        return this.raw.isAutoSubsteppingEnabled();
+    }
+
+    /**
+     * The number of substeps executed by the last simulation step.
+     */
+    get lastNumSubsteps(): number {
+        return this.raw.lastNumSubsteps();
+    }
+
+    /**
+     * The simulation time, i.e., the sum of the timesteps of all the substeps executed.
+     */
+    get simulationTime(): number {
+        return this.raw.simulationTime();
+    }
+
+    /**
+     * Sets the simulation time, e.g., to synchronize it with another clock.
+     */
+    set simulationTime(time: number) {
+        this.raw.setSimulationTime(time);
+    }
+
+    /**
+     * The number of substeps of the current step already executed, or 0 if the current step
+     * isn’t partially executed.
+     */
+    get substepIndex(): number {
+        return this.raw.substepIndex();
+    }
+
+    /**
+     * Sets the function called before each substep to compute forces applied during that
+     * substep only, or removes it if `null`.
+     *
+     * The callback must not access the physics world: the rigid-body states are passed as
+     * arguments and the forces are returned.
+     */
+    public setSubstepCallback(callback: SubstepCallback | null) {
+        this.raw.setSubstepCallback(callback ?? undefined);
+    }
+
+    /**
+     * Advances the simulation by one step.
+     *
+     * @param numSubsteps - If set, only this number of substeps is executed, starting new steps
+     *   as needed. Otherwise, the remaining substeps of the current step are executed if it was
+     *   partially executed.
+     */
     public step(
         gravity: Vector,
         integrationParameters: IntegrationParameters,
@@ -45,6 +357,7 @@ export class PhysicsPipeline {
         ccdSolver: CCDSolver,
         eventQueue?: EventQueue,
         hooks?: PhysicsHooks,
+        numSubsteps?: number,
     ) {
         let rawG = VectorOps.intoRaw(gravity);
 
@@ -64,6 +377,7 @@ export class PhysicsPipeline {
                 hooks,
                 !!hooks ? hooks.filterContactPair : null,
                 !!hooks ? hooks.filterIntersectionPair : null,
+                numSubsteps,
             );
         } else {
             this.raw.step(
@@ -77,6 +391,7 @@ export class PhysicsPipeline {
                 impulseJoints.raw,
                 multibodyJoints.raw,
                 ccdSolver.raw,
+                numSubsteps,
             );
         }
 
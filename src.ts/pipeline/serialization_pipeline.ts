@@ -1,15 +1,33 @@
-import {RawSerializationPipeline} from "../raw";
-import {Vector, VectorOps} from "../math";
+import {RawHandleMapping, RawSerializationPipeline} from "../raw";
+import {Rotation, RotationOps, Vector, VectorOps} from "../math";
 import {
     IntegrationParameters,
     IslandManager,
     ImpulseJointSet,
     MultibodyJointSet,
+    RigidBodyHandle,
     RigidBodySet,
 } from "../dynamics";
 import {BroadPhase, ColliderSet, NarrowPhase} from "../geometry";
+import {PhysicsPipeline} from "./physics_pipeline";
 import {World} from "./world";
 
+/**
+ * The handles of the rigid-bodies, colliders, and impulse joints inserted into a physics world.
+ *
+ * The element at index `i` of `newBodies` is the handle, in the physics world they were inserted
+ * into, of the rigid-body whose handle was `oldBodies[i]` in the physics world it was serialized
+ * from. The same goes for the colliders and the impulse joints.
+ */
+export interface HandleMapping {
+    oldBodies: Float64Array;
+    newBodies: Float64Array;
+    oldColliders: Float64Array;
+    newColliders: Float64Array;
+    oldImpulseJoints: Float64Array;
+    newImpulseJoints: Float64Array;
+}
+
 /**
  * A pipeline for serializing the physics scene.
  *
@@ -43,6 +61,10 @@ export class SerializationPipeline {
      * @param colliders - The colliders taking part into the simulation.
      * @param impulseJoints - The impulse joints taking part into the simulation.
      * @param multibodyJoints - The multibody joints taking part into the simulation.
+     * @param compressed - If `true`, the snapshot is compressed with LZ4.
+     * @param physicsPipeline - The physics pipeline of the simulation, whose state (e.g. pair
+     *                          filtering rules) is saved too. If omitted, that state is saved
+     *                          with its default value.
      */
     public serializeAll(
         gravity: Vector,
@@ -54,15 +76,65 @@ export class SerializationPipeline {
         colliders: ColliderSet,
         impulseJoints: ImpulseJointSet,
         multibodyJoints: MultibodyJointSet,
+        compressed?: boolean,
+        physicsPipeline?: PhysicsPipeline,
     ): Uint8Array {
         let rawGra = VectorOps.intoRaw(gravity);
 
-        const res = this.raw.serializeAll(
+        const res = !!physicsPipeline
+            ? this.raw.serializeAllWithPipeline(
+                  rawGra,
+                  integrationParameters.raw,
+                  islands.raw,
+                  broadPhase.raw,
+                  narrowPhase.raw,
+                  bodies.raw,
+                  colliders.raw,
+                  impulseJoints.raw,
+                  multibodyJoints.raw,
+                  physicsPipeline.raw,
+                  !!compressed,
+              )
+            : this.raw.serializeAll(
+                  rawGra,
+                  integrationParameters.raw,
+                  islands.raw,
+                  broadPhase.raw,
+                  narrowPhase.raw,
+                  bodies.raw,
+                  colliders.raw,
+                  impulseJoints.raw,
+                  multibodyJoints.raw,
+                  !!compressed,
+              );
+        rawGra.free();
+
+        return res;
+    }
+
+    /**
+     * Describes a physics world as a human-readable JSON string, for debugging.
+     *
+     * @param gravity - The current gravity affecting the simulation.
+     * @param integrationParameters - The integration parameters of the simulation.
+     * @param bodies - The rigid-bodies taking part into the simulation.
+     * @param colliders - The colliders taking part into the simulation.
+     * @param impulseJoints - The impulse joints taking part into the simulation.
+     * @param multibodyJoints - The multibody joints taking part into the simulation.
+     */
+    public worldToDebugJson(
+        gravity: Vector,
+        integrationParameters: IntegrationParameters,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        impulseJoints: ImpulseJointSet,
+        multibodyJoints: MultibodyJointSet,
+    ): string {
+        let rawGra = VectorOps.intoRaw(gravity);
+
+        const res = this.raw.worldToDebugJson(
             rawGra,
             integrationParameters.raw,
-            islands.raw,
-            broadPhase.raw,
-            narrowPhase.raw,
             bodies.raw,
             colliders.raw,
             impulseJoints.raw,
@@ -77,8 +149,333 @@ export class SerializationPipeline {
      * Deserialize the complete physics state from a single byte array.
      *
      * @param data - The byte array to deserialize.
+     * @param compressed - Set to `true` if the snapshot was compressed by `serializeAll`.
+     */
+    public deserializeAll(data: Uint8Array, compressed?: boolean): World {
+        return World.fromRaw(this.raw.deserializeAll(data, !!compressed));
+    }
+
+    /**
+     * Inserts the rigid-bodies, colliders, and impulse joints of a snapshot into the given sets,
+     * alongside the elements they already contain.
+     *
+     * @param data - The byte array to deserialize.
+     * @param bodies - The rigid-bodies taking part into the simulation.
+     * @param colliders - The colliders taking part into the simulation.
+     * @param impulseJoints - The impulse joints taking part into the simulation.
+     * @param compressed - Set to `true` if the snapshot was compressed by `serializeAll`.
+     * @returns The inserted elements, or `null` if `data` is not a valid snapshot.
+     */
+    public deserializeInto(
+        data: Uint8Array,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        impulseJoints: ImpulseJointSet,
+        compressed?: boolean,
+    ): HandleMapping | null {
+        const rawMapping = this.raw.deserializeInto(
+            data,
+            bodies.raw,
+            colliders.raw,
+            impulseJoints.raw,
+            !!compressed,
+        );
+        return SerializationPipeline.mapInserted(
+            rawMapping,
+            bodies,
+            colliders,
+            impulseJoints,
+        );
+    }
+
+    // #if DIM3
+    /**
+     * Deserialize a snapshot taken by the f32 version of the bindings, converting it to f64.
+     *
+     * This requires the f64 bindings to be built with the `f32-snapshots` feature.
+     *
+     * @param data - The byte array to deserialize.
+     */
+    public deserializeAllF32(data: Uint8Array): World {
+        return World.fromRaw(this.raw.deserializeAllF32(data));
+    }
+    // #endif
+
+    /**
+     * Saves the positions, velocities, and sleep state of the non-fixed rigid-bodies, and the
+     * impulses of the impulse joints.
+     *
+     * @param bodies - The rigid-bodies taking part into the simulation.
+     * @param impulseJoints - The impulse joints taking part into the simulation.
+     */
+    public saveDynamicsState(
+        bodies: RigidBodySet,
+        impulseJoints: ImpulseJointSet,
+    ): Float64Array {
+        return this.raw.saveDynamicsState(bodies.raw, impulseJoints.raw);
+    }
+
+    /**
+     * Restores a state saved by `saveDynamicsState`.
+     *
+     * @param data - The saved dynamics state.
+     * @param bodies - The rigid-bodies taking part into the simulation.
+     * @param impulseJoints - The impulse joints taking part into the simulation.
+     * @returns `false` if `data` is not a valid dynamics state.
+     */
+    public restoreDynamicsState(
+        data: Float64Array,
+        bodies: RigidBodySet,
+        impulseJoints: ImpulseJointSet,
+    ): boolean {
+        return this.raw.restoreDynamicsState(
+            data,
+            bodies.raw,
+            impulseJoints.raw,
+        );
+    }
+
+    /**
+     * Encodes the positions, velocities, and sleep state of the given rigid-bodies into a compact
+     * buffer, for network synchronization.
+     *
+     * @param bodies - The rigid-bodies taking part into the simulation.
+     * @param handles - The handles of the rigid-bodies to encode.
+     * @param precision - The precision the translations are quantized to.
+     * @returns `null` if `precision` isn't a finite positive number.
+     */
+    public encodeNetworkState(
+        bodies: RigidBodySet,
+        handles: Float64Array | RigidBodyHandle[],
+        precision: number,
+    ): Uint8Array | null {
+        const data = this.raw.encodeNetworkState(
+            bodies.raw,
+            new Float64Array(handles),
+            precision,
+        );
+        return data === undefined ? null : data;
+    }
+
+    /**
+     * Applies a state encoded by `encodeNetworkState`.
+     *
+     * @param bodies - The rigid-bodies taking part into the simulation.
+     * @param data - The encoded state.
+     * @param smoothing - The fraction, in `[0, 1]`, of the distance to the encoded positions the
+     *                    rigid-bodies are not moved by.
+     * @returns `false` if `data` is not a valid encoded state.
+     */
+    public applyNetworkState(
+        bodies: RigidBodySet,
+        data: Uint8Array,
+        smoothing: number,
+    ): boolean {
+        return this.raw.applyNetworkState(bodies.raw, data, smoothing);
+    }
+
+    /**
+     * The rigid-bodies whose colliders are all contained in the given axis-aligned box.
+     *
+     * A rigid-body without collider is in the box if its origin is.
+     *
+     * @param bodies - The rigid-bodies taking part into the simulation.
+     * @param colliders - The colliders taking part into the simulation.
+     * @param mins - The lower corner of the box.
+     * @param maxs - The upper corner of the box.
+     */
+    public regionBodies(
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        mins: Vector,
+        maxs: Vector,
+    ): Float64Array {
+        let rawMins = VectorOps.intoRaw(mins);
+        let rawMaxs = VectorOps.intoRaw(maxs);
+        const res = this.raw.regionBodies(
+            bodies.raw,
+            colliders.raw,
+            rawMins,
+            rawMaxs,
+        );
+        rawMins.free();
+        rawMaxs.free();
+
+        return res;
+    }
+
+    /**
+     * The colliders without parent contained in the given axis-aligned box.
+     *
+     * @param colliders - The colliders taking part into the simulation.
+     * @param mins - The lower corner of the box.
+     * @param maxs - The upper corner of the box.
+     */
+    public regionColliders(
+        colliders: ColliderSet,
+        mins: Vector,
+        maxs: Vector,
+    ): Float64Array {
+        let rawMins = VectorOps.intoRaw(mins);
+        let rawMaxs = VectorOps.intoRaw(maxs);
+        const res = this.raw.regionColliders(colliders.raw, rawMins, rawMaxs);
+        rawMins.free();
+        rawMaxs.free();
+
+        return res;
+    }
+
+    /**
+     * Serializes the given rigid-bodies with their colliders, the given colliders without
+     * parent, and the impulse joints between the given rigid-bodies.
+     *
+     * @param bodies - The rigid-bodies taking part into the simulation.
+     * @param colliders - The colliders taking part into the simulation.
+     * @param impulseJoints - The impulse joints taking part into the simulation.
+     * @param bodyHandles - The handles of the rigid-bodies to serialize.
+     * @param colliderHandles - The handles of the colliders without parent to serialize.
+     */
+    public serializeRegion(
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        impulseJoints: ImpulseJointSet,
+        bodyHandles: Float64Array,
+        colliderHandles: Float64Array,
+    ): Uint8Array {
+        return this.raw.serializeRegion(
+            bodies.raw,
+            colliders.raw,
+            impulseJoints.raw,
+            bodyHandles,
+            colliderHandles,
+        );
+    }
+
+    /**
+     * Inserts the rigid-bodies, colliders, and impulse joints serialized by `serializeRegion`.
+     *
+     * @param data - The serialized region.
+     * @param bodies - The rigid-bodies taking part into the simulation.
+     * @param colliders - The colliders taking part into the simulation.
+     * @param impulseJoints - The impulse joints taking part into the simulation.
+     * @returns The inserted elements, or `null` if `data` is not a valid region.
      */
-    public deserializeAll(data: Uint8Array): World {
-        return World.fromRaw(this.raw.deserializeAll(data));
+    public insertRegion(
+        data: Uint8Array,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        impulseJoints: ImpulseJointSet,
+    ): HandleMapping | null {
+        const rawMapping = this.raw.insertRegion(
+            data,
+            bodies.raw,
+            colliders.raw,
+            impulseJoints.raw,
+        );
+        return SerializationPipeline.mapInserted(
+            rawMapping,
+            bodies,
+            colliders,
+            impulseJoints,
+        );
+    }
+
+    /**
+     * Serializes a rigid-body with its colliders, along with the non-fixed rigid-bodies connected
+     * to it through impulse joints, directly or not, their colliders, and the impulse joints
+     * between all these rigid-bodies.
+     *
+     * @param bodies - The rigid-bodies taking part into the simulation.
+     * @param colliders - The colliders taking part into the simulation.
+     * @param impulseJoints - The impulse joints taking part into the simulation.
+     * @param handle - The handle of the root rigid-body.
+     */
+    public serializeBodyTree(
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        impulseJoints: ImpulseJointSet,
+        handle: RigidBodyHandle,
+    ): Uint8Array {
+        return this.raw.serializeBodyTree(
+            bodies.raw,
+            colliders.raw,
+            impulseJoints.raw,
+            handle,
+        );
+    }
+
+    /**
+     * Inserts a copy of the rigid-bodies, colliders, and impulse joints serialized by
+     * `serializeBodyTree`, moved so that the root rigid-body is at the given position.
+     *
+     * @param data - The serialized body tree.
+     * @param bodies - The rigid-bodies taking part into the simulation.
+     * @param colliders - The colliders taking part into the simulation.
+     * @param impulseJoints - The impulse joints taking part into the simulation.
+     * @param translation - The translation of the root rigid-body of the copy.
+     * @param rotation - The rotation of the root rigid-body of the copy.
+     * @returns The inserted elements, or `null` if `data` is not a valid body tree.
+     */
+    public instantiatePrefab(
+        data: Uint8Array,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        impulseJoints: ImpulseJointSet,
+        translation: Vector,
+        rotation: Rotation,
+    ): HandleMapping | null {
+        let rawTra = VectorOps.intoRaw(translation);
+        let rawRot = RotationOps.intoRaw(rotation);
+        const rawMapping = this.raw.instantiatePrefab(
+            data,
+            bodies.raw,
+            colliders.raw,
+            impulseJoints.raw,
+            rawTra,
+            rawRot,
+        );
+        rawTra.free();
+        rawRot.free();
+
+        return SerializationPipeline.mapInserted(
+            rawMapping,
+            bodies,
+            colliders,
+            impulseJoints,
+        );
+    }
+
+    /**
+     * Internal function, do not call directly.
+     */
+    public static mapInserted(
+        rawMapping: RawHandleMapping | undefined,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        impulseJoints: ImpulseJointSet,
+    ): HandleMapping | null {
+        if (!rawMapping) return null;
+
+        const mapping: HandleMapping = {
+            oldBodies: rawMapping.oldBodies(),
+            newBodies: rawMapping.newBodies(),
+            oldColliders: rawMapping.oldColliders(),
+            newColliders: rawMapping.newColliders(),
+            oldImpulseJoints: rawMapping.oldImpulseJoints(),
+            newImpulseJoints: rawMapping.newImpulseJoints(),
+        };
+        rawMapping.free();
+
+        mapping.newBodies.forEach((handle) =>
+            bodies.mapInserted(handle, colliders),
+        );
+        mapping.newColliders.forEach((handle) =>
+            colliders.mapInserted(handle, bodies),
+        );
+        mapping.newImpulseJoints.forEach((handle) =>
+            impulseJoints.mapInserted(handle, bodies),
+        );
+
+        return mapping;
     }
 }
@@ -12,6 +12,9 @@ import {
     RawRigidBodySet,
     RawSerializationPipeline,
     RawDebugRenderPipeline,
+    scaleWorld,
+    mergeBodies,
+    unmergeBodies,
 } from "../raw";
 
 import {
@@ -22,17 +25,24 @@ import {
     ColliderSet,
     InteractionGroups,
     NarrowPhase,
+    PickHit,
     PointColliderProjection,
     Ray,
     RayColliderIntersection,
     RayColliderHit,
     Shape,
+    ShapeDepenetration,
+    ShapePlacement,
     ColliderShapeCastHit,
+    ColliderShapeContact,
     TempContactManifold,
+    TriggerSet,
 } from "../geometry";
 import {
+    BodyWeld,
     CCDSolver,
     IntegrationParameters,
+    Island,
     IslandManager,
     ImpulseJoint,
     ImpulseJointHandle,
@@ -47,9 +57,9 @@ import {
     RigidBodySet,
 } from "../dynamics";
 import {Rotation, Vector, VectorOps} from "../math";
-import {PhysicsPipeline} from "./physics_pipeline";
+import {PhysicsPipeline, SubstepCallback} from "./physics_pipeline";
 import {QueryFilterFlags} from "./query_pipeline";
-import {SerializationPipeline} from "./serialization_pipeline";
+import {HandleMapping, SerializationPipeline} from "./serialization_pipeline";
 import {EventQueue} from "./event_queue";
 import {PhysicsHooks} from "./physics_hooks";
 import {DebugRenderBuffers, DebugRenderPipeline} from "./debug_render_pipeline";
@@ -87,6 +97,7 @@ export class World {
     debugRenderPipeline: DebugRenderPipeline;
     characterControllers: Set<KinematicCharacterController>;
     pidControllers: Set<PidController>;
+    triggerSets: Set<TriggerSet>;
 
     // #if DIM3
     vehicleControllers: Set<DynamicRayCastVehicleController>;
@@ -114,6 +125,7 @@ export class World {
         this.debugRenderPipeline.free();
         this.characterControllers.forEach((controller) => controller.free());
         this.pidControllers.forEach((controller) => controller.free());
+        this.triggerSets.forEach((triggers) => triggers.free());
 
         // #if DIM3
         this.vehicleControllers.forEach((controller) => controller.free());
@@ -133,6 +145,7 @@ export class World {
         this.debugRenderPipeline = undefined;
         this.characterControllers = undefined;
         this.pidControllers = undefined;
+        this.triggerSets = undefined;
 
         // #if DIM3
         this.vehicleControllers = undefined;
@@ -175,6 +188,7 @@ export class World {
         );
         this.characterControllers = new Set<KinematicCharacterController>();
         this.pidControllers = new Set<PidController>();
+        this.triggerSets = new Set<TriggerSet>();
 
         // #if DIM3
         this.vehicleControllers = new Set<DynamicRayCastVehicleController>();
@@ -198,6 +212,8 @@ export class World {
             raw.takeColliders(),
             raw.takeImpulseJoints(),
             raw.takeMultibodyJoints(),
+            undefined,
+            raw.takePhysicsPipeline(),
         );
     }
 
@@ -206,8 +222,35 @@ export class World {
      *
      * Use `World.restoreSnapshot` to create a new physics world with a state identical to
      * the state when `.takeSnapshot()` is called.
-     */
-    public takeSnapshot(): Uint8Array {
+     *
+     * The snapshot also includes the following state attached to the world by these bindings:
+     * - the per-triangle materials of the colliders.
+     * - the rolling resistance of the colliders.
+     * - the drag coefficients of the colliders and the air density.
+     * - the user tags of the colliders.
+     * - the local linear damping, the gravity compensation, the upright constraints, the pose
+     *   tracking, the drag constraints, and the simulation layers of the rigid-bodies.
+     * - the dry friction of the impulse joints.
+     * - the contact pair ids.
+     * - the impacts and the proximity events recorded during the last step.
+     * - the intersection durations, and the contact loads of the last step.
+     * - the pair filtering rules and the collider and rigid-body collision pair exclusions.
+     * - the warm-start resets requested for the next step.
+     * - the time scale, and the active simulation layers.
+     * - the buffered remote poses of the rigid-bodies, and the remote pose clock and parameters.
+     * - the number of substeps, the automatic substepping parameters, and the number of
+     *   substeps executed by the last step.
+     * - the step partially executed by `World.stepSubsteps`, which is completed by the next
+     *   steps of the restored world.
+     * - the simulation time.
+     *
+     * The substep callback set with `World.setSubstepCallback` isn’t included.
+     *
+     * @param compressed - If `true`, the snapshot is compressed with LZ4, which makes it several
+     *                     times smaller for a small cost. The same flag must be given to
+     *                     `World.restoreSnapshot`.
+     */
+    public takeSnapshot(compressed?: boolean): Uint8Array {
         return this.serializationPipeline.serializeAll(
             this.gravity,
             this.integrationParameters,
@@ -218,6 +261,60 @@ export class World {
             this.colliders,
             this.impulseJoints,
             this.multibodyJoints,
+            compressed,
+            this.physicsPipeline,
+        );
+    }
+
+    /**
+     * Rescales all the lengths of this physics world by the given factor.
+     *
+     * This scales, in one pass, the gravity, the positions and linear velocities of the rigid-bodies,
+     * the shapes and positions of the colliders, the joint anchors, linear limits, and linear motor
+     * targets, and the length unit of the integration parameters. Masses are scaled as if the
+     * densities were preserved. This is useful to migrate content authored at the wrong unit scale.
+     *
+     * The scene queries take the new scale into account after the next call to `World.step`
+     * or `World.updateSceneQueries`.
+     *
+     * @param factor - The scaling factor, e.g., `0.01` to convert centimeters into meters.
+     */
+    public scale(factor: number) {
+        scaleWorld(
+            factor,
+            this.integrationParameters.raw,
+            this.bodies.raw,
+            this.colliders.raw,
+            this.impulseJoints.raw,
+            this.multibodyJoints.raw,
+        );
+
+        this.gravity.x *= factor;
+        this.gravity.y *= factor;
+        // #if DIM3
+        this.gravity.z *= factor;
+        // #endif
+
+        this.colliders.forEach((collider) => collider.clearShapeCache());
+    }
+
+    /**
+     * Describes this world as a human-readable JSON string, for debugging.
+     *
+     * The description includes the gravity, the integration parameters, the rigid-bodies, the
+     * colliders with their shape and parameters, and the joints with their frames, limits, and
+     * motors. It can be attached to bug reports, or compared with the description of another world.
+     * Handles are written as numbers, and rotations as quaternions `[x, y, z, w]` in 3D and
+     * angles in 2D.
+     */
+    public toDebugJson(): string {
+        return this.serializationPipeline.worldToDebugJson(
+            this.gravity,
+            this.integrationParameters,
+            this.bodies,
+            this.colliders,
+            this.impulseJoints,
+            this.multibodyJoints,
         );
     }
 
@@ -225,11 +322,253 @@ export class World {
      * Creates a new physics world from a snapshot.
      *
      * This new physics world will be an identical copy of the snapshoted physics world.
+     *
+     * @param compressed - Set to `true` if the snapshot was compressed by `World.takeSnapshot`.
+     */
+    public static restoreSnapshot(
+        data: Uint8Array,
+        compressed?: boolean,
+    ): World {
+        let deser = new SerializationPipeline();
+        return deser.deserializeAll(data, compressed);
+    }
+
+    /**
+     * Inserts the rigid-bodies, colliders, and impulse joints of a snapshot into this physics
+     * world, alongside the elements it already contains.
+     *
+     * The inserted elements are given new handles, reported by the returned mapping so the
+     * handles stored elsewhere can be updated. The other parts of the snapshot (gravity,
+     * integration parameters, multibody joints, etc.) are ignored.
+     *
+     * @param data - A snapshot taken with `World.takeSnapshot`.
+     * @param compressed - Set to `true` if the snapshot was compressed by `World.takeSnapshot`.
+     * @returns The handles of the inserted elements, or `null` if `data` is not a valid snapshot.
+     */
+    public mergeSnapshot(
+        data: Uint8Array,
+        compressed?: boolean,
+    ): HandleMapping | null {
+        return this.serializationPipeline.deserializeInto(
+            data,
+            this.bodies,
+            this.colliders,
+            this.impulseJoints,
+            compressed,
+        );
+    }
+
+    /**
+     * Saves the dynamics state of this physics world: the positions, velocities, and sleep state
+     * of the non-fixed rigid-bodies, and the impulses of the impulse joints.
+     *
+     * Unlike `World.takeSnapshot`, the shapes and the broad-phase/narrow-phase are not saved,
+     * which makes it cheap enough to be called at every step for rollback netcode. Use
+     * `World.restoreDynamicsState` to restore this state on the same world.
+     *
+     * The multibody joints aren’t saved, so the coordinates and velocities of their links aren’t
+     * restored.
+     */
+    public saveDynamicsState(): Float64Array {
+        return this.serializationPipeline.saveDynamicsState(
+            this.bodies,
+            this.impulseJoints,
+        );
+    }
+
+    /**
+     * Restores a state saved with `World.saveDynamicsState` on this physics world.
+     *
+     * The rigid-bodies and impulse joints removed since the state was saved are ignored.
+     *
+     * @param data - The saved dynamics state.
+     * @returns `false` if `data` is not a valid dynamics state.
+     */
+    public restoreDynamicsState(data: Float64Array): boolean {
+        return this.serializationPipeline.restoreDynamicsState(
+            data,
+            this.bodies,
+            this.impulseJoints,
+        );
+    }
+
+    /**
+     * Encodes the state of the given rigid-bodies into a compact buffer, for real-time network
+     * synchronization.
+     *
+     * The translations are quantized to multiples of `precision`, the rotations are encoded with
+     * the smallest-three method in 3D (as a 16-bits angle in 2D), and the velocities are encoded
+     * as half-floats. Use `World.applyNetworkState` to apply the encoded state on another world
+     * with the same rigid-body handles.
+     *
+     * @param handles - The handles of the rigid-bodies to encode. The ones that don’t exist are
+     *                  skipped.
+     * @param precision - The precision the translations are quantized to. Defaults to `0.001`.
+     * @returns `null` if `precision` isn't a finite positive number.
+     */
+    public encodeNetworkState(
+        handles: Float64Array | RigidBodyHandle[],
+        precision?: number,
+    ): Uint8Array | null {
+        return this.serializationPipeline.encodeNetworkState(
+            this.bodies,
+            handles,
+            precision ?? 0.001,
+        );
+    }
+
+    /**
+     * Applies a state encoded by `World.encodeNetworkState`.
+     *
+     * The velocities and sleep state of the rigid-bodies are set to the encoded ones. With a
+     * non-zero `smoothing`, the rigid-bodies are only moved part of the way to the encoded
+     * positions, to hide small corrections.
+     *
+     * @param data - The encoded state.
+     * @param smoothing - The fraction, in `[0, 1]`, of the distance to the encoded positions the
+     *                    rigid-bodies are not moved by. Defaults to `0`, i.e., the rigid-bodies
+     *                    are snapped to the encoded positions.
+     * @returns `false`, without applying anything, if `data` is not a valid encoded state.
+     */
+    public applyNetworkState(data: Uint8Array, smoothing?: number): boolean {
+        return this.serializationPipeline.applyNetworkState(
+            this.bodies,
+            data,
+            smoothing ?? 0.0,
+        );
+    }
+
+    /**
+     * Removes from this physics world the rigid-bodies whose colliders are all contained in the
+     * given axis-aligned box, and the colliders without parent contained in it.
+     *
+     * The removed rigid-bodies, their colliders, and the impulse joints between them are
+     * serialized into the returned buffer, which can be given to `World.insertRegion` to insert
+     * them back, in this physics world or another one. The impulse joints between a removed
+     * rigid-body and a rigid-body outside of the box are removed without being serialized.
+     *
+     * @param mins - The lower corner of the box.
+     * @param maxs - The upper corner of the box.
+     */
+    public extractRegion(mins: Vector, maxs: Vector): Uint8Array {
+        const bodyHandles = this.serializationPipeline.regionBodies(
+            this.bodies,
+            this.colliders,
+            mins,
+            maxs,
+        );
+        const colliderHandles = this.serializationPipeline.regionColliders(
+            this.colliders,
+            mins,
+            maxs,
+        );
+        const data = this.serializationPipeline.serializeRegion(
+            this.bodies,
+            this.colliders,
+            this.impulseJoints,
+            bodyHandles,
+            colliderHandles,
+        );
+
+        bodyHandles.forEach((handle) =>
+            this.bodies.remove(
+                handle,
+                this.islands,
+                this.colliders,
+                this.impulseJoints,
+                this.multibodyJoints,
+            ),
+        );
+        colliderHandles.forEach((handle) =>
+            this.colliders.remove(handle, this.islands, this.bodies, false),
+        );
+
+        return data;
+    }
+
+    /**
+     * Serializes a rigid-body with its colliders, along with the non-fixed rigid-bodies connected
+     * to it through impulse joints, directly or not, their colliders, and the impulse joints
+     * between all these rigid-bodies.
+     *
+     * The returned buffer can be given to `World.instantiatePrefab` to spawn copies of this
+     * assembly (e.g. a vehicle or a ragdoll), in this physics world or another one.
+     *
+     * @param handle - The handle of the root rigid-body.
+     * @returns The serialized body tree, or `undefined` if the rigid-body doesn’t exist.
+     */
+    public serializeBodyTree(handle: RigidBodyHandle): Uint8Array {
+        return this.serializationPipeline.serializeBodyTree(
+            this.bodies,
+            this.colliders,
+            this.impulseJoints,
+            handle,
+        );
+    }
+
+    /**
+     * Inserts into this physics world a copy of the rigid-bodies, colliders, and impulse joints
+     * serialized with `World.serializeBodyTree`.
+     *
+     * The copy is moved rigidly so that its root rigid-body is at the given position, and the
+     * velocities of its rigid-bodies are rotated accordingly. The root rigid-body of the copy
+     * is the first one of `HandleMapping.newBodies`.
+     *
+     * @param data - The buffer returned by `World.serializeBodyTree`.
+     * @param translation - The translation of the root rigid-body of the copy.
+     * @param rotation - The rotation of the root rigid-body of the copy.
+     * @returns The handles of the inserted elements, or `null` if `data` is not a valid body tree.
+     */
+    public instantiatePrefab(
+        data: Uint8Array,
+        translation: Vector,
+        rotation: Rotation,
+    ): HandleMapping | null {
+        return this.serializationPipeline.instantiatePrefab(
+            data,
+            this.bodies,
+            this.colliders,
+            this.impulseJoints,
+            translation,
+            rotation,
+        );
+    }
+
+    /**
+     * Inserts into this physics world the rigid-bodies, colliders, and impulse joints extracted
+     * with `World.extractRegion`.
+     *
+     * The inserted elements are given new handles, reported by the returned mapping.
+     *
+     * @param data - The buffer returned by `World.extractRegion`.
+     * @returns The handles of the inserted elements, or `null` if `data` is not a valid region.
+     */
+    public insertRegion(data: Uint8Array): HandleMapping | null {
+        return this.serializationPipeline.insertRegion(
+            data,
+            this.bodies,
+            this.colliders,
+            this.impulseJoints,
+        );
+    }
+
+    // #if DIM3
+    /**
+     * Creates a new physics world from a snapshot taken by the f32 version of the bindings.
+     *
+     * The snapshot must have been taken with bindings based on the same version of Rapier, and
+     * the f64 bindings must be built with the `f32-snapshots` feature. The rigid-bodies, colliders,
+     * and joints are converted to f64 while the broad-phase and narrow-phase are rebuilt during the
+     * next step. Rigid-body and collider handles are preserved only if no rigid-body or collider was
+     * removed before the snapshot was taken. Colliders with voxels or custom shapes are skipped.
+     *
+     * @returns The new world, or `null` if the snapshot could not be read.
      */
-    public static restoreSnapshot(data: Uint8Array): World {
+    public static restoreSnapshotF32(data: Uint8Array): World {
         let deser = new SerializationPipeline();
-        return deser.deserializeAll(data);
+        return deser.deserializeAllF32(data);
     }
+    // #endif
 
     /**
      * Computes all the lines (and their colors) needed to render the scene.
@@ -257,9 +596,39 @@ export class World {
         );
     }
 
+    /**
+     * Computes the simulation islands of this world, for diagnostic purposes.
+     *
+     * See `IslandManager.computeIslands` for details.
+     */
+    public computeIslands(): Island[] {
+        return this.islands.computeIslands(
+            this.bodies,
+            this.colliders,
+            this.narrowPhase,
+            this.impulseJoints,
+            this.multibodyJoints,
+        );
+    }
+
+    /**
+     * Computes line segments showing the current contact points and normals.
+     *
+     * This is independent of, and much cheaper than, `World.debugRender`. See
+     * `NarrowPhase.contactNormalLines` for the layout of the result.
+     *
+     * @param normalLength - The length of the segments drawn along the contact normals.
+     */
+    public contactNormalLines(normalLength?: number): Float64Array {
+        return this.narrowPhase.contactNormalLines(normalLength);
+    }
+
     /**
      * Advance the simulation by one time step.
      *
+     * If a step was partially executed by `stepSubsteps`, only its remaining substeps are
+     * executed.
+     *
      * All events generated by the physics engine are ignored.
      *
      * @param EventQueue - (optional) structure responsible for collecting
@@ -280,6 +649,70 @@ export class World {
             eventQueue,
             hooks,
         );
+        this.triggerSets.forEach((triggers) =>
+            triggers.update(this.narrowPhase, this.colliders),
+        );
+    }
+
+    /**
+     * Advance the simulation by the given number of substeps.
+     *
+     * Each step is split into `numSubsteps` substeps (or more with automatic substepping). This
+     * executes the next substeps only, starting new steps as needed, so custom logic can run
+     * between the substeps, e.g., for rollback networking or slow-motion debugging. A step
+     * partially executed is completed by the next calls to `stepSubsteps` or `step`.
+     *
+     * The next kinematic positions set between two calls are reached at the end of the current
+     * step: the position-based kinematic rigid-bodies move toward them at a constant velocity
+     * during its remaining substeps.
+     *
+     * @param numSubsteps - The number of substeps to execute.
+     * @param EventQueue - (optional) structure responsible for collecting
+     *   events generated by the physics engine.
+     */
+    public stepSubsteps(
+        numSubsteps: number,
+        eventQueue?: EventQueue,
+        hooks?: PhysicsHooks,
+    ) {
+        this.physicsPipeline.step(
+            this.gravity,
+            this.integrationParameters,
+            this.islands,
+            this.broadPhase,
+            this.narrowPhase,
+            this.bodies,
+            this.colliders,
+            this.impulseJoints,
+            this.multibodyJoints,
+            this.ccdSolver,
+            eventQueue,
+            hooks,
+            numSubsteps,
+        );
+        this.triggerSets.forEach((triggers) =>
+            triggers.update(this.narrowPhase, this.colliders),
+        );
+    }
+
+    /**
+     * Advance the simulation by a single substep.
+     *
+     * See `World.stepSubsteps`.
+     *
+     * @param EventQueue - (optional) structure responsible for collecting
+     *   events generated by the physics engine.
+     */
+    public stepSingleSubstep(eventQueue?: EventQueue, hooks?: PhysicsHooks) {
+        this.stepSubsteps(1, eventQueue, hooks);
+    }
+
+    /**
+     * The number of substeps of the current step already executed by `stepSubsteps`, or 0 if
+     * the current step isn’t partially executed.
+     */
+    get substepIndex(): number {
+        return this.physicsPipeline.substepIndex;
     }
 
     /**
@@ -295,16 +728,38 @@ export class World {
         );
     }
 
-    // TODO: This needs to trigger a broad-phase update but without emitting collision events?
-    // /**
-    //  * Ensure subsequent scene queries take into account the collider positions set before this method is called.
-    //  *
-    //  * This does not step the physics simulation forward.
-    //  */
-    // public updateSceneQueries() {
-    //     this.propagateModifiedBodyPositionsToColliders();
-    //     this.queryPipeline.update(this.colliders);
-    // }
+    /**
+     * Ensure subsequent scene queries take into account the collider positions set before this method is called.
+     *
+     * This does not step the physics simulation forward, and doesn’t emit any collision event: collision pairs
+     * are only updated by the next simulation step.
+     *
+     * @param changedColliders - The handles of the colliders that moved since the last step or update. Only
+     *                           these colliders will be refit in the scene-query structure. If not provided,
+     *                           all the colliders are refit.
+     */
+    public updateSceneQueries(changedColliders?: ColliderHandle[]) {
+        this.propagateModifiedBodyPositionsToColliders();
+        this.broadPhase.updateWithColliders(
+            this.integrationParameters,
+            this.colliders,
+            changedColliders,
+        );
+    }
+
+    /**
+     * Ensure subsequent scene queries take into account the changes made to the given collider
+     * since the last step, without running a simulation step.
+     *
+     * Changes to the collision groups, and disabling the collider, are taken into account by the
+     * scene queries right away. This is needed after moving, reshaping, or re-enabling the collider
+     * for the scene queries of the same frame to see these changes.
+     *
+     * @param collider - The collider that changed.
+     */
+    public refreshCollider(collider: Collider) {
+        this.updateSceneQueries([collider.handle]);
+    }
 
     /**
      * The current simulation timestep.
@@ -455,6 +910,27 @@ export class World {
         controller.free();
     }
 
+    /**
+     * Creates a new trigger set, tracking the colliders overlapping each of its sensors.
+     *
+     * The trigger set is updated automatically at the end of each call to `World.step`.
+     */
+    public createTriggerSet(): TriggerSet {
+        let triggers = new TriggerSet();
+        this.triggerSets.add(triggers);
+        return triggers;
+    }
+
+    /**
+     * Removes a trigger set from this world.
+     *
+     * @param triggers - The trigger set to remove.
+     */
+    public removeTriggerSet(triggers: TriggerSet) {
+        this.triggerSets.delete(triggers);
+        triggers.free();
+    }
+
     /**
      * Creates a new PID (Proportional-Integral-Derivative) controller.
      *
@@ -568,6 +1044,81 @@ export class World {
         );
     }
 
+    /**
+     * Attaches two rigid-bodies at their current relative pose, e.g., for docking or grappling.
+     *
+     * By default, the rigid-bodies are attached by a fixed joint. If `merge` is `true`, the
+     * colliders of the second rigid-body are instead moved to the first one, and the second
+     * rigid-body is disabled: the rigid-bodies then move as a single one, without any joint
+     * drift, and the mass properties of the first rigid-body include the merged colliders.
+     *
+     * @param body1 - The first rigid-body to attach.
+     * @param body2 - The second rigid-body to attach.
+     * @param merge - Should the colliders of `body2` be merged into `body1` instead of creating
+     *   a joint?
+     * @returns The weld to pass to `World.unweldBodies`, or `null` if the rigid-bodies can’t be
+     *   welded.
+     */
+    public weldBodies(
+        body1: RigidBody,
+        body2: RigidBody,
+        merge: boolean = false,
+    ): BodyWeld | null {
+        if (!merge) {
+            const joint = this.impulseJoints.weld(
+                this.bodies,
+                body1.handle,
+                body2.handle,
+                true,
+            );
+            return joint ? new BodyWeld(body1, body2, joint, null) : null;
+        }
+
+        const rawMerge = mergeBodies(
+            this.bodies.raw,
+            this.colliders.raw,
+            body1.handle,
+            body2.handle,
+        );
+        if (!rawMerge) {
+            return null;
+        }
+
+        this.refreshColliderParents(rawMerge.colliders());
+        return new BodyWeld(body1, body2, null, rawMerge);
+    }
+
+    /**
+     * Detaches two rigid-bodies attached by `World.weldBodies`.
+     *
+     * If the rigid-bodies were merged, the second rigid-body is re-enabled at its pose relative
+     * to the first one when they were welded, with the velocity of the first rigid-body, and
+     * gets its colliders back.
+     *
+     * @param weld - The weld to undo.
+     */
+    public unweldBodies(weld: BodyWeld) {
+        if (weld.joint) {
+            if (this.impulseJoints.get(weld.joint.handle)) {
+                this.removeImpulseJoint(weld.joint, true);
+            }
+        } else if (weld.rawMerge) {
+            unmergeBodies(weld.rawMerge, this.bodies.raw, this.colliders.raw);
+            this.refreshColliderParents(weld.rawMerge.colliders());
+            weld.rawMerge.free();
+            weld.rawMerge = null;
+        }
+    }
+
+    private refreshColliderParents(handles: Float64Array) {
+        handles.forEach((handle) => {
+            const collider = this.colliders.get(handle);
+            if (collider) {
+                collider.finalizeDeserialization(this.bodies);
+            }
+        });
+    }
+
     /**
      * Creates a new multibody joint from the given joint descriptor.
      *
@@ -617,6 +1168,20 @@ export class World {
         return this.impulseJoints.get(handle);
     }
 
+    /**
+     * Gets the impulse joints attached to both given rigid-bodies, e.g., to break all the
+     * connections between two structures.
+     *
+     * @param body1 - The first rigid-body.
+     * @param body2 - The second rigid-body.
+     */
+    public impulseJointsBetween(
+        body1: RigidBody,
+        body2: RigidBody,
+    ): ImpulseJoint[] {
+        return this.impulseJoints.jointsBetween(body1.handle, body2.handle);
+    }
+
     /**
      * Retrieves an multibody joint from its handle.
      *
@@ -627,17 +1192,131 @@ export class World {
     }
 
     /**
-     * Removes the given rigid-body from this physics world.
+     * Gets the rigid-body at the root of the multibody containing the given joint.
      *
-     * This will remove this rigid-body as well as all its attached colliders and joints.
-     * Every other bodies touching or attached by joints to this rigid-body will be woken-up.
+     * @param joint - Any joint of the multibody.
+     */
+    public multibodyRoot(joint: MultibodyJoint): RigidBody | null {
+        const handle = this.multibodyJoints.root(joint.handle);
+        return handle == null ? null : this.bodies.get(handle);
+    }
+
+    /**
+     * Makes the root of the multibody containing the given joint kinematic (position-based)
+     * or dynamic, while the rest of the multibody remains dynamic.
      *
-     * @param body - The rigid-body to remove.
+     * A kinematic root is animated with `setNextKinematicTranslation` and
+     * `setNextKinematicRotation` on the rigid-body returned by `multibodyRoot`, e.g., for
+     * ragdoll limbs hanging off an animated character.
+     *
+     * @param joint - Any joint of the multibody.
+     * @param kinematic - `true` to make the root kinematic, `false` to make it dynamic.
      */
-    public removeRigidBody(body: RigidBody) {
-        if (this.bodies) {
-            this.bodies.remove(
-                body.handle,
+    public setMultibodyRootKinematic(
+        joint: MultibodyJoint,
+        kinematic: boolean,
+    ) {
+        this.multibodyJoints.setRootKinematic(
+            this.bodies,
+            joint.handle,
+            kinematic,
+        );
+    }
+
+    /**
+     * Enables or disables the contacts between all the links of the multibody containing the
+     * given joint.
+     *
+     * To select which links collide with each other, keep self-contacts enabled, disable the
+     * contacts between adjacent links with `MultibodyJoint.setContactsEnabled`, and exclude
+     * other specific pairs of links with `World.excludeBodyCollisionPair`.
+     *
+     * @param joint - Any joint of the multibody.
+     * @param enabled - `false` to prevent all the links from colliding with each other.
+     */
+    public setMultibodySelfContactsEnabled(
+        joint: MultibodyJoint,
+        enabled: boolean,
+    ) {
+        this.multibodyJoints.setSelfContactsEnabled(joint.handle, enabled);
+    }
+
+    /**
+     * Drives the multibody containing the given joint toward a target pose, by configuring
+     * the position motors of all its joints at once, e.g., every frame for a robot arm or a
+     * powered ragdoll.
+     *
+     * @param joint - Any joint of the multibody.
+     * @param targets - The target positions, for each link after the root in the order of the
+     *   links, and for each free axis of its joint in the order of the axes (linear axes first).
+     *   See `MultibodyJointSet.numPoseCoordinates`.
+     * @param stiffness - The stiffness shared by all the motors.
+     * @param damping - The damping shared by all the motors.
+     * @param maxForce - The maximum force shared by all the motors.
+     * @returns `false`, without modifying any motor, if the number of target positions is
+     *   wrong.
+     */
+    public configureMultibodyPoseTarget(
+        joint: MultibodyJoint,
+        targets: ArrayLike<number>,
+        stiffness: number,
+        damping: number,
+        maxForce: number = Infinity,
+    ): boolean {
+        return this.multibodyJoints.configurePoseTarget(
+            this.bodies,
+            joint.handle,
+            targets,
+            stiffness,
+            damping,
+            maxForce,
+        );
+    }
+
+    /**
+     * The generalized mass matrix of the multibody containing the given joint, as computed
+     * during the last simulation step, in row-major order.
+     *
+     * See `MultibodyJointSet.massMatrix`.
+     *
+     * @param joint - Any joint of the multibody.
+     */
+    public multibodyMassMatrix(joint: MultibodyJoint): Float64Array {
+        return this.multibodyJoints.massMatrix(joint.handle);
+    }
+
+    /**
+     * The Jacobian of a point attached to the link of the given joint, as computed during
+     * the last simulation step, in row-major order.
+     *
+     * See `MultibodyJointSet.pointJacobian`.
+     *
+     * @param joint - The joint attaching the link to its parent.
+     * @param point - The point attached to the link, in world-space.
+     */
+    public multibodyPointJacobian(
+        joint: MultibodyJoint,
+        point: Vector,
+    ): Float64Array {
+        return this.multibodyJoints.pointJacobian(
+            this.bodies,
+            joint.handle,
+            point,
+        );
+    }
+
+    /**
+     * Removes the given rigid-body from this physics world.
+     *
+     * This will remove this rigid-body as well as all its attached colliders and joints.
+     * Every other bodies touching or attached by joints to this rigid-body will be woken-up.
+     *
+     * @param body - The rigid-body to remove.
+     */
+    public removeRigidBody(body: RigidBody) {
+        if (this.bodies) {
+            this.bodies.remove(
+                body.handle,
                 this.islands,
                 this.colliders,
                 this.impulseJoints,
@@ -718,6 +1397,17 @@ export class World {
         this.bodies.forEachActiveRigidBody(this.islands, f);
     }
 
+    /**
+     * Gets the dynamic rigid-bodies that are awake and were moving at the end of the last step.
+     *
+     * Unlike `forEachActiveRigidBody`, this excludes the kinematic rigid-bodies and the
+     * rigid-bodies that are awake but at rest, so renderers and network layers can iterate
+     * only on what changed instead of the entire set of rigid-bodies.
+     */
+    public activeDynamicBodies(): RigidBody[] {
+        return this.bodies.activeDynamicBodies(this.islands);
+    }
+
     /**
      * Find the closest intersection between a ray and the physics world.
      *
@@ -739,6 +1429,8 @@ export class World {
         filterExcludeCollider?: Collider,
         filterExcludeRigidBody?: RigidBody,
         filterPredicate?: (collider: Collider) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
     ): RayColliderHit | null {
         return this.broadPhase.castRay(
             this.narrowPhase,
@@ -752,6 +1444,8 @@ export class World {
             filterExcludeCollider ? filterExcludeCollider.handle : null,
             filterExcludeRigidBody ? filterExcludeRigidBody.handle : null,
             this.colliders.castClosure(filterPredicate),
+            filterRequireTags,
+            filterExcludeTags,
         );
     }
 
@@ -776,6 +1470,8 @@ export class World {
         filterExcludeCollider?: Collider,
         filterExcludeRigidBody?: RigidBody,
         filterPredicate?: (collider: Collider) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
     ): RayColliderIntersection | null {
         return this.broadPhase.castRayAndGetNormal(
             this.narrowPhase,
@@ -789,6 +1485,8 @@ export class World {
             filterExcludeCollider ? filterExcludeCollider.handle : null,
             filterExcludeRigidBody ? filterExcludeRigidBody.handle : null,
             this.colliders.castClosure(filterPredicate),
+            filterRequireTags,
+            filterExcludeTags,
         );
     }
 
@@ -815,6 +1513,8 @@ export class World {
         filterExcludeCollider?: Collider,
         filterExcludeRigidBody?: RigidBody,
         filterPredicate?: (collider: Collider) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
     ) {
         this.broadPhase.intersectionsWithRay(
             this.narrowPhase,
@@ -829,6 +1529,84 @@ export class World {
             filterExcludeCollider ? filterExcludeCollider.handle : null,
             filterExcludeRigidBody ? filterExcludeRigidBody.handle : null,
             this.colliders.castClosure(filterPredicate),
+            filterRequireTags,
+            filterExcludeTags,
+        );
+    }
+
+    /**
+     * Finds the closest collider hit by a ray, with its parent rigid-body, the hit point, the
+     * normal and the distance of the hit, e.g., to find the object under the mouse cursor.
+     *
+     * Unlike `World.castRayAndGetNormal`, the shapes are always considered solid and the
+     * distances are measured along the normalized direction of the ray.
+     *
+     * @param origin - The origin of the ray.
+     * @param dir - The direction of the ray. It doesn't need to be normalized.
+     * @param maxDistance - The maximum distance of the hit from the origin of the ray.
+     */
+    public pick(
+        origin: Vector,
+        dir: Vector,
+        maxDistance: number,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: Collider,
+        filterExcludeRigidBody?: RigidBody,
+        filterPredicate?: (collider: Collider) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
+    ): PickHit | null {
+        return this.broadPhase.pick(
+            this.narrowPhase,
+            this.bodies,
+            this.colliders,
+            origin,
+            dir,
+            maxDistance,
+            filterFlags,
+            filterGroups,
+            filterExcludeCollider ? filterExcludeCollider.handle : null,
+            filterExcludeRigidBody ? filterExcludeRigidBody.handle : null,
+            this.colliders.castClosure(filterPredicate),
+            filterRequireTags,
+            filterExcludeTags,
+        );
+    }
+
+    /**
+     * Finds all the colliders hit by a ray, sorted by increasing distance.
+     *
+     * @param origin - The origin of the ray.
+     * @param dir - The direction of the ray. It doesn't need to be normalized.
+     * @param maxDistance - The maximum distance of the hits from the origin of the ray.
+     */
+    public pickAll(
+        origin: Vector,
+        dir: Vector,
+        maxDistance: number,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: Collider,
+        filterExcludeRigidBody?: RigidBody,
+        filterPredicate?: (collider: Collider) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
+    ): PickHit[] {
+        return this.broadPhase.pickAll(
+            this.narrowPhase,
+            this.bodies,
+            this.colliders,
+            origin,
+            dir,
+            maxDistance,
+            filterFlags,
+            filterGroups,
+            filterExcludeCollider ? filterExcludeCollider.handle : null,
+            filterExcludeRigidBody ? filterExcludeRigidBody.handle : null,
+            this.colliders.castClosure(filterPredicate),
+            filterRequireTags,
+            filterExcludeTags,
         );
     }
 
@@ -850,6 +1628,8 @@ export class World {
         filterExcludeCollider?: Collider,
         filterExcludeRigidBody?: RigidBody,
         filterPredicate?: (collider: Collider) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
     ): Collider | null {
         let handle = this.broadPhase.intersectionWithShape(
             this.narrowPhase,
@@ -863,10 +1643,103 @@ export class World {
             filterExcludeCollider ? filterExcludeCollider.handle : null,
             filterExcludeRigidBody ? filterExcludeRigidBody.handle : null,
             this.colliders.castClosure(filterPredicate),
+            filterRequireTags,
+            filterExcludeTags,
         );
         return handle != null ? this.colliders.get(handle) : null;
     }
 
+    /**
+     * Find the projections of a point on the `count` closest colliders, sorted by increasing distance.
+     *
+     * This is useful for, e.g., pickup prompts or AI target selection.
+     *
+     * @param point - The point to project.
+     * @param count - The maximum number of colliders to report.
+     * @param solid - If this is set to `true` then the collider shapes are considered to
+     *   be plain (if the point is located inside of a plain shape, its projection is the point
+     *   itself). If it is set to `false` the collider shapes are considered to be hollow
+     *   (if the point is located inside of an hollow shape, it is projected on the shape's
+     *   boundary).
+     * @param maxDist - Colliders further than this distance from the point are ignored. Limiting this
+     *   distance reduces the number of colliders tested.
+     */
+    public nearestColliders(
+        point: Vector,
+        count: number,
+        solid: boolean,
+        maxDist: number = Infinity,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: Collider,
+        filterExcludeRigidBody?: RigidBody,
+        filterPredicate?: (collider: Collider) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
+    ): PointColliderProjection[] {
+        return this.broadPhase.nearestColliders(
+            this.narrowPhase,
+            this.bodies,
+            this.colliders,
+            point,
+            count,
+            maxDist,
+            solid,
+            filterFlags,
+            filterGroups,
+            filterExcludeCollider ? filterExcludeCollider.handle : null,
+            filterExcludeRigidBody ? filterExcludeRigidBody.handle : null,
+            this.colliders.castClosure(filterPredicate),
+            filterRequireTags,
+            filterExcludeTags,
+        );
+    }
+
+    /**
+     * Find the collider closest to the given shape, and the distance between them.
+     *
+     * This is useful for, e.g., clearance checks, navigation margins, or procedural placement.
+     * The first points and normals of the result are on the shape, the second ones on the
+     * collider, all in world-space. The distance is negative if the shape penetrates the
+     * collider.
+     *
+     * @param shapePos - The position of the shape.
+     * @param shapeRot - The orientation of the shape.
+     * @param shape - The shape.
+     * @param maxDist - Colliders further than this distance from the shape are ignored. Limiting
+     *   this distance reduces the number of colliders tested.
+     */
+    public projectShape(
+        shapePos: Vector,
+        shapeRot: Rotation,
+        shape: Shape,
+        maxDist: number = Infinity,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: Collider,
+        filterExcludeRigidBody?: RigidBody,
+        filterPredicate?: (collider: Collider) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
+    ): ColliderShapeContact | null {
+        return this.broadPhase.projectShape(
+            this.narrowPhase,
+            this.bodies,
+            this.colliders,
+            shapePos,
+            shapeRot,
+            shape,
+            maxDist,
+            filterFlags,
+            filterGroups,
+            filterExcludeCollider ? filterExcludeCollider.handle : null,
+            filterExcludeRigidBody ? filterExcludeRigidBody.handle : null,
+            this.colliders.castClosure(filterPredicate),
+            filterRequireTags,
+            filterExcludeTags,
+        );
+    }
+
     /**
      * Find the projection of a point on the closest collider.
      *
@@ -887,6 +1760,8 @@ export class World {
         filterExcludeCollider?: Collider,
         filterExcludeRigidBody?: RigidBody,
         filterPredicate?: (collider: Collider) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
     ): PointColliderProjection | null {
         return this.broadPhase.projectPoint(
             this.narrowPhase,
@@ -899,6 +1774,8 @@ export class World {
             filterExcludeCollider ? filterExcludeCollider.handle : null,
             filterExcludeRigidBody ? filterExcludeRigidBody.handle : null,
             this.colliders.castClosure(filterPredicate),
+            filterRequireTags,
+            filterExcludeTags,
         );
     }
 
@@ -916,6 +1793,8 @@ export class World {
         filterExcludeCollider?: Collider,
         filterExcludeRigidBody?: RigidBody,
         filterPredicate?: (collider: Collider) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
     ): PointColliderProjection | null {
         return this.broadPhase.projectPointAndGetFeature(
             this.narrowPhase,
@@ -927,6 +1806,8 @@ export class World {
             filterExcludeCollider ? filterExcludeCollider.handle : null,
             filterExcludeRigidBody ? filterExcludeRigidBody.handle : null,
             this.colliders.castClosure(filterPredicate),
+            filterRequireTags,
+            filterExcludeTags,
         );
     }
 
@@ -947,6 +1828,8 @@ export class World {
         filterExcludeCollider?: Collider,
         filterExcludeRigidBody?: RigidBody,
         filterPredicate?: (collider: Collider) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
     ) {
         this.broadPhase.intersectionsWithPoint(
             this.narrowPhase,
@@ -959,6 +1842,8 @@ export class World {
             filterExcludeCollider ? filterExcludeCollider.handle : null,
             filterExcludeRigidBody ? filterExcludeRigidBody.handle : null,
             this.colliders.castClosure(filterPredicate),
+            filterRequireTags,
+            filterExcludeTags,
         );
     }
 
@@ -994,6 +1879,8 @@ export class World {
         filterExcludeCollider?: Collider,
         filterExcludeRigidBody?: RigidBody,
         filterPredicate?: (collider: Collider) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
     ): ColliderShapeCastHit | null {
         return this.broadPhase.castShape(
             this.narrowPhase,
@@ -1011,6 +1898,131 @@ export class World {
             filterExcludeCollider ? filterExcludeCollider.handle : null,
             filterExcludeRigidBody ? filterExcludeRigidBody.handle : null,
             this.colliders.castClosure(filterPredicate),
+            filterRequireTags,
+            filterExcludeTags,
+        );
+    }
+
+    /**
+     * Casts a shape with both a linear and an angular velocity, and retrieve the first collider
+     * it hits.
+     *
+     * Unlike `castShape`, this accounts for the rotation of the shape along its trajectory, for
+     * accurate sweep tests of spinning objects. The shape rotates around its center of mass.
+     *
+     * @param shapePos - The initial position of the shape to cast.
+     * @param shapeRot - The initial rotation of the shape to cast.
+     * @param shapeLinvel - The constant linear velocity of the shape to cast.
+     * @param shapeAngvel - The constant angular velocity of the shape to cast.
+     * @param shape - The shape to cast.
+     * @param maxToi - The maximum time-of-impact that can be reported by this cast.
+     * @param stopAtPenetration - If set to `false`, the shape-cast won’t immediately stop if
+     *   the shape is penetrating another shape at its starting point **and** its trajectory is such
+     *   that it’s on a path to exit that penetration state.
+     * @param filterFlags - Flags for excluding whole sets of colliders from the cast.
+     * @param filterGroups - Only colliders with collision groups compatible with this one will
+     *   be hit.
+     * @param filterExcludeCollider - A collider to exclude from the cast.
+     * @param filterExcludeRigidBody - A rigid-body whose colliders are excluded from the cast.
+     * @param filterPredicate - Any collider for which this closure returns `false` will be excluded.
+     */
+    public castShapeNonlinear(
+        shapePos: Vector,
+        shapeRot: Rotation,
+        shapeLinvel: Vector,
+        // #if DIM2
+        shapeAngvel: number,
+        // #endif
+        // #if DIM3
+        shapeAngvel: Vector,
+        // #endif
+        shape: Shape,
+        maxToi: number,
+        stopAtPenetration: boolean,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: Collider,
+        filterExcludeRigidBody?: RigidBody,
+        filterPredicate?: (collider: Collider) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
+    ): ColliderShapeCastHit | null {
+        return this.broadPhase.castShapeNonlinear(
+            this.narrowPhase,
+            this.bodies,
+            this.colliders,
+            shapePos,
+            shapeRot,
+            shapeLinvel,
+            shapeAngvel,
+            shape,
+            maxToi,
+            stopAtPenetration,
+            filterFlags,
+            filterGroups,
+            filterExcludeCollider ? filterExcludeCollider.handle : null,
+            filterExcludeRigidBody ? filterExcludeRigidBody.handle : null,
+            this.colliders.castClosure(filterPredicate),
+            filterRequireTags,
+            filterExcludeTags,
+        );
+    }
+
+    /**
+     * Casts a shape at a constant linear velocity and retrieve all the colliders it hits along
+     * the way, sorted by increasing time of impact.
+     *
+     * This is useful for piercing projectiles, where `castShape` would only report the first hit.
+     *
+     * @param shapePos - The initial position of the shape to cast.
+     * @param shapeRot - The initial rotation of the shape to cast.
+     * @param shapeVel - The constant velocity of the shape to cast (i.e. the cast direction).
+     * @param shape - The shape to cast.
+     * @param targetDistance − If the shape moves closer to this distance from a collider, a hit
+     *                         will be returned.
+     * @param maxToi - The maximum time-of-impact that can be reported by this cast. This effectively
+     *   limits the distance traveled by the shape to `shapeVel.norm() * maxToi`.
+     * @param stopAtPenetration - If set to `false`, the linear shape-cast won’t immediately stop if
+     *   the shape is penetrating another shape at its starting point **and** its trajectory is such
+     *   that it’s on a path to exit that penetration state.
+     * @param maxHits - The maximum number of hits to report. All the hits are reported if not provided.
+     */
+    public castShapeAll(
+        shapePos: Vector,
+        shapeRot: Rotation,
+        shapeVel: Vector,
+        shape: Shape,
+        targetDistance: number,
+        maxToi: number,
+        stopAtPenetration: boolean,
+        maxHits?: number,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: Collider,
+        filterExcludeRigidBody?: RigidBody,
+        filterPredicate?: (collider: Collider) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
+    ): ColliderShapeCastHit[] {
+        return this.broadPhase.castShapeAll(
+            this.narrowPhase,
+            this.bodies,
+            this.colliders,
+            shapePos,
+            shapeRot,
+            shapeVel,
+            shape,
+            targetDistance,
+            maxToi,
+            stopAtPenetration,
+            maxHits,
+            filterFlags,
+            filterGroups,
+            filterExcludeCollider ? filterExcludeCollider.handle : null,
+            filterExcludeRigidBody ? filterExcludeRigidBody.handle : null,
+            this.colliders.castClosure(filterPredicate),
+            filterRequireTags,
+            filterExcludeTags,
         );
     }
 
@@ -1034,6 +2046,8 @@ export class World {
         filterExcludeCollider?: Collider,
         filterExcludeRigidBody?: RigidBody,
         filterPredicate?: (collider: Collider) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
     ) {
         this.broadPhase.intersectionsWithShape(
             this.narrowPhase,
@@ -1048,34 +2062,283 @@ export class World {
             filterExcludeCollider ? filterExcludeCollider.handle : null,
             filterExcludeRigidBody ? filterExcludeRigidBody.handle : null,
             this.colliders.castClosure(filterPredicate),
+            filterRequireTags,
+            filterExcludeTags,
         );
     }
 
     /**
-     * Finds the handles of all the colliders with an AABB intersecting the given AABB.
+     * Retrieve the handles of all the colliders intersecting the given shape.
      *
-     * @param aabbCenter - The center of the AABB to test.
-     * @param aabbHalfExtents - The half-extents of the AABB to test.
-     * @param callback - The callback that will be called with the handles of all the colliders
-     *                   currently intersecting the given AABB.
+     * This is similar to `intersectionsWithShape` but returns all the handles at once as a typed
+     * array instead of invoking a callback for each of them.
+     *
+     * @param shapePos - The position of the shape to test.
+     * @param shapeRot - The orientation of the shape to test.
+     * @param shape - The shape to test.
+     * @returns The handles of all the colliders intersecting the `shape`.
      */
-    public collidersWithAabbIntersectingAabb(
-        aabbCenter: Vector,
-        aabbHalfExtents: Vector,
-        callback: (handle: Collider) => boolean,
-    ) {
-        this.broadPhase.collidersWithAabbIntersectingAabb(
+    public intersectionsWithShapeCollect(
+        shapePos: Vector,
+        shapeRot: Rotation,
+        shape: Shape,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: Collider,
+        filterExcludeRigidBody?: RigidBody,
+        filterPredicate?: (collider: Collider) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
+    ): Float64Array {
+        return this.broadPhase.intersectionsWithShapeCollect(
             this.narrowPhase,
             this.bodies,
             this.colliders,
-            aabbCenter,
-            aabbHalfExtents,
-            this.colliders.castClosure(callback),
-        );
-    }
-
-    /**
-     * Enumerates all the colliders potentially in contact with the given collider.
+            shapePos,
+            shapeRot,
+            shape,
+            filterFlags,
+            filterGroups,
+            filterExcludeCollider ? filterExcludeCollider.handle : null,
+            filterExcludeRigidBody ? filterExcludeRigidBody.handle : null,
+            this.colliders.castClosure(filterPredicate),
+            filterRequireTags,
+            filterExcludeTags,
+        );
+    }
+
+    /**
+     * Tests if a shape can be placed at the given position without overlapping the existing
+     * colliders, and retrieves the handles of the colliders blocking it.
+     *
+     * The test runs in a single call without invoking any callback per collider, which makes it
+     * suitable for building or placement UIs testing a dragged object every frame. Use the
+     * `QueryFilterFlags.EXCLUDE_SENSORS` filter flag to ignore the sensors.
+     *
+     * @param shapePos - The position of the shape to place.
+     * @param shapeRot - The orientation of the shape to place.
+     * @param shape - The shape to place.
+     * @param tolerance - The penetration depth allowed without blocking the placement, e.g., a
+     *   small positive value for shapes resting on the ground to be placeable. Defaults to `0`,
+     *   where any intersection blocks the placement.
+     */
+    public canPlaceShape(
+        shapePos: Vector,
+        shapeRot: Rotation,
+        shape: Shape,
+        tolerance: number = 0,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: Collider,
+        filterExcludeRigidBody?: RigidBody,
+        filterPredicate?: (collider: Collider) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
+    ): ShapePlacement {
+        return new ShapePlacement(
+            this.broadPhase.placementBlockers(
+                this.narrowPhase,
+                this.bodies,
+                this.colliders,
+                shapePos,
+                shapeRot,
+                shape,
+                tolerance,
+                filterFlags,
+                filterGroups,
+                filterExcludeCollider ? filterExcludeCollider.handle : null,
+                filterExcludeRigidBody ? filterExcludeRigidBody.handle : null,
+                this.colliders.castClosure(filterPredicate),
+                filterRequireTags,
+                filterExcludeTags,
+            ),
+        );
+    }
+
+    /**
+     * Computes a position where the given shape no longer penetrates the colliders of the scene,
+     * e.g., to fix up a spawn point or a teleport destination.
+     *
+     * The shape is iteratively pushed out along the normals of its contacts, as done by
+     * character controllers to recover from overlaps. Its orientation is preserved.
+     *
+     * @param shapePos - The initial position of the shape.
+     * @param shapeRot - The orientation of the shape.
+     * @param shape - The shape to depenetrate.
+     * @param maxIterations - The maximum number of depenetration iterations (default: `8`).
+     * @param tolerance - The penetration depth left unresolved (default: `0`).
+     */
+    public depenetrateShape(
+        shapePos: Vector,
+        shapeRot: Rotation,
+        shape: Shape,
+        maxIterations: number = 8,
+        tolerance: number = 0,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: Collider,
+        filterExcludeRigidBody?: RigidBody,
+        filterPredicate?: (collider: Collider) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
+    ): ShapeDepenetration {
+        return this.broadPhase.depenetrateShape(
+            this.narrowPhase,
+            this.bodies,
+            this.colliders,
+            shapePos,
+            shapeRot,
+            shape,
+            maxIterations,
+            tolerance,
+            filterFlags,
+            filterGroups,
+            filterExcludeCollider ? filterExcludeCollider.handle : null,
+            filterExcludeRigidBody ? filterExcludeRigidBody.handle : null,
+            this.colliders.castClosure(filterPredicate),
+            filterRequireTags,
+            filterExcludeTags,
+        );
+    }
+
+    /**
+     * Computes the audio occlusion of several sound sources in a single call.
+     *
+     * For each source, this counts the colliders crossed by the segment between the listener and
+     * the source. A count of zero means the source isn’t occluded, which is always the case of a
+     * source at the position of the listener.
+     *
+     * @param listener - The position of the listener.
+     * @param sources - The flattened positions `[x, y, z]` (`[x, y]` in 2D) of the sources.
+     * @returns The number of colliders crossed for each source.
+     */
+    public occlusion(
+        listener: Vector,
+        sources: Float64Array,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: Collider,
+        filterExcludeRigidBody?: RigidBody,
+        filterPredicate?: (collider: Collider) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
+    ): Uint32Array {
+        return this.broadPhase.occlusion(
+            this.narrowPhase,
+            this.bodies,
+            this.colliders,
+            listener,
+            sources,
+            filterFlags,
+            filterGroups,
+            filterExcludeCollider ? filterExcludeCollider.handle : null,
+            filterExcludeRigidBody ? filterExcludeRigidBody.handle : null,
+            this.colliders.castClosure(filterPredicate),
+            filterRequireTags,
+            filterExcludeTags,
+        );
+    }
+
+    /**
+     * Tests the visibility of many target points from a single origin, e.g., for AI perception.
+     *
+     * The test of a target stops at the first collider found between the origin and the target,
+     * which is cheaper than casting individual rays with `World.castRay`.
+     *
+     * @param origin - The point the targets are seen from.
+     * @param targets - The flattened positions `[x, y, z]` (`[x, y]` in 2D) of the targets.
+     * @returns A bit mask with the bit `i % 32` of the element `i / 32` set if the target `i` is
+     *          visible, i.e., `(result[i >> 5] >>> (i & 31)) & 1`.
+     */
+    public lineOfSight(
+        origin: Vector,
+        targets: Float64Array,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: Collider,
+        filterExcludeRigidBody?: RigidBody,
+        filterPredicate?: (collider: Collider) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
+    ): Uint32Array {
+        return this.broadPhase.lineOfSight(
+            this.narrowPhase,
+            this.bodies,
+            this.colliders,
+            origin,
+            targets,
+            filterFlags,
+            filterGroups,
+            filterExcludeCollider ? filterExcludeCollider.handle : null,
+            filterExcludeRigidBody ? filterExcludeRigidBody.handle : null,
+            this.colliders.castClosure(filterPredicate),
+            filterRequireTags,
+            filterExcludeTags,
+        );
+    }
+
+    /**
+     * Finds the handles of all the colliders with an AABB intersecting the convex region bounded
+     * by the given planes (typically the 6 planes of a camera frustum).
+     *
+     * This reuses the broad-phase acceleration structure, which makes it suitable for view-frustum
+     * culling or interest management.
+     *
+     * @param planes - The flattened plane equations `[a, b, c, d]` (`[a, b, c]` in 2D) of each plane.
+     *                 A point `x` is inside of the region if `dot([a, b, c], x) + d >= 0` for every plane,
+     *                 i.e., the plane normals point toward the inside of the region.
+     * @returns The handles of the colliders with an AABB intersecting the region.
+     */
+    public collidersInFrustum(
+        planes: Float64Array,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterExcludeCollider?: Collider,
+        filterExcludeRigidBody?: RigidBody,
+        filterPredicate?: (collider: Collider) => boolean,
+        filterRequireTags?: number,
+        filterExcludeTags?: number,
+    ): Float64Array {
+        return this.broadPhase.collidersInFrustum(
+            this.narrowPhase,
+            this.bodies,
+            this.colliders,
+            planes,
+            filterFlags,
+            filterGroups,
+            filterExcludeCollider ? filterExcludeCollider.handle : null,
+            filterExcludeRigidBody ? filterExcludeRigidBody.handle : null,
+            this.colliders.castClosure(filterPredicate),
+            filterRequireTags,
+            filterExcludeTags,
+        );
+    }
+
+    /**
+     * Finds the handles of all the colliders with an AABB intersecting the given AABB.
+     *
+     * @param aabbCenter - The center of the AABB to test.
+     * @param aabbHalfExtents - The half-extents of the AABB to test.
+     * @param callback - The callback that will be called with the handles of all the colliders
+     *                   currently intersecting the given AABB.
+     */
+    public collidersWithAabbIntersectingAabb(
+        aabbCenter: Vector,
+        aabbHalfExtents: Vector,
+        callback: (handle: Collider) => boolean,
+    ) {
+        this.broadPhase.collidersWithAabbIntersectingAabb(
+            this.narrowPhase,
+            this.bodies,
+            this.colliders,
+            aabbCenter,
+            aabbHalfExtents,
+            this.colliders.castClosure(callback),
+        );
+    }
+
+    /**
+     * Enumerates all the colliders potentially in contact with the given collider.
      *
      * @param collider1 - The second collider involved in the contact.
      * @param f - Closure that will be called on each collider that is in contact with `collider1`.
@@ -1093,14 +2356,107 @@ export class World {
     /**
      * Enumerates all the colliders intersecting the given colliders, assuming one of them
      * is a sensor.
+     *
+     * @param collider1 - The collider to check.
+     * @param f - Closure called with each collider intersecting `collider1`, and the simulation time
+     *            elapsed since they started intersecting if `World.intersectionDurationsEnabled` is set.
      */
     public intersectionPairsWith(
         collider1: Collider,
-        f: (collider2: Collider) => void,
+        f: (collider2: Collider, duration?: number) => void,
     ) {
         this.narrowPhase.intersectionPairsWith(
             collider1.handle,
-            this.colliders.castClosure(f),
+            (handle, duration) => f(this.colliders.get(handle), duration),
+        );
+    }
+
+    /**
+     * Is the tracking of intersection durations enabled?
+     */
+    get intersectionDurationsEnabled(): boolean {
+        return this.narrowPhase.intersectionDurationsEnabled;
+    }
+
+    /**
+     * Enables or disables the tracking of the simulation time elapsed since each pair of colliders
+     * started intersecting (disabled by default), e.g., for "charge up after 2 seconds in the zone"
+     * gameplay.
+     *
+     * See `World.intersectionPairsWith` and `World.intersectionDuration`.
+     */
+    set intersectionDurationsEnabled(enabled: boolean) {
+        this.narrowPhase.intersectionDurationsEnabled = enabled;
+    }
+
+    /**
+     * The simulation time elapsed since the two colliders started intersecting.
+     *
+     * The time is counted from the end of the substep during which the intersection started, so
+     * it is `0` after the step where the colliders started intersecting.
+     *
+     * Returns `undefined` if they aren’t intersecting, or if `World.intersectionDurationsEnabled`
+     * isn’t set.
+     *
+     * @param collider1 - The first collider.
+     * @param collider2 - The second collider.
+     */
+    public intersectionDuration(
+        collider1: Collider,
+        collider2: Collider,
+    ): number | undefined {
+        return this.narrowPhase.intersectionDuration(
+            collider1.handle,
+            collider2.handle,
+        );
+    }
+
+    /**
+     * The rigid-bodies connected to the given rigid-body through the current contact graph,
+     * including itself, e.g., everything resting on a platform.
+     *
+     * Two rigid-bodies are connected if any of their colliders have an active contact. The traversal
+     * doesn’t continue past the fixed and kinematic rigid-bodies other than the starting one, so that
+     * everything resting on the same ground isn’t reported as connected, but these rigid-bodies are
+     * still part of the result.
+     *
+     * @param body - The rigid-body to start from.
+     * @param filterGroups - If set, only the contacts between colliders compatible with these
+     *   collision groups are traversed.
+     */
+    public connectedBodies(
+        body: RigidBody,
+        filterGroups?: InteractionGroups,
+    ): RigidBody[] {
+        return this.narrowPhase
+            .connectedBodies(
+                this.bodies,
+                this.colliders,
+                body.handle,
+                filterGroups,
+            )
+            .map((handle) => this.bodies.get(handle));
+    }
+
+    /**
+     * The distance below which contacts are generated between the two given colliders.
+     *
+     * This is the sum of their contact skins and of the prediction distance derived from the
+     * integration parameters, extended by the relative motion of their rigid-bodies if soft-CCD is
+     * enabled for any of them. It helps tuning `Collider.setContactSkin`.
+     *
+     * @param collider1 - The first collider.
+     * @param collider2 - The second collider.
+     */
+    public contactPredictionDistance(
+        collider1: Collider,
+        collider2: Collider,
+    ): number {
+        return this.colliders.effectivePredictionDistance(
+            collider1.handle,
+            collider2.handle,
+            this.integrationParameters,
+            this.bodies,
         );
     }
 
@@ -1121,6 +2477,300 @@ export class World {
         this.narrowPhase.contactPair(collider1.handle, collider2.handle, f);
     }
 
+    /**
+     * Computes the contacts between the colliders at their current positions, without advancing
+     * the simulation.
+     *
+     * This is useful for, e.g., previewing penetrations in an editor, or validating placements.
+     * Only the contact geometry is computed: the manifolds have no solver contacts, and no
+     * impulses. The contacts reported by `contactPair` are not affected.
+     *
+     * Like the scene queries, this relies on the collider positions known by the broad-phase:
+     * call `updateSceneQueries` first if rigid-bodies or colliders moved since the last step.
+     *
+     * @param f - Closure that will be called on each contact manifold, with the two colliders
+     *            in contact. The manifold is only valid during the call.
+     * @param prediction - The distance below which two colliders are considered in contact.
+     */
+    public previewContacts(
+        f: (
+            collider1: Collider,
+            collider2: Collider,
+            manifold: TempContactManifold,
+        ) => void,
+        prediction: number = 0.0,
+    ) {
+        this.narrowPhase.previewContacts(
+            this.broadPhase,
+            this.bodies,
+            this.colliders,
+            prediction,
+            (handle1, handle2, manifold) =>
+                f(
+                    this.colliders.get(handle1),
+                    this.colliders.get(handle2),
+                    manifold,
+                ),
+        );
+    }
+
+    /**
+     * Is the tracking of stable contact pair ids enabled?
+     */
+    get contactPairIdsEnabled(): boolean {
+        return this.narrowPhase.contactPairIdsEnabled;
+    }
+
+    /**
+     * Enables or disables the tracking of stable contact pair ids (disabled by default).
+     *
+     * See `World.contactPairId` and `EventQueue.drainCollisionEventsWithPairIds`.
+     */
+    set contactPairIdsEnabled(enabled: boolean) {
+        this.narrowPhase.contactPairIdsEnabled = enabled;
+    }
+
+    /**
+     * The stable id of the pair formed by two colliders in contact (or intersecting).
+     *
+     * This id stays the same across simulation steps until the colliders stop touching. This requires
+     * `World.contactPairIdsEnabled` to be set.
+     *
+     * @param collider1 - The first collider involved in the contact.
+     * @param collider2 - The second collider involved in the contact.
+     * @returns The pair id, or `null` if the colliders are not touching.
+     */
+    public contactPairId(
+        collider1: Collider,
+        collider2: Collider,
+    ): number | null {
+        return this.narrowPhase.contactPairId(
+            collider1.handle,
+            collider2.handle,
+        );
+    }
+
+    /**
+     * Is the recording of impacts enabled?
+     */
+    get impactTrackingEnabled(): boolean {
+        return this.narrowPhase.impactTrackingEnabled;
+    }
+
+    /**
+     * Enables or disables the recording of impacts (disabled by default).
+     *
+     * See `World.forEachImpact`.
+     */
+    set impactTrackingEnabled(enabled: boolean) {
+        this.narrowPhase.impactTrackingEnabled = enabled;
+    }
+
+    /**
+     * Applies the given closure to each pair of colliders that started being in contact during the
+     * last simulation step. This requires `World.impactTrackingEnabled` to be set.
+     *
+     * The normal impulse and the relative normal velocity can be used to scale impact sounds or damages.
+     *
+     * @param f - The closure called with the two colliders, the sum of the normal impulses applied at their
+     *            contact points, and their relative velocity along the contact normal before the impact
+     *            (negative when the colliders approach each other).
+     */
+    public forEachImpact(
+        f: (
+            collider1: Collider,
+            collider2: Collider,
+            normalImpulse: number,
+            relativeNormalVelocity: number,
+        ) => void,
+    ) {
+        this.narrowPhase.forEachImpact(
+            (handle1, handle2, normalImpulse, relativeNormalVelocity) => {
+                f(
+                    this.colliders.get(handle1),
+                    this.colliders.get(handle2),
+                    normalImpulse,
+                    relativeNormalVelocity,
+                );
+            },
+        );
+    }
+
+    // #if DIM3
+    /**
+     * Applies the given closure to each contact point corrected by the internal-edge correction of a
+     * triangle mesh or heightfield, e.g., to diagnose objects snagging on the seams of flat surfaces.
+     *
+     * See `Collider.setFixInternalEdges`.
+     *
+     * @param f - The closure called with the triangle mesh or heightfield, the other collider, and the
+     *            indices of the contact manifold and of the contact point in the contact pair.
+     */
+    public forEachCorrectedContact(
+        f: (
+            meshCollider: Collider,
+            otherCollider: Collider,
+            manifoldIndex: number,
+            contactIndex: number,
+        ) => void,
+    ) {
+        this.narrowPhase.forEachCorrectedContact(
+            this.colliders,
+            (handle1, handle2, manifoldIndex, contactIndex) => {
+                f(
+                    this.colliders.get(handle1),
+                    this.colliders.get(handle2),
+                    manifoldIndex,
+                    contactIndex,
+                );
+            },
+        );
+    }
+    // #endif
+
+    /**
+     * Is the accumulation of contact loads enabled?
+     */
+    get contactLoadsEnabled(): boolean {
+        return this.narrowPhase.contactLoadsEnabled;
+    }
+
+    /**
+     * Enables or disables the accumulation of contact loads (disabled by default).
+     *
+     * See `World.contactLoad` and `World.bodyContactLoad`.
+     */
+    set contactLoadsEnabled(enabled: boolean) {
+        this.narrowPhase.contactLoadsEnabled = enabled;
+    }
+
+    /**
+     * The total normal impulse applied between the two colliders during the last simulation step,
+     * including all its substeps. This requires `World.contactLoadsEnabled` to be set.
+     *
+     * Dividing it by the timestep gives the average force between the colliders, e.g., the load
+     * transmitted through a support.
+     *
+     * @param collider1 - The first collider.
+     * @param collider2 - The second collider.
+     * @returns The total normal impulse, or `undefined` if the colliders weren’t in contact.
+     */
+    public contactLoad(
+        collider1: Collider,
+        collider2: Collider,
+    ): number | undefined {
+        return this.narrowPhase.contactLoad(collider1.handle, collider2.handle);
+    }
+
+    /**
+     * Applies the given closure to each pair of colliders in contact during the last simulation step.
+     * This requires `World.contactLoadsEnabled` to be set.
+     *
+     * @param f - The closure called with the two colliders, and the total normal impulse applied between
+     *            them during the last step.
+     */
+    public forEachContactLoad(
+        f: (
+            collider1: Collider,
+            collider2: Collider,
+            normalImpulse: number,
+        ) => void,
+    ) {
+        this.narrowPhase.forEachContactLoad(
+            (handle1, handle2, normalImpulse) => {
+                f(
+                    this.colliders.get(handle1),
+                    this.colliders.get(handle2),
+                    normalImpulse,
+                );
+            },
+        );
+    }
+
+    /**
+     * The sum of the normal impulses applied by all the contacts of the given rigid-body during the
+     * last simulation step. This requires `World.contactLoadsEnabled` to be set.
+     *
+     * Comparing it to a threshold, e.g., after dividing it by the timestep, allows detecting the supports
+     * of a structure that are overloaded and should collapse.
+     *
+     * @param body - The rigid-body.
+     */
+    public bodyContactLoad(body: RigidBody): number {
+        return this.narrowPhase.bodyContactLoad(body.handle);
+    }
+
+    /**
+     * Applies the given closure to each rigid-body in contact during the last simulation step. This
+     * requires `World.contactLoadsEnabled` to be set.
+     *
+     * @param f - The closure called with the rigid-body, and the sum of the normal impulses applied by
+     *            its contacts during the last step.
+     */
+    public forEachBodyContactLoad(
+        f: (body: RigidBody, normalImpulse: number) => void,
+    ) {
+        this.narrowPhase.forEachBodyContactLoad((handle, normalImpulse) => {
+            f(this.bodies.get(handle), normalImpulse);
+        });
+    }
+
+    /**
+     * The density of the fluid the colliders with an aerodynamic drag coefficient move through.
+     */
+    get airDensity(): number {
+        return this.colliders.airDensity;
+    }
+
+    /**
+     * Sets the density of the fluid the colliders with an aerodynamic drag coefficient move
+     * through, e.g., `1.225` for air at sea level (the default) or `1000` for water, in kg/m³.
+     *
+     * See `ColliderDesc.setDragCoefficient`.
+     */
+    set airDensity(density: number) {
+        this.colliders.airDensity = density;
+    }
+
+    /**
+     * Is the recording of proximity events enabled?
+     */
+    get proximityEventsEnabled(): boolean {
+        return this.narrowPhase.proximityEventsEnabled;
+    }
+
+    /**
+     * Enables or disables the recording of proximity events (disabled by default).
+     *
+     * See `World.forEachProximityEvent`.
+     */
+    set proximityEventsEnabled(enabled: boolean) {
+        this.narrowPhase.proximityEventsEnabled = enabled;
+    }
+
+    /**
+     * Applies the given closure to each pair of colliders whose AABBs started or stopped overlapping
+     * during the last simulation step. This requires `World.proximityEventsEnabled` to be set.
+     *
+     * These events are detected by the broad-phase, before any contact or intersection is computed, and
+     * regardless of the collision groups. They are reported earlier and more cheaply than collision events,
+     * e.g., for interest management or level-of-detail systems.
+     *
+     * @param f - The closure called with the two colliders, and `true` if their AABBs started overlapping,
+     *            `false` if they stopped. A collider is `null` if the pair stopped because it was removed.
+     */
+    public forEachProximityEvent(
+        f: (collider1: Collider, collider2: Collider, started: boolean) => void,
+    ) {
+        this.narrowPhase.forEachProximityEvent((handle1, handle2, started) => {
+            f(
+                this.colliders.get(handle1),
+                this.colliders.get(handle2),
+                started,
+            );
+        });
+    }
+
     /**
      * Returns `true` if `collider1` and `collider2` intersect and at least one of them is a sensor.
      * @param collider1 − The first collider involved in the intersection.
@@ -1133,6 +2783,398 @@ export class World {
         );
     }
 
+    /**
+     * Adds a rule, evaluated natively, preventing the colliders members of `groups1` from
+     * generating contacts or intersections with the colliders members of `groups2`, as long as
+     * the relative linear velocity of their rigid-bodies is smaller than `velocityThreshold`.
+     *
+     * This avoids calling JavaScript physics hooks for common filtering. The
+     * `ActiveHooks.FILTER_CONTACT_PAIRS` and `ActiveHooks.FILTER_INTERSECTION_PAIR` active hooks are
+     * set automatically on the colliders members of `groups1` or `groups2`, including the ones
+     * created later, and removed once no rule or exclusion applies to them, unless they were
+     * already set. The physics hooks given to `World.step` are only called for the pairs where at
+     * least one collider has these active hooks set by the user, not only automatically.
+     *
+     * @param groups1 - Bit mask of the first set of collision groups.
+     * @param groups2 - Bit mask of the second set of collision groups.
+     * @param velocityThreshold - The relative velocity above which the rule no longer applies.
+     *                            Defaults to `Infinity`, i.e., the rule always applies.
+     * @returns The id of the rule, to be given to `World.removePairFilterRule`.
+     */
+    public addPairFilterRule(
+        groups1: number,
+        groups2: number,
+        velocityThreshold?: number,
+    ): number {
+        return this.physicsPipeline.addPairFilterRule(
+            this.colliders,
+            groups1,
+            groups2,
+            velocityThreshold,
+        );
+    }
+
+    /**
+     * Removes the pair filter rule with the given id.
+     *
+     * @param id - The id returned by `World.addPairFilterRule`.
+     */
+    public removePairFilterRule(id: number) {
+        this.physicsPipeline.removePairFilterRule(this.colliders, id);
+    }
+
+    /**
+     * Removes all the pair filter rules.
+     */
+    public clearPairFilterRules() {
+        this.physicsPipeline.clearPairFilterRules(this.colliders);
+    }
+
+    /**
+     * Prevents the two given colliders from generating contacts or intersections with each other,
+     * until `World.removeExclusion` is called.
+     *
+     * This is useful to temporarily ignore the collisions between two specific objects, e.g.,
+     * a carried item and its carrier, without modifying their collision groups. The exclusion is
+     * evaluated natively. The filtering active hooks are set automatically on both colliders, and
+     * removed by `World.removeExclusion` unless they were already set.
+     *
+     * @param collider1 - The first collider.
+     * @param collider2 - The second collider.
+     */
+    public excludeCollisionPair(collider1: Collider, collider2: Collider) {
+        this.physicsPipeline.excludeCollisionPair(
+            this.colliders,
+            collider1.handle,
+            collider2.handle,
+        );
+    }
+
+    /**
+     * Allows the two given colliders, previously given to `World.excludeCollisionPair`, to interact
+     * with each other again.
+     *
+     * @param collider1 - The first collider.
+     * @param collider2 - The second collider.
+     */
+    public removeExclusion(collider1: Collider, collider2: Collider) {
+        this.physicsPipeline.removeExclusion(
+            this.colliders,
+            collider1.handle,
+            collider2.handle,
+        );
+    }
+
+    /**
+     * Are the two given colliders prevented from interacting with each other by
+     * `World.excludeCollisionPair`?
+     *
+     * @param collider1 - The first collider.
+     * @param collider2 - The second collider.
+     */
+    public isCollisionPairExcluded(
+        collider1: Collider,
+        collider2: Collider,
+    ): boolean {
+        return this.physicsPipeline.isCollisionPairExcluded(
+            collider1.handle,
+            collider2.handle,
+        );
+    }
+
+    /**
+     * Prevents the colliders attached to the two given rigid-bodies from generating contacts or
+     * intersections with each other, until `World.removeBodyExclusion` is called.
+     *
+     * This is useful to select which links of a multibody collide with each other, e.g., to let
+     * an elbow collide with the torso while ignoring the collisions between the forearm and the
+     * hand. The exclusion is evaluated natively. The filtering active hooks are set automatically on
+     * the colliders of both rigid-bodies, and removed by `World.removeBodyExclusion` unless they were
+     * already set.
+     *
+     * @param body1 - The first rigid-body.
+     * @param body2 - The second rigid-body.
+     */
+    public excludeBodyCollisionPair(body1: RigidBody, body2: RigidBody) {
+        this.physicsPipeline.excludeBodyCollisionPair(
+            this.colliders,
+            body1.handle,
+            body2.handle,
+        );
+    }
+
+    /**
+     * Allows the colliders of the two given rigid-bodies, previously given to
+     * `World.excludeBodyCollisionPair`, to interact with each other again.
+     *
+     * @param body1 - The first rigid-body.
+     * @param body2 - The second rigid-body.
+     */
+    public removeBodyExclusion(body1: RigidBody, body2: RigidBody) {
+        this.physicsPipeline.removeBodyExclusion(
+            this.colliders,
+            body1.handle,
+            body2.handle,
+        );
+    }
+
+    /**
+     * Are the colliders of the two given rigid-bodies prevented from interacting with each other
+     * by `World.excludeBodyCollisionPair`?
+     *
+     * @param body1 - The first rigid-body.
+     * @param body2 - The second rigid-body.
+     */
+    public isBodyCollisionPairExcluded(
+        body1: RigidBody,
+        body2: RigidBody,
+    ): boolean {
+        return this.physicsPipeline.isBodyCollisionPairExcluded(
+            body1.handle,
+            body2.handle,
+        );
+    }
+
+    /**
+     * Discards the warm-start impulses of all the contacts and joints at the next simulation step.
+     *
+     * Warm-starting reuses the impulses computed at the previous step to speed up the solver
+     * convergence, but these stale impulses may cause visible pops after large state changes,
+     * e.g., after restoring a snapshot or teleporting many bodies.
+     */
+    public resetWarmstart() {
+        this.physicsPipeline.resetWarmstart();
+    }
+
+    /**
+     * Discards the warm-start impulses of the contacts and impulse joints involving the given
+     * rigid-body, e.g., after teleporting it.
+     *
+     * The joint impulses are discarded immediately. The contact impulses are discarded at the next
+     * simulation step, for which the `ActiveHooks.MODIFY_SOLVER_CONTACTS` active hook is set
+     * automatically on the colliders of the rigid-body.
+     *
+     * @param body - The rigid-body.
+     */
+    public resetBodyWarmstart(body: RigidBody) {
+        this.physicsPipeline.resetBodyWarmstart(
+            this.impulseJoints,
+            body.handle,
+        );
+    }
+
+    /**
+     * Discards, at the next simulation step, the warm-start impulses of the contacts between the
+     * two given colliders.
+     *
+     * The `ActiveHooks.MODIFY_SOLVER_CONTACTS` active hook is set automatically on both colliders
+     * for that step.
+     *
+     * @param collider1 - The first collider.
+     * @param collider2 - The second collider.
+     */
+    public resetPairWarmstart(collider1: Collider, collider2: Collider) {
+        this.physicsPipeline.resetPairWarmstart(
+            collider1.handle,
+            collider2.handle,
+        );
+    }
+
+    /**
+     * The ratio between the time simulated by a step and the timestep (default: 1).
+     */
+    get timeScale(): number {
+        return this.physicsPipeline.timeScale;
+    }
+
+    /**
+     * Sets the ratio between the time simulated by a step and the timestep, e.g., `0.2` for
+     * a slow-motion (bullet-time) effect.
+     *
+     * Prefer this to scaling the timestep or the velocities from JS: the motor target
+     * velocities, damping coefficients and spring frequencies are expressed per second of
+     * simulated time and are slowed down consistently, while `World.timestep` and the other
+     * integration parameters are left unchanged. A time scale of 0 pauses the simulation.
+     *
+     * @param factor - The time scale, must be positive or zero.
+     */
+    set timeScale(factor: number) {
+        this.physicsPipeline.timeScale = factor;
+    }
+
+    /**
+     * The bit mask of the active simulation layers (default: all the layers).
+     */
+    get activeLayers(): number {
+        return this.physicsPipeline.activeLayers;
+    }
+
+    /**
+     * Sets the bit mask of the active simulation layers, e.g., to freeze the gameplay
+     * rigid-bodies during a cutscene while the rigid-bodies of the UI keep being simulated.
+     *
+     * Only the rigid-bodies with any of their simulation layers active (see
+     * `RigidBody.setSimulationLayers`) are simulated. The other rigid-bodies are frozen in
+     * place: they keep their velocities, which apply again once any of their layers is
+     * reactivated, and act as static obstacles for the simulated rigid-bodies.
+     *
+     * @param mask - The bit mask of the active layers.
+     */
+    set activeLayers(mask: number) {
+        this.physicsPipeline.activeLayers = mask;
+    }
+
+    /**
+     * The clock the timestamps of the remote poses are compared to (see
+     * `RigidBody.pushRemotePose`). It is advanced by the simulated time at each step.
+     */
+    get remotePoseTime(): number {
+        return this.bodies.raw.remotePoseTime();
+    }
+
+    /**
+     * Sets the clock the timestamps of the remote poses are compared to, e.g., to synchronize
+     * it with the clock of a server.
+     *
+     * @param time - The current time.
+     */
+    set remotePoseTime(time: number) {
+        this.bodies.raw.setRemotePoseTime(time);
+    }
+
+    /**
+     * The delay between `World.remotePoseTime` and the time the remote poses are interpolated
+     * at (default: 0.1).
+     */
+    get remotePoseDelay(): number {
+        return this.bodies.raw.remotePoseDelay();
+    }
+
+    /**
+     * Sets the delay between `World.remotePoseTime` and the time the remote poses are
+     * interpolated at. It should exceed the interval between two received poses, so there is
+     * usually a pose to interpolate toward.
+     *
+     * @param delay - The interpolation delay.
+     */
+    set remotePoseDelay(delay: number) {
+        this.bodies.raw.setRemotePoseDelay(delay);
+    }
+
+    /**
+     * The maximum duration the motion of a rigid-body is extrapolated for past its last
+     * remote pose (default: 0.25).
+     */
+    get maxRemotePoseExtrapolation(): number {
+        return this.bodies.raw.maxRemotePoseExtrapolation();
+    }
+
+    /**
+     * Sets the maximum duration the motion of a rigid-body is extrapolated for past its last
+     * remote pose. The rigid-body stops once it is reached.
+     *
+     * @param duration - The maximum extrapolation duration.
+     */
+    set maxRemotePoseExtrapolation(duration: number) {
+        this.bodies.raw.setMaxRemotePoseExtrapolation(duration);
+    }
+
+    /**
+     * The number of substeps each simulation step is split into (default: 1).
+     */
+    get numSubsteps(): number {
+        return this.physicsPipeline.numSubsteps;
+    }
+
+    /**
+     * Sets the number of substeps each simulation step is split into.
+     *
+     * Each substep runs a full pipeline step with a timestep equal to
+     * `timestep / numSubsteps`. Events are accumulated over all the substeps. The
+     * position-based kinematic rigid-bodies move toward their next kinematic position at a
+     * constant velocity across the substeps.
+     */
+    set numSubsteps(numSubsteps: number) {
+        this.physicsPipeline.numSubsteps = numSubsteps;
+    }
+
+    /**
+     * Enables the automatic increase of the number of substeps when a rigid-body moves fast
+     * relative to the size of its colliders, to reduce tunneling without always paying the
+     * cost of substepping.
+     *
+     * Before each step, the number of substeps is increased so that no awake dynamic
+     * rigid-body travels, during a substep, more than `maxTravelRatio` times the smallest
+     * extent of its colliders, assuming its current linear velocity. It never drops below
+     * `numSubsteps`.
+     *
+     * @param maxTravelRatio - The maximum travel distance per substep, relative to the
+     *   smallest extent of the colliders of the rigid-body, e.g., `0.5`.
+     * @param maxSubsteps - The maximum number of substeps of a step.
+     * @returns `false`, leaving the substepping unchanged, if `maxTravelRatio` isn't a positive
+     *   number or `maxSubsteps` isn't a positive integer.
+     */
+    public enableAutoSubstepping(
+        maxTravelRatio: number,
+        maxSubsteps: number,
+    ): boolean {
+        return this.physicsPipeline.enableAutoSubstepping(
+            maxTravelRatio,
+            maxSubsteps,
+        );
+    }
+
+    /**
+     * Disables the automatic substepping: each step is split into `numSubsteps` substeps.
+     */
+    public disableAutoSubstepping() {
+        this.physicsPipeline.disableAutoSubstepping();
+    }
+
+    /**
+     * Indicates if the automatic substepping is enabled.
+     */
+    get autoSubsteppingEnabled(): boolean {
+        return this.physicsPipeline.autoSubsteppingEnabled;
+    }
+
+    /**
+     * The number of substeps executed by the last simulation step.
+     */
+    get lastNumSubsteps(): number {
+        return this.physicsPipeline.lastNumSubsteps;
+    }
+
+    /**
+     * The simulation time, i.e., the sum of the timesteps of all the substeps executed.
+     *
+     * This is the time used to tag the events when `EventQueue.setSubstepTagsEnabled` is set.
+     */
+    get simulationTime(): number {
+        return this.physicsPipeline.simulationTime;
+    }
+
+    /**
+     * Sets the simulation time, e.g., to synchronize it with another clock.
+     */
+    set simulationTime(time: number) {
+        this.physicsPipeline.simulationTime = time;
+    }
+
+    /**
+     * Sets a function called before each substep to compute forces applied during that
+     * substep only, e.g., custom aerodynamics or magnetic forces, or removes it if `null`.
+     *
+     * The callback receives the state of the awake dynamic rigid-bodies and returns the
+     * forces to apply. It must not access the world while it runs. The callback isn’t saved by
+     * `World.takeSnapshot`.
+     *
+     * @param callback - The function called before each substep.
+     */
+    public setSubstepCallback(callback: SubstepCallback | null) {
+        this.physicsPipeline.setSubstepCallback(callback);
+    }
+
     /**
      * Sets whether internal performance profiling is enabled (default: false).
      *
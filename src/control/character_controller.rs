@@ -2,15 +2,15 @@ use crate::dynamics::RawRigidBodySet;
 use crate::geometry::{RawBroadPhase, RawColliderSet, RawNarrowPhase};
 use crate::math::RawVector;
 use crate::utils::{self, FlatHandle};
-use na::{Isometry, Unit};
+use na::{Isometry, Translation, Unit};
 use rapier::control::{
     CharacterAutostep, CharacterCollision, CharacterLength, EffectiveCharacterMovement,
     KinematicCharacterController,
 };
-use rapier::geometry::{ColliderHandle, ShapeCastHit};
-use rapier::math::{Point, Real, Vector};
-use rapier::parry::query::ShapeCastStatus;
-use rapier::pipeline::{QueryFilter, QueryFilterFlags};
+use rapier::geometry::{Capsule, ColliderHandle, Shape, ShapeCastHit, SharedShape};
+use rapier::math::{Point, Real, Vector, DIM};
+use rapier::parry::query::{self, ShapeCastStatus};
+use rapier::pipeline::{QueryFilter, QueryFilterFlags, QueryPipeline};
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
@@ -26,6 +26,52 @@ fn length_value(length: CharacterLength) -> Real {
         CharacterLength::Relative(val) => val,
     }
 }
+
+/// The maximum number of attempts at pushing a resized character out of its surroundings.
+const MAX_DEPENETRATION_ITERATIONS: usize = 4;
+
+impl RawKinematicCharacterController {
+    /// Computes the translation needed for `shape`, at the position `pose`, to stop overlapping
+    /// the colliders of `query_pipeline`.
+    ///
+    /// Returns `None` if the shape overlaps and `depenetrate` is `false`, or if no
+    /// non-overlapping position could be found.
+    fn depenetration(
+        &self,
+        query_pipeline: &QueryPipeline,
+        pose: &Isometry<Real>,
+        shape: &dyn Shape,
+        depenetrate: bool,
+    ) -> Option<Vector<Real>> {
+        let offset = length_value(self.controller.offset);
+        let mut translation = Vector::zeros();
+
+        for _ in 0..MAX_DEPENETRATION_ITERATIONS {
+            let pos = Translation::from(translation) * pose;
+            let mut overlapping = false;
+
+            for (_, co) in query_pipeline.intersect_shape(pos, shape) {
+                if !depenetrate {
+                    return None;
+                }
+
+                overlapping = true;
+                if let Ok(Some(contact)) =
+                    query::contact(&pos, shape, co.position(), co.shape(), 0.0)
+                {
+                    translation += contact.normal1.into_inner() * (contact.dist - offset);
+                }
+            }
+
+            if !overlapping {
+                return Some(translation);
+            }
+        }
+
+        None
+    }
+}
+
 #[wasm_bindgen]
 impl RawKinematicCharacterController {
     #[wasm_bindgen(constructor)]
@@ -212,6 +258,80 @@ impl RawKinematicCharacterController {
         }
     }
 
+    /// Resizes the capsule shape of the given character collider, e.g., to crouch or stand up.
+    ///
+    /// The lowest point of the capsule along the `up` direction of this controller is
+    /// preserved, so the character keeps its feet on the ground. If the resized capsule would
+    /// overlap other colliders (e.g. when standing up under a low ceiling), the resize is blocked,
+    /// unless `depenetrate` is `true` in which case the translation needed for the resized
+    /// character to stop overlapping is computed.
+    ///
+    /// Returns `None`, and leaves the shape unchanged, if the collider isn’t a capsule or if
+    /// the resize is blocked. Otherwise, returns the translation that must be applied to the
+    /// character to resolve the overlaps (zero if there were none).
+    pub fn resizeCapsule(
+        &self,
+        broad_phase: &RawBroadPhase,
+        narrow_phase: &RawNarrowPhase,
+        bodies: &RawRigidBodySet,
+        colliders: &mut RawColliderSet,
+        collider_handle: FlatHandle,
+        half_height: Real,
+        radius: Real,
+        depenetrate: bool,
+        filter_flags: u32,
+        filter_groups: Option<u32>,
+        filter_predicate: &js_sys::Function,
+    ) -> Option<RawVector> {
+        let handle = utils::collider_handle(collider_handle);
+        let collider = colliders.0.get(handle)?;
+        let capsule = collider.shape().as_capsule()?;
+        let pose = *collider.position();
+        let parent = collider.parent();
+
+        // Shift the resized segment so the lowest point of the capsule doesn’t move.
+        let local_up = pose
+            .rotation
+            .inverse_transform_unit_vector(&self.controller.up);
+        let lowest = |a: &Point<Real>, b: &Point<Real>, radius: Real| {
+            a.coords.dot(&local_up).min(b.coords.dot(&local_up)) - radius
+        };
+        let segment = capsule.segment;
+        let axis = Unit::try_new(segment.b - segment.a, 1.0e-6).unwrap_or(Vector::y_axis());
+        let center = na::center(&segment.a, &segment.b);
+        let a = center - *axis * half_height;
+        let b = center + *axis * half_height;
+        let shift =
+            *local_up * (lowest(&segment.a, &segment.b, capsule.radius) - lowest(&a, &b, radius));
+        let resized = Capsule::new(a + shift, b + shift, radius);
+
+        let translation = utils::with_filter(filter_predicate, |predicate| {
+            let query_filter = QueryFilter {
+                flags: QueryFilterFlags::from_bits(filter_flags)
+                    .unwrap_or(QueryFilterFlags::empty()),
+                groups: filter_groups.map(crate::geometry::unpack_interaction_groups),
+                exclude_collider: Some(handle),
+                exclude_rigid_body: parent,
+                predicate,
+            };
+
+            let query_pipeline = broad_phase.0.as_query_pipeline(
+                narrow_phase.0.query_dispatcher(),
+                &bodies.0,
+                &colliders.0,
+                query_filter,
+            );
+
+            self.depenetration(&query_pipeline, &pose, &resized, depenetrate)
+        })?;
+
+        colliders
+            .0
+            .get_mut(handle)?
+            .set_shape(SharedShape::new(resized));
+        Some(translation.into())
+    }
+
     pub fn computedMovement(&self) -> RawVector {
         self.result.translation.into()
     }
@@ -224,6 +344,22 @@ impl RawKinematicCharacterController {
         self.events.len()
     }
 
+    /// All the collisions detected by the last call to `computeColliderMovement`, flattened.
+    ///
+    /// Each collision is described by `1 + 1 + 2 * DIM` consecutive values: the handle of the
+    /// collider hit, the time of impact, the world-space normal of the collider hit, and the
+    /// translation applied to the character before the collision.
+    pub fn computedCollisionsFlat(&self) -> Vec<f64> {
+        let mut result = Vec::with_capacity(self.events.len() * (2 + 2 * DIM));
+        for coll in &self.events {
+            result.push(utils::flat_handle(coll.handle.0));
+            result.push(coll.hit.time_of_impact);
+            result.extend_from_slice(coll.hit.normal1.as_slice());
+            result.extend_from_slice(coll.translation_applied.as_slice());
+        }
+        result
+    }
+
     pub fn computedCollision(&self, i: usize, collision: &mut RawCharacterCollision) -> bool {
         if let Some(coll) = self.events.get(i) {
             collision.0 = *coll;
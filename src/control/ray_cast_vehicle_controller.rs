@@ -2,13 +2,442 @@ use crate::dynamics::RawRigidBodySet;
 use crate::geometry::{RawBroadPhase, RawColliderSet, RawNarrowPhase};
 use crate::math::RawVector;
 use crate::utils::{self, FlatHandle};
-use rapier::control::{DynamicRayCastVehicleController, WheelTuning};
-use rapier::pipeline::{QueryFilter, QueryFilterFlags};
+use na::UnitQuaternion;
+use rapier::control::{DynamicRayCastVehicleController, Wheel, WheelTuning};
+use rapier::dynamics::{RigidBody, RigidBodySet};
+use rapier::geometry::{Ball, ColliderHandle, ColliderSet, Cylinder, Shape};
+use rapier::math::{Isometry, Point, Real, Rotation, Vector};
+use rapier::parry::query::ShapeCastOptions;
+use rapier::pipeline::{QueryFilter, QueryFilterFlags, QueryPipeline};
 use wasm_bindgen::prelude::*;
 
+/// The ground speed below which the slip ratio is computed relative to this speed instead, to
+/// avoid dividing by zero when the vehicle is (nearly) stopped.
+const MIN_SLIP_REFERENCE_SPEED: Real = 1.0e-3;
+
+/// The slip of a wheel against the ground, computed at the end of each vehicle update.
+#[derive(Copy, Clone, Debug, Default)]
+struct WheelSlip {
+    /// The difference between the rolling speed of the wheel and its forward speed relative to
+    /// the ground, divided by that forward speed.
+    ratio: Real,
+    /// The angle, in radians, between the rolling direction of the wheel and its velocity
+    /// relative to the ground.
+    angle: Real,
+}
+
+/// The shape swept along the suspension of the wheels to detect the ground.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum WheelShape {
+    /// A single ray, as cast by the vehicle controller of Rapier.
+    Ray,
+    Ball,
+    /// A cylinder aligned with the axle of the wheel.
+    Cylinder,
+}
+
+impl WheelShape {
+    fn from_u32(shape: u32) -> Self {
+        match shape {
+            1 => WheelShape::Ball,
+            2 => WheelShape::Cylinder,
+            _ => WheelShape::Ray,
+        }
+    }
+}
+
+/// The ground contact of a wheel found by sweeping its shape along its suspension.
+#[derive(Copy, Clone, Debug)]
+struct WheelContact {
+    suspension_length: Real,
+    contact_point_ws: Point<Real>,
+    contact_normal_ws: Vector<Real>,
+    ground_object: ColliderHandle,
+    /// The suspension force for this contact, computed like the vehicle controller does for
+    /// its ray-cast contacts.
+    suspension_force: Real,
+}
+
+/// The fraction of the side friction impulse of a wheel applied at the height of its contact
+/// point, the rest being applied at the height of the chassis center of mass. This is the value
+/// used by the vehicle controller of Rapier, which doesn’t expose it.
+const ROLL_INFLUENCE: Real = 0.1;
+
+/// The inverse of the mass of a rigid-body, as seen by an impulse applied at `point` along `dir`.
+fn inv_mass_at_point(rb: &RigidBody, point: &Point<Real>, dir: &Vector<Real>) -> Real {
+    if !rb.is_dynamic() {
+        return 0.0;
+    }
+
+    let mprops = rb.mass_properties();
+    let ang = (point - rb.center_of_mass()).cross(dir);
+    dir.component_mul(&mprops.effective_inv_mass).dot(dir)
+        + ang.dot(&(mprops.effective_world_inv_inertia * ang))
+}
+
+/// The forward and side directions of the friction of a wheel on a surface with the given normal,
+/// computed like the vehicle controller does for its ray-cast contacts.
+fn friction_directions(wheel: &Wheel, normal: &Vector<Real>) -> (Vector<Real>, Vector<Real>) {
+    let axle = wheel.axle();
+    let axle = (axle - normal * axle.dot(normal))
+        .try_normalize(1.0e-5)
+        .unwrap_or_else(Vector::zeros);
+    let forward = normal
+        .cross(&axle)
+        .try_normalize(1.0e-5)
+        .unwrap_or_else(Vector::zeros);
+    (forward, axle)
+}
+
+/// The point where the side friction impulse of a wheel touching the ground at `point` is
+/// applied, lowered toward the chassis center of mass to limit the roll of the vehicle.
+fn side_impulse_point(chassis: &RigidBody, up_axis: usize, point: &Point<Real>) -> Point<Real> {
+    let up = chassis.position().rotation * Vector::ith(up_axis, 1.0);
+    point - up * (up.dot(&(point - chassis.center_of_mass())) * (1.0 - ROLL_INFLUENCE))
+}
+
+/// The forward and side friction impulses of a wheel at a swept contact, computed like the
+/// vehicle controller does for its ray-cast contacts: the side impulse cancels part of the
+/// sideways velocity, the forward impulse comes from the engine or the brake, and both are
+/// scaled down together if they exceed the grip of the wheel.
+fn friction_impulses(
+    wheel: &Wheel,
+    chassis: &RigidBody,
+    ground: Option<&RigidBody>,
+    contact: &WheelContact,
+    (forward, axle): (&Vector<Real>, &Vector<Real>),
+    num_wheels_on_ground: usize,
+    dt: Real,
+) -> (Real, Real) {
+    let point = &contact.contact_point_ws;
+    let ground_vel = ground
+        .map(|rb| rb.velocity_at_point(point))
+        .unwrap_or_else(Vector::zeros);
+    let rel_vel = chassis.velocity_at_point(point) - ground_vel;
+    let inv_mass = |dir: &Vector<Real>| {
+        inv_mass_at_point(chassis, point, dir)
+            + ground
+                .map(|rb| inv_mass_at_point(rb, point, dir))
+                .unwrap_or(0.0)
+    };
+
+    let side_inv_mass = inv_mass(axle);
+    let mut side_impulse = if side_inv_mass > 0.0 {
+        -0.2 * rel_vel.dot(axle) / side_inv_mass * wheel.side_friction_stiffness
+    } else {
+        0.0
+    };
+
+    let forward_inv_mass = inv_mass(forward);
+    let mut forward_impulse = if wheel.engine_force != 0.0 {
+        wheel.engine_force * dt
+    } else if forward_inv_mass > 0.0 {
+        let max_impulse = wheel.brake.abs();
+        (-rel_vel.dot(forward) / forward_inv_mass / num_wheels_on_ground.max(1) as Real)
+            .clamp(-max_impulse, max_impulse)
+    } else {
+        0.0
+    };
+
+    let max_impulse = wheel.wheel_suspension_force * dt * wheel.friction_slip;
+    let impulse = (forward_impulse * 0.5).hypot(side_impulse);
+    if impulse > max_impulse {
+        let factor = max_impulse / impulse;
+        forward_impulse *= factor;
+        side_impulse *= factor;
+    }
+
+    (forward_impulse, side_impulse)
+}
+
+/// The world-space axle of a wheel, taking its steering into account.
+fn wheel_axle_ws(chassis_rot: &Rotation<Real>, wheel: &Wheel) -> Vector<Real> {
+    let steering = UnitQuaternion::new(-wheel.direction_cs * wheel.steering);
+    chassis_rot * (steering * wheel.axle_cs)
+}
+
 #[wasm_bindgen]
 pub struct RawDynamicRayCastVehicleController {
     controller: DynamicRayCastVehicleController,
+    wheel_slips: Vec<WheelSlip>,
+    wheel_shape: WheelShape,
+    /// The width of the wheels, if they are swept as cylinders.
+    wheel_width: Real,
+    /// The swept contacts replacing the ray-cast contacts of the wheels during the last update.
+    wheel_contacts: Vec<Option<WheelContact>>,
+}
+
+impl RawDynamicRayCastVehicleController {
+    /// Sweeps the shape of each wheel along its suspension, from the current chassis pose.
+    ///
+    /// This must be called before the vehicle update, so the suspension forces are computed from
+    /// the same chassis velocity as the ray-cast ones.
+    fn cast_wheel_shapes(
+        &self,
+        bodies: &RigidBodySet,
+        query_pipeline: &QueryPipeline,
+    ) -> Vec<Option<WheelContact>> {
+        let chassis = match bodies.get(self.controller.chassis) {
+            Some(chassis) => chassis,
+            None => return vec![],
+        };
+        let chassis_pos = chassis.position();
+
+        self.controller
+            .wheels()
+            .iter()
+            .map(|wheel| {
+                let hard_point = chassis_pos * wheel.chassis_connection_point_cs;
+                let direction = chassis_pos * wheel.direction_cs;
+                let ball;
+                let cylinder;
+                let (shape, rotation): (&dyn Shape, _) = match self.wheel_shape {
+                    WheelShape::Cylinder => {
+                        cylinder = Cylinder::new(self.wheel_width / 2.0, wheel.radius);
+                        let axle = wheel_axle_ws(&chassis_pos.rotation, wheel);
+                        let rotation = UnitQuaternion::rotation_between(&Vector::y(), &axle)
+                            .unwrap_or_else(UnitQuaternion::identity);
+                        (&cylinder, rotation)
+                    }
+                    _ => {
+                        ball = Ball::new(wheel.radius);
+                        (&ball, UnitQuaternion::identity())
+                    }
+                };
+                let shape_pos = Isometry::from_parts(hard_point.coords.into(), rotation);
+
+                let (handle, hit) = query_pipeline.cast_shape(
+                    &shape_pos,
+                    &direction,
+                    shape,
+                    ShapeCastOptions {
+                        max_time_of_impact: wheel.suspension_rest_length,
+                        target_distance: 0.0,
+                        stop_at_penetration: true,
+                        compute_impact_geometry_on_penetration: true,
+                    },
+                )?;
+
+                let min_length = wheel.suspension_rest_length - wheel.max_suspension_travel;
+                let max_length = wheel.suspension_rest_length + wheel.max_suspension_travel;
+                let suspension_length = hit.time_of_impact.clamp(min_length, max_length);
+                let point = hit.witness1;
+                let normal = if hit.normal1.norm_squared() == 0.0 {
+                    -direction
+                } else {
+                    hit.normal1.into_inner()
+                };
+
+                // Same spring-damper as the vehicle controller.
+                let denominator = normal.dot(&direction);
+                let (relative_velocity, inv_contact_dot_suspension) = if denominator >= -0.1 {
+                    (0.0, 1.0 / 0.1)
+                } else {
+                    let inv = -1.0 / denominator;
+                    (normal.dot(&chassis.velocity_at_point(&point)) * inv, inv)
+                };
+                let spring = wheel.suspension_stiffness
+                    * (wheel.suspension_rest_length - suspension_length)
+                    * inv_contact_dot_suspension;
+                let damping = if relative_velocity < 0.0 {
+                    wheel.damping_compression
+                } else {
+                    wheel.damping_relaxation
+                };
+
+                Some(WheelContact {
+                    suspension_length,
+                    contact_point_ws: point,
+                    contact_normal_ws: normal,
+                    ground_object: handle,
+                    suspension_force: ((spring - damping * relative_velocity) * chassis.mass())
+                        .max(0.0),
+                })
+            })
+            .collect()
+    }
+
+    /// Replaces the ray-cast contact of the wheels by their swept contact if it is closer to the
+    /// chassis, e.g., when the ray falls into a gap the wheel can’t fit in, and corrects the
+    /// impulses applied to the chassis accordingly.
+    ///
+    /// The suspension and friction impulses computed from the ray-cast contact of these wheels
+    /// are undone, and computed again from their swept contact point and normal.
+    fn apply_wheel_contacts(
+        &mut self,
+        dt: Real,
+        contacts: Vec<Option<WheelContact>>,
+        bodies: &mut RigidBodySet,
+        colliders: &ColliderSet,
+    ) {
+        self.wheel_contacts.clear();
+
+        let chassis_handle = self.controller.chassis;
+        let up_axis = self.controller.index_up_axis;
+        if bodies.get(chassis_handle).is_none() {
+            return;
+        }
+
+        let contacts: Vec<_> = self
+            .controller
+            .wheels()
+            .iter()
+            .zip(contacts)
+            .map(|(wheel, contact)| {
+                let info = wheel.raycast_info();
+                contact
+                    .filter(|c| !info.is_in_contact || c.suspension_length < info.suspension_length)
+            })
+            .collect();
+        let num_wheels_on_ground = self
+            .controller
+            .wheels()
+            .iter()
+            .zip(&contacts)
+            .filter(|(wheel, contact)| contact.is_some() || wheel.raycast_info().is_in_contact)
+            .count();
+
+        for (wheel, contact) in self.controller.wheels_mut().iter_mut().zip(&contacts) {
+            let contact = match contact {
+                Some(contact) => contact,
+                None => continue,
+            };
+            let info = *wheel.raycast_info();
+            let chassis = &bodies[chassis_handle];
+            let mut impulses = Vec::with_capacity(6);
+
+            // Undo the impulses computed from the ray-cast contact.
+            let ray_force = wheel.wheel_suspension_force.min(wheel.max_suspension_force);
+            impulses.push((
+                -info.contact_normal_ws * ray_force * dt,
+                info.contact_point_ws,
+            ));
+            if info.is_in_contact {
+                let (forward, axle) = friction_directions(wheel, &info.contact_normal_ws);
+                impulses.push((-forward * wheel.forward_impulse, info.contact_point_ws));
+                impulses.push((
+                    -axle * wheel.side_impulse,
+                    side_impulse_point(chassis, up_axis, &info.contact_point_ws),
+                ));
+            }
+
+            // And apply the ones of the swept contact.
+            wheel.wheel_suspension_force = contact.suspension_force;
+            let force = contact.suspension_force.min(wheel.max_suspension_force);
+            impulses.push((
+                contact.contact_normal_ws * force * dt,
+                contact.contact_point_ws,
+            ));
+
+            let ground = colliders
+                .get(contact.ground_object)
+                .and_then(|co| co.parent())
+                .and_then(|h| bodies.get(h));
+            let (forward, axle) = friction_directions(wheel, &contact.contact_normal_ws);
+            let (forward_impulse, side_impulse) = friction_impulses(
+                wheel,
+                chassis,
+                ground,
+                contact,
+                (&forward, &axle),
+                num_wheels_on_ground,
+                dt,
+            );
+            wheel.forward_impulse = forward_impulse;
+            wheel.side_impulse = side_impulse;
+            impulses.push((forward * forward_impulse, contact.contact_point_ws));
+            impulses.push((
+                axle * side_impulse,
+                side_impulse_point(chassis, up_axis, &contact.contact_point_ws),
+            ));
+
+            if let Some(chassis) = bodies.get_mut(chassis_handle) {
+                for (impulse, point) in impulses {
+                    chassis.apply_impulse_at_point(impulse, point, false);
+                }
+            }
+        }
+
+        self.wheel_contacts = contacts;
+    }
+
+    fn wheel_contact(&self, i: usize) -> Option<WheelContact> {
+        self.wheel_contacts.get(i).copied().flatten()
+    }
+
+    fn update_wheel_slips(
+        &mut self,
+        dt: Real,
+        previous_rotations: &[Real],
+        bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+    ) {
+        self.wheel_slips.clear();
+
+        let chassis = match bodies.get(self.controller.chassis) {
+            Some(chassis) => chassis,
+            None => return,
+        };
+        let chassis_rot = chassis.position().rotation;
+        let chassis_forward = chassis_rot * Vector::ith(self.controller.index_forward_axis, 1.0);
+
+        for (i, (wheel, previous_rotation)) in self
+            .controller
+            .wheels()
+            .iter()
+            .zip(previous_rotations)
+            .enumerate()
+        {
+            let info = wheel.raycast_info();
+            let (point, normal, ground_object) = match self.wheel_contacts.get(i) {
+                Some(Some(c)) => (
+                    c.contact_point_ws,
+                    c.contact_normal_ws,
+                    Some(c.ground_object),
+                ),
+                _ => (
+                    info.contact_point_ws,
+                    info.contact_normal_ws,
+                    info.ground_object,
+                ),
+            };
+            if ground_object.is_none() || dt == 0.0 {
+                self.wheel_slips.push(WheelSlip::default());
+                continue;
+            }
+
+            let ground_vel = ground_object
+                .and_then(|h| colliders.get(h))
+                .and_then(|co| co.parent())
+                .and_then(|h| bodies.get(h))
+                .map(|rb| rb.velocity_at_point(&point))
+                .unwrap_or_else(Vector::zeros);
+            let rel_vel = chassis.velocity_at_point(&point) - ground_vel;
+
+            let axle = wheel_axle_ws(&chassis_rot, wheel);
+            let side = match (axle - normal * axle.dot(&normal)).try_normalize(1.0e-6) {
+                Some(side) => side,
+                None => {
+                    self.wheel_slips.push(WheelSlip::default());
+                    continue;
+                }
+            };
+            let mut forward = normal.cross(&side);
+            if forward.dot(&chassis_forward) < 0.0 {
+                forward = -forward;
+            }
+
+            let forward_speed = rel_vel.dot(&forward);
+            let side_speed = rel_vel.dot(&side);
+            let rolling_speed = (wheel.rotation - previous_rotation) * wheel.radius / dt;
+
+            self.wheel_slips.push(WheelSlip {
+                ratio: (rolling_speed - forward_speed)
+                    / forward_speed.abs().max(MIN_SLIP_REFERENCE_SPEED),
+                angle: side_speed.atan2(forward_speed.abs()),
+            });
+        }
+    }
 }
 
 #[wasm_bindgen]
@@ -17,9 +446,30 @@ impl RawDynamicRayCastVehicleController {
     pub fn new(chassis: FlatHandle) -> Self {
         Self {
             controller: DynamicRayCastVehicleController::new(utils::body_handle(chassis)),
+            wheel_slips: vec![],
+            wheel_shape: WheelShape::Ray,
+            wheel_width: 0.0,
+            wheel_contacts: vec![],
         }
     }
 
+    /// The shape swept along the suspension of the wheels to detect the ground: `0` for a ray,
+    /// `1` for a ball, and `2` for a cylinder.
+    pub fn wheel_shape(&self) -> u32 {
+        self.wheel_shape as u32
+    }
+
+    /// Sets the shape swept along the suspension of the wheels to detect the ground.
+    ///
+    /// With a ball or a cylinder, the wheels are still ray-cast by the vehicle controller, but
+    /// their shape is also swept along their suspension: if it touches the ground before the
+    /// ray, its contact replaces the ray-cast one. The `width` of the wheels is only used for
+    /// cylinders.
+    pub fn set_wheel_shape(&mut self, shape: u32, width: f64) {
+        self.wheel_shape = WheelShape::from_u32(shape);
+        self.wheel_width = width;
+    }
+
     pub fn current_vehicle_speed(&self) -> f64 {
         self.controller.current_vehicle_speed
     }
@@ -75,6 +525,13 @@ impl RawDynamicRayCastVehicleController {
         filter_groups: Option<u32>,
         filter_predicate: &js_sys::Function,
     ) {
+        let previous_rotations: Vec<_> = self
+            .controller
+            .wheels()
+            .iter()
+            .map(|w| w.rotation)
+            .collect();
+
         crate::utils::with_filter(filter_predicate, |predicate| {
             let query_filter = QueryFilter {
                 flags: QueryFilterFlags::from_bits(filter_flags)
@@ -85,6 +542,18 @@ impl RawDynamicRayCastVehicleController {
                 exclude_collider: None,
             };
 
+            let contacts = if self.wheel_shape == WheelShape::Ray {
+                vec![]
+            } else {
+                let query_pipeline = broad_phase.0.as_query_pipeline(
+                    narrow_phase.0.query_dispatcher(),
+                    &bodies.0,
+                    &colliders.0,
+                    query_filter,
+                );
+                self.cast_wheel_shapes(&bodies.0, &query_pipeline)
+            };
+
             let query_pipeline = broad_phase.0.as_query_pipeline_mut(
                 narrow_phase.0.query_dispatcher(),
                 &mut bodies.0,
@@ -93,7 +562,10 @@ impl RawDynamicRayCastVehicleController {
             );
 
             self.controller.update_vehicle(dt, query_pipeline);
+            self.apply_wheel_contacts(dt, contacts, &mut bodies.0, &colliders.0);
         });
+
+        self.update_wheel_slips(dt, &previous_rotations, &bodies.0, &colliders.0);
     }
 
     /*
@@ -290,6 +762,10 @@ impl RawDynamicRayCastVehicleController {
     }
 
     pub fn wheel_contact_normal_ws(&self, i: usize) -> Option<RawVector> {
+        if let Some(contact) = self.wheel_contact(i) {
+            return Some(contact.contact_normal_ws.into());
+        }
+
         self.controller
             .wheels()
             .get(i)
@@ -297,6 +773,10 @@ impl RawDynamicRayCastVehicleController {
     }
 
     pub fn wheel_contact_point_ws(&self, i: usize) -> Option<RawVector> {
+        if let Some(contact) = self.wheel_contact(i) {
+            return Some(contact.contact_point_ws.into());
+        }
+
         self.controller
             .wheels()
             .get(i)
@@ -304,6 +784,10 @@ impl RawDynamicRayCastVehicleController {
     }
 
     pub fn wheel_suspension_length(&self, i: usize) -> Option<f64> {
+        if let Some(contact) = self.wheel_contact(i) {
+            return Some(contact.suspension_length);
+        }
+
         self.controller
             .wheels()
             .get(i)
@@ -318,14 +802,38 @@ impl RawDynamicRayCastVehicleController {
     }
 
     pub fn wheel_is_in_contact(&self, i: usize) -> bool {
+        self.wheel_contact(i).is_some()
+            || self
+                .controller
+                .wheels()
+                .get(i)
+                .map(|w| w.raycast_info().is_in_contact)
+                .unwrap_or(false)
+    }
+
+    /// How much the suspension of the i-th wheel is compressed, i.e., its rest length minus
+    /// its current length.
+    pub fn wheel_compression(&self, i: usize) -> Option<f64> {
+        let suspension_length = self.wheel_suspension_length(i)?;
         self.controller
             .wheels()
             .get(i)
-            .map(|w| w.raycast_info().is_in_contact)
-            .unwrap_or(false)
+            .map(|w| w.suspension_rest_length - suspension_length)
+    }
+
+    pub fn wheel_slip_ratio(&self, i: usize) -> Option<f64> {
+        self.wheel_slips.get(i).map(|slip| slip.ratio)
+    }
+
+    pub fn wheel_slip_angle(&self, i: usize) -> Option<f64> {
+        self.wheel_slips.get(i).map(|slip| slip.angle)
     }
 
     pub fn wheel_ground_object(&self, i: usize) -> Option<FlatHandle> {
+        if let Some(contact) = self.wheel_contact(i) {
+            return Some(utils::flat_handle(contact.ground_object.0));
+        }
+
         self.controller
             .wheels()
             .get(i)
@@ -0,0 +1,390 @@
+use crate::dynamics::{ang_dot, cross, inv_mass};
+use rapier::dynamics::{RigidBody, RigidBodyHandle, RigidBodySet};
+use rapier::math::{AngVector, Isometry, Point, Real, Rotation, Vector};
+use std::collections::HashMap;
+
+/// A spring-damper keeping a local axis of a rigid-body aligned with a world-space axis.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct UprightConstraint {
+    /// The unit axis, in the local frame of the rigid-body, to keep aligned.
+    pub local_axis: Vector<Real>,
+    /// The unit world-space axis the local axis is pulled toward.
+    pub target_axis: Vector<Real>,
+    /// The angular acceleration per radian of tilt.
+    pub stiffness: Real,
+    /// The angular acceleration per unit of tilt angular velocity.
+    pub damping: Real,
+}
+
+/// A PD controller driving a rigid-body toward a target pose.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct PoseTracking {
+    /// The pose the rigid-body is driven toward.
+    pub target: Isometry<Real>,
+    /// The linear acceleration per unit of position error.
+    pub lin_stiffness: Real,
+    /// The linear acceleration per unit of linear velocity.
+    pub lin_damping: Real,
+    /// The angular acceleration per radian of rotation error.
+    pub ang_stiffness: Real,
+    /// The angular acceleration per unit of angular velocity.
+    pub ang_damping: Real,
+    /// The maximum norm of the force applied to the rigid-body.
+    pub max_force: Real,
+    /// The maximum norm of the torque applied to the rigid-body.
+    pub max_torque: Real,
+}
+
+/// A spring-damper pulling a point of a rigid-body toward a world-space target point, e.g.,
+/// to grab a rigid-body with the mouse.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct DragConstraint {
+    /// The grabbed point, in the local frame of the rigid-body.
+    pub local_point: Point<Real>,
+    /// The world-space point the grabbed point is pulled toward.
+    pub target: Point<Real>,
+    /// The acceleration of the grabbed point per unit of distance to the target.
+    pub stiffness: Real,
+    /// The acceleration of the grabbed point per unit of velocity.
+    pub damping: Real,
+    /// The maximum norm of the force applied to the rigid-body.
+    pub max_force: Real,
+}
+
+/// The velocity change of an implicitly integrated spring-damper with the given error,
+/// velocity, stiffness and damping, over a timestep `dt`.
+fn spring_damper_dvel<T>(error: T, vel: T, stiffness: Real, damping: Real, dt: Real) -> T
+where
+    T: std::ops::Mul<Real, Output = T> + std::ops::Sub<Output = T>,
+{
+    (error * stiffness - vel * damping) * (dt / (1.0 + dt * damping + dt * dt * stiffness))
+}
+
+/// The rotation, as an axis scaled by an angle, bringing `rotation` onto `target`.
+#[cfg(feature = "dim2")]
+fn rotation_error(rotation: &Rotation<Real>, target: &Rotation<Real>) -> AngVector<Real> {
+    (target * rotation.inverse()).angle()
+}
+
+/// The rotation, as an axis scaled by an angle, bringing `rotation` onto `target`.
+#[cfg(feature = "dim3")]
+fn rotation_error(rotation: &Rotation<Real>, target: &Rotation<Real>) -> AngVector<Real> {
+    (target * rotation.inverse()).scaled_axis()
+}
+
+/// The rotation, as an axis scaled by an angle, bringing `axis` onto `target`.
+#[cfg(feature = "dim2")]
+fn tilt(axis: &Vector<Real>, target: &Vector<Real>) -> AngVector<Real> {
+    axis.perp(target).atan2(axis.dot(target))
+}
+
+/// The rotation, as an axis scaled by an angle, bringing `axis` onto `target`.
+#[cfg(feature = "dim3")]
+fn tilt(axis: &Vector<Real>, target: &Vector<Real>) -> AngVector<Real> {
+    let cross = axis.cross(target);
+    let sin = cross.norm();
+    let cos = axis.dot(target);
+
+    if sin > Real::EPSILON {
+        cross * (sin.atan2(cos) / sin)
+    } else if cos < 0.0 {
+        // The axes are opposite: any rotation axis orthogonal to them works.
+        let other = if axis.x.abs() < 0.9 {
+            Vector::x()
+        } else {
+            Vector::y()
+        };
+        axis.cross(&other).normalize() * std::f64::consts::PI
+    } else {
+        AngVector::zeros()
+    }
+}
+
+/// The part of an angular velocity tilting a body away from the given axis, i.e., without
+/// the spin around it.
+#[cfg(feature = "dim2")]
+fn tilt_angvel(angvel: AngVector<Real>, _axis: &Vector<Real>) -> AngVector<Real> {
+    angvel
+}
+
+/// The part of an angular velocity tilting a body away from the given axis, i.e., without
+/// the spin around it.
+#[cfg(feature = "dim3")]
+fn tilt_angvel(angvel: AngVector<Real>, axis: &Vector<Real>) -> AngVector<Real> {
+    angvel - axis * axis.dot(&angvel)
+}
+
+/// The forces applied to the rigid-bodies by the bindings, in addition to the ones handled
+/// by the physics pipeline.
+///
+/// They are applied to the velocities of the rigid-bodies before each substep.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct BodyForces {
+    /// The linear damping coefficients along each local axis of the rigid-bodies.
+    local_linear_dampings: HashMap<RigidBodyHandle, Vector<Real>>,
+    /// The fraction of the gravity cancelled for each rigid-body.
+    gravity_compensations: HashMap<RigidBodyHandle, Real>,
+    upright_constraints: HashMap<RigidBodyHandle, UprightConstraint>,
+    pose_trackings: HashMap<RigidBodyHandle, PoseTracking>,
+    drag_constraints: HashMap<RigidBodyHandle, DragConstraint>,
+}
+
+impl BodyForces {
+    pub fn local_linear_damping(&self, handle: RigidBodyHandle) -> Vector<Real> {
+        self.local_linear_dampings
+            .get(&handle)
+            .copied()
+            .unwrap_or_else(Vector::zeros)
+    }
+
+    pub fn set_local_linear_damping(&mut self, handle: RigidBodyHandle, damping: Vector<Real>) {
+        let damping = damping.map(|d| d.max(0.0));
+
+        if damping.iter().all(|d| *d == 0.0) {
+            let _ = self.local_linear_dampings.remove(&handle);
+        } else {
+            let _ = self.local_linear_dampings.insert(handle, damping);
+        }
+    }
+
+    pub fn gravity_compensation(&self, handle: RigidBodyHandle) -> Real {
+        self.gravity_compensations
+            .get(&handle)
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    pub fn set_gravity_compensation(&mut self, handle: RigidBodyHandle, fraction: Real) {
+        if fraction == 0.0 {
+            let _ = self.gravity_compensations.remove(&handle);
+        } else {
+            let _ = self.gravity_compensations.insert(handle, fraction);
+        }
+    }
+
+    pub fn upright_constraint(&self, handle: RigidBodyHandle) -> Option<&UprightConstraint> {
+        self.upright_constraints.get(&handle)
+    }
+
+    pub fn set_upright_constraint(
+        &mut self,
+        handle: RigidBodyHandle,
+        constraint: Option<UprightConstraint>,
+    ) {
+        match constraint {
+            Some(constraint) => {
+                let _ = self.upright_constraints.insert(handle, constraint);
+            }
+            None => {
+                let _ = self.upright_constraints.remove(&handle);
+            }
+        }
+    }
+
+    pub fn pose_tracking(&self, handle: RigidBodyHandle) -> Option<&PoseTracking> {
+        self.pose_trackings.get(&handle)
+    }
+
+    pub fn pose_tracking_mut(&mut self, handle: RigidBodyHandle) -> Option<&mut PoseTracking> {
+        self.pose_trackings.get_mut(&handle)
+    }
+
+    pub fn set_pose_tracking(&mut self, handle: RigidBodyHandle, tracking: Option<PoseTracking>) {
+        match tracking {
+            Some(tracking) => {
+                let _ = self.pose_trackings.insert(handle, tracking);
+            }
+            None => {
+                let _ = self.pose_trackings.remove(&handle);
+            }
+        }
+    }
+
+    pub fn drag_constraint(&self, handle: RigidBodyHandle) -> Option<&DragConstraint> {
+        self.drag_constraints.get(&handle)
+    }
+
+    pub fn drag_constraint_mut(&mut self, handle: RigidBodyHandle) -> Option<&mut DragConstraint> {
+        self.drag_constraints.get_mut(&handle)
+    }
+
+    pub fn set_drag_constraint(
+        &mut self,
+        handle: RigidBodyHandle,
+        constraint: Option<DragConstraint>,
+    ) {
+        match constraint {
+            Some(constraint) => {
+                let _ = self.drag_constraints.insert(handle, constraint);
+            }
+            None => {
+                let _ = self.drag_constraints.remove(&handle);
+            }
+        }
+    }
+
+    pub fn remove(&mut self, handle: RigidBodyHandle) {
+        let _ = self.local_linear_dampings.remove(&handle);
+        let _ = self.gravity_compensations.remove(&handle);
+        let _ = self.upright_constraints.remove(&handle);
+        let _ = self.pose_trackings.remove(&handle);
+        let _ = self.drag_constraints.remove(&handle);
+    }
+
+    /// Applies the forces to all the rigid-bodies for a substep of length `dt`.
+    pub fn apply(&mut self, bodies: &mut RigidBodySet, gravity: &Vector<Real>, dt: Real) {
+        // Forget the rigid-bodies removed without going through the bindings.
+        self.local_linear_dampings
+            .retain(|handle, _| bodies.get(*handle).is_some());
+        self.gravity_compensations
+            .retain(|handle, _| bodies.get(*handle).is_some());
+        self.upright_constraints
+            .retain(|handle, _| bodies.get(*handle).is_some());
+        self.pose_trackings
+            .retain(|handle, _| bodies.get(*handle).is_some());
+        self.drag_constraints
+            .retain(|handle, _| bodies.get(*handle).is_some());
+
+        for (handle, fraction) in &self.gravity_compensations {
+            let rb = match bodies.get_mut(*handle) {
+                Some(rb) if rb.is_dynamic() && !rb.is_sleeping() => rb,
+                _ => continue,
+            };
+
+            // The impulse cancelling the same fraction of the gravity impulse the pipeline
+            // applies to the rigid-body during the substep.
+            let impulse = gravity * (-fraction * rb.gravity_scale() * rb.mass() * dt);
+            rb.apply_impulse(impulse, false);
+        }
+
+        for (handle, damping) in &self.local_linear_dampings {
+            let rb = match bodies.get_mut(*handle) {
+                Some(rb) if rb.is_dynamic() && !rb.is_sleeping() => rb,
+                _ => continue,
+            };
+
+            // Same implicit integration as the isotropic damping of the pipeline, along each
+            // local axis.
+            let rotation = rb.position().rotation;
+            let local_linvel = rotation.inverse_transform_vector(rb.linvel());
+            let damped = local_linvel.zip_map(damping, |v, d| v / (1.0 + dt * d));
+            rb.set_linvel(rotation * damped, false);
+        }
+
+        for (handle, constraint) in &self.upright_constraints {
+            let rb = match bodies.get_mut(*handle) {
+                Some(rb) if rb.is_dynamic() && !rb.is_sleeping() => rb,
+                _ => continue,
+            };
+
+            Self::apply_upright_constraint(rb, constraint, dt);
+        }
+
+        for (handle, tracking) in &self.pose_trackings {
+            let rb = match bodies.get_mut(*handle) {
+                Some(rb) if rb.is_dynamic() => rb,
+                _ => continue,
+            };
+
+            Self::apply_pose_tracking(rb, tracking, dt);
+        }
+
+        for (handle, constraint) in &self.drag_constraints {
+            let rb = match bodies.get_mut(*handle) {
+                Some(rb) if rb.is_dynamic() => rb,
+                _ => continue,
+            };
+
+            Self::apply_drag_constraint(rb, constraint, dt);
+        }
+    }
+
+    /// Applies the angular impulse of an upright constraint, integrating its spring-damper
+    /// implicitly so that stiff constraints remain stable.
+    fn apply_upright_constraint(rb: &mut RigidBody, constraint: &UprightConstraint, dt: Real) {
+        let axis = rb.position().rotation * constraint.local_axis;
+        let error = tilt(&axis, &constraint.target_axis);
+        let angvel = tilt_angvel(rb.vels().angvel, &constraint.target_axis);
+
+        let dvel = spring_damper_dvel(error, angvel, constraint.stiffness, constraint.damping, dt);
+        let dvel_norm = ang_dot(&dvel, &dvel).sqrt();
+        if dvel_norm <= Real::EPSILON {
+            return;
+        }
+
+        let dir = dvel / dvel_norm;
+        let inv_inertia = inv_mass(rb, &Vector::zeros(), &dir);
+        if inv_inertia > 0.0 {
+            rb.apply_torque_impulse(dir * (dvel_norm / inv_inertia), false);
+        }
+    }
+
+    /// Applies the impulses of a PD controller driving a rigid-body toward its target pose,
+    /// clamped by the maximum force and torque of the controller.
+    fn apply_pose_tracking(rb: &mut RigidBody, tracking: &PoseTracking, dt: Real) {
+        let target_com = tracking.target * rb.local_center_of_mass();
+        let lin_error = target_com - rb.center_of_mass();
+        let dlinvel = spring_damper_dvel(
+            lin_error,
+            *rb.linvel(),
+            tracking.lin_stiffness,
+            tracking.lin_damping,
+            dt,
+        );
+        let dlinvel_norm = dlinvel.norm();
+
+        if dlinvel_norm > Real::EPSILON {
+            let dir = dlinvel / dlinvel_norm;
+            let inv_lin_mass = inv_mass(rb, &dir, &AngVector::default());
+            if inv_lin_mass > 0.0 {
+                let impulse = (dlinvel_norm / inv_lin_mass).min(tracking.max_force * dt);
+                rb.apply_impulse(dir * impulse, true);
+            }
+        }
+
+        let ang_error = rotation_error(&rb.position().rotation, &tracking.target.rotation);
+        let dangvel = spring_damper_dvel(
+            ang_error,
+            rb.vels().angvel,
+            tracking.ang_stiffness,
+            tracking.ang_damping,
+            dt,
+        );
+        let dangvel_norm = ang_dot(&dangvel, &dangvel).sqrt();
+
+        if dangvel_norm > Real::EPSILON {
+            let dir = dangvel / dangvel_norm;
+            let inv_inertia = inv_mass(rb, &Vector::zeros(), &dir);
+            if inv_inertia > 0.0 {
+                let impulse = (dangvel_norm / inv_inertia).min(tracking.max_torque * dt);
+                rb.apply_torque_impulse(dir * impulse, true);
+            }
+        }
+    }
+
+    /// Applies the impulse pulling the grabbed point of a drag constraint toward its target,
+    /// clamped by the maximum force of the constraint.
+    fn apply_drag_constraint(rb: &mut RigidBody, constraint: &DragConstraint, dt: Real) {
+        let point = rb.position() * constraint.local_point;
+        let dvel = spring_damper_dvel(
+            constraint.target - point,
+            rb.velocity_at_point(&point),
+            constraint.stiffness,
+            constraint.damping,
+            dt,
+        );
+        let dvel_norm = dvel.norm();
+        if dvel_norm <= Real::EPSILON {
+            return;
+        }
+
+        let dir = dvel / dvel_norm;
+        let r = point - rb.center_of_mass();
+        let k = inv_mass(rb, &dir, &cross(&r, &dir));
+        if k > 0.0 {
+            let impulse = (dvel_norm / k).min(constraint.max_force * dt);
+            rb.apply_impulse_at_point(dir * impulse, point, true);
+        }
+    }
+}
@@ -1,9 +1,65 @@
-use crate::dynamics::{RawImpulseJointSet, RawJointAxis, RawJointType, RawMotorModel};
+use crate::dynamics::{
+    joint_axes_bits, joint_axes_mask, RawImpulseJointSet, RawJointAxis, RawJointType,
+    RawMotorModel, RawRigidBodySet,
+};
 use crate::math::{RawRotation, RawVector};
 use crate::utils::{self, FlatHandle};
-use rapier::dynamics::JointAxis;
+use rapier::dynamics::{ImpulseJoint, JointAxis, RigidBody};
+use rapier::math::{Isometry, Real, Rotation, Vector, DIM};
+#[cfg(feature = "dim3")]
+use std::f64::consts::PI;
 use wasm_bindgen::prelude::*;
 
+/// The rotation angle of `rot` around the given axis.
+#[cfg(feature = "dim2")]
+fn twist_angle(rot: &Rotation<Real>, _axis: usize) -> Real {
+    rot.angle()
+}
+
+/// The rotation angle of `rot` around the given axis, i.e., the angle of the twist part of its
+/// swing-twist decomposition.
+#[cfg(feature = "dim3")]
+fn twist_angle(rot: &Rotation<Real>, axis: usize) -> Real {
+    let angle = 2.0 * rot.vector()[axis].atan2(rot.scalar());
+    if angle > PI {
+        angle - 2.0 * PI
+    } else if angle < -PI {
+        angle + 2.0 * PI
+    } else {
+        angle
+    }
+}
+
+/// The relative angular velocity of the rigid-bodies around the given axis of `frame1`.
+#[cfg(feature = "dim2")]
+fn relative_angvel(
+    rb1: &RigidBody,
+    rb2: &RigidBody,
+    _frame1: &Isometry<Real>,
+    _axis: usize,
+) -> Real {
+    rb2.angvel() - rb1.angvel()
+}
+
+/// The relative angular velocity of the rigid-bodies around the given axis of `frame1`.
+#[cfg(feature = "dim3")]
+fn relative_angvel(rb1: &RigidBody, rb2: &RigidBody, frame1: &Isometry<Real>, axis: usize) -> Real {
+    let dir = frame1.rotation * Vector::ith(axis, 1.0);
+    dir.dot(&(rb2.angvel() - rb1.angvel()))
+}
+
+/// The world-space frames of a joint and its rigid-bodies.
+fn joint_frames<'a>(
+    joint: &ImpulseJoint,
+    bodies: &'a RawRigidBodySet,
+) -> Option<(&'a RigidBody, &'a RigidBody, Isometry<Real>, Isometry<Real>)> {
+    let rb1 = bodies.0.get(joint.body1)?;
+    let rb2 = bodies.0.get(joint.body2)?;
+    let frame1 = rb1.position() * joint.data.local_frame1;
+    let frame2 = rb2.position() * joint.data.local_frame2;
+    Some((rb1, rb2, frame1, frame2))
+}
+
 #[wasm_bindgen]
 impl RawImpulseJointSet {
     /// The type of this joint.
@@ -61,6 +117,35 @@ impl RawImpulseJointSet {
         })
     }
 
+    /// Sets the angular part of the joint’s local frame relative to the first rigid-body it is
+    /// attached to.
+    pub fn jointSetLocalFrame1(&mut self, handle: FlatHandle, newRot: &RawRotation) {
+        self.map_mut(handle, |j| {
+            j.data.local_frame1.rotation = newRot.0;
+        })
+    }
+
+    /// Sets the angular part of the joint’s local frame relative to the second rigid-body it is
+    /// attached to.
+    pub fn jointSetLocalFrame2(&mut self, handle: FlatHandle, newRot: &RawRotation) {
+        self.map_mut(handle, |j| {
+            j.data.local_frame2.rotation = newRot.0;
+        })
+    }
+
+    /// Is this joint enabled?
+    pub fn jointIsEnabled(&self, handle: FlatHandle) -> bool {
+        self.map(handle, |j| j.data.is_enabled())
+    }
+
+    /// Enables or disables this joint.
+    ///
+    /// A disabled joint doesn’t constrain the rigid-bodies it is attached to, but keeps its
+    /// configuration, so it can be enabled again later.
+    pub fn jointSetEnabled(&mut self, handle: FlatHandle, enabled: bool) {
+        self.map_mut(handle, |j| j.data.set_enabled(enabled))
+    }
+
     /// Are contacts between the rigid-bodies attached by this joint enabled?
     pub fn jointContactsEnabled(&self, handle: FlatHandle) -> bool {
         self.map(handle, |j| j.data.contacts_enabled)
@@ -73,6 +158,23 @@ impl RawImpulseJointSet {
         });
     }
 
+    /// The axes coupled by this joint, with the bit layout of the `JointAxesMask` enum.
+    pub fn jointCoupledAxes(&self, handle: FlatHandle) -> u8 {
+        self.map(handle, |j| joint_axes_bits(j.data.coupled_axes))
+    }
+
+    /// Sets the axes coupled by this joint.
+    ///
+    /// The coupled axes are constrained together through their norm instead of independently.
+    ///
+    /// # Parameters
+    /// - `coupledAxes`: a mask with the bit layout of the `JointAxesMask` enum.
+    pub fn jointSetCoupledAxes(&mut self, handle: FlatHandle, coupledAxes: u8) {
+        self.map_mut(handle, |j| {
+            j.data.coupled_axes = joint_axes_mask(coupledAxes)
+        })
+    }
+
     /// Are the limits for this joint enabled?
     pub fn jointLimitsEnabled(&self, handle: FlatHandle, axis: RawJointAxis) -> bool {
         self.map(handle, |j| {
@@ -212,4 +314,89 @@ impl RawImpulseJointSet {
                 .set_motor(axis.into(), targetPos, targetVel, stiffness, damping);
         })
     }
+
+    /// The target position of the motor along the given joint axis, if it has a motor.
+    pub fn jointMotorTargetPos(&self, handle: FlatHandle, axis: RawJointAxis) -> Option<f64> {
+        self.map(handle, |j| j.data.motor(axis.into()).map(|m| m.target_pos))
+    }
+
+    /// The stiffness of the motor along the given joint axis, if it has a motor.
+    pub fn jointMotorStiffness(&self, handle: FlatHandle, axis: RawJointAxis) -> Option<f64> {
+        self.map(handle, |j| j.data.motor(axis.into()).map(|m| m.stiffness))
+    }
+
+    /// The damping of the motor along the given joint axis, if it has a motor.
+    pub fn jointMotorDamping(&self, handle: FlatHandle, axis: RawJointAxis) -> Option<f64> {
+        self.map(handle, |j| j.data.motor(axis.into()).map(|m| m.damping))
+    }
+
+    /// The dry friction force (or torque) along the given joint axis.
+    pub fn jointFriction(&self, handle: FlatHandle, axis: RawJointAxis) -> f64 {
+        self.1
+            .get(utils::impulse_joint_handle(handle), axis as usize)
+    }
+
+    /// Sets the dry friction along the given free joint axis.
+    ///
+    /// The friction opposes the relative motion of the rigid-bodies along the axis with a force
+    /// (or torque for angular axes) up to `friction`, without fighting the motor of the axis.
+    /// Set it to 0 to disable the friction.
+    pub fn jointSetFriction(&mut self, handle: FlatHandle, axis: RawJointAxis, friction: f64) {
+        let handle = utils::impulse_joint_handle(handle);
+        if self.0.get(handle).is_some() {
+            self.1.set(handle, axis as usize, friction);
+        }
+    }
+
+    /// The current position of the second rigid-body relative to the first one along the given
+    /// joint axis, i.e., the displacement along a linear axis or the angle around an angular
+    /// axis, in the joint frame of the first rigid-body.
+    pub fn jointCurrentPosition(
+        &self,
+        bodies: &RawRigidBodySet,
+        handle: FlatHandle,
+        axis: RawJointAxis,
+    ) -> f64 {
+        self.map(handle, |j| {
+            let (_, _, frame1, frame2) = match joint_frames(j, bodies) {
+                Some(frames) => frames,
+                None => return 0.0,
+            };
+            let axis = axis as usize;
+
+            if axis < DIM {
+                let dir = frame1.rotation * Vector::ith(axis, 1.0);
+                dir.dot(&(frame2.translation.vector - frame1.translation.vector))
+            } else {
+                twist_angle(&(frame1.rotation.inverse() * frame2.rotation), axis - DIM)
+            }
+        })
+    }
+
+    /// The current velocity of the second rigid-body relative to the first one along the given
+    /// joint axis, i.e., the linear velocity of the anchors along a linear axis or the angular
+    /// velocity around an angular axis.
+    pub fn jointCurrentVelocity(
+        &self,
+        bodies: &RawRigidBodySet,
+        handle: FlatHandle,
+        axis: RawJointAxis,
+    ) -> f64 {
+        self.map(handle, |j| {
+            let (rb1, rb2, frame1, frame2) = match joint_frames(j, bodies) {
+                Some(frames) => frames,
+                None => return 0.0,
+            };
+            let axis = axis as usize;
+
+            if axis < DIM {
+                let dir = frame1.rotation * Vector::ith(axis, 1.0);
+                let vel1 = rb1.velocity_at_point(&frame1.translation.vector.into());
+                let vel2 = rb2.velocity_at_point(&frame2.translation.vector.into());
+                dir.dot(&(vel2 - vel1))
+            } else {
+                relative_angvel(rb1, rb2, &frame1, axis - DIM)
+            }
+        })
+    }
 }
@@ -1,10 +1,10 @@
-use crate::dynamics::RawGenericJoint;
+use crate::dynamics::{JointFrictions, RawGenericJoint};
 use crate::utils::{self, FlatHandle};
 use rapier::dynamics::{ImpulseJoint, ImpulseJointSet};
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
-pub struct RawImpulseJointSet(pub(crate) ImpulseJointSet);
+pub struct RawImpulseJointSet(pub(crate) ImpulseJointSet, pub(crate) JointFrictions);
 
 impl RawImpulseJointSet {
     pub(crate) fn map<T>(&self, handle: FlatHandle, f: impl FnOnce(&ImpulseJoint) -> T) -> T {
@@ -33,7 +33,7 @@ impl RawImpulseJointSet {
 impl RawImpulseJointSet {
     #[wasm_bindgen(constructor)]
     pub fn new() -> Self {
-        RawImpulseJointSet(ImpulseJointSet::new())
+        RawImpulseJointSet(ImpulseJointSet::new(), JointFrictions::default())
     }
 
     pub fn createJoint(
@@ -58,6 +58,7 @@ impl RawImpulseJointSet {
     pub fn remove(&mut self, handle: FlatHandle, wakeUp: bool) {
         let handle = utils::impulse_joint_handle(handle);
         self.0.remove(handle, wakeUp);
+        self.1.remove(handle);
     }
 
     pub fn len(&self) -> usize {
@@ -89,4 +90,12 @@ impl RawImpulseJointSet {
             let _ = f.call1(&this, &JsValue::from(utils::flat_handle(handle.0)));
         }
     }
+
+    /// The handles of the joints attached to both given rigid-bodies, in any order.
+    pub fn jointsBetween(&self, body1: FlatHandle, body2: FlatHandle) -> Vec<FlatHandle> {
+        self.0
+            .joints_between(utils::body_handle(body1), utils::body_handle(body2))
+            .map(|(handle, _)| utils::flat_handle(handle.0))
+            .collect()
+    }
 }
@@ -21,6 +21,21 @@ impl RawIntegrationParameters {
         self.0.contact_erp()
     }
 
+    #[wasm_bindgen(getter)]
+    pub fn jointErp(&self) -> f64 {
+        self.0.joint_erp()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn jointNaturalFrequency(&self) -> f64 {
+        self.0.joint_natural_frequency
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn jointDampingRatio(&self) -> f64 {
+        self.0.joint_damping_ratio
+    }
+
     #[wasm_bindgen(getter)]
     pub fn normalizedAllowedLinearError(&self) -> f64 {
         self.0.normalized_allowed_linear_error
@@ -66,6 +81,16 @@ impl RawIntegrationParameters {
         self.0.contact_natural_frequency = value
     }
 
+    #[wasm_bindgen(setter)]
+    pub fn set_jointNaturalFrequency(&mut self, value: f64) {
+        self.0.joint_natural_frequency = value
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_jointDampingRatio(&mut self, value: f64) {
+        self.0.joint_damping_ratio = value
+    }
+
     #[wasm_bindgen(setter)]
     pub fn set_normalizedAllowedLinearError(&mut self, value: f64) {
         self.0.normalized_allowed_linear_error = value
@@ -1,5 +1,9 @@
-use crate::utils;
-use rapier::dynamics::IslandManager;
+use crate::dynamics::{RawImpulseJointSet, RawMultibodyJointSet, RawRigidBodySet};
+use crate::geometry::{RawColliderSet, RawNarrowPhase};
+use crate::utils::{self, FlatHandle};
+use rapier::dynamics::{IslandManager, RigidBodyHandle, RigidBodySet};
+use rapier::geometry::{ColliderHandle, ColliderSet};
+use std::collections::HashMap;
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
@@ -28,4 +32,185 @@ impl RawIslandManager {
             let _ = f.call1(&this, &JsValue::from(utils::flat_handle(handle.0)));
         }
     }
+
+    /// Computes the simulation islands, i.e., the groups of dynamic rigid-bodies connected by
+    /// contacts or joints, for diagnostic purposes.
+    ///
+    /// Like for the solver, fixed and kinematic rigid-bodies don’t connect islands together.
+    pub fn computeIslands(
+        &self,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        narrow_phase: &RawNarrowPhase,
+        impulse_joints: &RawImpulseJointSet,
+        multibody_joints: &RawMultibodyJointSet,
+    ) -> RawIslands {
+        let bodies = &bodies.0;
+        let mut ids = HashMap::new();
+        let mut handles = vec![];
+        for (handle, rb) in bodies.iter() {
+            if rb.is_dynamic() {
+                let _ = ids.insert(handle, handles.len());
+                handles.push(handle);
+            }
+        }
+
+        let mut sets = DisjointSets::new(handles.len());
+        let mut contacts = vec![];
+        let mut joints = vec![];
+
+        for pair in narrow_phase.0.contact_pairs() {
+            if !pair.has_any_active_contact {
+                continue;
+            }
+
+            let num_contacts = pair
+                .manifolds
+                .iter()
+                .map(|m| m.data.solver_contacts.len())
+                .sum::<usize>();
+            let id1 = collider_body_id(&colliders.0, &ids, pair.collider1);
+            let id2 = collider_body_id(&colliders.0, &ids, pair.collider2);
+            sets.connect(id1, id2);
+            contacts.extend(id1.or(id2).map(|id| (id, num_contacts)));
+        }
+
+        for (_, joint) in impulse_joints.0.iter() {
+            let id1 = ids.get(&joint.body1).copied();
+            let id2 = ids.get(&joint.body2).copied();
+            sets.connect(id1, id2);
+            joints.extend(id1.or(id2));
+        }
+
+        for multibody in multibody_joints.0.multibodies() {
+            for link in multibody.links() {
+                if let Some(parent) = link.parent_id().and_then(|id| multibody.link(id)) {
+                    let id1 = ids.get(&parent.rigid_body_handle()).copied();
+                    let id2 = ids.get(&link.rigid_body_handle()).copied();
+                    sets.connect(id1, id2);
+                    joints.extend(id1.or(id2));
+                }
+            }
+        }
+
+        let mut island_ids = HashMap::new();
+        let mut islands: Vec<IslandInfo> = vec![];
+        for (id, handle) in handles.iter().enumerate() {
+            let root = sets.find(id);
+            let island_id = *island_ids.entry(root).or_insert_with(|| {
+                islands.push(IslandInfo::default());
+                islands.len() - 1
+            });
+            islands[island_id].bodies.push(*handle);
+        }
+
+        for (id, num_contacts) in contacts {
+            islands[island_ids[&sets.find(id)]].num_contacts += num_contacts;
+        }
+
+        for id in joints {
+            islands[island_ids[&sets.find(id)]].num_joints += 1;
+        }
+
+        for island in &mut islands {
+            island.sleeping = island_sleeping(bodies, &island.bodies);
+        }
+
+        RawIslands(islands)
+    }
+}
+
+fn collider_body_id(
+    colliders: &ColliderSet,
+    ids: &HashMap<RigidBodyHandle, usize>,
+    handle: ColliderHandle,
+) -> Option<usize> {
+    colliders
+        .get(handle)
+        .and_then(|co| co.parent())
+        .and_then(|parent| ids.get(&parent).copied())
+}
+
+fn island_sleeping(bodies: &RigidBodySet, handles: &[RigidBodyHandle]) -> bool {
+    handles
+        .iter()
+        .all(|h| bodies.get(*h).map(|rb| rb.is_sleeping()).unwrap_or(true))
+}
+
+/// A union-find structure with path compression.
+struct DisjointSets {
+    parents: Vec<usize>,
+}
+
+impl DisjointSets {
+    fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+        }
+    }
+
+    fn find(&mut self, mut id: usize) -> usize {
+        while self.parents[id] != id {
+            self.parents[id] = self.parents[self.parents[id]];
+            id = self.parents[id];
+        }
+        id
+    }
+
+    /// Merges the sets of the two elements, if both are given.
+    fn connect(&mut self, id1: Option<usize>, id2: Option<usize>) {
+        if let (Some(id1), Some(id2)) = (id1, id2) {
+            let root1 = self.find(id1);
+            let root2 = self.find(id2);
+            self.parents[root1] = root2;
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct IslandInfo {
+    bodies: Vec<RigidBodyHandle>,
+    sleeping: bool,
+    num_contacts: usize,
+    num_joints: usize,
+}
+
+/// The simulation islands computed by `RawIslandManager::computeIslands`.
+#[wasm_bindgen]
+pub struct RawIslands(Vec<IslandInfo>);
+
+#[wasm_bindgen]
+impl RawIslands {
+    pub fn numIslands(&self) -> usize {
+        self.0.len()
+    }
+
+    /// The handles of the rigid-bodies of the i-th island.
+    pub fn bodies(&self, i: usize) -> Vec<FlatHandle> {
+        self.0
+            .get(i)
+            .map(|island| {
+                island
+                    .bodies
+                    .iter()
+                    .map(|h| utils::flat_handle(h.0))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Are all the rigid-bodies of the i-th island asleep?
+    pub fn isSleeping(&self, i: usize) -> bool {
+        self.0.get(i).map(|island| island.sleeping).unwrap_or(false)
+    }
+
+    /// The number of solver contacts involving the rigid-bodies of the i-th island.
+    pub fn numContacts(&self, i: usize) -> usize {
+        self.0.get(i).map(|island| island.num_contacts).unwrap_or(0)
+    }
+
+    /// The number of impulse and multibody joints involving the rigid-bodies of the i-th island.
+    pub fn numJoints(&self, i: usize) -> usize {
+        self.0.get(i).map(|island| island.num_joints).unwrap_or(0)
+    }
 }
@@ -103,6 +103,48 @@ impl From<RawMotorModel> for MotorModel {
     }
 }
 
+/// Converts a mask of joint axes using the bit layout of the `JointAxesMask` enum of the
+/// JavaScript bindings, which is the same in 2D and 3D.
+#[cfg(feature = "dim2")]
+pub(crate) fn joint_axes_mask(bits: u8) -> JointAxesMask {
+    let mut mask = JointAxesMask::empty();
+    mask.set(JointAxesMask::LIN_X, bits & (1 << 0) != 0);
+    mask.set(JointAxesMask::LIN_Y, bits & (1 << 1) != 0);
+    mask.set(JointAxesMask::ANG_X, bits & (1 << 3) != 0);
+    mask
+}
+
+/// Converts a mask of joint axes using the bit layout of the `JointAxesMask` enum of the
+/// JavaScript bindings, which is the same in 2D and 3D.
+#[cfg(feature = "dim3")]
+pub(crate) fn joint_axes_mask(bits: u8) -> JointAxesMask {
+    JointAxesMask::from_bits_truncate(bits)
+}
+
+/// Converts a mask of joint axes to the bit layout of the `JointAxesMask` enum of the
+/// JavaScript bindings.
+#[cfg(feature = "dim2")]
+pub(crate) fn joint_axes_bits(mask: JointAxesMask) -> u8 {
+    let mut bits = 0;
+    if mask.contains(JointAxesMask::LIN_X) {
+        bits |= 1 << 0;
+    }
+    if mask.contains(JointAxesMask::LIN_Y) {
+        bits |= 1 << 1;
+    }
+    if mask.contains(JointAxesMask::ANG_X) {
+        bits |= 1 << 3;
+    }
+    bits
+}
+
+/// Converts a mask of joint axes to the bit layout of the `JointAxesMask` enum of the
+/// JavaScript bindings.
+#[cfg(feature = "dim3")]
+pub(crate) fn joint_axes_bits(mask: JointAxesMask) -> u8 {
+    mask.bits()
+}
+
 #[cfg(feature = "dim2")]
 #[wasm_bindgen]
 #[derive(Copy, Clone)]
@@ -311,4 +353,16 @@ impl RawGenericJoint {
                 .into(),
         ))
     }
+
+    /// Sets the axes coupled by this joint.
+    ///
+    /// The coupled axes are constrained together through their norm instead of independently,
+    /// e.g., the coupled linear axes of spring and rope joints constrain the distance between
+    /// the anchors. The limits and motor of the coupled axes are set on their first axis.
+    ///
+    /// # Parameters
+    /// - `coupledAxes`: a mask with the bit layout of the `JointAxesMask` enum.
+    pub fn setCoupledAxes(&mut self, coupledAxes: u8) {
+        self.0.coupled_axes = joint_axes_mask(coupledAxes);
+    }
 }
@@ -0,0 +1,152 @@
+use rapier::dynamics::{
+    ImpulseJoint, ImpulseJointHandle, ImpulseJointSet, RigidBody, RigidBodySet,
+};
+use rapier::math::{AngVector, AngularInertia, Isometry, Real, Vector, DIM, SPATIAL_DIM};
+use std::collections::HashMap;
+
+#[cfg(feature = "dim2")]
+pub(crate) fn cross(a: &Vector<Real>, b: &Vector<Real>) -> AngVector<Real> {
+    a.perp(b)
+}
+
+#[cfg(feature = "dim3")]
+pub(crate) fn cross(a: &Vector<Real>, b: &Vector<Real>) -> AngVector<Real> {
+    a.cross(b)
+}
+
+#[cfg(feature = "dim2")]
+pub(crate) fn ang_dot(a: &AngVector<Real>, b: &AngVector<Real>) -> Real {
+    a * b
+}
+
+#[cfg(feature = "dim3")]
+pub(crate) fn ang_dot(a: &AngVector<Real>, b: &AngVector<Real>) -> Real {
+    a.dot(b)
+}
+
+#[cfg(feature = "dim2")]
+pub(crate) fn inertia_mul(inertia: &AngularInertia<Real>, v: &AngVector<Real>) -> AngVector<Real> {
+    inertia * v
+}
+
+#[cfg(feature = "dim3")]
+pub(crate) fn inertia_mul(inertia: &AngularInertia<Real>, v: &AngVector<Real>) -> AngVector<Real> {
+    *inertia * *v
+}
+
+/// The world-space direction of the given angular axis of a joint frame.
+#[cfg(feature = "dim2")]
+fn angular_axis(_frame: &Isometry<Real>, _axis: usize) -> AngVector<Real> {
+    1.0
+}
+
+/// The world-space direction of the given angular axis of a joint frame.
+#[cfg(feature = "dim3")]
+fn angular_axis(frame: &Isometry<Real>, axis: usize) -> AngVector<Real> {
+    frame.rotation * Vector::ith(axis, 1.0)
+}
+
+/// The inverse mass of a rigid-body along a velocity direction, given by its linear and
+/// angular parts.
+pub(crate) fn inv_mass(rb: &RigidBody, lin: &Vector<Real>, ang: &AngVector<Real>) -> Real {
+    if !rb.is_dynamic() {
+        return 0.0;
+    }
+
+    let mprops = rb.mass_properties();
+    lin.component_mul(&mprops.effective_inv_mass).dot(lin)
+        + ang_dot(ang, &inertia_mul(&mprops.effective_world_inv_inertia, ang))
+}
+
+/// The velocity of a rigid-body along a velocity direction, given by its linear and angular
+/// parts.
+fn velocity(rb: &RigidBody, lin: &Vector<Real>, ang: &AngVector<Real>) -> Real {
+    lin.dot(&rb.vels().linvel) + ang_dot(ang, &rb.vels().angvel)
+}
+
+/// The dry friction of the free axes of the impulse joints.
+///
+/// Before each substep, an impulse opposing the relative velocity of the rigid-bodies along
+/// each axis with friction is applied, up to the friction force (or torque) times the timestep.
+/// The joint motors and limits are then solved as usual, so the friction doesn’t fight the
+/// motor targets.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct JointFrictions(HashMap<ImpulseJointHandle, [Real; SPATIAL_DIM]>);
+
+impl JointFrictions {
+    pub fn get(&self, handle: ImpulseJointHandle, axis: usize) -> Real {
+        self.0.get(&handle).map_or(0.0, |frictions| frictions[axis])
+    }
+
+    pub fn set(&mut self, handle: ImpulseJointHandle, axis: usize, friction: Real) {
+        let frictions = self.0.entry(handle).or_insert([0.0; SPATIAL_DIM]);
+        frictions[axis] = friction.max(0.0);
+
+        if frictions.iter().all(|friction| *friction == 0.0) {
+            let _ = self.0.remove(&handle);
+        }
+    }
+
+    pub fn remove(&mut self, handle: ImpulseJointHandle) {
+        let _ = self.0.remove(&handle);
+    }
+
+    /// Applies the friction impulses of all the joints for a substep of length `dt`.
+    pub fn apply(&mut self, joints: &ImpulseJointSet, bodies: &mut RigidBodySet, dt: Real) {
+        // Forget the joints removed with their rigid-bodies.
+        self.0.retain(|handle, _| joints.get(*handle).is_some());
+
+        for (handle, frictions) in &self.0 {
+            let joint = match joints.get(*handle) {
+                Some(joint) if joint.data.is_enabled() => joint,
+                _ => continue,
+            };
+
+            for (axis, friction) in frictions.iter().enumerate() {
+                let locked = joint.data.locked_axes.bits() & (1 << axis) != 0;
+                if *friction > 0.0 && !locked {
+                    Self::apply_axis(joint, axis, friction * dt, bodies);
+                }
+            }
+        }
+    }
+
+    fn apply_axis(joint: &ImpulseJoint, axis: usize, max_impulse: Real, bodies: &mut RigidBodySet) {
+        let (rb1, rb2) = match (bodies.get(joint.body1), bodies.get(joint.body2)) {
+            (Some(rb1), Some(rb2)) => (rb1, rb2),
+            _ => return,
+        };
+        let frame1 = rb1.position() * joint.data.local_frame1;
+        let frame2 = rb2.position() * joint.data.local_frame2;
+
+        let (lin, ang1, ang2) = if axis < DIM {
+            let dir = frame1.rotation * Vector::ith(axis, 1.0);
+            let r1 = frame1.translation.vector - rb1.center_of_mass().coords;
+            let r2 = frame2.translation.vector - rb2.center_of_mass().coords;
+            (dir, cross(&r1, &dir), cross(&r2, &dir))
+        } else {
+            let dir = angular_axis(&frame1, axis - DIM);
+            (Vector::zeros(), dir, dir)
+        };
+
+        let k = inv_mass(rb1, &lin, &ang1) + inv_mass(rb2, &lin, &ang2);
+        if k <= 0.0 {
+            return;
+        }
+
+        let relative_velocity = velocity(rb2, &lin, &ang2) - velocity(rb1, &lin, &ang1);
+        let impulse = (-relative_velocity / k).clamp(-max_impulse, max_impulse);
+        if impulse == 0.0 {
+            return;
+        }
+
+        if let Some(rb1) = bodies.get_mut(joint.body1) {
+            rb1.apply_impulse(lin * -impulse, false);
+            rb1.apply_torque_impulse(ang1 * -impulse, false);
+        }
+        if let Some(rb2) = bodies.get_mut(joint.body2) {
+            rb2.apply_impulse(lin * impulse, false);
+            rb2.apply_torque_impulse(ang2 * impulse, false);
+        }
+    }
+}
@@ -1,20 +1,30 @@
 //! Structures related to dynamics: bodies, joints, etc.
 
+pub use self::body_forces::*;
 pub use self::ccd_solver::*;
 pub use self::impulse_joint_set::*;
 pub use self::integration_parameters::*;
 pub use self::island_manager::*;
 pub use self::joint::*;
+pub use self::joint_friction::*;
 pub use self::multibody_joint_set::*;
+pub use self::remote_poses::*;
 pub use self::rigid_body_set::*;
+pub use self::simulation_layers::*;
+pub use self::weld::*;
 
+mod body_forces;
 mod ccd_solver;
 mod impulse_joint;
 mod impulse_joint_set;
 mod integration_parameters;
 mod island_manager;
 mod joint;
+mod joint_friction;
 mod multibody_joint;
 mod multibody_joint_set;
+mod remote_poses;
 mod rigid_body;
 mod rigid_body_set;
+mod simulation_layers;
+mod weld;
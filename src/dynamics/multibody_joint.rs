@@ -47,6 +47,36 @@ impl RawMultibodyJointSet {
         self.map(handle, |j| j.data.local_frame2.translation.vector.into())
     }
 
+    /// Sets the position of the first local anchor
+    pub fn jointSetAnchor1(&mut self, handle: FlatHandle, newPos: &RawVector) {
+        self.map_mut(handle, |j| {
+            j.data.set_local_anchor1(newPos.0.into());
+        });
+    }
+
+    /// Sets the position of the second local anchor
+    pub fn jointSetAnchor2(&mut self, handle: FlatHandle, newPos: &RawVector) {
+        self.map_mut(handle, |j| {
+            j.data.set_local_anchor2(newPos.0.into());
+        })
+    }
+
+    /// Sets the angular part of the joint’s local frame relative to the first rigid-body it is
+    /// attached to.
+    pub fn jointSetLocalFrame1(&mut self, handle: FlatHandle, newRot: &RawRotation) {
+        self.map_mut(handle, |j| {
+            j.data.local_frame1.rotation = newRot.0;
+        })
+    }
+
+    /// Sets the angular part of the joint’s local frame relative to the second rigid-body it is
+    /// attached to.
+    pub fn jointSetLocalFrame2(&mut self, handle: FlatHandle, newRot: &RawRotation) {
+        self.map_mut(handle, |j| {
+            j.data.local_frame2.rotation = newRot.0;
+        })
+    }
+
     /// Are contacts between the rigid-bodies attached by this joint enabled?
     pub fn jointContactsEnabled(&self, handle: FlatHandle) -> bool {
         self.map(handle, |j| j.data.contacts_enabled)
@@ -1,8 +1,31 @@
-use crate::dynamics::RawGenericJoint;
+use crate::dynamics::{RawGenericJoint, RawRigidBodySet};
+use crate::math::RawVector;
 use crate::utils::{self, FlatHandle};
-use rapier::dynamics::{MultibodyJoint, MultibodyJointSet};
+use rapier::dynamics::{JointAxis, MultibodyJoint, MultibodyJointSet, RigidBodyType};
+use rapier::math::{Point, DIM};
 use wasm_bindgen::prelude::*;
 
+#[cfg(feature = "dim2")]
+const JOINT_AXES: [JointAxis; 3] = [JointAxis::LinX, JointAxis::LinY, JointAxis::AngX];
+#[cfg(feature = "dim3")]
+const JOINT_AXES: [JointAxis; 6] = [
+    JointAxis::LinX,
+    JointAxis::LinY,
+    JointAxis::LinZ,
+    JointAxis::AngX,
+    JointAxis::AngY,
+    JointAxis::AngZ,
+];
+
+/// The axes left free by a multibody joint, in the order of its coordinates.
+fn free_axes(joint: &MultibodyJoint) -> Vec<JointAxis> {
+    JOINT_AXES
+        .iter()
+        .copied()
+        .filter(|axis| !joint.data.locked_axes.contains((*axis).into()))
+        .collect()
+}
+
 #[wasm_bindgen]
 pub struct RawMultibodyJointSet(pub(crate) MultibodyJointSet);
 
@@ -82,4 +105,195 @@ impl RawMultibodyJointSet {
             let _ = f.call1(&this, &JsValue::from(utils::flat_handle(handle.0)));
         }
     }
+
+    /// The handle of the rigid-body at the root of the multibody containing the given joint.
+    pub fn multibodyRoot(&self, handle: FlatHandle) -> Option<FlatHandle> {
+        self.0
+            .get(utils::multibody_joint_handle(handle))
+            .map(|(multibody, _)| utils::flat_handle(multibody.root().rigid_body_handle().0))
+    }
+
+    /// Makes the root of the multibody containing the given joint kinematic (position-based)
+    /// or dynamic.
+    ///
+    /// With a kinematic root, the root is animated by setting the next kinematic position of
+    /// its rigid-body while the rest of the multibody remains dynamic, e.g., for ragdoll limbs
+    /// hanging off an animated character.
+    pub fn multibodySetRootKinematic(
+        &mut self,
+        bodies: &mut RawRigidBodySet,
+        handle: FlatHandle,
+        kinematic: bool,
+    ) {
+        let root = match self.0.get(utils::multibody_joint_handle(handle)) {
+            Some((multibody, _)) => multibody.root().rigid_body_handle(),
+            None => return,
+        };
+        let body_type = if kinematic {
+            RigidBodyType::KinematicPositionBased
+        } else {
+            RigidBodyType::Dynamic
+        };
+
+        if let Some(rb) = bodies.0.get_mut(root) {
+            rb.set_body_type(body_type, true);
+        }
+    }
+
+    /// Is the root of the multibody containing the given joint kinematic?
+    pub fn multibodyIsRootKinematic(&self, bodies: &RawRigidBodySet, handle: FlatHandle) -> bool {
+        self.0
+            .get(utils::multibody_joint_handle(handle))
+            .and_then(|(multibody, _)| bodies.0.get(multibody.root().rigid_body_handle()))
+            .is_some_and(|rb| rb.is_kinematic())
+    }
+
+    /// Are contacts enabled between the links of the multibody containing the given joint?
+    pub fn multibodySelfContactsEnabled(&self, handle: FlatHandle) -> bool {
+        self.0
+            .get(utils::multibody_joint_handle(handle))
+            .is_some_and(|(multibody, _)| multibody.self_contacts_enabled())
+    }
+
+    /// Enables or disables the contacts between all the links of the multibody containing
+    /// the given joint.
+    ///
+    /// Contacts between adjacent links are additionally controlled by the `contactsEnabled`
+    /// flag of the joint attaching them.
+    pub fn multibodySetSelfContactsEnabled(&mut self, handle: FlatHandle, enabled: bool) {
+        if let Some((multibody, _)) = self.0.get_mut(utils::multibody_joint_handle(handle)) {
+            multibody.set_self_contacts_enabled(enabled);
+        }
+    }
+
+    /// The number of free coordinates of the joints of the multibody containing the given
+    /// joint, excluding its root, i.e., the number of target positions of a pose.
+    pub fn multibodyNumPoseCoordinates(&self, handle: FlatHandle) -> usize {
+        self.0
+            .get(utils::multibody_joint_handle(handle))
+            .map(|(multibody, _)| {
+                multibody
+                    .links()
+                    .skip(1)
+                    .map(|link| free_axes(link.joint()).len())
+                    .sum()
+            })
+            .unwrap_or(0)
+    }
+
+    /// Configures the position motors of all the joints of the multibody containing the given
+    /// joint at once, to drive it toward a target pose.
+    ///
+    /// The target positions are given for each link after the root, in the order of the links,
+    /// and for each free axis of its joint, in the order of the axes (linear axes first). All
+    /// the motors share the same stiffness, damping, and maximum force.
+    ///
+    /// Returns `false`, without modifying any motor, if the number of target positions doesn’t
+    /// match `multibodyNumPoseCoordinates`.
+    pub fn multibodyConfigurePoseTarget(
+        &mut self,
+        bodies: &mut RawRigidBodySet,
+        handle: FlatHandle,
+        targets: Vec<f64>,
+        stiffness: f64,
+        damping: f64,
+        maxForce: f64,
+    ) -> bool {
+        if targets.len() != self.multibodyNumPoseCoordinates(handle) {
+            return false;
+        }
+
+        let (multibody, _) = match self.0.get_mut(utils::multibody_joint_handle(handle)) {
+            Some(multibody) => multibody,
+            None => return false,
+        };
+
+        let mut targets = targets.into_iter();
+        for link_id in 1..multibody.num_links() {
+            if let Some(link) = multibody.link_mut(link_id) {
+                for axis in free_axes(&link.joint) {
+                    let target = targets.next().unwrap_or_default();
+                    link.joint
+                        .data
+                        .set_motor(axis, target, 0.0, stiffness, damping)
+                        .set_motor_max_force(axis, maxForce);
+                }
+            }
+        }
+
+        let root = multibody.root().rigid_body_handle();
+        if let Some(rb) = bodies.0.get_mut(root) {
+            rb.wake_up(true);
+        }
+
+        true
+    }
+
+    /// The number of degrees of freedom of the multibody containing the given joint,
+    /// including the free degrees of freedom of its root if it is dynamic.
+    pub fn multibodyNumDofs(&self, handle: FlatHandle) -> usize {
+        self.0
+            .get(utils::multibody_joint_handle(handle))
+            .map(|(multibody, _)| multibody.ndofs())
+            .unwrap_or(0)
+    }
+
+    /// The generalized mass matrix of the multibody containing the given joint, as computed
+    /// during the last simulation step, in row-major order.
+    ///
+    /// This is a square matrix with `multibodyNumDofs` rows. It is augmented with the
+    /// contributions of the joint damping, like the matrix used by the solver.
+    pub fn multibodyMassMatrix(&self, handle: FlatHandle) -> Vec<f64> {
+        let (multibody, _) = match self.0.get(utils::multibody_joint_handle(handle)) {
+            Some(multibody) => multibody,
+            None => return vec![],
+        };
+        multibody.augmented_mass().transpose().as_slice().to_vec()
+    }
+
+    /// The Jacobian of a point attached to the link of the given joint, as computed during the
+    /// last simulation step, in row-major order.
+    ///
+    /// The Jacobian maps the generalized velocities of the multibody to the velocity of the
+    /// point. It has `DIM` rows for the linear velocity of the point followed by one row (in
+    /// 2D) or three rows (in 3D) for the angular velocity of the link, and `multibodyNumDofs`
+    /// columns.
+    ///
+    /// # Parameters
+    /// - `point`: the point attached to the link, in world-space.
+    pub fn multibodyPointJacobian(
+        &self,
+        bodies: &RawRigidBodySet,
+        handle: FlatHandle,
+        point: &RawVector,
+    ) -> Vec<f64> {
+        let (multibody, link_id) = match self.0.get(utils::multibody_joint_handle(handle)) {
+            Some(multibody) => multibody,
+            None => return vec![],
+        };
+        let com = multibody
+            .link(link_id)
+            .and_then(|link| bodies.0.get(link.rigid_body_handle()))
+            .map(|rb| *rb.center_of_mass())
+            .unwrap_or_else(Point::origin);
+        let r = point.0 - com.coords;
+        let jacobian = multibody.body_jacobian(link_id);
+        let mut result = jacobian.clone_owned();
+
+        // The velocity of the point is `linvel + angvel × r`.
+        #[cfg(feature = "dim2")]
+        {
+            let ang = jacobian.row(DIM);
+            result.row_mut(0).axpy(-r.y, &ang, 1.0);
+            result.row_mut(1).axpy(r.x, &ang, 1.0);
+        }
+        #[cfg(feature = "dim3")]
+        {
+            let ang = jacobian.fixed_rows::<3>(DIM);
+            let lin = jacobian.fixed_rows::<3>(0) - r.cross_matrix() * ang;
+            result.fixed_rows_mut::<3>(0).copy_from(&lin);
+        }
+
+        result.transpose().as_slice().to_vec()
+    }
 }
@@ -0,0 +1,107 @@
+use rapier::dynamics::{RigidBodyHandle, RigidBodySet};
+use rapier::math::{Isometry, Real};
+use std::collections::{HashMap, VecDeque};
+
+/// The maximum number of poses buffered for a single rigid-body.
+const MAX_BUFFERED_POSES: usize = 64;
+
+/// A pose received for a remote-controlled rigid-body, with its timestamp.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+struct TimedPose {
+    time: Real,
+    pose: Isometry<Real>,
+}
+
+/// Buffers of timestamped poses driving the kinematic targets of remote-controlled rigid-bodies.
+///
+/// At each step, the clock is advanced by the timestep and the next kinematic position of each
+/// rigid-body with a buffer is set to its pose at `time - delay`, interpolated between the two
+/// buffered poses around that time. Past the last buffered pose, the motion between the two last
+/// poses is extrapolated, for at most `max_extrapolation`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct RemotePoses {
+    buffers: HashMap<RigidBodyHandle, VecDeque<TimedPose>>,
+    pub time: Real,
+    pub delay: Real,
+    pub max_extrapolation: Real,
+}
+
+impl Default for RemotePoses {
+    fn default() -> Self {
+        Self {
+            buffers: HashMap::new(),
+            time: 0.0,
+            delay: 0.1,
+            max_extrapolation: 0.25,
+        }
+    }
+}
+
+impl RemotePoses {
+    /// Adds a timestamped pose to the buffer of the given rigid-body.
+    ///
+    /// Poses older than the last one buffered are ignored.
+    pub fn push(&mut self, handle: RigidBodyHandle, time: Real, pose: Isometry<Real>) {
+        let buffer = self.buffers.entry(handle).or_default();
+        if buffer.back().map(|last| time <= last.time) == Some(true) {
+            return;
+        }
+
+        if buffer.len() == MAX_BUFFERED_POSES {
+            let _ = buffer.pop_front();
+        }
+        buffer.push_back(TimedPose { time, pose });
+    }
+
+    pub fn remove(&mut self, handle: RigidBodyHandle) {
+        let _ = self.buffers.remove(&handle);
+    }
+
+    /// Advances the clock by `dt` and sets the next kinematic position of the rigid-bodies with
+    /// buffered poses.
+    pub fn advance(&mut self, dt: Real, bodies: &mut RigidBodySet) {
+        // Forget the rigid-bodies removed without going through the bindings.
+        self.buffers
+            .retain(|handle, _| bodies.get(*handle).is_some());
+
+        self.time += dt;
+        let time = self.time - self.delay;
+
+        for (handle, buffer) in self.buffers.iter_mut() {
+            // Only keep the last pose before the sampled time, the older ones are no longer
+            // needed to interpolate.
+            while buffer.len() > 2 && buffer[1].time <= time {
+                let _ = buffer.pop_front();
+            }
+
+            if let Some(pose) = Self::sample(buffer, time, self.max_extrapolation) {
+                if let Some(rb) = bodies.get_mut(*handle) {
+                    rb.set_next_kinematic_position(pose);
+                }
+            }
+        }
+    }
+
+    fn sample(
+        buffer: &VecDeque<TimedPose>,
+        time: Real,
+        max_extrapolation: Real,
+    ) -> Option<Isometry<Real>> {
+        let (first, last) = (buffer.front()?, buffer.back()?);
+        if buffer.len() == 1 || time <= first.time {
+            return Some(first.pose);
+        }
+
+        let (from, to) = if time <= last.time {
+            let i = buffer.iter().position(|sample| sample.time >= time)?;
+            (&buffer[i - 1], &buffer[i])
+        } else {
+            (&buffer[buffer.len() - 2], last)
+        };
+
+        let time = time.min(last.time + max_extrapolation);
+        let t = (time - from.time) / (to.time - from.time);
+        Some(from.pose.lerp_slerp(&to.pose, t))
+    }
+}
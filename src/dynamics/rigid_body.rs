@@ -1,13 +1,71 @@
-use crate::dynamics::{RawRigidBodySet, RawRigidBodyType};
-use crate::geometry::RawColliderSet;
+use crate::dynamics::{
+    DragConstraint, PoseTracking, RawRigidBodySet, RawRigidBodyType, UprightConstraint,
+};
+use crate::geometry::{RawColliderSet, RawMassProperties};
 #[cfg(feature = "dim3")]
 use crate::math::RawSdpMatrix3;
 use crate::math::{RawRotation, RawVector};
 use crate::utils::{self, FlatHandle};
 use na::Point;
-use rapier::dynamics::MassProperties;
+use rapier::dynamics::{
+    MassProperties, RigidBody, RigidBodyAdditionalMassProps, RigidBodyPosition, RigidBodyType,
+    RigidBodyVelocity,
+};
+use rapier::math::Isometry;
 use wasm_bindgen::prelude::*;
 
+/// The velocity the solver uses for a rigid-body during a timestep of length `dt`.
+///
+/// For a position-based kinematic rigid-body, this is the velocity inferred from its current
+/// position and its next kinematic position. For the other rigid-bodies, this is their velocity.
+fn effective_kinematic_velocity(rb: &RigidBody, dt: f64) -> RigidBodyVelocity {
+    if rb.body_type() == RigidBodyType::KinematicPositionBased && dt > 0.0 {
+        let pos = RigidBodyPosition {
+            position: *rb.position(),
+            next_position: *rb.next_position(),
+        };
+        pos.interpolate_velocity(1.0 / dt, rb.local_center_of_mass())
+    } else {
+        *rb.vels()
+    }
+}
+
+/// The pose of a rigid-body, i.e., its translation and orientation.
+#[wasm_bindgen]
+pub struct RawRigidBodyPose(pub(crate) Isometry<f64>);
+
+#[wasm_bindgen]
+impl RawRigidBodyPose {
+    pub fn translation(&self) -> RawVector {
+        RawVector(self.0.translation.vector)
+    }
+
+    pub fn rotation(&self) -> RawRotation {
+        RawRotation(self.0.rotation)
+    }
+}
+
+/// The linear and angular velocities of a rigid-body.
+#[wasm_bindgen]
+pub struct RawRigidBodyVelocity(pub(crate) RigidBodyVelocity);
+
+#[wasm_bindgen]
+impl RawRigidBodyVelocity {
+    pub fn linvel(&self) -> RawVector {
+        RawVector(self.0.linvel)
+    }
+
+    #[cfg(feature = "dim2")]
+    pub fn angvel(&self) -> f64 {
+        self.0.angvel
+    }
+
+    #[cfg(feature = "dim3")]
+    pub fn angvel(&self) -> RawVector {
+        RawVector(self.0.angvel)
+    }
+}
+
 #[wasm_bindgen]
 impl RawRigidBodySet {
     /// The world-space translation of this rigid-body.
@@ -55,6 +113,17 @@ impl RawRigidBodySet {
         self.map(handle, |rb| RawRotation(rb.next_position().rotation))
     }
 
+    /// The world-space pose this rigid-body would have after integrating its current velocity
+    /// and forces for a time `dt`, without running a simulation step.
+    ///
+    /// Contacts and joints are ignored, and the forces are those applied since the last step,
+    /// including the gravity computed by the last step.
+    pub fn rbPredictPose(&self, handle: FlatHandle, dt: f64) -> RawRigidBodyPose {
+        self.map(handle, |rb| {
+            RawRigidBodyPose(rb.predict_position_using_velocity_and_forces(dt))
+        })
+    }
+
     /// Sets the translation of this rigid-body.
     ///
     /// # Parameters
@@ -281,6 +350,34 @@ impl RawRigidBodySet {
         })
     }
 
+    /// The additional mass of this rigid-body, set with `rbSetAdditionalMass` or
+    /// `rbSetAdditionalMassProperties`, or zero if none was set.
+    pub fn rbAdditionalMass(&self, handle: FlatHandle) -> f64 {
+        self.map(handle, |rb| {
+            match rb.mass_properties().additional_local_mprops.as_deref() {
+                Some(RigidBodyAdditionalMassProps::Mass(mass)) => *mass,
+                Some(RigidBodyAdditionalMassProps::MassProps(mprops)) => mprops.mass(),
+                None => 0.0,
+            }
+        })
+    }
+
+    /// The additional mass-properties of this rigid-body, expressed in its local-space.
+    ///
+    /// Returns `None` if no additional mass-properties were set with
+    /// `rbSetAdditionalMassProperties`, including if only an additional mass was set with
+    /// `rbSetAdditionalMass`.
+    pub fn rbAdditionalMassProperties(&self, handle: FlatHandle) -> Option<RawMassProperties> {
+        self.map(handle, |rb| {
+            match rb.mass_properties().additional_local_mprops.as_deref() {
+                Some(RigidBodyAdditionalMassProps::MassProps(mprops)) => {
+                    Some(RawMassProperties(*mprops))
+                }
+                _ => None,
+            }
+        })
+    }
+
     /// The linear velocity of this rigid-body.
     pub fn rbLinvel(&self, handle: FlatHandle) -> RawVector {
         self.map(handle, |rb| RawVector(*rb.linvel()))
@@ -298,6 +395,24 @@ impl RawRigidBodySet {
         self.map(handle, |rb| RawVector(*rb.angvel()))
     }
 
+    /// The linear and angular velocities the solver uses for this rigid-body during the next
+    /// timestep.
+    ///
+    /// For a position-based kinematic rigid-body, these are inferred from its current pose and
+    /// its next kinematic pose. For the other rigid-bodies, these are their velocities.
+    ///
+    /// # Parameters
+    /// - `dt`: the length of the next timestep.
+    pub fn rbEffectiveKinematicVelocity(
+        &self,
+        handle: FlatHandle,
+        dt: f64,
+    ) -> RawRigidBodyVelocity {
+        self.map(handle, |rb| {
+            RawRigidBodyVelocity(effective_kinematic_velocity(rb, dt))
+        })
+    }
+
     /// The velocity of the given world-space point on this rigid-body.
     pub fn rbVelocityAtPoint(&self, handle: FlatHandle, point: &RawVector) -> RawVector {
         self.map(handle, |rb| {
@@ -354,6 +469,53 @@ impl RawRigidBodySet {
         })
     }
 
+    /// The translation and rotation locks of this rigid-body, as a bit mask.
+    ///
+    /// The bits `0` to `DIM - 1` are set if the translations along the corresponding
+    /// coordinate axes are locked, and the bits `3` to `5` if the rotations about the X, Y,
+    /// and Z coordinate axes are locked.
+    pub fn rbLockedAxes(&self, handle: FlatHandle) -> u8 {
+        self.map(handle, |rb| rb.locked_axes().bits())
+    }
+
+    /// The simulation layers of this rigid-body, as a bit mask.
+    pub fn rbSimulationLayers(&self, handle: FlatHandle) -> u32 {
+        self.1.simulation_layers.layers(utils::body_handle(handle))
+    }
+
+    /// Sets the simulation layers of this rigid-body, as a bit mask.
+    ///
+    /// The rigid-body is only simulated if any of its layers is active in the physics pipeline.
+    /// The rigid-bodies are in the first layer by default.
+    pub fn rbSetSimulationLayers(&mut self, handle: FlatHandle, layers: u32) {
+        self.1
+            .simulation_layers
+            .set_layers(utils::body_handle(handle), layers)
+    }
+
+    /// Adds a timestamped pose to the buffer driving the kinematic target of this rigid-body.
+    ///
+    /// At each step, the next kinematic position of the rigid-body is interpolated from its
+    /// buffered poses. Poses older than the last one buffered are ignored.
+    pub fn rbPushRemotePose(
+        &mut self,
+        handle: FlatHandle,
+        time: f64,
+        translation: &RawVector,
+        rotation: &RawRotation,
+    ) {
+        let pose = Isometry::from_parts(translation.0.into(), rotation.0);
+        self.1
+            .remote_poses
+            .push(utils::body_handle(handle), time, pose)
+    }
+
+    /// Removes all the buffered poses of this rigid-body, which stops driving its kinematic
+    /// target.
+    pub fn rbClearRemotePoses(&mut self, handle: FlatHandle) {
+        self.1.remote_poses.remove(utils::body_handle(handle))
+    }
+
     pub fn rbDominanceGroup(&self, handle: FlatHandle) -> i8 {
         self.map(handle, |rb| rb.dominance_group())
     }
@@ -522,6 +684,40 @@ impl RawRigidBodySet {
         self.map(handle, |rb| utils::flat_handle(rb.colliders()[at].0))
     }
 
+    /// Sets the collision groups of all the colliders attached to this rigid-body.
+    pub fn rbSetCollisionGroups(
+        &self,
+        handle: FlatHandle,
+        colliders: &mut RawColliderSet,
+        groups: u32,
+    ) {
+        let groups = crate::geometry::unpack_interaction_groups(groups);
+        self.map(handle, |rb| {
+            for co_handle in rb.colliders() {
+                if let Some(co) = colliders.0.get_mut(*co_handle) {
+                    co.set_collision_groups(groups);
+                }
+            }
+        })
+    }
+
+    /// Sets the solver groups of all the colliders attached to this rigid-body.
+    pub fn rbSetSolverGroups(
+        &self,
+        handle: FlatHandle,
+        colliders: &mut RawColliderSet,
+        groups: u32,
+    ) {
+        let groups = crate::geometry::unpack_interaction_groups(groups);
+        self.map(handle, |rb| {
+            for co_handle in rb.colliders() {
+                if let Some(co) = colliders.0.get_mut(*co_handle) {
+                    co.set_solver_groups(groups);
+                }
+            }
+        })
+    }
+
     /// The status of this rigid-body: fixed, dynamic, or kinematic.
     pub fn rbBodyType(&self, handle: FlatHandle) -> RawRigidBodyType {
         self.map(handle, |rb| rb.body_type().into())
@@ -565,6 +761,218 @@ impl RawRigidBodySet {
         self.map_mut(handle, |rb| rb.set_angular_damping(factor));
     }
 
+    /// The linear damping coefficients of this rigid-body along each of its local axes.
+    ///
+    /// They are applied in addition to the linear damping coefficient of the rigid-body.
+    pub fn rbLocalLinearDamping(&self, handle: FlatHandle) -> RawVector {
+        RawVector(
+            self.1
+                .forces
+                .local_linear_damping(utils::body_handle(handle)),
+        )
+    }
+
+    /// Sets the linear damping coefficients of this rigid-body along each of its local axes.
+    ///
+    /// This allows anisotropic drag, e.g., a strong lateral damping for an arrow or a boat.
+    /// Each component of the local linear velocity is damped the same way the linear damping
+    /// coefficient damps the whole linear velocity. Negative values are treated as 0.
+    pub fn rbSetLocalLinearDamping(&mut self, handle: FlatHandle, damping: &RawVector) {
+        let handle = utils::body_handle(handle);
+        if self.0.get(handle).is_some() {
+            self.1.forces.set_local_linear_damping(handle, damping.0);
+        }
+    }
+
+    /// The fraction of the gravity cancelled for this rigid-body.
+    pub fn rbGravityCompensation(&self, handle: FlatHandle) -> f64 {
+        self.1
+            .forces
+            .gravity_compensation(utils::body_handle(handle))
+    }
+
+    /// Sets the fraction of the gravity cancelled for this rigid-body, e.g., `1.0` for a
+    /// hovering body, or `0.5` for a body falling at half the rate of the others.
+    ///
+    /// The compensation applies on top of the gravity scale: it cancels the given fraction of
+    /// the scaled gravity, without changing the mass of the rigid-body. Values greater than 1
+    /// make it rise. Set it to 0 to disable the compensation.
+    pub fn rbSetGravityCompensation(&mut self, handle: FlatHandle, fraction: f64) {
+        let handle = utils::body_handle(handle);
+        if self.0.get(handle).is_some() {
+            self.1.forces.set_gravity_compensation(handle, fraction);
+        }
+    }
+
+    /// Keeps the given local axis of this rigid-body aligned with the given world-space axis,
+    /// e.g., for hover vehicles or characters simulated as dynamic rigid-bodies.
+    ///
+    /// Before each substep, an angular impulse pulls the local axis toward the target axis,
+    /// as a spring-damper integrated implicitly. Its rotation around the target axis is left
+    /// free. The stiffness and damping are angular accelerations (per radian, and per radian
+    /// per second, of tilt) so they don’t depend on the inertia of the rigid-body.
+    ///
+    /// Does nothing if one of the axes is zero.
+    pub fn rbSetUprightConstraint(
+        &mut self,
+        handle: FlatHandle,
+        localAxis: &RawVector,
+        targetAxis: &RawVector,
+        stiffness: f64,
+        damping: f64,
+    ) {
+        let handle = utils::body_handle(handle);
+        let local_axis = localAxis.0.try_normalize(0.0);
+        let target_axis = targetAxis.0.try_normalize(0.0);
+
+        if let (Some(local_axis), Some(target_axis), Some(_)) =
+            (local_axis, target_axis, self.0.get(handle))
+        {
+            let constraint = UprightConstraint {
+                local_axis,
+                target_axis,
+                stiffness: stiffness.max(0.0),
+                damping: damping.max(0.0),
+            };
+            self.1
+                .forces
+                .set_upright_constraint(handle, Some(constraint));
+        }
+    }
+
+    /// Removes the upright constraint of this rigid-body, if it has one.
+    pub fn rbRemoveUprightConstraint(&mut self, handle: FlatHandle) {
+        self.1
+            .forces
+            .set_upright_constraint(utils::body_handle(handle), None);
+    }
+
+    /// Does this rigid-body have an upright constraint?
+    pub fn rbHasUprightConstraint(&self, handle: FlatHandle) -> bool {
+        self.1
+            .forces
+            .upright_constraint(utils::body_handle(handle))
+            .is_some()
+    }
+
+    /// Drives this rigid-body toward a target pose with a PD controller evaluated at each
+    /// substep, e.g., for physically-animated characters.
+    ///
+    /// The stiffnesses and dampings are accelerations (per unit of pose error, and per unit of
+    /// velocity) so they don’t depend on the mass of the rigid-body. The controller is
+    /// integrated implicitly, so high gains remain stable, and the resulting force and torque
+    /// are clamped to `maxForce` and `maxTorque`. The target can then be updated with
+    /// `rbSetPoseTrackingTarget`.
+    pub fn rbSetPoseTracking(
+        &mut self,
+        handle: FlatHandle,
+        targetTranslation: &RawVector,
+        targetRotation: &RawRotation,
+        linStiffness: f64,
+        linDamping: f64,
+        angStiffness: f64,
+        angDamping: f64,
+        maxForce: f64,
+        maxTorque: f64,
+    ) {
+        let handle = utils::body_handle(handle);
+        if self.0.get(handle).is_some() {
+            let tracking = PoseTracking {
+                target: Isometry::from_parts(targetTranslation.0.into(), targetRotation.0),
+                lin_stiffness: linStiffness.max(0.0),
+                lin_damping: linDamping.max(0.0),
+                ang_stiffness: angStiffness.max(0.0),
+                ang_damping: angDamping.max(0.0),
+                max_force: maxForce.max(0.0),
+                max_torque: maxTorque.max(0.0),
+            };
+            self.1.forces.set_pose_tracking(handle, Some(tracking));
+        }
+    }
+
+    /// Sets the target pose of the PD controller of this rigid-body, if it has one.
+    pub fn rbSetPoseTrackingTarget(
+        &mut self,
+        handle: FlatHandle,
+        targetTranslation: &RawVector,
+        targetRotation: &RawRotation,
+    ) {
+        if let Some(tracking) = self.1.forces.pose_tracking_mut(utils::body_handle(handle)) {
+            tracking.target = Isometry::from_parts(targetTranslation.0.into(), targetRotation.0);
+        }
+    }
+
+    /// Removes the PD controller of this rigid-body, if it has one.
+    pub fn rbRemovePoseTracking(&mut self, handle: FlatHandle) {
+        self.1
+            .forces
+            .set_pose_tracking(utils::body_handle(handle), None);
+    }
+
+    /// Is this rigid-body driven toward a target pose?
+    pub fn rbHasPoseTracking(&self, handle: FlatHandle) -> bool {
+        self.1
+            .forces
+            .pose_tracking(utils::body_handle(handle))
+            .is_some()
+    }
+
+    /// Pulls a point of this rigid-body toward a world-space target point with a
+    /// spring-damper, e.g., to grab the rigid-body with the mouse or an editor gizmo.
+    ///
+    /// The stiffness and damping are accelerations of the grabbed point (per unit of distance
+    /// to the target, and per unit of velocity), so they don’t depend on the mass of the
+    /// rigid-body. The resulting force is clamped to `maxForce`. The target can then be updated
+    /// with `rbSetDragTarget`. Replaces the previous drag constraint of the rigid-body, if any.
+    pub fn rbSetDragConstraint(
+        &mut self,
+        handle: FlatHandle,
+        localPoint: &RawVector,
+        target: &RawVector,
+        stiffness: f64,
+        damping: f64,
+        maxForce: f64,
+    ) {
+        let handle = utils::body_handle(handle);
+        if self.0.get(handle).is_some() {
+            let constraint = DragConstraint {
+                local_point: localPoint.0.into(),
+                target: target.0.into(),
+                stiffness: stiffness.max(0.0),
+                damping: damping.max(0.0),
+                max_force: maxForce.max(0.0),
+            };
+            self.1.forces.set_drag_constraint(handle, Some(constraint));
+        }
+    }
+
+    /// Sets the world-space target point of the drag constraint of this rigid-body, if it has
+    /// one.
+    pub fn rbSetDragTarget(&mut self, handle: FlatHandle, target: &RawVector) {
+        if let Some(constraint) = self
+            .1
+            .forces
+            .drag_constraint_mut(utils::body_handle(handle))
+        {
+            constraint.target = target.0.into();
+        }
+    }
+
+    /// Removes the drag constraint of this rigid-body, if it has one.
+    pub fn rbRemoveDragConstraint(&mut self, handle: FlatHandle) {
+        self.1
+            .forces
+            .set_drag_constraint(utils::body_handle(handle), None);
+    }
+
+    /// Does this rigid-body have a drag constraint?
+    pub fn rbHasDragConstraint(&self, handle: FlatHandle) -> bool {
+        self.1
+            .forces
+            .drag_constraint(utils::body_handle(handle))
+            .is_some()
+    }
+
     pub fn rbSetEnabled(&mut self, handle: FlatHandle, enabled: bool) {
         self.map_mut(handle, |rb| rb.set_enabled(enabled))
     }
@@ -617,6 +1025,31 @@ impl RawRigidBodySet {
         })
     }
 
+    /// Adds a force, expressed in the local frame of this rigid-body, at its center-of-mass.
+    ///
+    /// # Parameters
+    /// - `force`: the local-space force to apply on the rigid-body.
+    /// - `wakeUp`: should the rigid-body be automatically woken-up?
+    pub fn rbAddForceLocal(&mut self, handle: FlatHandle, force: &RawVector, wakeUp: bool) {
+        self.map_mut(handle, |rb| {
+            let force = rb.position().rotation * force.0;
+            rb.add_force(force, wakeUp);
+        })
+    }
+
+    /// Applies an impulse, expressed in the local frame of this rigid-body, at its
+    /// center-of-mass.
+    ///
+    /// # Parameters
+    /// - `impulse`: the local-space impulse to apply on the rigid-body.
+    /// - `wakeUp`: should the rigid-body be automatically woken-up?
+    pub fn rbApplyImpulseLocal(&mut self, handle: FlatHandle, impulse: &RawVector, wakeUp: bool) {
+        self.map_mut(handle, |rb| {
+            let impulse = rb.position().rotation * impulse.0;
+            rb.apply_impulse(impulse, wakeUp);
+        })
+    }
+
     /// Adds a torque at the center-of-mass of this rigid-body.
     ///
     /// # Parameters
@@ -670,6 +1103,19 @@ impl RawRigidBodySet {
         })
     }
 
+    /// Adds a torque, expressed in the local frame of this rigid-body, at its center-of-mass.
+    ///
+    /// # Parameters
+    /// - `torque`: the local-space torque to apply on the rigid-body.
+    /// - `wakeUp`: should the rigid-body be automatically woken-up?
+    #[cfg(feature = "dim3")]
+    pub fn rbAddTorqueLocal(&mut self, handle: FlatHandle, torque: &RawVector, wakeUp: bool) {
+        self.map_mut(handle, |rb| {
+            let torque = rb.position().rotation * torque.0;
+            rb.add_torque(torque, wakeUp);
+        })
+    }
+
     /// Adds a force at the given world-space point of this rigid-body.
     ///
     /// # Parameters
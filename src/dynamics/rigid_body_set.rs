@@ -1,4 +1,7 @@
-use crate::dynamics::{RawImpulseJointSet, RawIslandManager, RawMultibodyJointSet};
+use crate::dynamics::{
+    BodyForces, RawImpulseJointSet, RawIslandManager, RawMultibodyJointSet, RemotePoses,
+    SimulationLayers,
+};
 use crate::geometry::RawColliderSet;
 use crate::math::{RawRotation, RawVector};
 use crate::utils::{self, FlatHandle};
@@ -35,8 +38,18 @@ impl Into<RawRigidBodyType> for RigidBodyType {
     }
 }
 
+/// The state attached to the rigid-bodies by these bindings, on top of the rigid-body set of
+/// Rapier.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct RigidBodyExt {
+    pub forces: BodyForces,
+    pub simulation_layers: SimulationLayers,
+    pub remote_poses: RemotePoses,
+}
+
 #[wasm_bindgen]
-pub struct RawRigidBodySet(pub(crate) RigidBodySet);
+pub struct RawRigidBodySet(pub(crate) RigidBodySet, pub(crate) RigidBodyExt);
 
 impl RawRigidBodySet {
     pub(crate) fn map<T>(&self, handle: FlatHandle, f: impl FnOnce(&RigidBody) -> T) -> T {
@@ -62,7 +75,7 @@ impl RawRigidBodySet {
 impl RawRigidBodySet {
     #[wasm_bindgen(constructor)]
     pub fn new() -> Self {
-        RawRigidBodySet(RigidBodySet::new())
+        RawRigidBodySet(RigidBodySet::new(), RigidBodyExt::default())
     }
 
     #[cfg(feature = "dim3")]
@@ -199,6 +212,9 @@ impl RawRigidBodySet {
         articulations: &mut RawMultibodyJointSet,
     ) {
         let handle = utils::body_handle(handle);
+        self.1.forces.remove(handle);
+        self.1.simulation_layers.remove(handle);
+        self.1.remote_poses.remove(handle);
         self.0.remove(
             handle,
             &mut islands.0,
@@ -219,6 +235,34 @@ impl RawRigidBodySet {
         self.0.get(utils::body_handle(handle)).is_some()
     }
 
+    /// The clock the timestamps of the remote poses are compared to, advanced at each step.
+    pub fn remotePoseTime(&self) -> f64 {
+        self.1.remote_poses.time
+    }
+
+    pub fn setRemotePoseTime(&mut self, time: f64) {
+        self.1.remote_poses.time = time;
+    }
+
+    /// The delay between the clock and the time the remote poses are interpolated at.
+    pub fn remotePoseDelay(&self) -> f64 {
+        self.1.remote_poses.delay
+    }
+
+    pub fn setRemotePoseDelay(&mut self, delay: f64) {
+        self.1.remote_poses.delay = delay.max(0.0);
+    }
+
+    /// The maximum duration the motion of a rigid-body is extrapolated for, past its last
+    /// remote pose.
+    pub fn maxRemotePoseExtrapolation(&self) -> f64 {
+        self.1.remote_poses.max_extrapolation
+    }
+
+    pub fn setMaxRemotePoseExtrapolation(&mut self, duration: f64) {
+        self.1.remote_poses.max_extrapolation = duration.max(0.0);
+    }
+
     /// Applies the given JavaScript function to the integer handle of each rigid-body managed by this set.
     ///
     /// # Parameters
@@ -230,6 +274,25 @@ impl RawRigidBodySet {
         }
     }
 
+    /// The handles of the dynamic rigid-bodies that are awake and were moving at the end of the
+    /// last step.
+    ///
+    /// This lets renderers and network layers iterate only on the rigid-bodies that changed
+    /// instead of the whole set.
+    pub fn rbActiveDynamicBodies(&self, islands: &RawIslandManager) -> Vec<FlatHandle> {
+        islands
+            .0
+            .active_bodies()
+            .iter()
+            .filter(|handle| {
+                self.0
+                    .get(**handle)
+                    .is_some_and(|rb| rb.is_dynamic() && rb.is_moving())
+            })
+            .map(|handle| utils::flat_handle(handle.0))
+            .collect()
+    }
+
     pub fn propagateModifiedBodyPositionsToColliders(&mut self, colliders: &mut RawColliderSet) {
         self.0
             .propagate_modified_body_positions_to_colliders(&mut colliders.0);
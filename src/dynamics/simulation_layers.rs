@@ -0,0 +1,145 @@
+use rapier::dynamics::{LockedAxes, RigidBodyHandle, RigidBodySet, RigidBodyType};
+use rapier::math::{AngVector, Isometry, Real, Vector};
+use std::collections::HashMap;
+
+/// The simulation layers of the rigid-bodies without explicit layers.
+pub(crate) const DEFAULT_SIMULATION_LAYERS: u32 = 1;
+
+/// The state of a rigid-body frozen for the duration of a step, restored after it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct FrozenBody {
+    handle: RigidBodyHandle,
+    linvel: Vector<Real>,
+    angvel: AngVector<Real>,
+    locked_axes: LockedAxes,
+    next_position: Isometry<Real>,
+    sleeping: bool,
+}
+
+/// The rigid-bodies frozen during a step because none of their simulation layers is active.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct FrozenBodies(Vec<FrozenBody>);
+
+/// The simulation layers of the rigid-bodies, as bit masks.
+///
+/// A rigid-body is only simulated if any of its layers is active. The other rigid-bodies are
+/// frozen in place during the step: they keep their velocities for when their layers are
+/// reactivated, and act as static obstacles for the simulated rigid-bodies.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct SimulationLayers {
+    layers: HashMap<RigidBodyHandle, u32>,
+}
+
+impl SimulationLayers {
+    pub fn layers(&self, handle: RigidBodyHandle) -> u32 {
+        self.layers
+            .get(&handle)
+            .copied()
+            .unwrap_or(DEFAULT_SIMULATION_LAYERS)
+    }
+
+    pub fn set_layers(&mut self, handle: RigidBodyHandle, layers: u32) {
+        if layers == DEFAULT_SIMULATION_LAYERS {
+            let _ = self.layers.remove(&handle);
+        } else {
+            let _ = self.layers.insert(handle, layers);
+        }
+    }
+
+    pub fn remove(&mut self, handle: RigidBodyHandle) {
+        let _ = self.layers.remove(&handle);
+    }
+
+    /// Freezes the rigid-bodies without any active layer, until `unfreeze` is called.
+    ///
+    /// The dynamic rigid-bodies have all their axes locked and their velocities set to zero,
+    /// the velocity-based kinematic rigid-bodies have their velocities set to zero, and the
+    /// position-based kinematic rigid-bodies have their next position set to their current
+    /// position.
+    pub fn freeze(&mut self, active_layers: u32, bodies: &mut RigidBodySet) -> FrozenBodies {
+        // Forget the rigid-bodies removed without going through the bindings.
+        self.layers
+            .retain(|handle, _| bodies.get(*handle).is_some());
+
+        if active_layers == u32::MAX {
+            return FrozenBodies::default();
+        }
+
+        // Only the rigid-bodies with explicit layers can be frozen if the default layers are
+        // active, avoid going through all the rigid-bodies in that case.
+        let candidates: Vec<_> = if DEFAULT_SIMULATION_LAYERS & active_layers != 0 {
+            self.layers.keys().copied().collect()
+        } else {
+            bodies.iter().map(|(handle, _)| handle).collect()
+        };
+
+        let mut frozen = vec![];
+        for handle in candidates {
+            let rb = match bodies.get_mut(handle) {
+                Some(rb) if rb.body_type() != RigidBodyType::Fixed => rb,
+                _ => continue,
+            };
+
+            if self.layers(handle) & active_layers != 0 {
+                continue;
+            }
+
+            frozen.push(FrozenBody {
+                handle,
+                linvel: *rb.linvel(),
+                angvel: rb.vels().angvel,
+                locked_axes: rb.locked_axes(),
+                next_position: *rb.next_position(),
+                sleeping: rb.is_sleeping(),
+            });
+
+            match rb.body_type() {
+                RigidBodyType::Dynamic => {
+                    rb.set_locked_axes(LockedAxes::all(), false);
+                    rb.set_vels(Default::default(), false);
+                }
+                RigidBodyType::KinematicVelocityBased => {
+                    rb.set_vels(Default::default(), false);
+                }
+                RigidBodyType::KinematicPositionBased => {
+                    let position = *rb.position();
+                    rb.set_next_kinematic_position(position);
+                }
+                RigidBodyType::Fixed => {}
+            }
+        }
+
+        FrozenBodies(frozen)
+    }
+
+    /// Restores the state of the rigid-bodies frozen by `freeze`.
+    pub fn unfreeze(frozen: FrozenBodies, bodies: &mut RigidBodySet) {
+        for body in frozen.0 {
+            let rb = match bodies.get_mut(body.handle) {
+                Some(rb) => rb,
+                None => continue,
+            };
+
+            match rb.body_type() {
+                RigidBodyType::Dynamic => {
+                    rb.set_locked_axes(body.locked_axes, false);
+                    rb.set_linvel(body.linvel, false);
+                    rb.set_angvel(body.angvel, false);
+                }
+                RigidBodyType::KinematicVelocityBased => {
+                    rb.set_linvel(body.linvel, false);
+                    rb.set_angvel(body.angvel, false);
+                }
+                RigidBodyType::KinematicPositionBased => {
+                    rb.set_next_kinematic_position(body.next_position);
+                }
+                RigidBodyType::Fixed => {}
+            }
+
+            // A frozen rigid-body doesn’t move, so it may have been put to sleep.
+            if !body.sleeping && rb.is_sleeping() {
+                rb.wake_up(true);
+            }
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use crate::dynamics::{RawImpulseJointSet, RawRigidBodySet};
+use crate::geometry::RawColliderSet;
+use crate::utils::{self, FlatHandle};
+use rapier::dynamics::{FixedJointBuilder, RigidBodyHandle};
+use rapier::geometry::ColliderHandle;
+use rapier::math::{Isometry, Point, Real};
+use wasm_bindgen::prelude::*;
+
+/// The record of two rigid-bodies merged by `mergeBodies`, needed to separate them again.
+#[wasm_bindgen]
+pub struct RawBodyMerge {
+    body1: RigidBodyHandle,
+    body2: RigidBodyHandle,
+    /// The pose of the second rigid-body relative to the first one when they were merged.
+    relative_pose: Isometry<Real>,
+    /// The colliders moved from the second rigid-body, with their original position relative
+    /// to it.
+    colliders: Vec<(ColliderHandle, Isometry<Real>)>,
+}
+
+#[wasm_bindgen]
+impl RawBodyMerge {
+    /// The rigid-body the colliders were moved to.
+    pub fn body1(&self) -> FlatHandle {
+        utils::flat_handle(self.body1.0)
+    }
+
+    /// The rigid-body the colliders were moved from, disabled until the bodies are separated.
+    pub fn body2(&self) -> FlatHandle {
+        utils::flat_handle(self.body2.0)
+    }
+
+    /// The handles of the colliders moved from the second rigid-body to the first one.
+    pub fn colliders(&self) -> Vec<FlatHandle> {
+        self.colliders
+            .iter()
+            .map(|(handle, _)| utils::flat_handle(handle.0))
+            .collect()
+    }
+}
+
+#[wasm_bindgen]
+impl RawImpulseJointSet {
+    /// Attaches two rigid-bodies with a fixed joint at their current relative pose.
+    ///
+    /// The joint frame is located at the origin of the second rigid-body. Returns the handle
+    /// of the joint, or `undefined` if one of the rigid-bodies doesn’t exist.
+    pub fn weld(
+        &mut self,
+        bodies: &RawRigidBodySet,
+        handle1: FlatHandle,
+        handle2: FlatHandle,
+        wakeUp: bool,
+    ) -> Option<FlatHandle> {
+        let handle1 = utils::body_handle(handle1);
+        let handle2 = utils::body_handle(handle2);
+        let rb1 = bodies.0.get(handle1)?;
+        let rb2 = bodies.0.get(handle2)?;
+        let joint = FixedJointBuilder::new()
+            .local_frame1(rb1.position().inv_mul(rb2.position()))
+            .local_frame2(Isometry::identity());
+
+        Some(utils::flat_handle(
+            self.0.insert(handle1, handle2, joint, wakeUp).0,
+        ))
+    }
+}
+
+/// Merges a rigid-body into another one, by moving its colliders to the other rigid-body at
+/// their current pose, and disabling it.
+///
+/// Unlike a fixed joint, this can’t drift or break apart and doesn’t cost any solver effort.
+/// The mass properties of the first rigid-body are updated from its new colliders at the next
+/// step. Its velocity is kept. Returns `undefined` if both handles are the same, or one of the
+/// rigid-bodies doesn’t exist.
+#[wasm_bindgen]
+pub fn mergeBodies(
+    bodies: &mut RawRigidBodySet,
+    colliders: &mut RawColliderSet,
+    handle1: FlatHandle,
+    handle2: FlatHandle,
+) -> Option<RawBodyMerge> {
+    let body1 = utils::body_handle(handle1);
+    let body2 = utils::body_handle(handle2);
+    if body1 == body2 {
+        return None;
+    }
+
+    let pose1 = *bodies.0.get(body1)?.position();
+    let rb2 = bodies.0.get_mut(body2)?;
+    let relative_pose = pose1.inv_mul(rb2.position());
+    let collider_handles = rb2.colliders().to_vec();
+    rb2.set_enabled(false);
+
+    let mut merged = vec![];
+    for handle in collider_handles {
+        if let Some(co) = colliders.0.get(handle) {
+            let pos_wrt_parent = co
+                .position_wrt_parent()
+                .copied()
+                .unwrap_or_else(Isometry::identity);
+            let new_pos_wrt_parent = pose1.inv_mul(co.position());
+            colliders.0.set_parent(handle, Some(body1), &mut bodies.0);
+            if let Some(co) = colliders.0.get_mut(handle) {
+                co.set_position_wrt_parent(new_pos_wrt_parent);
+            }
+            merged.push((handle, pos_wrt_parent));
+        }
+    }
+
+    Some(RawBodyMerge {
+        body1,
+        body2,
+        relative_pose,
+        colliders: merged,
+    })
+}
+
+/// Separates two rigid-bodies merged by `mergeBodies`.
+///
+/// The colliders that still exist and are still attached to the first rigid-body are moved
+/// back to the second one, which is re-enabled at its pose relative to the first rigid-body
+/// when they were merged, with the velocity of the first rigid-body at that pose.
+#[wasm_bindgen]
+pub fn unmergeBodies(
+    merge: &RawBodyMerge,
+    bodies: &mut RawRigidBodySet,
+    colliders: &mut RawColliderSet,
+) {
+    let (pose2, linvel2, angvel2) = match bodies.0.get(merge.body1) {
+        Some(rb1) => {
+            let pose2 = rb1.position() * merge.relative_pose;
+            let local_com2 = bodies
+                .0
+                .get(merge.body2)
+                .map_or_else(Point::origin, |rb2| *rb2.local_center_of_mass());
+            let com2 = pose2 * local_com2;
+            (pose2, rb1.velocity_at_point(&com2), rb1.vels().angvel)
+        }
+        None => return,
+    };
+
+    if let Some(rb2) = bodies.0.get_mut(merge.body2) {
+        rb2.set_position(pose2, false);
+        rb2.set_linvel(linvel2, false);
+        rb2.set_angvel(angvel2, false);
+        rb2.set_enabled(true);
+    } else {
+        return;
+    }
+
+    for (handle, pos_wrt_parent) in &merge.colliders {
+        let attached = colliders
+            .0
+            .get(*handle)
+            .is_some_and(|co| co.parent() == Some(merge.body1));
+        if attached {
+            colliders
+                .0
+                .set_parent(*handle, Some(merge.body2), &mut bodies.0);
+            if let Some(co) = colliders.0.get_mut(*handle) {
+                co.set_position_wrt_parent(*pos_wrt_parent);
+            }
+        }
+    }
+
+    if let Some(rb2) = bodies.0.get_mut(merge.body2) {
+        rb2.wake_up(true);
+    }
+}
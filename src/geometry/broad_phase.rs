@@ -1,18 +1,65 @@
-use crate::dynamics::RawRigidBodySet;
+use crate::dynamics::{RawIntegrationParameters, RawRigidBodySet};
 use crate::geometry::{
-    RawColliderSet, RawColliderShapeCastHit, RawNarrowPhase, RawPointColliderProjection,
-    RawRayColliderHit, RawRayColliderIntersection, RawShape,
+    RawColliderSet, RawColliderShapeCastHit, RawColliderShapeContact, RawNarrowPhase,
+    RawPointColliderProjection, RawRayColliderHit, RawRayColliderIntersection, RawShape,
+    RawShapeDepenetration, TagFilter,
 };
 use crate::math::{RawRotation, RawVector};
 use crate::utils::{self, FlatHandle};
 use rapier::geometry::DefaultBroadPhase;
-use rapier::geometry::{Aabb, ColliderHandle, Ray};
-use rapier::math::{Isometry, Point};
-use rapier::parry::query::ShapeCastOptions;
+use rapier::geometry::{Aabb, Collider, ColliderHandle, ColliderSet, Ray, RayIntersection};
+use rapier::math::{AngVector, Isometry, Point, Real, Vector, DIM};
+use rapier::parry::bounding_volume::BoundingVolume;
+use rapier::parry::query::{self, NonlinearRigidMotion, PointQuery, ShapeCastOptions};
 use rapier::pipeline::{QueryFilter, QueryFilterFlags};
 use rapier::prelude::FeatureId;
 use wasm_bindgen::prelude::*;
 
+/// The number of values written for each hit by `pick` and `pickAll`: the collider handle,
+/// the parent handle (NaN if none), the hit point, the normal and the distance.
+const PICK_HIT_LEN: usize = 3 + 2 * DIM;
+
+/// The ray used for picking, with a normalized direction so its time-of-impact is a distance.
+fn picking_ray(origin: &RawVector, dir: &RawVector) -> Option<Ray> {
+    let dir = dir.0.try_normalize(Real::EPSILON)?;
+    Some(Ray::new(origin.0.into(), dir))
+}
+
+/// Like `utils::with_filter`, but also excludes the disabled colliders, and the colliders
+/// rejected by the tag filter.
+///
+/// Disabled colliders are only removed from the broad-phase by the next simulation step, so
+/// this ensures that disabling a collider is reflected by the scene queries right away.
+fn with_query_filter<T>(
+    filter: &js_sys::Function,
+    tags: TagFilter,
+    f: impl FnOnce(Option<&dyn Fn(ColliderHandle, &Collider) -> bool>) -> T,
+) -> T {
+    utils::with_filter(filter, |predicate| {
+        let enabled = |handle: ColliderHandle, co: &Collider| {
+            co.is_enabled()
+                && tags.matches(handle)
+                && predicate.map_or(true, |predicate| predicate(handle, co))
+        };
+        f(Some(&enabled))
+    })
+}
+
+fn write_pick_hit(
+    out: &mut Vec<f64>,
+    colliders: &ColliderSet,
+    ray: &Ray,
+    handle: ColliderHandle,
+    inter: &RayIntersection,
+) {
+    let parent = colliders.get(handle).and_then(|co| co.parent());
+    out.push(utils::flat_handle(handle.0));
+    out.push(parent.map(|h| utils::flat_handle(h.0)).unwrap_or(f64::NAN));
+    out.extend_from_slice(ray.point_at(inter.time_of_impact).coords.as_slice());
+    out.extend_from_slice(inter.normal.as_slice());
+    out.push(inter.time_of_impact);
+}
+
 #[wasm_bindgen]
 pub struct RawBroadPhase(pub(crate) DefaultBroadPhase);
 
@@ -37,8 +84,14 @@ impl RawBroadPhase {
         filter_exclude_collider: Option<FlatHandle>,
         filter_exclude_rigid_body: Option<FlatHandle>,
         filter_predicate: &js_sys::Function,
+        filter_require_tags: u32,
+        filter_exclude_tags: u32,
     ) -> Option<RawRayColliderHit> {
-        let (handle, timeOfImpact) = utils::with_filter(filter_predicate, |predicate| {
+        let tags = colliders
+            .1
+            .tags
+            .filter(filter_require_tags, filter_exclude_tags);
+        let (handle, timeOfImpact) = with_query_filter(filter_predicate, tags, |predicate| {
             let query_filter = QueryFilter {
                 flags: QueryFilterFlags::from_bits(filter_flags)
                     .unwrap_or(QueryFilterFlags::empty()),
@@ -61,6 +114,7 @@ impl RawBroadPhase {
 
         Some(RawRayColliderHit {
             handle,
+            parent: colliders.0.get(handle).and_then(|co| co.parent()),
             timeOfImpact,
         })
     }
@@ -79,8 +133,14 @@ impl RawBroadPhase {
         filter_exclude_collider: Option<FlatHandle>,
         filter_exclude_rigid_body: Option<FlatHandle>,
         filter_predicate: &js_sys::Function,
+        filter_require_tags: u32,
+        filter_exclude_tags: u32,
     ) -> Option<RawRayColliderIntersection> {
-        let (handle, inter) = utils::with_filter(filter_predicate, |predicate| {
+        let tags = colliders
+            .1
+            .tags
+            .filter(filter_require_tags, filter_exclude_tags);
+        let (handle, inter) = with_query_filter(filter_predicate, tags, |predicate| {
             let query_filter = QueryFilter {
                 flags: QueryFilterFlags::from_bits(filter_flags)
                     .unwrap_or(QueryFilterFlags::empty()),
@@ -101,7 +161,11 @@ impl RawBroadPhase {
             query_pipeline.cast_ray_and_get_normal(&ray, maxToi, solid)
         })?;
 
-        Some(RawRayColliderIntersection { handle, inter })
+        Some(RawRayColliderIntersection {
+            handle,
+            parent: colliders.0.get(handle).and_then(|co| co.parent()),
+            inter,
+        })
     }
 
     // The callback is of type (RawRayColliderIntersection) => bool
@@ -120,8 +184,14 @@ impl RawBroadPhase {
         filter_exclude_collider: Option<FlatHandle>,
         filter_exclude_rigid_body: Option<FlatHandle>,
         filter_predicate: &js_sys::Function,
+        filter_require_tags: u32,
+        filter_exclude_tags: u32,
     ) {
-        utils::with_filter(filter_predicate, |predicate| {
+        let tags = colliders
+            .1
+            .tags
+            .filter(filter_require_tags, filter_exclude_tags);
+        with_query_filter(filter_predicate, tags, |predicate| {
             let query_filter = QueryFilter {
                 flags: QueryFilterFlags::from_bits(filter_flags)
                     .unwrap_or(QueryFilterFlags::empty()),
@@ -133,7 +203,11 @@ impl RawBroadPhase {
 
             let ray = Ray::new(rayOrig.0.into(), rayDir.0);
             let rcallback = |handle, inter| {
-                let result = RawRayColliderIntersection { handle, inter };
+                let result = RawRayColliderIntersection {
+                    handle,
+                    parent: colliders.0.get(handle).and_then(|co| co.parent()),
+                    inter,
+                };
                 match callback.call1(&JsValue::null(), &JsValue::from(result)) {
                     Err(_) => true,
                     Ok(val) => val.as_bool().unwrap_or(true),
@@ -155,6 +229,125 @@ impl RawBroadPhase {
         });
     }
 
+    /// Finds the closest collider hit by a ray, e.g., under the mouse cursor.
+    ///
+    /// The direction of the ray doesn't need to be normalized: `maxDistance` and the returned
+    /// distance are measured along the normalized direction. Returns an empty array if nothing
+    /// was hit, or the collider handle, the handle of its parent (NaN if none), the hit
+    /// point, the normal and the distance of the hit.
+    pub fn pick(
+        &self,
+        narrow_phase: &RawNarrowPhase,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        rayOrig: &RawVector,
+        rayDir: &RawVector,
+        maxDistance: f64,
+        filter_flags: u32,
+        filter_groups: Option<u32>,
+        filter_exclude_collider: Option<FlatHandle>,
+        filter_exclude_rigid_body: Option<FlatHandle>,
+        filter_predicate: &js_sys::Function,
+        filter_require_tags: u32,
+        filter_exclude_tags: u32,
+    ) -> Vec<f64> {
+        let mut result = Vec::with_capacity(PICK_HIT_LEN);
+        let ray = match picking_ray(rayOrig, rayDir) {
+            Some(ray) => ray,
+            None => return result,
+        };
+
+        let tags = colliders
+            .1
+            .tags
+            .filter(filter_require_tags, filter_exclude_tags);
+        let hit = with_query_filter(filter_predicate, tags, |predicate| {
+            let query_filter = QueryFilter {
+                flags: QueryFilterFlags::from_bits(filter_flags)
+                    .unwrap_or(QueryFilterFlags::empty()),
+                groups: filter_groups.map(crate::geometry::unpack_interaction_groups),
+                exclude_collider: filter_exclude_collider.map(crate::utils::collider_handle),
+                exclude_rigid_body: filter_exclude_rigid_body.map(crate::utils::body_handle),
+                predicate,
+            };
+
+            let query_pipeline = self.0.as_query_pipeline(
+                narrow_phase.0.query_dispatcher(),
+                &bodies.0,
+                &colliders.0,
+                query_filter,
+            );
+
+            query_pipeline.cast_ray_and_get_normal(&ray, maxDistance, true)
+        });
+
+        if let Some((handle, inter)) = hit {
+            write_pick_hit(&mut result, &colliders.0, &ray, handle, &inter);
+        }
+
+        result
+    }
+
+    /// Finds all the colliders hit by a ray, sorted by increasing distance.
+    ///
+    /// Same as `pick`, except that the returned array contains the data of every hit, one
+    /// after the other.
+    pub fn pickAll(
+        &self,
+        narrow_phase: &RawNarrowPhase,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        rayOrig: &RawVector,
+        rayDir: &RawVector,
+        maxDistance: f64,
+        filter_flags: u32,
+        filter_groups: Option<u32>,
+        filter_exclude_collider: Option<FlatHandle>,
+        filter_exclude_rigid_body: Option<FlatHandle>,
+        filter_predicate: &js_sys::Function,
+        filter_require_tags: u32,
+        filter_exclude_tags: u32,
+    ) -> Vec<f64> {
+        let ray = match picking_ray(rayOrig, rayDir) {
+            Some(ray) => ray,
+            None => return Vec::new(),
+        };
+
+        let tags = colliders
+            .1
+            .tags
+            .filter(filter_require_tags, filter_exclude_tags);
+        let mut hits = with_query_filter(filter_predicate, tags, |predicate| {
+            let query_filter = QueryFilter {
+                flags: QueryFilterFlags::from_bits(filter_flags)
+                    .unwrap_or(QueryFilterFlags::empty()),
+                groups: filter_groups.map(crate::geometry::unpack_interaction_groups),
+                exclude_collider: filter_exclude_collider.map(crate::utils::collider_handle),
+                exclude_rigid_body: filter_exclude_rigid_body.map(crate::utils::body_handle),
+                predicate,
+            };
+
+            let query_pipeline = self.0.as_query_pipeline(
+                narrow_phase.0.query_dispatcher(),
+                &bodies.0,
+                &colliders.0,
+                query_filter,
+            );
+
+            query_pipeline
+                .intersect_ray(ray, maxDistance, true)
+                .map(|(handle, _, inter)| (handle, inter))
+                .collect::<Vec<_>>()
+        });
+        hits.sort_by(|a, b| a.1.time_of_impact.total_cmp(&b.1.time_of_impact));
+
+        let mut result = Vec::with_capacity(hits.len() * PICK_HIT_LEN);
+        for (handle, inter) in &hits {
+            write_pick_hit(&mut result, &colliders.0, &ray, *handle, inter);
+        }
+        result
+    }
+
     pub fn intersectionWithShape(
         &self,
         narrow_phase: &RawNarrowPhase,
@@ -168,8 +361,14 @@ impl RawBroadPhase {
         filter_exclude_collider: Option<FlatHandle>,
         filter_exclude_rigid_body: Option<FlatHandle>,
         filter_predicate: &js_sys::Function,
+        filter_require_tags: u32,
+        filter_exclude_tags: u32,
     ) -> Option<FlatHandle> {
-        utils::with_filter(filter_predicate, |predicate| {
+        let tags = colliders
+            .1
+            .tags
+            .filter(filter_require_tags, filter_exclude_tags);
+        with_query_filter(filter_predicate, tags, |predicate| {
             let query_filter = QueryFilter {
                 flags: QueryFilterFlags::from_bits(filter_flags)
                     .unwrap_or(QueryFilterFlags::empty()),
@@ -210,8 +409,14 @@ impl RawBroadPhase {
         filter_exclude_collider: Option<FlatHandle>,
         filter_exclude_rigid_body: Option<FlatHandle>,
         filter_predicate: &js_sys::Function,
+        filter_require_tags: u32,
+        filter_exclude_tags: u32,
     ) -> Option<RawPointColliderProjection> {
-        utils::with_filter(filter_predicate, |predicate| {
+        let tags = colliders
+            .1
+            .tags
+            .filter(filter_require_tags, filter_exclude_tags);
+        with_query_filter(filter_predicate, tags, |predicate| {
             let query_filter = QueryFilter {
                 flags: QueryFilterFlags::from_bits(filter_flags)
                     .unwrap_or(QueryFilterFlags::empty()),
@@ -249,8 +454,14 @@ impl RawBroadPhase {
         filter_exclude_collider: Option<FlatHandle>,
         filter_exclude_rigid_body: Option<FlatHandle>,
         filter_predicate: &js_sys::Function,
+        filter_require_tags: u32,
+        filter_exclude_tags: u32,
     ) -> Option<RawPointColliderProjection> {
-        utils::with_filter(filter_predicate, |predicate| {
+        let tags = colliders
+            .1
+            .tags
+            .filter(filter_require_tags, filter_exclude_tags);
+        with_query_filter(filter_predicate, tags, |predicate| {
             let query_filter = QueryFilter {
                 flags: QueryFilterFlags::from_bits(filter_flags)
                     .unwrap_or(QueryFilterFlags::empty()),
@@ -277,6 +488,141 @@ impl RawBroadPhase {
         })
     }
 
+    /// Finds the `count` colliders closest to the given point, and reports their projections
+    /// by increasing distance.
+    ///
+    /// The callback is called with a `RawPointColliderProjection` for each collider.
+    pub fn nearestColliders(
+        &self,
+        narrow_phase: &RawNarrowPhase,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        point: &RawVector,
+        count: u32,
+        maxDist: f64,
+        solid: bool,
+        callback: &js_sys::Function,
+        filter_flags: u32,
+        filter_groups: Option<u32>,
+        filter_exclude_collider: Option<FlatHandle>,
+        filter_exclude_rigid_body: Option<FlatHandle>,
+        filter_predicate: &js_sys::Function,
+        filter_require_tags: u32,
+        filter_exclude_tags: u32,
+    ) {
+        let tags = colliders
+            .1
+            .tags
+            .filter(filter_require_tags, filter_exclude_tags);
+        with_query_filter(filter_predicate, tags, |predicate| {
+            let query_filter = QueryFilter {
+                flags: QueryFilterFlags::from_bits(filter_flags)
+                    .unwrap_or(QueryFilterFlags::empty()),
+                groups: filter_groups.map(crate::geometry::unpack_interaction_groups),
+                exclude_collider: filter_exclude_collider.map(crate::utils::collider_handle),
+                exclude_rigid_body: filter_exclude_rigid_body.map(crate::utils::body_handle),
+                predicate,
+            };
+
+            let query_pipeline = self.0.as_query_pipeline(
+                narrow_phase.0.query_dispatcher(),
+                &bodies.0,
+                &colliders.0,
+                query_filter,
+            );
+
+            let count = count as usize;
+            let pt = Point::from(point.0);
+            let bounds = Aabb::new(pt - Vector::repeat(maxDist), pt + Vector::repeat(maxDist));
+            let mut nearest: Vec<_> = query_pipeline
+                .intersect_aabb_conservative(bounds)
+                .filter_map(|(handle, co)| {
+                    let proj = co.shape().project_point(co.position(), &pt, solid);
+                    let dist = na::distance(&proj.point, &pt);
+                    (dist <= maxDist).then_some((dist, handle, proj))
+                })
+                .collect();
+
+            if nearest.len() > count {
+                let _ = nearest.select_nth_unstable_by(count, |a, b| a.0.total_cmp(&b.0));
+                nearest.truncate(count);
+            }
+            nearest.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            for (_, handle, proj) in nearest {
+                let result = RawPointColliderProjection {
+                    handle,
+                    proj,
+                    feature: FeatureId::Unknown,
+                };
+                match callback.call1(&JsValue::null(), &JsValue::from(result)) {
+                    Err(_) => {}
+                    Ok(val) => {
+                        if !val.as_bool().unwrap_or(true) {
+                            break;
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    /// Finds the collider closest to the given shape, within `maxDist`.
+    ///
+    /// The contact points and normals of the result are in world-space, the first ones being
+    /// on the given shape. The distance is negative if the shape penetrates the collider.
+    pub fn projectShape(
+        &self,
+        narrow_phase: &RawNarrowPhase,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        shapePos: &RawVector,
+        shapeRot: &RawRotation,
+        shape: &RawShape,
+        maxDist: f64,
+        filter_flags: u32,
+        filter_groups: Option<u32>,
+        filter_exclude_collider: Option<FlatHandle>,
+        filter_exclude_rigid_body: Option<FlatHandle>,
+        filter_predicate: &js_sys::Function,
+        filter_require_tags: u32,
+        filter_exclude_tags: u32,
+    ) -> Option<RawColliderShapeContact> {
+        let tags = colliders
+            .1
+            .tags
+            .filter(filter_require_tags, filter_exclude_tags);
+        with_query_filter(filter_predicate, tags, |predicate| {
+            let query_filter = QueryFilter {
+                flags: QueryFilterFlags::from_bits(filter_flags)
+                    .unwrap_or(QueryFilterFlags::empty()),
+                groups: filter_groups.map(crate::geometry::unpack_interaction_groups),
+                exclude_collider: filter_exclude_collider.map(crate::utils::collider_handle),
+                exclude_rigid_body: filter_exclude_rigid_body.map(crate::utils::body_handle),
+                predicate,
+            };
+
+            let query_pipeline = self.0.as_query_pipeline(
+                narrow_phase.0.query_dispatcher(),
+                &bodies.0,
+                &colliders.0,
+                query_filter,
+            );
+
+            let pos = Isometry::from_parts(shapePos.0.into(), shapeRot.0);
+            let bounds = shape.0.compute_aabb(&pos).loosened(maxDist);
+            query_pipeline
+                .intersect_aabb_conservative(bounds)
+                .filter_map(|(handle, co)| {
+                    query::contact(&pos, &*shape.0, co.position(), co.shape(), maxDist)
+                        .ok()
+                        .flatten()
+                        .map(|contact| RawColliderShapeContact { handle, contact })
+                })
+                .min_by(|a, b| a.contact.dist.total_cmp(&b.contact.dist))
+        })
+    }
+
     // The callback is of type (u32) => bool
     pub fn intersectionsWithPoint(
         &self,
@@ -290,8 +636,14 @@ impl RawBroadPhase {
         filter_exclude_collider: Option<FlatHandle>,
         filter_exclude_rigid_body: Option<FlatHandle>,
         filter_predicate: &js_sys::Function,
+        filter_require_tags: u32,
+        filter_exclude_tags: u32,
     ) {
-        utils::with_filter(filter_predicate, |predicate| {
+        let tags = colliders
+            .1
+            .tags
+            .filter(filter_require_tags, filter_exclude_tags);
+        with_query_filter(filter_predicate, tags, |predicate| {
             let query_filter = QueryFilter {
                 flags: QueryFilterFlags::from_bits(filter_flags)
                     .unwrap_or(QueryFilterFlags::empty()),
@@ -341,8 +693,14 @@ impl RawBroadPhase {
         filter_exclude_collider: Option<FlatHandle>,
         filter_exclude_rigid_body: Option<FlatHandle>,
         filter_predicate: &js_sys::Function,
+        filter_require_tags: u32,
+        filter_exclude_tags: u32,
     ) -> Option<RawColliderShapeCastHit> {
-        utils::with_filter(filter_predicate, |predicate| {
+        let tags = colliders
+            .1
+            .tags
+            .filter(filter_require_tags, filter_exclude_tags);
+        with_query_filter(filter_predicate, tags, |predicate| {
             let query_filter = QueryFilter {
                 flags: QueryFilterFlags::from_bits(filter_flags)
                     .unwrap_or(QueryFilterFlags::empty()),
@@ -372,10 +730,215 @@ impl RawBroadPhase {
                         target_distance,
                     },
                 )
-                .map(|(handle, hit)| RawColliderShapeCastHit { handle, hit })
+                .map(|(handle, hit)| {
+                    let co = &colliders.0[handle];
+                    RawColliderShapeCastHit::from_world_hit(
+                        handle,
+                        co.parent(),
+                        co.position(),
+                        co.shape(),
+                        &*shape.0,
+                        hit,
+                    )
+                })
+        })
+    }
+
+    /// Casts a shape at a constant linear velocity and reports every collider it hits along
+    /// the way, by increasing time of impact.
+    ///
+    /// The callback is called with a `RawColliderShapeCastHit` for each hit. If it returns
+    /// `false`, no further hits are reported.
+    pub fn castShapeAll(
+        &self,
+        narrow_phase: &RawNarrowPhase,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        shapePos: &RawVector,
+        shapeRot: &RawRotation,
+        shapeVel: &RawVector,
+        shape: &RawShape,
+        target_distance: f64,
+        maxToi: f64,
+        stop_at_penetration: bool,
+        callback: &js_sys::Function,
+        filter_flags: u32,
+        filter_groups: Option<u32>,
+        filter_exclude_collider: Option<FlatHandle>,
+        filter_exclude_rigid_body: Option<FlatHandle>,
+        filter_predicate: &js_sys::Function,
+        filter_require_tags: u32,
+        filter_exclude_tags: u32,
+    ) {
+        let tags = colliders
+            .1
+            .tags
+            .filter(filter_require_tags, filter_exclude_tags);
+        with_query_filter(filter_predicate, tags, |predicate| {
+            let query_filter = QueryFilter {
+                flags: QueryFilterFlags::from_bits(filter_flags)
+                    .unwrap_or(QueryFilterFlags::empty()),
+                groups: filter_groups.map(crate::geometry::unpack_interaction_groups),
+                exclude_collider: filter_exclude_collider.map(crate::utils::collider_handle),
+                exclude_rigid_body: filter_exclude_rigid_body.map(crate::utils::body_handle),
+                predicate,
+            };
+
+            let query_pipeline = self.0.as_query_pipeline(
+                narrow_phase.0.query_dispatcher(),
+                &bodies.0,
+                &colliders.0,
+                query_filter,
+            );
+
+            let pos = Isometry::from_parts(shapePos.0.into(), shapeRot.0);
+            let end_pos =
+                Isometry::from_parts((shapePos.0 + shapeVel.0 * maxToi).into(), shapeRot.0);
+            let swept_aabb = shape
+                .0
+                .compute_swept_aabb(&pos, &end_pos)
+                .loosened(target_distance);
+            let options = ShapeCastOptions {
+                max_time_of_impact: maxToi,
+                stop_at_penetration,
+                compute_impact_geometry_on_penetration: true,
+                target_distance,
+            };
+
+            let mut hits: Vec<_> = query_pipeline
+                .intersect_aabb_conservative(swept_aabb)
+                .filter_map(|(handle, co)| {
+                    query::cast_shapes(
+                        co.position(),
+                        &Vector::zeros(),
+                        co.shape(),
+                        &pos,
+                        &shapeVel.0,
+                        &*shape.0,
+                        options,
+                    )
+                    .ok()
+                    .flatten()
+                    .map(|hit| (handle, co, hit))
+                })
+                .collect();
+            hits.sort_by(|a, b| a.2.time_of_impact.total_cmp(&b.2.time_of_impact));
+
+            for (handle, co, hit) in hits {
+                let result = RawColliderShapeCastHit::from_local_hit(
+                    handle,
+                    co.parent(),
+                    co.position(),
+                    co.shape(),
+                    &*shape.0,
+                    hit,
+                );
+                match callback.call1(&JsValue::null(), &JsValue::from(result)) {
+                    Err(_) => {}
+                    Ok(val) => {
+                        if !val.as_bool().unwrap_or(true) {
+                            break;
+                        }
+                    }
+                }
+            }
         })
     }
 
+    /// Casts a shape with both a linear and an angular velocity, and returns the first collider
+    /// it hits.
+    ///
+    /// The shape rotates around its center of mass. Unlike `castShape`, this accounts for the
+    /// rotation of the shape along its trajectory, e.g., for sweep tests of spinning objects.
+    #[cfg(feature = "dim2")]
+    pub fn castShapeNonlinear(
+        &self,
+        narrow_phase: &RawNarrowPhase,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        shapePos: &RawVector,
+        shapeRot: &RawRotation,
+        shapeLinvel: &RawVector,
+        shapeAngvel: f64,
+        shape: &RawShape,
+        maxToi: f64,
+        stop_at_penetration: bool,
+        filter_flags: u32,
+        filter_groups: Option<u32>,
+        filter_exclude_collider: Option<FlatHandle>,
+        filter_exclude_rigid_body: Option<FlatHandle>,
+        filter_predicate: &js_sys::Function,
+        filter_require_tags: u32,
+        filter_exclude_tags: u32,
+    ) -> Option<RawColliderShapeCastHit> {
+        self.cast_shape_nonlinear(
+            narrow_phase,
+            bodies,
+            colliders,
+            shapePos,
+            shapeRot,
+            shapeLinvel,
+            shapeAngvel,
+            shape,
+            maxToi,
+            stop_at_penetration,
+            filter_flags,
+            filter_groups,
+            filter_exclude_collider,
+            filter_exclude_rigid_body,
+            filter_predicate,
+            filter_require_tags,
+            filter_exclude_tags,
+        )
+    }
+
+    /// Casts a shape with both a linear and an angular velocity, and returns the first collider
+    /// it hits.
+    ///
+    /// The shape rotates around its center of mass. Unlike `castShape`, this accounts for the
+    /// rotation of the shape along its trajectory, e.g., for sweep tests of spinning objects.
+    #[cfg(feature = "dim3")]
+    pub fn castShapeNonlinear(
+        &self,
+        narrow_phase: &RawNarrowPhase,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        shapePos: &RawVector,
+        shapeRot: &RawRotation,
+        shapeLinvel: &RawVector,
+        shapeAngvel: &RawVector,
+        shape: &RawShape,
+        maxToi: f64,
+        stop_at_penetration: bool,
+        filter_flags: u32,
+        filter_groups: Option<u32>,
+        filter_exclude_collider: Option<FlatHandle>,
+        filter_exclude_rigid_body: Option<FlatHandle>,
+        filter_predicate: &js_sys::Function,
+        filter_require_tags: u32,
+        filter_exclude_tags: u32,
+    ) -> Option<RawColliderShapeCastHit> {
+        self.cast_shape_nonlinear(
+            narrow_phase,
+            bodies,
+            colliders,
+            shapePos,
+            shapeRot,
+            shapeLinvel,
+            shapeAngvel.0,
+            shape,
+            maxToi,
+            stop_at_penetration,
+            filter_flags,
+            filter_groups,
+            filter_exclude_collider,
+            filter_exclude_rigid_body,
+            filter_predicate,
+            filter_require_tags,
+            filter_exclude_tags,
+        )
+    }
+
     // The callback has type (u32) => boolean
     pub fn intersectionsWithShape(
         &self,
@@ -391,8 +954,14 @@ impl RawBroadPhase {
         filter_exclude_collider: Option<FlatHandle>,
         filter_exclude_rigid_body: Option<FlatHandle>,
         filter_predicate: &js_sys::Function,
+        filter_require_tags: u32,
+        filter_exclude_tags: u32,
     ) {
-        utils::with_filter(filter_predicate, |predicate| {
+        let tags = colliders
+            .1
+            .tags
+            .filter(filter_require_tags, filter_exclude_tags);
+        with_query_filter(filter_predicate, tags, |predicate| {
             let query_filter = QueryFilter {
                 flags: QueryFilterFlags::from_bits(filter_flags)
                     .unwrap_or(QueryFilterFlags::empty()),
@@ -426,6 +995,398 @@ impl RawBroadPhase {
         })
     }
 
+    /// Retrieves the handles of all the colliders intersecting the given shape.
+    ///
+    /// This is similar to `intersectionsWithShape` but returns all the handles at once
+    /// instead of calling a JS function for each of them.
+    pub fn intersectionsWithShapeCollect(
+        &self,
+        narrow_phase: &RawNarrowPhase,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        shapePos: &RawVector,
+        shapeRot: &RawRotation,
+        shape: &RawShape,
+        filter_flags: u32,
+        filter_groups: Option<u32>,
+        filter_exclude_collider: Option<FlatHandle>,
+        filter_exclude_rigid_body: Option<FlatHandle>,
+        filter_predicate: &js_sys::Function,
+        filter_require_tags: u32,
+        filter_exclude_tags: u32,
+    ) -> Vec<FlatHandle> {
+        let tags = colliders
+            .1
+            .tags
+            .filter(filter_require_tags, filter_exclude_tags);
+        with_query_filter(filter_predicate, tags, |predicate| {
+            let query_filter = QueryFilter {
+                flags: QueryFilterFlags::from_bits(filter_flags)
+                    .unwrap_or(QueryFilterFlags::empty()),
+                groups: filter_groups.map(crate::geometry::unpack_interaction_groups),
+                exclude_collider: filter_exclude_collider.map(crate::utils::collider_handle),
+                exclude_rigid_body: filter_exclude_rigid_body.map(crate::utils::body_handle),
+                predicate,
+            };
+
+            let query_pipeline = self.0.as_query_pipeline(
+                narrow_phase.0.query_dispatcher(),
+                &bodies.0,
+                &colliders.0,
+                query_filter,
+            );
+
+            let pos = Isometry::from_parts(shapePos.0.into(), shapeRot.0);
+            query_pipeline
+                .intersect_shape(pos, &*shape.0)
+                .map(|(handle, _)| utils::flat_handle(handle.0))
+                .collect()
+        })
+    }
+
+    /// Retrieves the handles of the colliders preventing the given shape from being placed at
+    /// the given position, i.e., penetrating it by more than `tolerance`.
+    ///
+    /// With a small positive `tolerance`, shapes merely touching the placed shape, e.g., the
+    /// ground it rests on, don't block the placement.
+    pub fn placementBlockers(
+        &self,
+        narrow_phase: &RawNarrowPhase,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        shapePos: &RawVector,
+        shapeRot: &RawRotation,
+        shape: &RawShape,
+        tolerance: f64,
+        filter_flags: u32,
+        filter_groups: Option<u32>,
+        filter_exclude_collider: Option<FlatHandle>,
+        filter_exclude_rigid_body: Option<FlatHandle>,
+        filter_predicate: &js_sys::Function,
+        filter_require_tags: u32,
+        filter_exclude_tags: u32,
+    ) -> Vec<FlatHandle> {
+        let tags = colliders
+            .1
+            .tags
+            .filter(filter_require_tags, filter_exclude_tags);
+        with_query_filter(filter_predicate, tags, |predicate| {
+            let query_filter = QueryFilter {
+                flags: QueryFilterFlags::from_bits(filter_flags)
+                    .unwrap_or(QueryFilterFlags::empty()),
+                groups: filter_groups.map(crate::geometry::unpack_interaction_groups),
+                exclude_collider: filter_exclude_collider.map(crate::utils::collider_handle),
+                exclude_rigid_body: filter_exclude_rigid_body.map(crate::utils::body_handle),
+                predicate,
+            };
+
+            let query_pipeline = self.0.as_query_pipeline(
+                narrow_phase.0.query_dispatcher(),
+                &bodies.0,
+                &colliders.0,
+                query_filter,
+            );
+
+            let pos = Isometry::from_parts(shapePos.0.into(), shapeRot.0);
+            query_pipeline
+                .intersect_shape(pos, &*shape.0)
+                .filter(|(_, co)| {
+                    // Unsupported shape pairs conservatively block the placement.
+                    tolerance <= 0.0
+                        || query::contact(&pos, &*shape.0, co.position(), co.shape(), 0.0)
+                            .map_or(true, |contact| {
+                                contact.is_some_and(|contact| -contact.dist > tolerance)
+                            })
+                })
+                .map(|(handle, _)| utils::flat_handle(handle.0))
+                .collect()
+        })
+    }
+
+    /// Computes a position where the given shape no longer penetrates the colliders of the
+    /// scene by more than `tolerance`, e.g., to fix up a spawn or teleport location.
+    ///
+    /// At each iteration, the shape is pushed out along the normals of all its penetrating
+    /// contacts at once, by the smallest translation resolving each of them. The orientation of
+    /// the shape is preserved.
+    pub fn depenetrateShape(
+        &self,
+        narrow_phase: &RawNarrowPhase,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        shapePos: &RawVector,
+        shapeRot: &RawRotation,
+        shape: &RawShape,
+        maxIterations: u32,
+        tolerance: f64,
+        filter_flags: u32,
+        filter_groups: Option<u32>,
+        filter_exclude_collider: Option<FlatHandle>,
+        filter_exclude_rigid_body: Option<FlatHandle>,
+        filter_predicate: &js_sys::Function,
+        filter_require_tags: u32,
+        filter_exclude_tags: u32,
+    ) -> RawShapeDepenetration {
+        let tags = colliders
+            .1
+            .tags
+            .filter(filter_require_tags, filter_exclude_tags);
+        with_query_filter(filter_predicate, tags, |predicate| {
+            let query_filter = QueryFilter {
+                flags: QueryFilterFlags::from_bits(filter_flags)
+                    .unwrap_or(QueryFilterFlags::empty()),
+                groups: filter_groups.map(crate::geometry::unpack_interaction_groups),
+                exclude_collider: filter_exclude_collider.map(crate::utils::collider_handle),
+                exclude_rigid_body: filter_exclude_rigid_body.map(crate::utils::body_handle),
+                predicate,
+            };
+
+            let query_pipeline = self.0.as_query_pipeline(
+                narrow_phase.0.query_dispatcher(),
+                &bodies.0,
+                &colliders.0,
+                query_filter,
+            );
+
+            let tolerance = tolerance.max(0.0);
+            let mut pos = Isometry::from_parts(shapePos.0.into(), shapeRot.0);
+
+            for _ in 0..maxIterations {
+                let mut penetrations: Vec<_> = query_pipeline
+                    .intersect_shape(pos, &*shape.0)
+                    .filter_map(|(_, co)| {
+                        query::contact(&pos, &*shape.0, co.position(), co.shape(), 0.0)
+                            .ok()
+                            .flatten()
+                    })
+                    .filter(|contact| -contact.dist > tolerance)
+                    .map(|contact| (-contact.normal1.into_inner(), -contact.dist))
+                    .collect();
+
+                if penetrations.is_empty() {
+                    return RawShapeDepenetration {
+                        position: pos.translation.vector,
+                        resolved: true,
+                    };
+                }
+
+                // Resolve the deepest penetrations first, so the shallower ones are often
+                // already resolved by the same translation.
+                penetrations.sort_by(|a, b| b.1.total_cmp(&a.1));
+                let mut delta = Vector::zeros();
+                for (dir, depth) in penetrations {
+                    let remaining = depth - delta.dot(&dir);
+                    if remaining > 0.0 {
+                        delta += dir * remaining;
+                    }
+                }
+                pos.translation.vector += delta;
+            }
+
+            let resolved = query_pipeline
+                .intersect_shape(pos, &*shape.0)
+                .all(|(_, co)| {
+                    !query::contact(&pos, &*shape.0, co.position(), co.shape(), 0.0)
+                        .ok()
+                        .flatten()
+                        .is_some_and(|contact| -contact.dist > tolerance)
+                });
+            RawShapeDepenetration {
+                position: pos.translation.vector,
+                resolved,
+            }
+        })
+    }
+
+    /// Counts, for each sound source, the colliders crossed by the segment between the listener
+    /// and the source, e.g., to compute audio occlusion.
+    ///
+    /// # Parameters
+    /// - `sources`: the flattened positions of the sources.
+    ///
+    /// Returns one count per source, zero if the source isn’t occluded or coincides with the
+    /// listener.
+    pub fn occlusion(
+        &self,
+        narrow_phase: &RawNarrowPhase,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        listener: &RawVector,
+        sources: Vec<f64>,
+        filter_flags: u32,
+        filter_groups: Option<u32>,
+        filter_exclude_collider: Option<FlatHandle>,
+        filter_exclude_rigid_body: Option<FlatHandle>,
+        filter_predicate: &js_sys::Function,
+        filter_require_tags: u32,
+        filter_exclude_tags: u32,
+    ) -> Vec<u32> {
+        let tags = colliders
+            .1
+            .tags
+            .filter(filter_require_tags, filter_exclude_tags);
+        with_query_filter(filter_predicate, tags, |predicate| {
+            let query_filter = QueryFilter {
+                flags: QueryFilterFlags::from_bits(filter_flags)
+                    .unwrap_or(QueryFilterFlags::empty()),
+                groups: filter_groups.map(crate::geometry::unpack_interaction_groups),
+                exclude_collider: filter_exclude_collider.map(crate::utils::collider_handle),
+                exclude_rigid_body: filter_exclude_rigid_body.map(crate::utils::body_handle),
+                predicate,
+            };
+
+            let query_pipeline = self.0.as_query_pipeline(
+                narrow_phase.0.query_dispatcher(),
+                &bodies.0,
+                &colliders.0,
+                query_filter,
+            );
+
+            sources
+                .chunks_exact(DIM)
+                .map(|source| {
+                    // With an unnormalized direction, the source is at a time-of-impact of 1.
+                    let dir = Vector::from_column_slice(source) - listener.0;
+                    if dir == Vector::zeros() {
+                        // An empty segment doesn’t cross anything.
+                        return 0;
+                    }
+                    let ray = Ray::new(listener.0.into(), dir);
+                    query_pipeline.intersect_ray(ray, 1.0, true).count() as u32
+                })
+                .collect()
+        })
+    }
+
+    /// Tests the visibility of each target point from the origin.
+    ///
+    /// The test of a target stops at the first collider found between the origin and the target,
+    /// which is cheaper than finding the closest one with `castRay`.
+    ///
+    /// # Parameters
+    /// - `targets`: the flattened positions of the targets.
+    ///
+    /// Returns a bit mask, packed in 32-bit words, with the bit `i % 32` of the word `i / 32` set
+    /// if the target `i` is visible.
+    pub fn lineOfSight(
+        &self,
+        narrow_phase: &RawNarrowPhase,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        origin: &RawVector,
+        targets: Vec<f64>,
+        filter_flags: u32,
+        filter_groups: Option<u32>,
+        filter_exclude_collider: Option<FlatHandle>,
+        filter_exclude_rigid_body: Option<FlatHandle>,
+        filter_predicate: &js_sys::Function,
+        filter_require_tags: u32,
+        filter_exclude_tags: u32,
+    ) -> Vec<u32> {
+        let tags = colliders
+            .1
+            .tags
+            .filter(filter_require_tags, filter_exclude_tags);
+        with_query_filter(filter_predicate, tags, |predicate| {
+            let query_filter = QueryFilter {
+                flags: QueryFilterFlags::from_bits(filter_flags)
+                    .unwrap_or(QueryFilterFlags::empty()),
+                groups: filter_groups.map(crate::geometry::unpack_interaction_groups),
+                exclude_collider: filter_exclude_collider.map(crate::utils::collider_handle),
+                exclude_rigid_body: filter_exclude_rigid_body.map(crate::utils::body_handle),
+                predicate,
+            };
+
+            let query_pipeline = self.0.as_query_pipeline(
+                narrow_phase.0.query_dispatcher(),
+                &bodies.0,
+                &colliders.0,
+                query_filter,
+            );
+
+            let targets = targets.chunks_exact(DIM);
+            let mut visibility = vec![0; targets.len().div_ceil(32)];
+            for (i, target) in targets.enumerate() {
+                let dir = Vector::from_column_slice(target) - origin.0;
+                let ray = Ray::new(origin.0.into(), dir);
+                if query_pipeline
+                    .intersect_ray(ray, 1.0, true)
+                    .next()
+                    .is_none()
+                {
+                    visibility[i / 32] |= 1 << (i % 32);
+                }
+            }
+            visibility
+        })
+    }
+
+    /// Retrieves the handles of all the colliders with an AABB intersecting the convex region
+    /// bounded by the given planes (typically the 6 planes of a camera frustum).
+    ///
+    /// # Parameters
+    /// - `planes`: the flattened plane equations `[a, b, (c,) d]` of each plane. A point `x` is
+    ///   inside of the region if `dot([a, b, (c)], x) + d >= 0` for every plane, i.e., the plane
+    ///   normals point toward the inside of the region.
+    pub fn collidersInFrustum(
+        &self,
+        narrow_phase: &RawNarrowPhase,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        planes: Vec<f64>,
+        filter_flags: u32,
+        filter_groups: Option<u32>,
+        filter_exclude_collider: Option<FlatHandle>,
+        filter_exclude_rigid_body: Option<FlatHandle>,
+        filter_predicate: &js_sys::Function,
+        filter_require_tags: u32,
+        filter_exclude_tags: u32,
+    ) -> Vec<FlatHandle> {
+        let tags = colliders
+            .1
+            .tags
+            .filter(filter_require_tags, filter_exclude_tags);
+        with_query_filter(filter_predicate, tags, |predicate| {
+            let query_filter = QueryFilter {
+                flags: QueryFilterFlags::from_bits(filter_flags)
+                    .unwrap_or(QueryFilterFlags::empty()),
+                groups: filter_groups.map(crate::geometry::unpack_interaction_groups),
+                exclude_collider: filter_exclude_collider.map(crate::utils::collider_handle),
+                exclude_rigid_body: filter_exclude_rigid_body.map(crate::utils::body_handle),
+                predicate,
+            };
+
+            let query_pipeline = self.0.as_query_pipeline(
+                narrow_phase.0.query_dispatcher(),
+                &bodies.0,
+                &colliders.0,
+                query_filter,
+            );
+
+            let planes: Vec<_> = planes
+                .chunks_exact(DIM + 1)
+                .map(|p| (Vector::from_column_slice(&p[..DIM]), p[DIM]))
+                .collect();
+            let bounds = frustum_aabb(&planes).unwrap_or_else(|| {
+                Aabb::new(
+                    Point::from(Vector::repeat(-Real::MAX)),
+                    Point::from(Vector::repeat(Real::MAX)),
+                )
+            });
+
+            query_pipeline
+                .intersect_aabb_conservative(bounds)
+                .filter(|(_, co)| {
+                    let aabb = co.compute_aabb();
+                    planes
+                        .iter()
+                        .all(|(normal, offset)| !aabb_outside_plane(&aabb, normal, *offset))
+                })
+                .map(|(handle, _)| utils::flat_handle(handle.0))
+                .collect()
+        })
+    }
+
     pub fn collidersWithAabbIntersectingAabb(
         &self,
         narrow_phase: &RawNarrowPhase,
@@ -459,4 +1420,170 @@ impl RawBroadPhase {
             }
         }
     }
+
+    /// Refits the scene-query acceleration structure to the current position of the given colliders.
+    ///
+    /// This makes the scene queries take the new collider positions into account without
+    /// running a simulation step. Collision pairs, and their events, are not updated until the
+    /// next simulation step.
+    ///
+    /// # Parameters
+    /// - `handles`: the colliders that changed since the last update. If `None`, all the colliders
+    ///   are updated.
+    pub fn updateWithColliders(
+        &mut self,
+        integrationParameters: &RawIntegrationParameters,
+        colliders: &RawColliderSet,
+        handles: Option<Vec<FlatHandle>>,
+    ) {
+        let params = &integrationParameters.0;
+        let prediction = params.prediction_distance();
+        let mut update = |handle: ColliderHandle| {
+            if let Some(co) = colliders.0.get(handle) {
+                if co.is_enabled() {
+                    self.0
+                        .set_aabb(params, handle, co.compute_collision_aabb(prediction));
+                }
+            }
+        };
+
+        match handles {
+            Some(handles) => handles
+                .into_iter()
+                .for_each(|handle| update(utils::collider_handle(handle))),
+            None => colliders.0.iter().for_each(|(handle, _)| update(handle)),
+        }
+    }
+}
+
+impl RawBroadPhase {
+    fn cast_shape_nonlinear(
+        &self,
+        narrow_phase: &RawNarrowPhase,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        shapePos: &RawVector,
+        shapeRot: &RawRotation,
+        shapeLinvel: &RawVector,
+        shapeAngvel: AngVector<Real>,
+        shape: &RawShape,
+        maxToi: f64,
+        stop_at_penetration: bool,
+        filter_flags: u32,
+        filter_groups: Option<u32>,
+        filter_exclude_collider: Option<FlatHandle>,
+        filter_exclude_rigid_body: Option<FlatHandle>,
+        filter_predicate: &js_sys::Function,
+        filter_require_tags: u32,
+        filter_exclude_tags: u32,
+    ) -> Option<RawColliderShapeCastHit> {
+        let tags = colliders
+            .1
+            .tags
+            .filter(filter_require_tags, filter_exclude_tags);
+        with_query_filter(filter_predicate, tags, |predicate| {
+            let query_filter = QueryFilter {
+                flags: QueryFilterFlags::from_bits(filter_flags)
+                    .unwrap_or(QueryFilterFlags::empty()),
+                groups: filter_groups.map(crate::geometry::unpack_interaction_groups),
+                exclude_collider: filter_exclude_collider.map(crate::utils::collider_handle),
+                exclude_rigid_body: filter_exclude_rigid_body.map(crate::utils::body_handle),
+                predicate,
+            };
+
+            let query_pipeline = self.0.as_query_pipeline(
+                narrow_phase.0.query_dispatcher(),
+                &bodies.0,
+                &colliders.0,
+                query_filter,
+            );
+
+            let motion = NonlinearRigidMotion::new(
+                Isometry::from_parts(shapePos.0.into(), shapeRot.0),
+                shape.0.mass_properties(1.0).local_com,
+                shapeLinvel.0,
+                shapeAngvel,
+            );
+            query_pipeline
+                .cast_shape_nonlinear(&motion, &*shape.0, 0.0, maxToi, stop_at_penetration)
+                .map(|(handle, hit)| {
+                    let co = &colliders.0[handle];
+                    RawColliderShapeCastHit::from_world_hit(
+                        handle,
+                        co.parent(),
+                        co.position(),
+                        co.shape(),
+                        &*shape.0,
+                        hit,
+                    )
+                })
+        })
+    }
+}
+
+fn aabb_outside_plane(aabb: &Aabb, normal: &Vector<Real>, offset: Real) -> bool {
+    // The AABB corner furthest along the plane normal.
+    let support =
+        aabb.mins.coords.zip_zip_map(
+            &aabb.maxs.coords,
+            normal,
+            |min, max, n| {
+                if n >= 0.0 {
+                    max
+                } else {
+                    min
+                }
+            },
+        );
+    normal.dot(&support) + offset < 0.0
+}
+
+/// The AABB of the region bounded by the given planes, or `None` if it
+/// isn’t a closed frustum.
+fn frustum_aabb(planes: &[(Vector<Real>, Real)]) -> Option<Aabb> {
+    if planes.len() < 2 * DIM {
+        return None;
+    }
+
+    let mut corners = vec![];
+
+    #[cfg(feature = "dim2")]
+    for i in 0..planes.len() {
+        for j in i + 1..planes.len() {
+            let mat = na::Matrix2::from_rows(&[planes[i].0.transpose(), planes[j].0.transpose()]);
+            let rhs = Vector::new(-planes[i].1, -planes[j].1);
+            if let Some(inv) = mat.try_inverse() {
+                corners.push(Point::from(inv * rhs));
+            }
+        }
+    }
+
+    #[cfg(feature = "dim3")]
+    for i in 0..planes.len() {
+        for j in i + 1..planes.len() {
+            for k in j + 1..planes.len() {
+                let mat = na::Matrix3::from_rows(&[
+                    planes[i].0.transpose(),
+                    planes[j].0.transpose(),
+                    planes[k].0.transpose(),
+                ]);
+                let rhs = Vector::new(-planes[i].1, -planes[j].1, -planes[k].1);
+                if let Some(inv) = mat.try_inverse() {
+                    corners.push(Point::from(inv * rhs));
+                }
+            }
+        }
+    }
+
+    corners.retain(|pt| {
+        planes.iter().all(|(normal, offset)| {
+            normal.dot(&pt.coords) + offset >= -1.0e-6 * (1.0 + offset.abs())
+        })
+    });
+
+    if corners.len() < 1 << DIM {
+        return None;
+    }
+
+    Some(Aabb::from_points(corners.iter()))
 }
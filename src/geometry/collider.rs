@@ -1,18 +1,80 @@
+use crate::dynamics::{RawIntegrationParameters, RawRigidBodySet};
 use crate::geometry::shape::SharedShapeUtility;
 use crate::geometry::{
-    RawColliderSet, RawColliderShapeCastHit, RawPointProjection, RawRayIntersection, RawShape,
-    RawShapeCastHit, RawShapeContact, RawShapeType,
+    RawColliderSet, RawColliderShapeCastHit, RawMeshData, RawPointProjection, RawRayIntersection,
+    RawShape, RawShapeCastHit, RawShapeContact, RawShapePenetration, RawShapeType,
+    TriangleMaterials,
 };
 use crate::math::{RawRotation, RawVector};
 use crate::utils::{self, FlatHandle};
-use rapier::dynamics::MassProperties;
-use rapier::geometry::{ActiveCollisionTypes, ShapeType};
-use rapier::math::{Isometry, Point, Vector};
+use rapier::dynamics::{IntegrationParameters, MassProperties, RigidBodySet};
+use rapier::geometry::{ActiveCollisionTypes, Collider, HeightField, ShapeType, SharedShape};
+use rapier::math::{Isometry, Point, Real, Vector, DIM};
 use rapier::parry::query;
 use rapier::parry::query::ShapeCastOptions;
 use rapier::pipeline::{ActiveEvents, ActiveHooks};
 use wasm_bindgen::prelude::*;
 
+/// The prediction distance used by the narrow-phase to generate the contacts between two
+/// colliders: the sum of their contact skins and of the global prediction distance, extended
+/// by the relative motion of their rigid-bodies if soft-CCD is enabled for any of them.
+fn effective_prediction_distance(
+    params: &IntegrationParameters,
+    bodies: &RigidBodySet,
+    co1: &Collider,
+    co2: &Collider,
+) -> Real {
+    let contact_skin_sum = co1.contact_skin() + co2.contact_skin();
+    let rb1 = co1.parent().and_then(|h| bodies.get(h));
+    let rb2 = co2.parent().and_then(|h| bodies.get(h));
+    let soft_ccd_prediction1 = rb1.map(|rb| rb.soft_ccd_prediction()).unwrap_or(0.0);
+    let soft_ccd_prediction2 = rb2.map(|rb| rb.soft_ccd_prediction()).unwrap_or(0.0);
+
+    if soft_ccd_prediction1 > 0.0 || soft_ccd_prediction2 > 0.0 {
+        let inv_dt = if params.dt != 0.0 {
+            1.0 / params.dt
+        } else {
+            0.0
+        };
+        let linvel1 = rb1
+            .map(|rb| rb.linvel().cap_magnitude(soft_ccd_prediction1 * inv_dt))
+            .unwrap_or_default();
+        let linvel2 = rb2
+            .map(|rb| rb.linvel().cap_magnitude(soft_ccd_prediction2 * inv_dt))
+            .unwrap_or_default();
+        params
+            .prediction_distance()
+            .max(params.dt * (linvel1 - linvel2).norm())
+            + contact_skin_sum
+    } else {
+        params.prediction_distance() + contact_skin_sum
+    }
+}
+
+/// A new heightfield with the given heights, and the same scale and removed segments as the
+/// given one.
+#[cfg(feature = "dim2")]
+fn rebuild_heightfield(heightfield: &HeightField, heights: na::DVector<Real>) -> HeightField {
+    let mut rebuilt = HeightField::new(heights, *heightfield.scale());
+    for i in 0..heightfield.num_cells() {
+        if heightfield.is_segment_removed(i) {
+            rebuilt.set_segment_removed(i, true);
+        }
+    }
+    rebuilt
+}
+
+/// A new heightfield with the given heights, and the same scale, cell statuses and flags as
+/// the given one.
+#[cfg(feature = "dim3")]
+fn rebuild_heightfield(heightfield: &HeightField, heights: na::DMatrix<Real>) -> HeightField {
+    let mut rebuilt = HeightField::with_flags(heights, *heightfield.scale(), heightfield.flags());
+    rebuilt
+        .cells_statuses_mut()
+        .copy_from(heightfield.cells_statuses());
+    rebuilt
+}
+
 #[wasm_bindgen]
 impl RawColliderSet {
     /// The world-space translation of this collider.
@@ -128,6 +190,31 @@ impl RawColliderSet {
         self.map_mut(handle, |co| co.set_rotation_wrt_parent(angle))
     }
 
+    /// Sets both the world-space translation and rotation of this collider.
+    ///
+    /// Unlike setting the translation and the rotation separately, this results in
+    /// a single broad-phase update.
+    pub fn coSetPosition(&mut self, handle: FlatHandle, pos: &RawVector, rot: &RawRotation) {
+        self.map_mut(handle, |co| {
+            co.set_position(Isometry::from_parts(pos.0.into(), rot.0))
+        })
+    }
+
+    /// Sets both the translation and rotation of this collider relative to its parent rigid-body.
+    ///
+    /// Does nothing if this collider isn’t attached to a rigid-body. Unlike setting the
+    /// translation and the rotation separately, this results in a single broad-phase update.
+    pub fn coSetPositionWrtParent(
+        &mut self,
+        handle: FlatHandle,
+        pos: &RawVector,
+        rot: &RawRotation,
+    ) {
+        self.map_mut(handle, |co| {
+            co.set_position_wrt_parent(Isometry::from_parts(pos.0.into(), rot.0))
+        })
+    }
+
     /// Is this collider a sensor?
     pub fn coIsSensor(&self, handle: FlatHandle) -> bool {
         self.map(handle, |co| co.is_sensor())
@@ -316,6 +403,29 @@ impl RawColliderSet {
         })
     }
 
+    /// The shape types and primary shape parameters of all the colliders, packed in a single
+    /// array.
+    ///
+    /// For each collider, the array contains its handle, its shape type, the half-extents of
+    /// its cuboid (`DIM` values), its radius, its half height, and its round radius. The
+    /// parameters that don’t apply to the shape are set to NaN.
+    pub fn coShapeSummaries(&self) -> Vec<f64> {
+        let mut data = Vec::with_capacity(self.0.len() * (DIM + 5));
+        for (handle, _) in self.0.iter() {
+            let handle = utils::flat_handle(handle.0);
+            data.push(handle);
+            data.push(self.coShapeType(handle) as u32 as f64);
+            match self.coHalfExtents(handle) {
+                Some(half_extents) => data.extend(half_extents.0.iter()),
+                None => data.extend([f64::NAN; DIM]),
+            }
+            data.push(self.coRadius(handle).unwrap_or(f64::NAN));
+            data.push(self.coHalfHeight(handle).unwrap_or(f64::NAN));
+            data.push(self.coRoundRadius(handle).unwrap_or(f64::NAN));
+        }
+        data
+    }
+
     /// Set the radius of the round edges of this collider.
     pub fn coSetRoundRadius(&mut self, handle: FlatHandle, newBorderRadius: f64) {
         self.map_mut(handle, |co| match co.shape().shape_type() {
@@ -370,6 +480,46 @@ impl RawColliderSet {
         })
     }
 
+    /// The range of grid coordinates covered by the voxels of this collider, if it is a voxel
+    /// collider.
+    ///
+    /// Returns the `DIM` minimum coordinates (included) followed by the `DIM` maximum
+    /// coordinates (excluded). Voxels outside of this range are guaranteed to be empty.
+    pub fn coVoxelDomain(&self, handle: FlatHandle) -> Option<Vec<i32>> {
+        self.map(handle, |co| {
+            let [mins, maxs] = co.shape().as_voxels()?.domain();
+            Some(mins.iter().chain(maxs.iter()).copied().collect())
+        })
+    }
+
+    /// The number of filled voxels of this collider, if it is a voxel collider.
+    pub fn coNumFilledVoxels(&self, handle: FlatHandle) -> Option<usize> {
+        self.map(handle, |co| {
+            let vox = co.shape().as_voxels()?;
+            Some(vox.voxels().filter(|vox| !vox.state.is_empty()).count())
+        })
+    }
+
+    #[cfg(feature = "dim2")]
+    pub fn coVoxelIsFilled(&self, handle: FlatHandle, ix: i32, iy: i32) -> bool {
+        self.map(handle, |co| {
+            co.shape().as_voxels().is_some_and(|vox| {
+                vox.voxel_state(Point::new(ix, iy))
+                    .is_some_and(|state| !state.is_empty())
+            })
+        })
+    }
+
+    #[cfg(feature = "dim3")]
+    pub fn coVoxelIsFilled(&self, handle: FlatHandle, ix: i32, iy: i32, iz: i32) -> bool {
+        self.map(handle, |co| {
+            co.shape().as_voxels().is_some_and(|vox| {
+                vox.voxel_state(Point::new(ix, iy, iz))
+                    .is_some_and(|state| !state.is_empty())
+            })
+        })
+    }
+
     #[cfg(feature = "dim2")]
     pub fn coSetVoxel(&mut self, handle: FlatHandle, ix: i32, iy: i32, filled: bool) {
         self.map_mut(handle, |co| {
@@ -442,6 +592,67 @@ impl RawColliderSet {
         })
     }
 
+    /// Propagates the changes of several voxels of the first collider to the second one.
+    ///
+    /// The `voxels` array contains the `DIM` integer coordinates of each modified voxel,
+    /// consecutively.
+    #[cfg(feature = "dim2")]
+    pub fn coPropagateVoxelChanges(
+        &mut self,
+        handle1: FlatHandle,
+        handle2: FlatHandle,
+        voxels: Vec<i32>,
+        shift_x: i32,
+        shift_y: i32,
+    ) {
+        self.map_pair_mut(handle1, handle2, |co1, co2| {
+            if let (Some(co1), Some(co2)) = (co1, co2) {
+                if let (Some(vox1), Some(vox2)) = (
+                    co1.shape_mut().as_voxels_mut(),
+                    co2.shape_mut().as_voxels_mut(),
+                ) {
+                    let shift = Vector::new(shift_x, shift_y);
+                    for ids in voxels.chunks_exact(2) {
+                        vox1.propagate_voxel_change(vox2, Point::new(ids[0], ids[1]), shift);
+                    }
+                }
+            }
+        })
+    }
+
+    /// Propagates the changes of several voxels of the first collider to the second one.
+    ///
+    /// The `voxels` array contains the `DIM` integer coordinates of each modified voxel,
+    /// consecutively.
+    #[cfg(feature = "dim3")]
+    pub fn coPropagateVoxelChanges(
+        &mut self,
+        handle1: FlatHandle,
+        handle2: FlatHandle,
+        voxels: Vec<i32>,
+        shift_x: i32,
+        shift_y: i32,
+        shift_z: i32,
+    ) {
+        self.map_pair_mut(handle1, handle2, |co1, co2| {
+            if let (Some(co1), Some(co2)) = (co1, co2) {
+                if let (Some(vox1), Some(vox2)) = (
+                    co1.shape_mut().as_voxels_mut(),
+                    co2.shape_mut().as_voxels_mut(),
+                ) {
+                    let shift = Vector::new(shift_x, shift_y, shift_z);
+                    for ids in voxels.chunks_exact(3) {
+                        vox1.propagate_voxel_change(
+                            vox2,
+                            Point::new(ids[0], ids[1], ids[2]),
+                            shift,
+                        );
+                    }
+                }
+            }
+        })
+    }
+
     #[cfg(feature = "dim2")]
     pub fn coCombineVoxelStates(
         &mut self,
@@ -588,6 +799,37 @@ impl RawColliderSet {
         })
     }
 
+    /// Replaces the heights of this heightfield, if it is one, keeping its scale and its
+    /// removed cells (and its flags in 3D).
+    ///
+    /// The heights have the same layout as the ones returned by `coHeightfieldHeights`: in 3D,
+    /// a column-major matrix with `nrows + 1` rows and `ncols + 1` columns, where `nrows` and
+    /// `ncols` are the numbers of cells along each axis. Returns `false`, leaving the collider
+    /// unchanged, if it isn’t a heightfield or if the number of heights doesn’t match.
+    pub fn coSetHeightfieldHeights(&mut self, handle: FlatHandle, heights: Vec<f64>) -> bool {
+        self.map_mut(handle, |co| {
+            let hf = match co.shape().as_heightfield() {
+                Some(hf) if hf.heights().len() == heights.len() => hf,
+                _ => return false,
+            };
+            let mut new_heights = hf.heights().clone();
+            new_heights.as_mut_slice().copy_from_slice(&heights);
+            let rebuilt = rebuild_heightfield(hf, new_heights);
+            co.set_shape(SharedShape::new(rebuilt));
+            true
+        })
+    }
+
+    /// The collision surface of this heightfield if it is one: a polyline in 2D, a triangle
+    /// mesh in 3D.
+    pub fn coHeightfieldToTrimesh(&self, handle: FlatHandle) -> Option<RawMeshData> {
+        self.map(handle, |co| {
+            co.shape()
+                .as_heightfield()
+                .map(crate::geometry::heightfield_mesh)
+        })
+    }
+
     /// The number of rows on this heightfield's height matrix, if it is one.
     #[cfg(feature = "dim3")]
     pub fn coHeightfieldNRows(&self, handle: FlatHandle) -> Option<usize> {
@@ -627,6 +869,26 @@ impl RawColliderSet {
         self.map(handle, |co| co.contact_skin())
     }
 
+    /// The distance below which contacts are generated between this collider and the given
+    /// one, taking their contact skins, the global prediction distance, and soft-CCD into
+    /// account.
+    pub fn coEffectivePredictionDistance(
+        &self,
+        handle: FlatHandle,
+        collider2handle: FlatHandle,
+        params: &RawIntegrationParameters,
+        bodies: &RawRigidBodySet,
+    ) -> f64 {
+        let co2 = self
+            .0
+            .get(utils::collider_handle(collider2handle))
+            .expect("Invalid Collider reference. It may have been removed from the physics World.");
+
+        self.map(handle, |co| {
+            effective_prediction_distance(&params.0, &bodies.0, co, co2)
+        })
+    }
+
     /// The friction coefficient of this collider.
     pub fn coFriction(&self, handle: FlatHandle) -> f64 {
         self.map(handle, |co| co.material().friction)
@@ -658,6 +920,17 @@ impl RawColliderSet {
         })
     }
 
+    /// Is this collider a member of the given collision group?
+    ///
+    /// # Parameters
+    /// - `group`: the index of the group, in `[0, 16)`. Returns `false` for any other index.
+    pub fn coCollisionGroupsContain(&self, handle: FlatHandle, group: u32) -> bool {
+        group < 16
+            && self.map(handle, |co| {
+                co.collision_groups().memberships.bits() & (1 << group) != 0
+            })
+    }
+
     /// The solver groups of this collider.
     pub fn coSolverGroups(&self, handle: FlatHandle) -> u32 {
         self.map(handle, |co| {
@@ -666,8 +939,12 @@ impl RawColliderSet {
     }
 
     /// The physics hooks enabled for this collider.
+    ///
+    /// The hooks set natively by these bindings, e.g., for the pair filtering rules, aren’t
+    /// included.
     pub fn coActiveHooks(&self, handle: FlatHandle) -> u32 {
-        self.map(handle, |co| co.active_hooks().bits())
+        let native = self.1.native_hooks(utils::collider_handle(handle));
+        self.map(handle, |co| (co.active_hooks() - native).bits())
     }
 
     /// The collision types enabled for this collider.
@@ -754,10 +1031,13 @@ impl RawColliderSet {
             )
             .unwrap_or(None)
             .map_or(None, |hit| {
-                Some(RawColliderShapeCastHit {
-                    handle: handle2,
+                Some(RawColliderShapeCastHit::new(
+                    handle2,
+                    co2.parent(),
+                    co.shape(),
+                    co2.shape(),
                     hit,
-                })
+                ))
             })
         })
     }
@@ -818,6 +1098,44 @@ impl RawColliderSet {
         })
     }
 
+    /// Computes the penetration depth and minimum translation vector separating this collider
+    /// from the given shape, or `None` if they don't overlap.
+    pub fn coPenetrationShape(
+        &self,
+        handle: FlatHandle,
+        shape2: &RawShape,
+        shapePos2: &RawVector,
+        shapeRot2: &RawRotation,
+    ) -> Option<RawShapePenetration> {
+        let pos2 = Isometry::from_parts(shapePos2.0.into(), shapeRot2.0);
+
+        self.map(handle, |co| {
+            co.shared_shape()
+                .contactShape(co.position(), &*shape2.0, &pos2, 0.0)
+                .and_then(|contact| RawShapePenetration::from_contact(contact.contact))
+        })
+    }
+
+    /// Computes the penetration depth and minimum translation vector separating this collider
+    /// from the second one, or `None` if they don't overlap.
+    pub fn coPenetrationCollider(
+        &self,
+        handle: FlatHandle,
+        collider2handle: FlatHandle,
+    ) -> Option<RawShapePenetration> {
+        let co2 = self
+            .0
+            .get(utils::collider_handle(collider2handle))
+            .expect("Invalid Collider reference. It may have been removed from the physics World.");
+
+        self.map(handle, |co| {
+            query::contact(co.position(), co.shape(), co2.position(), co2.shape(), 0.0)
+                .ok()
+                .flatten()
+                .and_then(RawShapePenetration::from_contact)
+        })
+    }
+
     pub fn coProjectPoint(
         &self,
         handle: FlatHandle,
@@ -830,6 +1148,17 @@ impl RawColliderSet {
         })
     }
 
+    pub fn coProjectPointAndGetFeature(
+        &self,
+        handle: FlatHandle,
+        point: &RawVector,
+    ) -> RawPointProjection {
+        self.map(handle, |co| {
+            co.shared_shape()
+                .projectPointAndGetFeature(co.position(), &point.0.into())
+        })
+    }
+
     pub fn coIntersectsRay(
         &self,
         handle: FlatHandle,
@@ -897,6 +1226,168 @@ impl RawColliderSet {
         self.map_mut(handle, |co| co.set_friction(friction))
     }
 
+    /// Assigns a material to each triangle of this collider.
+    ///
+    /// The friction and restitution of the material replace the collider’s own coefficients
+    /// for any contact involving the corresponding triangle.
+    ///
+    /// Returns `false`, leaving the collider unchanged, if its shape isn’t a triangle mesh, if
+    /// the number of material indices doesn’t match its number of triangles, or if a material
+    /// index is out of bounds of `friction` or `restitution`.
+    ///
+    /// # Parameters
+    /// - `triangleMaterials`: the material index of each triangle of the collider’s shape.
+    /// - `friction`: the friction coefficient of each material.
+    /// - `restitution`: the restitution coefficient of each material.
+    pub fn coSetTriangleMaterials(
+        &mut self,
+        handle: FlatHandle,
+        triangleMaterials: Vec<u32>,
+        friction: Vec<f64>,
+        restitution: Vec<f64>,
+    ) -> bool {
+        let num_materials = friction.len().min(restitution.len());
+        if triangleMaterials
+            .iter()
+            .any(|m| *m as usize >= num_materials)
+        {
+            return false;
+        }
+
+        let collider_handle = utils::collider_handle(handle);
+        let previous = self.1.materials.get(collider_handle).cloned();
+        let added = self.map_mut(handle, |co| {
+            let num_triangles = match co.shape().as_trimesh() {
+                Some(trimesh) => trimesh.indices().len(),
+                None => return None,
+            };
+            if num_triangles != triangleMaterials.len() {
+                return None;
+            }
+
+            let hooks = co.active_hooks();
+            co.set_active_hooks(hooks | ActiveHooks::MODIFY_SOLVER_CONTACTS);
+            // Keep the state of the hook from before the first assignment.
+            let had_hook = previous
+                .map(|m| m.had_modify_solver_contacts)
+                .unwrap_or(hooks.contains(ActiveHooks::MODIFY_SOLVER_CONTACTS));
+            Some((num_triangles as u32, had_hook))
+        });
+
+        match added {
+            Some((num_triangles, had_modify_solver_contacts)) => {
+                self.1.materials.insert(
+                    collider_handle,
+                    TriangleMaterials {
+                        triangle_materials: triangleMaterials,
+                        friction,
+                        restitution,
+                        num_triangles,
+                        had_modify_solver_contacts,
+                    },
+                );
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes the per-triangle materials of this collider.
+    ///
+    /// The `MODIFY_SOLVER_CONTACTS` active hook set by `coSetTriangleMaterials` is removed,
+    /// unless it was already set before.
+    pub fn coClearTriangleMaterials(&mut self, handle: FlatHandle) {
+        let removed = self
+            .1
+            .materials
+            .remove_triangles(utils::collider_handle(handle));
+        if let Some(materials) = removed {
+            if !materials.had_modify_solver_contacts {
+                self.map_mut(handle, |co| {
+                    co.set_active_hooks(co.active_hooks() - ActiveHooks::MODIFY_SOLVER_CONTACTS)
+                });
+            }
+        }
+    }
+
+    /// The material index of the given triangle of this collider.
+    ///
+    /// The triangle can be identified either by its index, or by the face feature id
+    /// reported by a ray-cast. Returns `None` if this triangle has no material.
+    pub fn coTriangleMaterial(&self, handle: FlatHandle, triangle: u32) -> Option<u32> {
+        self.1
+            .materials
+            .get(utils::collider_handle(handle))
+            .and_then(|m| m.material(triangle))
+    }
+
+    /// The rolling resistance coefficient of this collider.
+    pub fn coRollingResistance(&self, handle: FlatHandle) -> f64 {
+        self.1
+            .materials
+            .rolling_resistance(utils::collider_handle(handle))
+            .coefficient
+    }
+
+    /// Sets the rolling resistance coefficient of this collider, i.e., the maximum torque
+    /// resisting the rolling of the bodies in contact with it, per unit of normal force.
+    pub fn coSetRollingResistance(&mut self, handle: FlatHandle, coefficient: f64) {
+        let handle = utils::collider_handle(handle);
+        let mut rolling_resistance = self.1.materials.rolling_resistance(handle);
+        rolling_resistance.coefficient = coefficient.max(0.0);
+        self.1
+            .materials
+            .set_rolling_resistance(handle, rolling_resistance);
+    }
+
+    pub fn coRollingResistanceCombineRule(&self, handle: FlatHandle) -> u32 {
+        self.1
+            .materials
+            .rolling_resistance(utils::collider_handle(handle))
+            .combine_rule
+    }
+
+    pub fn coSetRollingResistanceCombineRule(&mut self, handle: FlatHandle, rule: u32) {
+        let handle = utils::collider_handle(handle);
+        let mut rolling_resistance = self.1.materials.rolling_resistance(handle);
+        rolling_resistance.combine_rule = super::combine_rule_from_u32(rule) as u32;
+        self.1
+            .materials
+            .set_rolling_resistance(handle, rolling_resistance);
+    }
+
+    /// The aerodynamic drag coefficient of this collider.
+    pub fn coDragCoefficient(&self, handle: FlatHandle) -> f64 {
+        self.1
+            .materials
+            .drag_coefficient(utils::collider_handle(handle))
+    }
+
+    /// Sets the aerodynamic drag coefficient of this collider.
+    ///
+    /// If it is positive, a quadratic drag force, proportional to the coefficient, the air
+    /// density, and the area of the projection of the collider’s bounding box along its
+    /// velocity, is applied to its rigid-body before each substep. Set it to 0 to disable the
+    /// drag.
+    pub fn coSetDragCoefficient(&mut self, handle: FlatHandle, coefficient: f64) {
+        self.1
+            .materials
+            .set_drag_coefficient(utils::collider_handle(handle), coefficient.max(0.0));
+    }
+
+    /// The user tags of this collider, as a 32-bit mask.
+    pub fn coTags(&self, handle: FlatHandle) -> u32 {
+        self.1.tags.get(utils::collider_handle(handle))
+    }
+
+    /// Sets the user tags of this collider, as a 32-bit mask.
+    ///
+    /// The tags don’t affect the contacts, they can be required or excluded by the scene
+    /// queries.
+    pub fn coSetTags(&mut self, handle: FlatHandle, tags: u32) {
+        self.1.tags.set(utils::collider_handle(handle), tags)
+    }
+
     pub fn coFrictionCombineRule(&self, handle: FlatHandle) -> u32 {
         self.map(handle, |co| co.friction_combine_rule() as u32)
     }
@@ -920,14 +1411,33 @@ impl RawColliderSet {
         self.map_mut(handle, |co| co.set_collision_groups(groups))
     }
 
+    /// Sets the same collision groups to all the given colliders.
+    pub fn coSetCollisionGroupsBatch(&mut self, handles: Vec<FlatHandle>, groups: u32) {
+        let groups = super::unpack_interaction_groups(groups);
+        for handle in handles {
+            self.map_mut(handle, |co| co.set_collision_groups(groups))
+        }
+    }
+
     pub fn coSetSolverGroups(&mut self, handle: FlatHandle, groups: u32) {
         let groups = super::unpack_interaction_groups(groups);
         self.map_mut(handle, |co| co.set_solver_groups(groups))
     }
 
+    /// Sets the physics hooks enabled for this collider.
+    ///
+    /// The hooks set natively by these bindings, e.g., for the pair filtering rules, are kept.
+    /// If some of them are part of `hooks`, they are now considered as set by the user.
     pub fn coSetActiveHooks(&mut self, handle: FlatHandle, hooks: u32) {
         let hooks = ActiveHooks::from_bits(hooks).unwrap_or(ActiveHooks::empty());
-        self.map_mut(handle, |co| co.set_active_hooks(hooks));
+        let collider_handle = utils::collider_handle(handle);
+        self.1.added_hooks.remove(collider_handle, hooks);
+        self.1.materials.set_user_modify_solver_contacts(
+            collider_handle,
+            hooks.contains(ActiveHooks::MODIFY_SOLVER_CONTACTS),
+        );
+        let native = self.1.native_hooks(collider_handle);
+        self.map_mut(handle, |co| co.set_active_hooks(hooks | native));
     }
 
     pub fn coSetActiveEvents(&mut self, handle: FlatHandle, events: u32) {
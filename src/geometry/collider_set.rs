@@ -1,6 +1,7 @@
 use crate::dynamics::{RawIslandManager, RawRigidBodySet};
-use crate::geometry::RawShape;
+use crate::geometry::{ColliderMaterials, ColliderTags, RawShape};
 use crate::math::{RawRotation, RawVector};
+use crate::pipeline::AddedHooks;
 use crate::utils::{self, FlatHandle};
 use rapier::prelude::*;
 use wasm_bindgen::prelude::*;
@@ -12,8 +13,55 @@ enum MassPropsMode {
     MassProps,
 }
 
+/// Moves each bit `i` of the given group to the bit `table[i]`.
+///
+/// The bits without an entry in the table are kept in place, and the bits mapped to an index
+/// greater than 15 are dropped.
+fn remap_group(group: Group, table: &[u8]) -> Group {
+    let bits = group.bits();
+    let remapped = (0..16).filter(|i| bits & (1 << i) != 0).fold(0, |acc, i| {
+        match table.get(i as usize).copied().unwrap_or(i) {
+            target @ 0..=15 => acc | (1 << target),
+            _ => acc,
+        }
+    });
+    Group::from_bits_retain(remapped)
+}
+
+fn remap_groups(groups: InteractionGroups, table: &[u8]) -> InteractionGroups {
+    InteractionGroups::new(
+        remap_group(groups.memberships, table),
+        remap_group(groups.filter, table),
+    )
+}
+
+/// The state attached to the colliders by these bindings, on top of the collider set of Rapier.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct ColliderExt {
+    pub materials: ColliderMaterials,
+    pub tags: ColliderTags,
+    /// The filtering active hooks set natively for the pair filtering rules and exclusions.
+    pub added_hooks: AddedHooks,
+}
+
+impl ColliderExt {
+    /// The active hooks set natively on a collider by these bindings, and not by the user.
+    pub fn native_hooks(&self, handle: ColliderHandle) -> ActiveHooks {
+        let mut hooks = self.added_hooks.get(handle);
+        if self
+            .materials
+            .get(handle)
+            .is_some_and(|m| !m.had_modify_solver_contacts)
+        {
+            hooks |= ActiveHooks::MODIFY_SOLVER_CONTACTS;
+        }
+        hooks
+    }
+}
+
 #[wasm_bindgen]
-pub struct RawColliderSet(pub(crate) ColliderSet);
+pub struct RawColliderSet(pub(crate) ColliderSet, pub(crate) ColliderExt);
 
 impl RawColliderSet {
     pub(crate) fn map<T>(&self, handle: FlatHandle, f: impl FnOnce(&Collider) -> T) -> T {
@@ -138,13 +186,26 @@ impl RawColliderSet {
 impl RawColliderSet {
     #[wasm_bindgen(constructor)]
     pub fn new() -> Self {
-        RawColliderSet(ColliderSet::new())
+        RawColliderSet(ColliderSet::new(), ColliderExt::default())
     }
 
     pub fn len(&self) -> usize {
         self.0.len()
     }
 
+    /// The density of the fluid the colliders with an aerodynamic drag coefficient move
+    /// through.
+    pub fn airDensity(&self) -> f64 {
+        self.1.materials.air_density
+    }
+
+    /// Sets the density of the fluid the colliders with an aerodynamic drag coefficient move
+    /// through, e.g., `1.225` for air at sea level or `1000` for water (in kg/m³). Negative
+    /// values are treated as 0.
+    pub fn setAirDensity(&mut self, density: f64) {
+        self.1.materials.air_density = density.max(0.0);
+    }
+
     pub fn contains(&self, handle: FlatHandle) -> bool {
         self.0.get(utils::collider_handle(handle)).is_some()
     }
@@ -273,6 +334,9 @@ impl RawColliderSet {
     ) {
         let handle = utils::collider_handle(handle);
         self.0.remove(handle, &mut islands.0, &mut bodies.0, wakeUp);
+        self.1.materials.remove(handle);
+        self.1.tags.remove(handle);
+        self.1.added_hooks.remove(handle, ActiveHooks::all());
     }
 
     /// Checks if a collider with the given integer handle exists.
@@ -290,4 +354,29 @@ impl RawColliderSet {
             let _ = f.call1(&this, &JsValue::from(utils::flat_handle(handle.0)));
         }
     }
+
+    /// Remaps the collision group bits of all the colliders, in a single pass.
+    ///
+    /// Both the memberships and the filters are remapped, e.g., to swap the groups of two teams.
+    ///
+    /// # Parameters
+    /// - `table`: the bit `i` of the groups is moved to the bit `table[i]`. The bits without an
+    ///   entry in the table are kept in place, and the bits mapped to an index greater than 15
+    ///   are cleared.
+    /// - `solverGroups`: if `true`, the solver groups are remapped too.
+    pub fn remapCollisionGroups(&mut self, table: Vec<u8>, solverGroups: bool) {
+        for (_, co) in self.0.iter_mut() {
+            let collision_groups = remap_groups(co.collision_groups(), &table);
+            if collision_groups != co.collision_groups() {
+                co.set_collision_groups(collision_groups);
+            }
+
+            if solverGroups {
+                let solver_groups = remap_groups(co.solver_groups(), &table);
+                if solver_groups != co.solver_groups() {
+                    co.set_solver_groups(solver_groups);
+                }
+            }
+        }
+    }
 }
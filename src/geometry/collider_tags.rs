@@ -0,0 +1,54 @@
+use rapier::geometry::ColliderHandle;
+use std::collections::HashMap;
+
+/// The user tags of the colliders of a collider set, as 32-bit masks.
+///
+/// Unlike the collision groups, the tags don’t affect the contacts: they only serve as an
+/// additional filter for the scene queries.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct ColliderTags {
+    tags: HashMap<ColliderHandle, u32>,
+}
+
+impl ColliderTags {
+    pub fn get(&self, handle: ColliderHandle) -> u32 {
+        self.tags.get(&handle).copied().unwrap_or(0)
+    }
+
+    pub fn set(&mut self, handle: ColliderHandle, tags: u32) {
+        if tags == 0 {
+            let _ = self.tags.remove(&handle);
+        } else {
+            let _ = self.tags.insert(handle, tags);
+        }
+    }
+
+    pub fn remove(&mut self, handle: ColliderHandle) {
+        let _ = self.tags.remove(&handle);
+    }
+
+    /// A filter accepting the colliders with all the `required` tags and none of the `excluded`
+    /// ones.
+    pub fn filter(&self, required: u32, excluded: u32) -> TagFilter<'_> {
+        TagFilter {
+            tags: self,
+            required,
+            excluded,
+        }
+    }
+}
+
+/// A filter on the tags of the colliders, see `ColliderTags::filter`.
+#[derive(Copy, Clone)]
+pub(crate) struct TagFilter<'a> {
+    tags: &'a ColliderTags,
+    required: u32,
+    excluded: u32,
+}
+
+impl TagFilter<'_> {
+    pub fn matches(&self, handle: ColliderHandle) -> bool {
+        let tags = self.tags.get(handle);
+        tags & self.required == self.required && tags & self.excluded == 0
+    }
+}
@@ -1,4 +1,7 @@
 use crate::math::RawVector;
+use crate::utils::{self, FlatHandle};
+use rapier::geometry::ColliderHandle;
+use rapier::math::{Real, Vector};
 use rapier::parry::query;
 use wasm_bindgen::prelude::*;
 
@@ -29,3 +32,82 @@ impl RawShapeContact {
         self.contact.normal2.into_inner().into()
     }
 }
+
+/// The contact info between a shape and the collider closest to it.
+#[wasm_bindgen]
+pub struct RawColliderShapeContact {
+    pub(crate) handle: ColliderHandle,
+    pub(crate) contact: query::Contact,
+}
+
+#[wasm_bindgen]
+impl RawColliderShapeContact {
+    pub fn colliderHandle(&self) -> FlatHandle {
+        utils::flat_handle(self.handle.0)
+    }
+
+    pub fn distance(&self) -> f64 {
+        self.contact.dist
+    }
+
+    pub fn point1(&self) -> RawVector {
+        self.contact.point1.coords.into()
+    }
+
+    pub fn point2(&self) -> RawVector {
+        self.contact.point2.coords.into()
+    }
+
+    pub fn normal1(&self) -> RawVector {
+        self.contact.normal1.into_inner().into()
+    }
+
+    pub fn normal2(&self) -> RawVector {
+        self.contact.normal2.into_inner().into()
+    }
+}
+
+/// The penetration between two overlapping shapes.
+#[wasm_bindgen]
+pub struct RawShapePenetration {
+    pub(crate) contact: query::Contact,
+}
+
+impl RawShapePenetration {
+    /// The penetration described by the given contact, if the shapes overlap.
+    pub(crate) fn from_contact(contact: query::Contact) -> Option<Self> {
+        (contact.dist < 0.0).then_some(RawShapePenetration { contact })
+    }
+}
+
+#[wasm_bindgen]
+impl RawShapePenetration {
+    pub fn depth(&self) -> f64 {
+        -self.contact.dist
+    }
+
+    /// The minimum translation to apply to the first shape to separate it from the second one.
+    pub fn mtv(&self) -> RawVector {
+        (self.contact.normal1.into_inner() * self.contact.dist).into()
+    }
+}
+
+/// The result of the depenetration of a shape from the colliders of the scene.
+#[wasm_bindgen]
+pub struct RawShapeDepenetration {
+    pub(crate) position: Vector<Real>,
+    pub(crate) resolved: bool,
+}
+
+#[wasm_bindgen]
+impl RawShapeDepenetration {
+    /// The corrected position of the shape.
+    pub fn position(&self) -> RawVector {
+        self.position.into()
+    }
+
+    /// Were all the penetrations resolved within the allowed number of iterations?
+    pub fn resolved(&self) -> bool {
+        self.resolved
+    }
+}
@@ -0,0 +1,91 @@
+use crate::dynamics::RawRigidBodySet;
+use crate::geometry::{RawColliderSet, RawNarrowPhase};
+use crate::utils::{self, FlatHandle};
+use rapier::dynamics::RigidBodyHandle;
+use std::collections::{HashSet, VecDeque};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+impl RawNarrowPhase {
+    /// The rigid-bodies connected to the given rigid-body through the current contact graph,
+    /// including itself.
+    ///
+    /// Two rigid-bodies are connected if any of their colliders have an active contact. The
+    /// traversal doesn’t continue past the non-dynamic rigid-bodies other than the starting one,
+    /// so that everything resting on the same ground isn’t reported as connected, but these
+    /// rigid-bodies are still part of the result. Sensors and colliders without parent are
+    /// ignored.
+    ///
+    /// # Parameters
+    /// - `filterGroups`: if set, only the contacts between colliders whose collision groups are
+    ///   compatible with these groups are traversed.
+    pub fn connectedBodies(
+        &self,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        handle: FlatHandle,
+        filterGroups: Option<u32>,
+    ) -> Vec<FlatHandle> {
+        let start = utils::body_handle(handle);
+        if bodies.0.get(start).is_none() {
+            return vec![];
+        }
+
+        let groups = filterGroups.map(crate::geometry::unpack_interaction_groups);
+        let accepts = |co_handle| {
+            colliders
+                .0
+                .get(co_handle)
+                .is_some_and(|co| groups.map_or(true, |groups| groups.test(co.collision_groups())))
+        };
+
+        let mut visited: HashSet<RigidBodyHandle> = HashSet::new();
+        let mut queue = VecDeque::new();
+        let mut result = vec![];
+        let _ = visited.insert(start);
+        queue.push_back(start);
+
+        while let Some(body) = queue.pop_front() {
+            result.push(utils::flat_handle(body.0));
+
+            let rb = match bodies.0.get(body) {
+                Some(rb) => rb,
+                None => continue,
+            };
+
+            if body != start && !rb.is_dynamic() {
+                continue;
+            }
+
+            for co_handle in rb.colliders() {
+                if !accepts(*co_handle) {
+                    continue;
+                }
+
+                for pair in self.0.contact_pairs_with(*co_handle) {
+                    if !pair.has_any_active_contact {
+                        continue;
+                    }
+
+                    let other = if pair.collider1 == *co_handle {
+                        pair.collider2
+                    } else {
+                        pair.collider1
+                    };
+
+                    if !accepts(other) {
+                        continue;
+                    }
+
+                    if let Some(parent) = colliders.0.get(other).and_then(|co| co.parent()) {
+                        if visited.insert(parent) {
+                            queue.push_back(parent);
+                        }
+                    }
+                }
+            }
+        }
+
+        result
+    }
+}
@@ -0,0 +1,153 @@
+use crate::geometry::{RawColliderSet, RawNarrowPhase};
+use crate::utils::{self, FlatHandle};
+use rapier::geometry::{ContactManifold, Shape, TriMeshFlags};
+use rapier::math::{Real, Vector};
+use rapier::parry::shape::HeightFieldFlags;
+use wasm_bindgen::prelude::*;
+
+/// The maximum angle between a contact normal and the normal of a triangle for the contact
+/// normal to be considered as corrected to the triangle normal.
+const CORRECTED_NORMAL_ANGLE: Real = 1.0e-3;
+
+/// The face normal of the given triangle of a triangle mesh or heightfield, in the local-space
+/// of the shape.
+fn triangle_normal(shape: &dyn Shape, triangle: u32) -> Option<Vector<Real>> {
+    let triangle = if let Some(trimesh) = shape.as_trimesh() {
+        (triangle < trimesh.num_triangles() as u32).then(|| trimesh.triangle(triangle))?
+    } else {
+        shape.as_heightfield()?.triangle_at_id(triangle)?
+    };
+    triangle.normal().map(|n| n.into_inner())
+}
+
+/// Does the internal-edge correction of the given shape, a triangle mesh or a heightfield,
+/// apply?
+fn fixes_internal_edges(shape: &dyn Shape) -> Option<bool> {
+    if let Some(trimesh) = shape.as_trimesh() {
+        Some(trimesh.flags().contains(TriMeshFlags::FIX_INTERNAL_EDGES))
+    } else {
+        let heightfield = shape.as_heightfield()?;
+        Some(
+            heightfield
+                .flags()
+                .contains(HeightFieldFlags::FIX_INTERNAL_EDGES),
+        )
+    }
+}
+
+/// The indices of the contacts of a manifold, on the edge or vertex of a triangle of `shape`,
+/// whose normal matches the face normal of the triangle. These are the contacts that would
+/// otherwise have a normal pointing out of the triangle edge, i.e., that have been corrected
+/// by the internal-edge correction of `shape`.
+fn corrected_contacts(shape: &dyn Shape, manifold: &ContactManifold, first: bool) -> Vec<usize> {
+    let (subshape, local_n) = if first {
+        (manifold.subshape1, manifold.local_n1)
+    } else {
+        (manifold.subshape2, manifold.local_n2)
+    };
+
+    let normal = match triangle_normal(shape, subshape) {
+        Some(normal) => normal,
+        None => return vec![],
+    };
+
+    // The contact normal points out of the triangle, like the face normal, or in the opposite
+    // direction for a back-face contact.
+    if normal.dot(&local_n).abs() < CORRECTED_NORMAL_ANGLE.cos() {
+        return vec![];
+    }
+
+    manifold
+        .points
+        .iter()
+        .enumerate()
+        .filter(|(_, contact)| {
+            let fid = if first { contact.fid1 } else { contact.fid2 };
+            !fid.is_face()
+        })
+        .map(|(i, _)| i)
+        .collect()
+}
+
+#[wasm_bindgen]
+impl RawColliderSet {
+    /// Enables or disables, at runtime, the correction of the contacts with the internal
+    /// edges of this collider, if it is a triangle mesh or a heightfield.
+    ///
+    /// This prevents objects sliding on flat surfaces from snagging on the edges between
+    /// triangles. Returns `false` if the collider isn’t a triangle mesh or a heightfield, or
+    /// if the triangle mesh topology needed for the correction couldn’t be computed.
+    pub fn coSetFixInternalEdges(&mut self, handle: FlatHandle, enabled: bool) -> bool {
+        self.map_mut(handle, |co| {
+            let shape = co.shape_mut();
+            if let Some(trimesh) = shape.as_trimesh_mut() {
+                let mut flags = trimesh.flags();
+                flags.set(TriMeshFlags::FIX_INTERNAL_EDGES, enabled);
+                trimesh.set_flags(flags).is_ok()
+            } else if let Some(heightfield) = shape.as_heightfield_mut() {
+                let mut flags = heightfield.flags();
+                flags.set(HeightFieldFlags::FIX_INTERNAL_EDGES, enabled);
+                heightfield.set_flags(flags);
+                true
+            } else {
+                false
+            }
+        })
+    }
+
+    /// Is the correction of the contacts with the internal edges of this collider enabled?
+    ///
+    /// Returns `None` if the collider isn’t a triangle mesh or a heightfield.
+    pub fn coFixInternalEdges(&self, handle: FlatHandle) -> Option<bool> {
+        self.map(handle, |co| fixes_internal_edges(co.shape()))
+    }
+}
+
+#[wasm_bindgen]
+impl RawNarrowPhase {
+    /// Applies the given JavaScript function to each contact point corrected by the
+    /// internal-edge correction of a triangle mesh or heightfield.
+    ///
+    /// A contact is reported if it lies on an edge or a vertex of a triangle, and its normal
+    /// was aligned with the face normal of the triangle. This helps diagnosing objects snagging
+    /// on the seams of flat surfaces: the contacts at the seams of a collider without
+    /// internal-edge correction aren’t reported.
+    ///
+    /// # Parameters
+    /// - `f(meshHandle, otherHandle, manifoldIndex, contactIndex)`: the function called with
+    ///   the handle of the triangle mesh or heightfield, the handle of the other collider, and
+    ///   the indices of the contact manifold and of the contact point in the contact pair.
+    pub fn forEachCorrectedContact(&self, colliders: &RawColliderSet, f: &js_sys::Function) {
+        let this = JsValue::null();
+
+        for pair in self.0.contact_pairs() {
+            if !pair.has_any_active_contact {
+                continue;
+            }
+
+            for (mesh, other, first) in [
+                (pair.collider1, pair.collider2, true),
+                (pair.collider2, pair.collider1, false),
+            ] {
+                let shape = match colliders.0.get(mesh) {
+                    Some(co) if fixes_internal_edges(co.shape()) == Some(true) => co.shape(),
+                    _ => continue,
+                };
+
+                for (i, manifold) in pair.manifolds.iter().enumerate() {
+                    for j in corrected_contacts(shape, manifold, first) {
+                        let _ = f.apply(
+                            &this,
+                            &js_sys::Array::of4(
+                                &JsValue::from(utils::flat_handle(mesh.0)),
+                                &JsValue::from(utils::flat_handle(other.0)),
+                                &JsValue::from(i as u32),
+                                &JsValue::from(j as u32),
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::geometry::RawColliderSet;
+#[cfg(feature = "dim3")]
+use crate::math::RawSdpMatrix3;
+use crate::math::RawVector;
+use crate::utils::FlatHandle;
+use rapier::dynamics::MassProperties;
+#[cfg(feature = "dim3")]
+use rapier::parry::utils::SdpMatrix3;
+use wasm_bindgen::prelude::*;
+
+/// Mass properties: a mass, a center of mass, and an angular inertia.
+#[wasm_bindgen]
+pub struct RawMassProperties(pub(crate) MassProperties);
+
+#[wasm_bindgen]
+impl RawMassProperties {
+    pub fn mass(&self) -> f64 {
+        self.0.mass()
+    }
+
+    /// The center of mass, in the frame these mass properties are expressed in.
+    pub fn centerOfMass(&self) -> RawVector {
+        self.0.local_com.coords.into()
+    }
+
+    /// The angular inertia, relative to the center of mass.
+    #[cfg(feature = "dim2")]
+    pub fn angularInertia(&self) -> f64 {
+        self.0.principal_inertia()
+    }
+
+    /// The angular inertia tensor relative to the center of mass, in the frame these mass
+    /// properties are expressed in.
+    #[cfg(feature = "dim3")]
+    pub fn angularInertia(&self) -> RawSdpMatrix3 {
+        SdpMatrix3::from_sdp_matrix(self.0.reconstruct_inertia_matrix()).into()
+    }
+}
+
+#[wasm_bindgen]
+impl RawColliderSet {
+    /// The mass properties this collider contributes to its parent rigid-body, expressed in
+    /// world-space.
+    ///
+    /// These are computed from the collider density, or set explicitly, depending on how the
+    /// collider was configured.
+    pub fn coMassProperties(&self, handle: FlatHandle) -> RawMassProperties {
+        self.map(handle, |co| {
+            RawMassProperties(co.mass_properties().transform_by(co.position()))
+        })
+    }
+}
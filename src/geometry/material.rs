@@ -0,0 +1,377 @@
+use crate::dynamics::{ang_dot, cross, inertia_mul, inv_mass};
+use rapier::dynamics::{RigidBody, RigidBodyHandle, RigidBodySet};
+use rapier::geometry::{ColliderHandle, ColliderSet, NarrowPhase};
+use rapier::math::{AngVector, Point, Real, Vector};
+use rapier::pipeline::ContactModificationContext;
+use std::collections::HashMap;
+
+/// A material lookup table assigning a friction and restitution coefficient
+/// to each triangle of a triangle-mesh collider.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct TriangleMaterials {
+    /// The material index of each triangle.
+    pub triangle_materials: Vec<u32>,
+    /// The friction coefficient of each material.
+    pub friction: Vec<f64>,
+    /// The restitution coefficient of each material.
+    pub restitution: Vec<f64>,
+    /// The number of triangles of the collider’s shape.
+    pub num_triangles: u32,
+    /// Whether the `MODIFY_SOLVER_CONTACTS` active hook is also set by the user, either
+    /// before these materials were assigned or since then.
+    pub had_modify_solver_contacts: bool,
+}
+
+impl TriangleMaterials {
+    /// The material index of the given triangle, if it has one.
+    ///
+    /// Face feature ids of back-facing hits are offset by the number of triangles, so
+    /// the triangle index is wrapped around the number of triangles.
+    pub fn material(&self, triangle: u32) -> Option<u32> {
+        if self.num_triangles == 0 {
+            return None;
+        }
+
+        let material = *self
+            .triangle_materials
+            .get((triangle % self.num_triangles) as usize)?;
+        ((material as usize) < self.friction.len()).then_some(material)
+    }
+
+    fn coefficients(&self, triangle: u32) -> Option<(f64, f64)> {
+        self.material(triangle).map(|m| {
+            let m = m as usize;
+            (
+                self.friction[m],
+                self.restitution.get(m).copied().unwrap_or(0.0),
+            )
+        })
+    }
+}
+
+/// The rolling resistance of a collider, opposing the rolling of the rigid-bodies in contact
+/// with it.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct RollingResistance {
+    /// The rolling resistance coefficient, i.e., the maximum resisting torque per unit of
+    /// normal force. It has the dimension of a length.
+    pub coefficient: Real,
+    pub combine_rule: u32,
+}
+
+/// The component of an angular velocity making a body roll on a surface with the given normal.
+#[cfg(feature = "dim2")]
+fn rolling_angvel(angvel: AngVector<Real>, _normal: &Vector<Real>) -> AngVector<Real> {
+    angvel
+}
+
+/// The component of an angular velocity making a body roll on a surface with the given normal.
+#[cfg(feature = "dim3")]
+fn rolling_angvel(angvel: AngVector<Real>, normal: &Vector<Real>) -> AngVector<Real> {
+    angvel - normal * normal.dot(&angvel)
+}
+
+fn ang_norm(v: &AngVector<Real>) -> Real {
+    ang_dot(v, v).sqrt()
+}
+
+fn inv_angular_inertia(rb: Option<&RigidBody>, dir: &AngVector<Real>) -> Real {
+    match rb {
+        Some(rb) if rb.is_dynamic() => ang_dot(
+            dir,
+            &inertia_mul(&rb.mass_properties().effective_world_inv_inertia, dir),
+        ),
+        _ => 0.0,
+    }
+}
+
+/// The area of the projection of a box with the given half-extents on a plane (or line in 2D)
+/// orthogonal to the unit direction `dir`, expressed in the local frame of the box.
+#[cfg(feature = "dim2")]
+fn projected_area(half_extents: &Vector<Real>, dir: &Vector<Real>) -> Real {
+    2.0 * (dir.x.abs() * half_extents.y + dir.y.abs() * half_extents.x)
+}
+
+/// The area of the projection of a box with the given half-extents on a plane (or line in 2D)
+/// orthogonal to the unit direction `dir`, expressed in the local frame of the box.
+#[cfg(feature = "dim3")]
+fn projected_area(half_extents: &Vector<Real>, dir: &Vector<Real>) -> Real {
+    4.0 * (dir.x.abs() * half_extents.y * half_extents.z
+        + dir.y.abs() * half_extents.x * half_extents.z
+        + dir.z.abs() * half_extents.x * half_extents.y)
+}
+
+/// The default density of the fluid the colliders with a drag coefficient move through, i.e.,
+/// the density of the air at sea level in kg/m³.
+pub(crate) const DEFAULT_AIR_DENSITY: Real = 1.225;
+
+/// The materials attached to the colliders of a collider set, in addition to their
+/// friction and restitution.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct ColliderMaterials {
+    triangles: HashMap<ColliderHandle, TriangleMaterials>,
+    rolling_resistances: HashMap<ColliderHandle, RollingResistance>,
+    drag_coefficients: HashMap<ColliderHandle, Real>,
+    /// The density of the fluid the colliders with a drag coefficient move through.
+    pub air_density: Real,
+}
+
+impl Default for ColliderMaterials {
+    fn default() -> Self {
+        Self {
+            triangles: HashMap::new(),
+            rolling_resistances: HashMap::new(),
+            drag_coefficients: HashMap::new(),
+            air_density: DEFAULT_AIR_DENSITY,
+        }
+    }
+}
+
+impl ColliderMaterials {
+    pub fn get(&self, handle: ColliderHandle) -> Option<&TriangleMaterials> {
+        self.triangles.get(&handle)
+    }
+
+    pub fn insert(&mut self, handle: ColliderHandle, materials: TriangleMaterials) {
+        let _ = self.triangles.insert(handle, materials);
+    }
+
+    /// Records whether the user also set the `MODIFY_SOLVER_CONTACTS` active hook of a
+    /// collider with per-triangle materials.
+    pub fn set_user_modify_solver_contacts(&mut self, handle: ColliderHandle, enabled: bool) {
+        if let Some(materials) = self.triangles.get_mut(&handle) {
+            materials.had_modify_solver_contacts = enabled;
+        }
+    }
+
+    pub fn remove_triangles(&mut self, handle: ColliderHandle) -> Option<TriangleMaterials> {
+        self.triangles.remove(&handle)
+    }
+
+    pub fn rolling_resistance(&self, handle: ColliderHandle) -> RollingResistance {
+        self.rolling_resistances
+            .get(&handle)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn set_rolling_resistance(
+        &mut self,
+        handle: ColliderHandle,
+        rolling_resistance: RollingResistance,
+    ) {
+        if rolling_resistance.coefficient > 0.0 || rolling_resistance.combine_rule != 0 {
+            let _ = self.rolling_resistances.insert(handle, rolling_resistance);
+        } else {
+            let _ = self.rolling_resistances.remove(&handle);
+        }
+    }
+
+    pub fn drag_coefficient(&self, handle: ColliderHandle) -> Real {
+        self.drag_coefficients.get(&handle).copied().unwrap_or(0.0)
+    }
+
+    pub fn set_drag_coefficient(&mut self, handle: ColliderHandle, coefficient: Real) {
+        if coefficient > 0.0 {
+            let _ = self.drag_coefficients.insert(handle, coefficient);
+        } else {
+            let _ = self.drag_coefficients.remove(&handle);
+        }
+    }
+
+    /// Removes all the materials of the given collider.
+    pub fn remove(&mut self, handle: ColliderHandle) {
+        let _ = self.triangles.remove(&handle);
+        let _ = self.rolling_resistances.remove(&handle);
+        let _ = self.drag_coefficients.remove(&handle);
+    }
+
+    /// Applies the quadratic aerodynamic drag of the colliders to their rigid-bodies, for a
+    /// substep of length `dt`.
+    ///
+    /// The drag force of each collider with a drag coefficient is `0.5 * ρ * Cd * A * |v|²`,
+    /// opposing the velocity `v` of the center of its bounding box, where `A` is the area of
+    /// the projection of its local bounding box along `v`. The force is applied at the center
+    /// of the bounding box, so an asymmetric body also experiences a drag torque. The impulse
+    /// never exceeds the one stopping the center of the bounding box.
+    pub fn apply_aerodynamic_drag(
+        &mut self,
+        colliders: &ColliderSet,
+        bodies: &mut RigidBodySet,
+        dt: Real,
+    ) {
+        if self.drag_coefficients.is_empty() || self.air_density <= 0.0 {
+            return;
+        }
+
+        // Forget the colliders removed with their rigid-bodies.
+        self.drag_coefficients
+            .retain(|handle, _| colliders.get(*handle).is_some());
+
+        for (handle, coefficient) in &self.drag_coefficients {
+            let co = match colliders.get(*handle) {
+                Some(co) if co.is_enabled() => co,
+                _ => continue,
+            };
+            let rb = match co.parent().and_then(|h| bodies.get_mut(h)) {
+                Some(rb) if rb.is_dynamic() && !rb.is_sleeping() => rb,
+                _ => continue,
+            };
+
+            let local_aabb = co.shape().compute_local_aabb();
+            let point: Point<Real> = co.position() * local_aabb.center();
+            let velocity = rb.velocity_at_point(&point);
+            let speed = velocity.norm();
+            if speed <= Real::EPSILON {
+                continue;
+            }
+
+            let dir = velocity / speed;
+            let local_dir = co.position().rotation.inverse_transform_vector(&dir);
+            let area = projected_area(&local_aabb.half_extents(), &local_dir);
+            let drag = 0.5 * self.air_density * coefficient * area * speed * speed;
+
+            let r = point - rb.center_of_mass();
+            let k = inv_mass(rb, &dir, &cross(&r, &dir));
+            if k <= 0.0 {
+                continue;
+            }
+
+            let impulse = (drag * dt).min(speed / k);
+            rb.apply_impulse_at_point(dir * -impulse, point, false);
+        }
+    }
+
+    /// Applies the rolling resistance between the rigid-bodies in contact, for a substep.
+    ///
+    /// For each contact pair involving a collider with rolling resistance, an angular impulse
+    /// opposing the relative rolling velocity of the rigid-bodies is applied, up to the
+    /// combined coefficient times the normal impulse of the contact computed by the last
+    /// substep.
+    pub fn apply_rolling_resistance(
+        &mut self,
+        narrow_phase: &NarrowPhase,
+        colliders: &ColliderSet,
+        bodies: &mut RigidBodySet,
+    ) {
+        if self.rolling_resistances.is_empty() {
+            return;
+        }
+
+        // Forget the colliders removed with their rigid-bodies.
+        self.rolling_resistances
+            .retain(|handle, _| colliders.get(*handle).is_some());
+
+        let mut impulses: Vec<(RigidBodyHandle, AngVector<Real>)> = vec![];
+
+        for pair in narrow_phase.contact_pairs() {
+            if !pair.has_any_active_contact {
+                continue;
+            }
+
+            let resistance1 = self.rolling_resistances.get(&pair.collider1);
+            let resistance2 = self.rolling_resistances.get(&pair.collider2);
+            if resistance1.is_none() && resistance2.is_none() {
+                continue;
+            }
+
+            let resistance1 = resistance1.copied().unwrap_or_default();
+            let resistance2 = resistance2.copied().unwrap_or_default();
+            let coefficient = combine_coefficients(
+                resistance1.coefficient,
+                resistance2.coefficient,
+                resistance1.combine_rule.max(resistance2.combine_rule),
+            );
+
+            for manifold in &pair.manifolds {
+                let normal_impulse: Real = manifold.points.iter().map(|c| c.data.impulse).sum();
+                let max_impulse = coefficient * normal_impulse;
+                if max_impulse <= 0.0 {
+                    continue;
+                }
+
+                let rb1 = manifold.data.rigid_body1.and_then(|h| bodies.get(h));
+                let rb2 = manifold.data.rigid_body2.and_then(|h| bodies.get(h));
+                let angvel1 = rb1.map(|rb| rb.vels().angvel).unwrap_or_default();
+                let angvel2 = rb2.map(|rb| rb.vels().angvel).unwrap_or_default();
+                let relative_angvel = rolling_angvel(angvel2 - angvel1, &manifold.data.normal);
+                let speed = ang_norm(&relative_angvel);
+                if speed <= Real::EPSILON {
+                    continue;
+                }
+
+                let dir = relative_angvel / speed;
+                let k = inv_angular_inertia(rb1, &dir) + inv_angular_inertia(rb2, &dir);
+                if k <= 0.0 {
+                    continue;
+                }
+
+                let impulse = (speed / k).min(max_impulse);
+                if let Some(handle) = manifold.data.rigid_body1 {
+                    impulses.push((handle, dir * impulse));
+                }
+                if let Some(handle) = manifold.data.rigid_body2 {
+                    impulses.push((handle, dir * -impulse));
+                }
+            }
+        }
+
+        for (handle, impulse) in impulses {
+            if let Some(rb) = bodies.get_mut(handle) {
+                if rb.is_dynamic() {
+                    rb.apply_torque_impulse(impulse, false);
+                }
+            }
+        }
+    }
+
+    /// Overrides the friction and restitution of the solver contacts involving a
+    /// triangle with a material.
+    pub fn modify_solver_contacts(&self, ctxt: &mut ContactModificationContext) {
+        if self.triangles.is_empty() {
+            return;
+        }
+
+        let coeffs1 = self
+            .get(ctxt.collider1)
+            .and_then(|m| m.coefficients(ctxt.manifold.subshape1));
+        let coeffs2 = self
+            .get(ctxt.collider2)
+            .and_then(|m| m.coefficients(ctxt.manifold.subshape2));
+
+        if coeffs1.is_none() && coeffs2.is_none() {
+            return;
+        }
+
+        let co1 = &ctxt.colliders[ctxt.collider1];
+        let co2 = &ctxt.colliders[ctxt.collider2];
+        let (friction1, restitution1) =
+            coeffs1.unwrap_or((co1.material().friction, co1.material().restitution));
+        let (friction2, restitution2) =
+            coeffs2.unwrap_or((co2.material().friction, co2.material().restitution));
+
+        let friction_rule =
+            (co1.friction_combine_rule() as u32).max(co2.friction_combine_rule() as u32);
+        let restitution_rule =
+            (co1.restitution_combine_rule() as u32).max(co2.restitution_combine_rule() as u32);
+        let friction = combine_coefficients(friction1, friction2, friction_rule);
+        let restitution = combine_coefficients(restitution1, restitution2, restitution_rule);
+
+        for contact in ctxt.solver_contacts.iter_mut() {
+            contact.friction = friction;
+            contact.restitution = restitution;
+        }
+    }
+}
+
+fn combine_coefficients(coeff1: f64, coeff2: f64, rule: u32) -> f64 {
+    use rapier::dynamics::CoefficientCombineRule;
+
+    match super::combine_rule_from_u32(rule) {
+        CoefficientCombineRule::Average => (coeff1 + coeff2) / 2.0,
+        CoefficientCombineRule::Min => coeff1.min(coeff2),
+        CoefficientCombineRule::Multiply => coeff1 * coeff2,
+        _ => coeff1.max(coeff2),
+    }
+}
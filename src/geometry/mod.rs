@@ -2,24 +2,41 @@
 
 pub use self::broad_phase::*;
 pub use self::collider_set::*;
+pub use self::collider_tags::*;
 pub use self::contact::*;
 pub use self::feature::*;
+pub use self::mass_properties::*;
+pub(crate) use self::material::*;
 pub use self::narrow_phase::*;
 pub use self::point::*;
+#[cfg(feature = "dim2")]
+pub use self::polygon::*;
 pub use self::ray::*;
 pub use self::shape::*;
 pub use self::toi::*;
+pub use self::trigger_set::*;
 
 mod broad_phase;
 mod collider;
 mod collider_set;
+mod collider_tags;
 mod contact;
+mod contact_graph;
 mod feature;
+#[cfg(feature = "dim3")]
+mod internal_edges;
+mod mass_properties;
+mod material;
 mod narrow_phase;
 mod point;
+#[cfg(feature = "dim2")]
+mod polygon;
 mod ray;
 mod shape;
+#[cfg(feature = "dim2")]
+mod tilemap;
 mod toi;
+mod trigger_set;
 
 use rapier::dynamics::CoefficientCombineRule;
 use rapier::geometry::InteractionGroups;
@@ -1,17 +1,551 @@
+use crate::dynamics::RawRigidBodySet;
+use crate::geometry::{RawBroadPhase, RawColliderSet};
 use crate::math::RawVector;
 use crate::utils::{self, FlatHandle};
-use rapier::geometry::{ContactManifold, ContactPair, NarrowPhase};
-use rapier::math::Real;
+use rapier::dynamics::{RigidBodyHandle, RigidBodySet, RigidBodyType, RigidBodyVelocity};
+use rapier::geometry::{Collider, ColliderHandle, ContactManifold, ContactPair, NarrowPhase};
+use rapier::math::{Point, Real, Vector};
+use rapier::parry::bounding_volume::BoundingVolume;
+use rapier::pipeline::QueryFilter;
+use std::collections::{HashMap, HashSet};
 use wasm_bindgen::prelude::*;
 
+/// Stable identifiers of the pairs of colliders in contact (or intersecting), persisting
+/// across simulation steps for as long as the pair is touching.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct ContactPairIds {
+    pub enabled: bool,
+    active: HashMap<(u64, u64), u32>,
+    /// The pairs that stopped touching during the last step. They are kept until the next
+    /// update so that the ids of the stopped collision events can still be retrieved.
+    ended: HashMap<(u64, u64), u32>,
+    next_id: u32,
+}
+
+impl ContactPairIds {
+    fn key(handle1: ColliderHandle, handle2: ColliderHandle) -> (u64, u64) {
+        let h1 = utils::flat_handle(handle1.0).to_bits();
+        let h2 = utils::flat_handle(handle2.0).to_bits();
+        (h1.min(h2), h1.max(h2))
+    }
+
+    pub fn get(&self, handle1: ColliderHandle, handle2: ColliderHandle) -> Option<u32> {
+        let key = Self::key(handle1, handle2);
+        self.active
+            .get(&key)
+            .or_else(|| self.ended.get(&key))
+            .copied()
+    }
+
+    /// Forgets the pairs that stopped touching during the previous step.
+    pub fn begin_step(&mut self) {
+        self.ended.clear();
+    }
+
+    /// Assigns new ids to the pairs that started touching since the last update, and retires
+    /// the ids of the pairs that stopped touching.
+    pub fn update(&mut self, narrow_phase: &NarrowPhase) {
+        if !self.enabled {
+            return;
+        }
+
+        let contacts = narrow_phase
+            .contact_pairs()
+            .filter(|pair| pair.has_any_active_contact)
+            .map(|pair| (pair.collider1, pair.collider2));
+        let intersections = narrow_phase
+            .intersection_pairs()
+            .filter(|(_, _, intersecting)| *intersecting)
+            .map(|(h1, h2, _)| (h1, h2));
+        let mut active = HashMap::with_capacity(self.active.len());
+
+        for (h1, h2) in contacts.chain(intersections) {
+            let key = Self::key(h1, h2);
+            let id = self.active.remove(&key).unwrap_or_else(|| {
+                let id = self.next_id;
+                self.next_id = self.next_id.wrapping_add(1);
+                id
+            });
+            let _ = active.insert(key, id);
+        }
+
+        // The remaining pairs are the ones that ended.
+        let ended = std::mem::replace(&mut self.active, active);
+        self.ended.extend(ended);
+    }
+}
+
+/// The impact between two colliders that started being in contact during the last step.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct Impact {
+    pub collider1: ColliderHandle,
+    pub collider2: ColliderHandle,
+    /// The sum of the normal impulses applied at each contact point of the pair.
+    pub normal_impulse: Real,
+    /// The relative velocity of the colliders along the contact normal, at the beginning of
+    /// the step. It is negative when the colliders are approaching each other.
+    pub relative_normal_velocity: Real,
+}
+
+/// Records the impacts of the pairs of colliders that start being in contact.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct ImpactTracker {
+    pub enabled: bool,
+    touching: HashSet<(ColliderHandle, ColliderHandle)>,
+    /// The velocity and world-space center-of-mass of each moving rigid-body at the
+    /// beginning of the step. It is only filled during a step.
+    #[serde(skip)]
+    velocities: HashMap<RigidBodyHandle, (RigidBodyVelocity, Point<Real>)>,
+    pub impacts: Vec<Impact>,
+}
+
+impl ImpactTracker {
+    /// Saves the velocities of the moving rigid-bodies before they get modified by the solver.
+    pub fn begin_step(&mut self, bodies: &RigidBodySet) {
+        if !self.enabled {
+            return;
+        }
+
+        self.velocities.clear();
+        for (handle, rb) in bodies.iter() {
+            if !rb.is_fixed() && !rb.is_sleeping() {
+                let _ = self
+                    .velocities
+                    .insert(handle, (*rb.vels(), *rb.center_of_mass()));
+            }
+        }
+    }
+
+    fn velocity_at_point(
+        &self,
+        body: Option<RigidBodyHandle>,
+        point: &Point<Real>,
+    ) -> Vector<Real> {
+        body.and_then(|h| self.velocities.get(&h))
+            .map(|(vels, com)| vels.velocity_at_point(point, com))
+            .unwrap_or_else(Vector::zeros)
+    }
+
+    /// Forgets the impacts recorded during the previous step.
+    pub fn clear_impacts(&mut self) {
+        self.impacts.clear();
+    }
+
+    /// Records the impacts of the pairs that started being in contact during the last step.
+    pub fn update(&mut self, narrow_phase: &NarrowPhase) {
+        if !self.enabled {
+            return;
+        }
+
+        let mut touching = HashSet::with_capacity(self.touching.len());
+
+        for pair in narrow_phase.contact_pairs() {
+            if !pair.has_any_active_contact {
+                continue;
+            }
+
+            let key = (pair.collider1, pair.collider2);
+            let _ = touching.insert(key);
+
+            if self.touching.contains(&key) {
+                continue;
+            }
+
+            let mut normal_impulse = 0.0;
+            let mut relative_normal_velocity: Real = 0.0;
+
+            for manifold in &pair.manifolds {
+                normal_impulse += manifold.points.iter().map(|c| c.data.impulse).sum::<Real>();
+
+                for contact in &manifold.data.solver_contacts {
+                    let vel1 = self.velocity_at_point(manifold.data.rigid_body1, &contact.point);
+                    let vel2 = self.velocity_at_point(manifold.data.rigid_body2, &contact.point);
+                    let normal_vel = (vel2 - vel1).dot(&manifold.data.normal);
+                    relative_normal_velocity = relative_normal_velocity.min(normal_vel);
+                }
+            }
+
+            self.impacts.push(Impact {
+                collider1: pair.collider1,
+                collider2: pair.collider2,
+                normal_impulse,
+                relative_normal_velocity,
+            });
+        }
+
+        self.touching = touching;
+        self.velocities.clear();
+    }
+}
+
+/// Records the pairs of colliders whose AABBs started or stopped overlapping.
+///
+/// The narrow-phase holds one pair for each pair of overlapping AABBs detected by the
+/// broad-phase, whether the colliders actually touch or not, so the proximity events are
+/// deduced from its pairs.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct ProximityTracker {
+    pub enabled: bool,
+    pairs: HashSet<(ColliderHandle, ColliderHandle)>,
+    /// The pairs that started (`true`) or stopped (`false`) overlapping during the last step.
+    pub events: Vec<(ColliderHandle, ColliderHandle, bool)>,
+}
+
+impl ProximityTracker {
+    /// Forgets the events recorded during the previous step.
+    pub fn clear_events(&mut self) {
+        self.events.clear();
+    }
+
+    /// Records the pairs that started or stopped overlapping since the last update.
+    pub fn update(&mut self, narrow_phase: &NarrowPhase) {
+        if !self.enabled {
+            return;
+        }
+
+        let contacts = narrow_phase
+            .contact_pairs()
+            .map(|pair| (pair.collider1, pair.collider2));
+        let intersections = narrow_phase
+            .intersection_pairs()
+            .map(|(h1, h2, _)| (h1, h2));
+        let mut pairs = HashSet::with_capacity(self.pairs.len());
+
+        for key in contacts.chain(intersections) {
+            if !self.pairs.remove(&key) {
+                self.events.push((key.0, key.1, true));
+            }
+            let _ = pairs.insert(key);
+        }
+
+        // The remaining pairs are the ones that stopped overlapping.
+        for (h1, h2) in std::mem::replace(&mut self.pairs, pairs) {
+            self.events.push((h1, h2, false));
+        }
+    }
+}
+
+/// The simulation time elapsed since each pair of intersecting colliders started intersecting.
+///
+/// The time is counted from the end of the substep where the pair started intersecting, i.e.,
+/// only the substeps completed while intersecting are added.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct IntersectionDurations {
+    pub enabled: bool,
+    durations: HashMap<(u64, u64), Real>,
+}
+
+impl IntersectionDurations {
+    pub fn get(&self, handle1: ColliderHandle, handle2: ColliderHandle) -> Option<Real> {
+        self.durations
+            .get(&ContactPairIds::key(handle1, handle2))
+            .copied()
+    }
+
+    /// Adds the length `dt` of the last substep to the duration of the pairs still
+    /// intersecting, and forgets the pairs that stopped intersecting.
+    pub fn update(&mut self, narrow_phase: &NarrowPhase, dt: Real) {
+        if !self.enabled {
+            return;
+        }
+
+        let mut durations = HashMap::with_capacity(self.durations.len());
+        for (h1, h2, intersecting) in narrow_phase.intersection_pairs() {
+            if intersecting {
+                let key = ContactPairIds::key(h1, h2);
+                let duration = self.durations.get(&key).map_or(0.0, |d| d + dt);
+                let _ = durations.insert(key, duration);
+            }
+        }
+        self.durations = durations;
+    }
+}
+
+/// Accumulates the normal impulses applied between each pair of colliders in contact during
+/// a step, and the total of these impulses applied to each rigid-body.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct ContactLoads {
+    pub enabled: bool,
+    pairs: HashMap<(u64, u64), (ColliderHandle, ColliderHandle, Real)>,
+    bodies: HashMap<RigidBodyHandle, Real>,
+}
+
+impl ContactLoads {
+    pub fn get(&self, handle1: ColliderHandle, handle2: ColliderHandle) -> Option<Real> {
+        self.pairs
+            .get(&ContactPairIds::key(handle1, handle2))
+            .map(|(_, _, impulse)| *impulse)
+    }
+
+    /// Forgets the loads accumulated during the previous step.
+    pub fn clear(&mut self) {
+        self.pairs.clear();
+        self.bodies.clear();
+    }
+
+    /// Adds the normal impulses applied during the last substep.
+    pub fn update(&mut self, narrow_phase: &NarrowPhase) {
+        if !self.enabled {
+            return;
+        }
+
+        for pair in narrow_phase.contact_pairs() {
+            if !pair.has_any_active_contact {
+                continue;
+            }
+
+            let mut pair_impulse = 0.0;
+            for manifold in &pair.manifolds {
+                let impulse = manifold.points.iter().map(|c| c.data.impulse).sum::<Real>();
+                for body in [manifold.data.rigid_body1, manifold.data.rigid_body2]
+                    .iter()
+                    .flatten()
+                {
+                    *self.bodies.entry(*body).or_default() += impulse;
+                }
+                pair_impulse += impulse;
+            }
+
+            let key = ContactPairIds::key(pair.collider1, pair.collider2);
+            self.pairs
+                .entry(key)
+                .or_insert((pair.collider1, pair.collider2, 0.0))
+                .2 += pair_impulse;
+        }
+    }
+}
+
+/// The state attached to the narrow-phase by these bindings, on top of the narrow-phase of
+/// Rapier.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct NarrowPhaseExt {
+    pub pair_ids: ContactPairIds,
+    pub impact_tracker: ImpactTracker,
+    pub proximity_tracker: ProximityTracker,
+    pub intersection_durations: IntersectionDurations,
+    pub contact_loads: ContactLoads,
+}
+
 #[wasm_bindgen]
-pub struct RawNarrowPhase(pub(crate) NarrowPhase);
+pub struct RawNarrowPhase(pub(crate) NarrowPhase, pub(crate) NarrowPhaseExt);
 
 #[wasm_bindgen]
 impl RawNarrowPhase {
     #[wasm_bindgen(constructor)]
     pub fn new() -> Self {
-        RawNarrowPhase(NarrowPhase::new())
+        RawNarrowPhase(NarrowPhase::new(), NarrowPhaseExt::default())
+    }
+
+    /// Enables the tracking of stable contact pair ids (disabled by default).
+    ///
+    /// The ids are assigned at the end of each simulation step.
+    pub fn setContactPairIdsEnabled(&mut self, enabled: bool) {
+        self.1.pair_ids.enabled = enabled;
+        if !enabled {
+            self.1.pair_ids = ContactPairIds::default();
+        }
+    }
+
+    pub fn contactPairIdsEnabled(&self) -> bool {
+        self.1.pair_ids.enabled
+    }
+
+    /// The stable id of the pair of colliders in contact (or intersecting).
+    ///
+    /// Returns `None` if the pair isn’t touching, or if contact pair ids are disabled.
+    pub fn contactPairId(&self, handle1: FlatHandle, handle2: FlatHandle) -> Option<u32> {
+        self.1.pair_ids.get(
+            utils::collider_handle(handle1),
+            utils::collider_handle(handle2),
+        )
+    }
+
+    /// Enables the recording of the impacts of the pairs of colliders that start being in
+    /// contact (disabled by default).
+    pub fn setImpactTrackingEnabled(&mut self, enabled: bool) {
+        self.1.impact_tracker.enabled = enabled;
+        if !enabled {
+            self.1.impact_tracker = ImpactTracker::default();
+        }
+    }
+
+    pub fn impactTrackingEnabled(&self) -> bool {
+        self.1.impact_tracker.enabled
+    }
+
+    /// Applies the given JavaScript function to each impact recorded during the last step.
+    ///
+    /// # Parameters
+    /// - `f(handle1, handle2, normalImpulse, relativeNormalVelocity)`: the function called with
+    ///   the handles of the two colliders, the total normal impulse applied between them, and
+    ///   their relative velocity along the contact normal before the impact.
+    pub fn forEachImpact(&self, f: &js_sys::Function) {
+        let this = JsValue::null();
+        for impact in &self.1.impact_tracker.impacts {
+            let _ = f.apply(
+                &this,
+                &js_sys::Array::of4(
+                    &JsValue::from(utils::flat_handle(impact.collider1.0)),
+                    &JsValue::from(utils::flat_handle(impact.collider2.0)),
+                    &JsValue::from(impact.normal_impulse),
+                    &JsValue::from(impact.relative_normal_velocity),
+                ),
+            );
+        }
+    }
+
+    /// Enables the recording of the pairs of colliders whose AABBs start or stop overlapping
+    /// (disabled by default).
+    pub fn setProximityEventsEnabled(&mut self, enabled: bool) {
+        self.1.proximity_tracker.enabled = enabled;
+        if !enabled {
+            self.1.proximity_tracker = ProximityTracker::default();
+        }
+    }
+
+    pub fn proximityEventsEnabled(&self) -> bool {
+        self.1.proximity_tracker.enabled
+    }
+
+    /// Applies the given JavaScript function to each proximity event recorded during the last
+    /// step.
+    ///
+    /// # Parameters
+    /// - `f(handle1, handle2, started)`: the function called with the handles of the two
+    ///   colliders, and `true` if their AABBs started overlapping, `false` if they stopped.
+    pub fn forEachProximityEvent(&self, f: &js_sys::Function) {
+        let this = JsValue::null();
+        for (handle1, handle2, started) in &self.1.proximity_tracker.events {
+            let _ = f.call3(
+                &this,
+                &JsValue::from(utils::flat_handle(handle1.0)),
+                &JsValue::from(utils::flat_handle(handle2.0)),
+                &JsValue::from(*started),
+            );
+        }
+    }
+
+    /// Enables the tracking of the simulation time elapsed since each pair of colliders
+    /// started intersecting (disabled by default).
+    pub fn setIntersectionDurationsEnabled(&mut self, enabled: bool) {
+        self.1.intersection_durations.enabled = enabled;
+        if !enabled {
+            self.1.intersection_durations = IntersectionDurations::default();
+        }
+    }
+
+    pub fn intersectionDurationsEnabled(&self) -> bool {
+        self.1.intersection_durations.enabled
+    }
+
+    /// The simulation time elapsed since the two colliders started intersecting.
+    ///
+    /// It is counted from the end of the substep during which they started intersecting, so it
+    /// is `0` after the step where the intersection started.
+    ///
+    /// Returns `None` if they aren’t intersecting, or if the intersection durations are
+    /// disabled.
+    pub fn intersectionDuration(&self, handle1: FlatHandle, handle2: FlatHandle) -> Option<f64> {
+        self.1.intersection_durations.get(
+            utils::collider_handle(handle1),
+            utils::collider_handle(handle2),
+        )
+    }
+
+    /// Enables the accumulation of the normal impulses applied between the colliders in
+    /// contact during each step (disabled by default).
+    pub fn setContactLoadsEnabled(&mut self, enabled: bool) {
+        self.1.contact_loads.enabled = enabled;
+        if !enabled {
+            self.1.contact_loads = ContactLoads::default();
+        }
+    }
+
+    pub fn contactLoadsEnabled(&self) -> bool {
+        self.1.contact_loads.enabled
+    }
+
+    /// The total normal impulse applied between the two colliders during the last step,
+    /// including all its substeps.
+    ///
+    /// Returns `None` if they weren’t in contact, or if the contact loads are disabled.
+    pub fn contactLoad(&self, handle1: FlatHandle, handle2: FlatHandle) -> Option<f64> {
+        self.1.contact_loads.get(
+            utils::collider_handle(handle1),
+            utils::collider_handle(handle2),
+        )
+    }
+
+    /// Applies the given JavaScript function to each pair of colliders in contact during the
+    /// last step.
+    ///
+    /// # Parameters
+    /// - `f(handle1, handle2, normalImpulse)`: the function called with the handles of the two
+    ///   colliders, and the total normal impulse applied between them during the last step.
+    pub fn forEachContactLoad(&self, f: &js_sys::Function) {
+        let this = JsValue::null();
+        for (handle1, handle2, impulse) in self.1.contact_loads.pairs.values() {
+            let _ = f.call3(
+                &this,
+                &JsValue::from(utils::flat_handle(handle1.0)),
+                &JsValue::from(utils::flat_handle(handle2.0)),
+                &JsValue::from(*impulse),
+            );
+        }
+    }
+
+    /// The sum of the normal impulses applied by all the contacts of the given rigid-body
+    /// during the last step, e.g., the load a support carries.
+    ///
+    /// Dividing it by the timestep gives the average contact force.
+    pub fn bodyContactLoad(&self, handle: FlatHandle) -> f64 {
+        self.1
+            .contact_loads
+            .bodies
+            .get(&utils::body_handle(handle))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Applies the given JavaScript function to each rigid-body in contact during the last
+    /// step.
+    ///
+    /// # Parameters
+    /// - `f(handle, normalImpulse)`: the function called with the handle of the rigid-body,
+    ///   and the sum of the normal impulses applied by its contacts during the last step.
+    pub fn forEachBodyContactLoad(&self, f: &js_sys::Function) {
+        let this = JsValue::null();
+        for (handle, impulse) in &self.1.contact_loads.bodies {
+            let _ = f.call2(
+                &this,
+                &JsValue::from(utils::flat_handle(handle.0)),
+                &JsValue::from(*impulse),
+            );
+        }
+    }
+
+    /// The current contact points and normals, as line segments for debug-rendering.
+    ///
+    /// Each segment starts at a solver contact point and extends along the contact normal
+    /// by `normalLength`. The result contains the two consecutive endpoints of each segment,
+    /// each with `DIM` coordinates, like the vertices of the debug render pipeline.
+    pub fn contactNormalLines(&self, normalLength: Real) -> Vec<f64> {
+        let mut lines = vec![];
+        for pair in self.0.contact_pairs() {
+            if !pair.has_any_active_contact {
+                continue;
+            }
+
+            for manifold in &pair.manifolds {
+                let normal = manifold.data.normal * normalLength;
+                for contact in &manifold.data.solver_contacts {
+                    lines.extend_from_slice(contact.point.coords.as_slice());
+                    lines.extend_from_slice((contact.point + normal).coords.as_slice());
+                }
+            }
+        }
+        lines
     }
 
     pub fn contact_pairs_with(&self, handle1: FlatHandle, f: js_sys::Function) {
@@ -46,8 +580,13 @@ impl RawNarrowPhase {
                 } else {
                     utils::flat_handle(h1.0)
                 };
+                let duration = self
+                    .1
+                    .intersection_durations
+                    .get(h1, h2)
+                    .map_or(JsValue::UNDEFINED, JsValue::from);
 
-                let _ = f.call1(&this, &JsValue::from(handle2));
+                let _ = f.call2(&this, &JsValue::from(handle2), &duration);
             }
         }
     }
@@ -57,6 +596,101 @@ impl RawNarrowPhase {
         let handle2 = utils::collider_handle(handle2);
         self.0.intersection_pair(handle1, handle2) == Some(true)
     }
+
+    /// Computes the contacts between the colliders at their current positions, without updating
+    /// this narrow-phase nor advancing the simulation, e.g., to preview penetrations.
+    ///
+    /// Only the contact geometry is computed: the manifolds have no solver contacts, and no
+    /// impulses. The sensors, the disabled colliders, and the pairs of colliders filtered out by
+    /// their collision groups or active collision types are ignored. The physics hooks are not
+    /// called.
+    ///
+    /// # Parameters
+    /// - `prediction`: the distance below which two colliders are considered in contact.
+    /// - `f(handle1, handle2, manifold)`: the function called with the handles of the two
+    ///   colliders for each of their contact manifolds. The manifold is only valid during the
+    ///   call.
+    pub fn previewContacts(
+        &self,
+        broad_phase: &RawBroadPhase,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        prediction: f64,
+        f: &js_sys::Function,
+    ) {
+        let this = JsValue::null();
+        let dispatcher = self.0.query_dispatcher();
+        let query_pipeline = broad_phase.0.as_query_pipeline(
+            dispatcher,
+            &bodies.0,
+            &colliders.0,
+            QueryFilter::default(),
+        );
+        let body_type = |co: &Collider| {
+            co.parent()
+                .and_then(|handle| bodies.0.get(handle))
+                .map_or(RigidBodyType::Fixed, |rb| rb.body_type())
+        };
+
+        for (handle1, co1) in colliders.0.iter() {
+            if co1.is_sensor() || !co1.is_enabled() {
+                continue;
+            }
+
+            let aabb = co1.compute_aabb().loosened(prediction);
+            for (handle2, co2) in query_pipeline.intersect_aabb_conservative(aabb) {
+                // Each pair is reported once, from its collider with the smallest handle.
+                if utils::flat_handle(handle2.0) <= utils::flat_handle(handle1.0)
+                    || co2.is_sensor()
+                    || !co2.is_enabled()
+                    || (co1.parent().is_some() && co1.parent() == co2.parent())
+                    || !co1.collision_groups().test(co2.collision_groups())
+                {
+                    continue;
+                }
+
+                let (type1, type2) = (body_type(co1), body_type(co2));
+                if !co1.active_collision_types().test(type1, type2)
+                    && !co2.active_collision_types().test(type1, type2)
+                {
+                    continue;
+                }
+
+                let mut manifolds: Vec<ContactManifold> = vec![];
+                let pos12 = co1.position().inv_mul(co2.position());
+                if dispatcher
+                    .contact_manifolds(
+                        &pos12,
+                        co1.shape(),
+                        co2.shape(),
+                        prediction,
+                        &mut manifolds,
+                        &mut None,
+                    )
+                    .is_err()
+                {
+                    continue;
+                }
+
+                for manifold in &mut manifolds {
+                    if manifold.points.is_empty() {
+                        continue;
+                    }
+
+                    manifold.data.rigid_body1 = co1.parent();
+                    manifold.data.rigid_body2 = co2.parent();
+                    manifold.data.normal = co1.position() * manifold.local_n1;
+
+                    let _ = f.call3(
+                        &this,
+                        &JsValue::from(utils::flat_handle(handle1.0)),
+                        &JsValue::from(utils::flat_handle(handle2.0)),
+                        &JsValue::from(RawContactManifold(manifold as *const ContactManifold)),
+                    );
+                }
+            }
+        }
+    }
 }
 
 #[wasm_bindgen]
@@ -9,7 +9,7 @@ use rapier::{
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
-pub struct RawPointProjection(pub(crate) PointProjection);
+pub struct RawPointProjection(pub(crate) PointProjection, pub(crate) FeatureId);
 
 #[wasm_bindgen]
 impl RawPointProjection {
@@ -20,6 +20,14 @@ impl RawPointProjection {
     pub fn isInside(&self) -> bool {
         self.0.is_inside
     }
+
+    pub fn featureType(&self) -> RawFeatureType {
+        self.1.into_type()
+    }
+
+    pub fn featureId(&self) -> Option<u32> {
+        self.1.into_value()
+    }
 }
 
 #[wasm_bindgen]
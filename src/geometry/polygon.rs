@@ -0,0 +1,184 @@
+use crate::geometry::RawShape;
+use rapier::geometry::TriMesh;
+use rapier::math::{Point, Real};
+use std::collections::HashMap;
+use wasm_bindgen::prelude::*;
+
+/// The convex polygons resulting from the decomposition of a polygon.
+#[wasm_bindgen]
+pub struct RawConvexParts(Vec<Vec<f64>>);
+
+#[wasm_bindgen]
+impl RawConvexParts {
+    pub fn numParts(&self) -> usize {
+        self.0.len()
+    }
+
+    /// The coordinates of the vertices of the i-th convex polygon, in counter-clockwise order.
+    pub fn part(&self, i: usize) -> Option<Vec<f64>> {
+        self.0.get(i).cloned()
+    }
+}
+
+/// The vertices of a simple polygon, in counter-clockwise order, and whether they had to be
+/// reversed for that.
+fn ccw_polygon(vertices: &[f64]) -> (Vec<Point<Real>>, bool) {
+    let mut points: Vec<_> = vertices.chunks_exact(2).map(Point::from_slice).collect();
+    let doubled_area: Real = (0..points.len())
+        .map(|i| {
+            let (a, b) = (points[i], points[(i + 1) % points.len()]);
+            a.x * b.y - b.x * a.y
+        })
+        .sum();
+
+    let reversed = doubled_area < 0.0;
+    if reversed {
+        points.reverse();
+    }
+
+    (points, reversed)
+}
+
+/// The triangles of a simple polygon given in counter-clockwise order, also in
+/// counter-clockwise order.
+fn triangulate(points: &[Point<Real>]) -> Option<Vec<[u32; 3]>> {
+    let trimesh = TriMesh::from_polygon(points.to_vec())?;
+    let triangles = trimesh
+        .indices()
+        .iter()
+        .map(|&[a, b, c]| {
+            let (pa, pb, pc) = (points[a as usize], points[b as usize], points[c as usize]);
+            if (pb - pa).perp(&(pc - pa)) < 0.0 {
+                [a, c, b]
+            } else {
+                [a, b, c]
+            }
+        })
+        .collect();
+    Some(triangles)
+}
+
+/// Is the polygon with the given vertex indices, in counter-clockwise order, convex?
+fn is_convex(points: &[Point<Real>], polygon: &[u32]) -> bool {
+    let n = polygon.len();
+    (0..n).all(|i| {
+        let a = points[polygon[i] as usize];
+        let b = points[polygon[(i + 1) % n] as usize];
+        let c = points[polygon[(i + 2) % n] as usize];
+        (b - a).perp(&(c - b)) >= -Real::EPSILON
+    })
+}
+
+/// Merges two counter-clockwise polygons sharing the edge going from `a` to `b` in the
+/// first polygon, and from `b` to `a` in the second one.
+fn merge_polygons(polygon1: &[u32], polygon2: &[u32], a: u32, b: u32) -> Vec<u32> {
+    let rotated = |polygon: &[u32], first: u32| {
+        let start = polygon.iter().position(|&v| v == first).unwrap_or(0);
+        let mut rotated = polygon[start..].to_vec();
+        rotated.extend_from_slice(&polygon[..start]);
+        rotated
+    };
+
+    // The first polygon goes from `b` around to `a`, the second one from `a` around to `b`.
+    let mut merged = rotated(polygon1, b);
+    let polygon2 = rotated(polygon2, a);
+    merged.extend_from_slice(&polygon2[1..polygon2.len() - 1]);
+    merged
+}
+
+/// Decomposes a simple polygon into convex polygons, by merging the triangles of its
+/// triangulation as long as they stay convex (Hertel-Mehlhorn algorithm).
+fn convex_decomposition(points: &[Point<Real>], triangles: &[[u32; 3]]) -> Vec<Vec<u32>> {
+    let mut polygons: Vec<Option<Vec<u32>>> =
+        triangles.iter().map(|tri| Some(tri.to_vec())).collect();
+    // The index of the polygon each triangle has been merged into.
+    let mut owners: Vec<usize> = (0..triangles.len()).collect();
+    let mut edges = HashMap::new();
+
+    for (i, tri) in triangles.iter().enumerate() {
+        for k in 0..3 {
+            let _ = edges.insert((tri[k], tri[(k + 1) % 3]), i);
+        }
+    }
+
+    let find = |owners: &mut Vec<usize>, mut i: usize| {
+        while owners[i] != i {
+            owners[i] = owners[owners[i]];
+            i = owners[i];
+        }
+        i
+    };
+
+    for (i, tri) in triangles.iter().enumerate() {
+        for k in 0..3 {
+            let (a, b) = (tri[k], tri[(k + 1) % 3]);
+            // Each diagonal is seen from both of its triangles, only handle it once.
+            let Some(&j) = edges.get(&(b, a)) else {
+                continue;
+            };
+            if j < i {
+                continue;
+            }
+
+            let (p1, p2) = (find(&mut owners, i), find(&mut owners, j));
+            if p1 == p2 {
+                continue;
+            }
+
+            if let (Some(polygon1), Some(polygon2)) = (&polygons[p1], &polygons[p2]) {
+                let merged = merge_polygons(polygon1, polygon2, a, b);
+                if is_convex(points, &merged) {
+                    polygons[p1] = Some(merged);
+                    polygons[p2] = None;
+                    owners[p2] = p1;
+                }
+            }
+        }
+    }
+
+    polygons.into_iter().flatten().collect()
+}
+
+#[wasm_bindgen]
+impl RawShape {
+    /// Triangulates a simple polygon by ear-clipping.
+    ///
+    /// The polygon vertices can be given in clockwise or counter-clockwise order. Returns the
+    /// three vertex indices of each triangle, in counter-clockwise order, or `None` if the
+    /// polygon isn’t simple or has a zero area.
+    pub fn polygonTriangulation(vertices: Vec<f64>) -> Option<Vec<u32>> {
+        let (points, reversed) = ccw_polygon(&vertices);
+        let triangles = triangulate(&points)?;
+        let num_points = points.len() as u32;
+
+        // Map the indices back to the input order if it was reversed.
+        Some(
+            triangles
+                .into_iter()
+                .flatten()
+                .map(|i| if reversed { num_points - 1 - i } else { i })
+                .collect(),
+        )
+    }
+
+    /// Decomposes a simple polygon into convex polygons.
+    ///
+    /// The polygon is triangulated by ear-clipping, then its triangles are merged as long as
+    /// they stay convex. This is exact, and the number of convex polygons is at most four times
+    /// the optimal one. The polygon vertices can be given in clockwise or counter-clockwise
+    /// order. Returns `None` if the polygon isn’t simple or has a zero area.
+    pub fn polygonDecomposition(vertices: Vec<f64>) -> Option<RawConvexParts> {
+        let (points, _) = ccw_polygon(&vertices);
+        let triangles = triangulate(&points)?;
+        let parts = convex_decomposition(&points, &triangles)
+            .into_iter()
+            .map(|polygon| {
+                polygon
+                    .iter()
+                    .flat_map(|&i| points[i as usize].coords.iter().copied())
+                    .collect()
+            })
+            .collect();
+        Some(RawConvexParts(parts))
+    }
+}
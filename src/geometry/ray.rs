@@ -2,6 +2,7 @@ use crate::geometry::feature::IntoTypeValue;
 use crate::geometry::RawFeatureType;
 use crate::math::RawVector;
 use crate::utils::{self, FlatHandle};
+use rapier::dynamics::RigidBodyHandle;
 use rapier::geometry::{ColliderHandle, RayIntersection};
 use wasm_bindgen::prelude::*;
 
@@ -30,6 +31,7 @@ impl RawRayIntersection {
 #[wasm_bindgen]
 pub struct RawRayColliderIntersection {
     pub(crate) handle: ColliderHandle,
+    pub(crate) parent: Option<RigidBodyHandle>,
     pub(crate) inter: RayIntersection,
 }
 
@@ -39,6 +41,11 @@ impl RawRayColliderIntersection {
         utils::flat_handle(self.handle.0)
     }
 
+    /// The handle of the rigid-body the hit collider is attached to, if any.
+    pub fn parentHandle(&self) -> Option<FlatHandle> {
+        self.parent.map(|h| utils::flat_handle(h.0))
+    }
+
     pub fn normal(&self) -> RawVector {
         self.inter.normal.into()
     }
@@ -59,6 +66,7 @@ impl RawRayColliderIntersection {
 #[wasm_bindgen]
 pub struct RawRayColliderHit {
     pub(crate) handle: ColliderHandle,
+    pub(crate) parent: Option<RigidBodyHandle>,
     pub(crate) timeOfImpact: f64,
 }
 
@@ -68,6 +76,11 @@ impl RawRayColliderHit {
         utils::flat_handle(self.handle.0)
     }
 
+    /// The handle of the rigid-body the hit collider is attached to, if any.
+    pub fn parentHandle(&self) -> Option<FlatHandle> {
+        self.parent.map(|h| utils::flat_handle(h.0))
+    }
+
     pub fn timeOfImpact(&self) -> f64 {
         self.timeOfImpact
     }
@@ -1,14 +1,17 @@
-use crate::geometry::{RawPointProjection, RawRayIntersection, RawShapeCastHit, RawShapeContact};
+use crate::geometry::{
+    RawPointProjection, RawRayIntersection, RawShapeCastHit, RawShapeContact, RawShapePenetration,
+};
 use crate::math::{RawRotation, RawVector};
 #[cfg(feature = "dim3")]
 use na::DMatrix;
 #[cfg(feature = "dim2")]
 use na::DVector;
 use na::Unit;
-use rapier::geometry::{Shape, SharedShape, TriMeshFlags};
+use rapier::geometry::{Aabb, HeightField, Shape, SharedShape, TriMeshFlags};
 use rapier::math::{Isometry, Point, Vector, DIM};
 use rapier::parry::query;
-use rapier::parry::query::{Ray, ShapeCastOptions};
+use rapier::parry::query::{Ray, RayCast, ShapeCastOptions};
+use rapier::prelude::FeatureId;
 use wasm_bindgen::prelude::*;
 
 pub trait SharedShapeUtility {
@@ -48,6 +51,12 @@ pub trait SharedShapeUtility {
         solid: bool,
     ) -> RawPointProjection;
 
+    fn projectPointAndGetFeature(
+        &self,
+        shapePos: &Isometry<f64>,
+        point: &Point<f64>,
+    ) -> RawPointProjection;
+
     fn intersectsRay(
         &self,
         shapePos: &Isometry<f64>,
@@ -104,7 +113,7 @@ impl SharedShapeUtility for SharedShape {
         )
         .ok()
         .flatten()
-        .map(|hit| RawShapeCastHit { hit })
+        .map(|hit| RawShapeCastHit::new(&*self.0, shape2, hit))
     }
 
     fn intersectsShape(
@@ -139,7 +148,19 @@ impl SharedShapeUtility for SharedShape {
         point: &Point<f64>,
         solid: bool,
     ) -> RawPointProjection {
-        RawPointProjection(self.as_ref().project_point(shapePos, point, solid))
+        RawPointProjection(
+            self.as_ref().project_point(shapePos, point, solid),
+            FeatureId::Unknown,
+        )
+    }
+
+    fn projectPointAndGetFeature(
+        &self,
+        shapePos: &Isometry<f64>,
+        point: &Point<f64>,
+    ) -> RawPointProjection {
+        let (proj, feature) = self.as_ref().project_point_and_get_feature(shapePos, point);
+        RawPointProjection(proj, feature)
     }
 
     fn intersectsRay(
@@ -224,6 +245,118 @@ pub enum RawShapeType {
     Voxels = 18,
 }
 
+/// The grid coordinate of the voxel, with the given size, containing the coordinate `x`.
+fn voxel_coord(x: f64, voxel_size: f64) -> i32 {
+    (x / voxel_size).floor() as i32
+}
+
+/// Calls `push_voxel` with the vertical coordinate of each voxel of the column centered at
+/// `column_center`, between the bottom of the heightfield and its surface, i.e., whose center
+/// lies under the surface. Nothing is pushed if there is no surface above this column, e.g.,
+/// in a hole of the heightfield.
+fn push_heightfield_voxel_column(
+    heightfield: &dyn Shape,
+    aabb: &Aabb,
+    column_center: Point<f64>,
+    voxel_size: &Vector<f64>,
+    mut push_voxel: impl FnMut(i32),
+) {
+    let mut origin = column_center;
+    origin.y = aabb.maxs.y + voxel_size.y;
+    let ray = Ray::new(origin, -Vector::y());
+
+    if let Some(toi) = heightfield.cast_local_ray(&ray, f64::MAX, true) {
+        let surface = origin.y - toi;
+        let bottom = voxel_coord(aabb.mins.y, voxel_size.y);
+        let top = voxel_coord(surface - voxel_size.y / 2.0, voxel_size.y).max(bottom);
+        for iy in bottom..=top {
+            push_voxel(iy);
+        }
+    }
+}
+
+/// The grid coordinates of the voxels filling the space under the surface of a heightfield.
+#[cfg(feature = "dim2")]
+fn heightfield_voxels(heightfield: &dyn Shape, voxel_size: &Vector<f64>) -> Vec<i32> {
+    let aabb = heightfield.compute_local_aabb();
+    let mut coords = vec![];
+    let x_range = voxel_coord(aabb.mins.x, voxel_size.x)..=voxel_coord(aabb.maxs.x, voxel_size.x);
+
+    for ix in x_range {
+        let center = Point::new((ix as f64 + 0.5) * voxel_size.x, 0.0);
+        push_heightfield_voxel_column(heightfield, &aabb, center, voxel_size, |iy| {
+            coords.extend_from_slice(&[ix, iy])
+        });
+    }
+
+    coords
+}
+
+/// The grid coordinates of the voxels filling the space under the surface of a heightfield.
+#[cfg(feature = "dim3")]
+fn heightfield_voxels(heightfield: &dyn Shape, voxel_size: &Vector<f64>) -> Vec<i32> {
+    let aabb = heightfield.compute_local_aabb();
+    let mut coords = vec![];
+    let x_range = voxel_coord(aabb.mins.x, voxel_size.x)..=voxel_coord(aabb.maxs.x, voxel_size.x);
+    let z_range = voxel_coord(aabb.mins.z, voxel_size.z)..=voxel_coord(aabb.maxs.z, voxel_size.z);
+
+    for ix in x_range {
+        for iz in z_range.clone() {
+            let center = Point::new(
+                (ix as f64 + 0.5) * voxel_size.x,
+                0.0,
+                (iz as f64 + 0.5) * voxel_size.z,
+            );
+            push_heightfield_voxel_column(heightfield, &aabb, center, voxel_size, |iy| {
+                coords.extend_from_slice(&[ix, iy, iz])
+            });
+        }
+    }
+
+    coords
+}
+
+/// The vertices and indices of a mesh.
+#[wasm_bindgen]
+pub struct RawMeshData {
+    pub(crate) vertices: Vec<f64>,
+    pub(crate) indices: Vec<u32>,
+}
+
+#[wasm_bindgen]
+impl RawMeshData {
+    /// The coordinates of the vertices of the mesh, with `DIM` coordinates per vertex.
+    pub fn vertices(&self) -> Vec<f64> {
+        self.vertices.clone()
+    }
+
+    /// The indices of the vertices of each element of the mesh: two per segment in 2D, three
+    /// per triangle in 3D.
+    pub fn indices(&self) -> Vec<u32> {
+        self.indices.clone()
+    }
+}
+
+/// The polyline formed by the segments of a heightfield, skipping its removed segments.
+#[cfg(feature = "dim2")]
+pub(crate) fn heightfield_mesh(heightfield: &HeightField) -> RawMeshData {
+    let (vertices, indices) = heightfield.to_polyline();
+    RawMeshData {
+        vertices: vertices.iter().flat_map(|p| p.iter()).copied().collect(),
+        indices: indices.into_iter().flatten().collect(),
+    }
+}
+
+/// The triangle mesh formed by the triangles of a heightfield.
+#[cfg(feature = "dim3")]
+pub(crate) fn heightfield_mesh(heightfield: &HeightField) -> RawMeshData {
+    let (vertices, indices) = heightfield.to_trimesh();
+    RawMeshData {
+        vertices: vertices.iter().flat_map(|p| p.iter()).copied().collect(),
+        indices: indices.into_iter().flatten().collect(),
+    }
+}
+
 #[wasm_bindgen]
 pub struct RawShape(pub(crate) SharedShape);
 
@@ -300,6 +433,39 @@ impl RawShape {
         Self(SharedShape::voxels_from_points(voxel_size.0, &points))
     }
 
+    /// The grid coordinates of the voxels, with the given size, filling the space under the
+    /// surface of a heightfield, down to its lowest point.
+    ///
+    /// A voxel is filled if its center is under the surface. The result contains `DIM`
+    /// coordinates per voxel and can be passed to `RawShape::voxels`.
+    #[cfg(feature = "dim2")]
+    pub fn voxelsFromHeightfield(
+        heights: Vec<f64>,
+        scale: &RawVector,
+        voxelSize: &RawVector,
+    ) -> Vec<i32> {
+        let heightfield = Self::heightfield(heights, scale);
+        heightfield_voxels(&*heightfield.0, &voxelSize.0)
+    }
+
+    /// The grid coordinates of the voxels, with the given size, filling the space under the
+    /// surface of a heightfield, down to its lowest point.
+    ///
+    /// A voxel is filled if its center is under the surface. The result contains `DIM`
+    /// coordinates per voxel and can be passed to `RawShape::voxels`.
+    #[cfg(feature = "dim3")]
+    pub fn voxelsFromHeightfield(
+        nrows: u32,
+        ncols: u32,
+        heights: Vec<f64>,
+        scale: &RawVector,
+        flags: u32,
+        voxelSize: &RawVector,
+    ) -> Vec<i32> {
+        let heightfield = Self::heightfield(nrows, ncols, heights, scale, flags);
+        heightfield_voxels(&*heightfield.0, &voxelSize.0)
+    }
+
     pub fn polyline(vertices: Vec<f64>, indices: Vec<u32>) -> Self {
         let vertices = vertices.chunks(DIM).map(|v| Point::from_slice(v)).collect();
         let indices: Vec<_> = indices.chunks(2).map(|v| [v[0], v[1]]).collect();
@@ -339,6 +505,26 @@ impl RawShape {
         Self(SharedShape::heightfield_with_flags(heights, scale.0, flags))
     }
 
+    /// The segments of a heightfield, as a polyline.
+    #[cfg(feature = "dim2")]
+    pub fn heightfieldToTrimesh(heights: Vec<f64>, scale: &RawVector) -> RawMeshData {
+        let heightfield = HeightField::new(DVector::from_vec(heights), scale.0);
+        heightfield_mesh(&heightfield)
+    }
+
+    /// The triangles of a heightfield, as a triangle mesh.
+    #[cfg(feature = "dim3")]
+    pub fn heightfieldToTrimesh(
+        nrows: u32,
+        ncols: u32,
+        heights: Vec<f64>,
+        scale: &RawVector,
+    ) -> RawMeshData {
+        let heights = DMatrix::from_vec(nrows as usize + 1, ncols as usize + 1, heights);
+        let heightfield = HeightField::new(heights, scale.0);
+        heightfield_mesh(&heightfield)
+    }
+
     pub fn segment(p1: &RawVector, p2: &RawVector) -> Self {
         Self(SharedShape::segment(p1.0.into(), p2.0.into()))
     }
@@ -458,6 +644,24 @@ impl RawShape {
         self.0.contactShape(&pos1, &*shape2.0, &pos2, prediction)
     }
 
+    /// Computes the penetration depth and minimum translation vector separating this shape
+    /// from the second one, or `None` if they don't overlap.
+    pub fn penetrationShape(
+        &self,
+        shapePos1: &RawVector,
+        shapeRot1: &RawRotation,
+        shape2: &RawShape,
+        shapePos2: &RawVector,
+        shapeRot2: &RawRotation,
+    ) -> Option<RawShapePenetration> {
+        let pos1 = Isometry::from_parts(shapePos1.0.into(), shapeRot1.0);
+        let pos2 = Isometry::from_parts(shapePos2.0.into(), shapeRot2.0);
+
+        self.0
+            .contactShape(&pos1, &*shape2.0, &pos2, 0.0)
+            .and_then(|contact| RawShapePenetration::from_contact(contact.contact))
+    }
+
     pub fn containsPoint(
         &self,
         shapePos: &RawVector,
@@ -481,6 +685,17 @@ impl RawShape {
         self.0.projectPoint(&pos, &point.0.into(), solid)
     }
 
+    pub fn projectPointAndGetFeature(
+        &self,
+        shapePos: &RawVector,
+        shapeRot: &RawRotation,
+        point: &RawVector,
+    ) -> RawPointProjection {
+        let pos = Isometry::from_parts(shapePos.0.into(), shapeRot.0);
+
+        self.0.projectPointAndGetFeature(&pos, &point.0.into())
+    }
+
     pub fn intersectsRay(
         &self,
         shapePos: &RawVector,
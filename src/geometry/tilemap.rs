@@ -0,0 +1,164 @@
+use crate::geometry::{RawMeshData, RawShape};
+use crate::math::RawVector;
+use std::collections::HashMap;
+use wasm_bindgen::prelude::*;
+
+/// A grid of tiles, stored row by row, where any non-zero value is a filled tile.
+struct Tilemap<'a> {
+    occupancy: &'a [u8],
+    width: u32,
+    height: u32,
+}
+
+impl Tilemap<'_> {
+    /// Is the tile at the given coordinates filled? The tiles outside of the grid are empty.
+    fn is_filled(&self, x: i64, y: i64) -> bool {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return false;
+        }
+
+        self.occupancy
+            .get((y * self.width as i64 + x) as usize)
+            .is_some_and(|tile| *tile != 0)
+    }
+
+    /// Covers the filled tiles with as few rectangles as possible, greedily: each rectangle
+    /// is first extended along `x`, then along `y`.
+    fn rectangles(&self) -> Vec<[u32; 4]> {
+        let (width, height) = (self.width as usize, self.height as usize);
+        let mut covered = vec![false; width * height];
+        let mut rectangles = vec![];
+        let free = |covered: &[bool], x: usize, y: usize| {
+            self.is_filled(x as i64, y as i64) && !covered[y * width + x]
+        };
+
+        for y in 0..height {
+            for x in 0..width {
+                if !free(&covered, x, y) {
+                    continue;
+                }
+
+                let mut w = 1;
+                while x + w < width && free(&covered, x + w, y) {
+                    w += 1;
+                }
+
+                let mut h = 1;
+                while y + h < height && (x..x + w).all(|x| free(&covered, x, y + h)) {
+                    h += 1;
+                }
+
+                for ry in y..y + h {
+                    covered[ry * width + x..ry * width + x + w].fill(true);
+                }
+
+                rectangles.push([x as u32, y as u32, w as u32, h as u32]);
+            }
+        }
+
+        rectangles
+    }
+
+    /// The segments separating the filled tiles from the empty ones, in tile units.
+    ///
+    /// Collinear segments are merged as long as they bound tiles on the same side, so that
+    /// flat surfaces are made of a single segment, without internal edges at tile seams.
+    fn outline(&self) -> Vec<[(i64, i64); 2]> {
+        let (width, height) = (self.width as i64, self.height as i64);
+        let mut segments = vec![];
+
+        // The horizontal boundaries, between the rows `y - 1` and `y`.
+        for y in 0..=height {
+            let side = |x| match (self.is_filled(x, y - 1), self.is_filled(x, y)) {
+                (true, false) => 1,
+                (false, true) => -1,
+                _ => 0,
+            };
+            let mut x = 0;
+            while x < width {
+                let s = side(x);
+                let start = x;
+                while x < width && side(x) == s {
+                    x += 1;
+                }
+                if s != 0 {
+                    segments.push([(start, y), (x, y)]);
+                }
+            }
+        }
+
+        // The vertical boundaries, between the columns `x - 1` and `x`.
+        for x in 0..=width {
+            let side = |y| match (self.is_filled(x - 1, y), self.is_filled(x, y)) {
+                (true, false) => 1,
+                (false, true) => -1,
+                _ => 0,
+            };
+            let mut y = 0;
+            while y < height {
+                let s = side(y);
+                let start = y;
+                while y < height && side(y) == s {
+                    y += 1;
+                }
+                if s != 0 {
+                    segments.push([(x, start), (x, y)]);
+                }
+            }
+        }
+
+        segments
+    }
+}
+
+#[wasm_bindgen]
+impl RawShape {
+    /// Covers the filled tiles of a tilemap with rectangles.
+    ///
+    /// The tilemap has `width * height` tiles stored row by row, where any non-zero value is
+    /// a filled tile. Returns the coordinates of the first tile, the width and the height of
+    /// each rectangle, in tiles.
+    pub fn tilemapRectangles(occupancy: Vec<u8>, width: u32, height: u32) -> Vec<u32> {
+        let tilemap = Tilemap {
+            occupancy: &occupancy,
+            width,
+            height,
+        };
+        tilemap.rectangles().into_iter().flatten().collect()
+    }
+
+    /// The polyline separating the filled tiles of a tilemap from the empty ones.
+    ///
+    /// The tilemap has `width * height` tiles stored row by row, where any non-zero value is
+    /// a filled tile. The tile `(x, y)` covers the area from `(x, y) * tileSize` to
+    /// `(x + 1, y + 1) * tileSize`. Collinear segments are merged, so that flat surfaces don’t
+    /// have internal edges at tile seams.
+    pub fn tilemapOutline(
+        occupancy: Vec<u8>,
+        width: u32,
+        height: u32,
+        tileSize: &RawVector,
+    ) -> RawMeshData {
+        let tilemap = Tilemap {
+            occupancy: &occupancy,
+            width,
+            height,
+        };
+        let mut vertex_ids = HashMap::new();
+        let mut vertices = vec![];
+        let mut indices = vec![];
+
+        for segment in tilemap.outline() {
+            for (x, y) in segment {
+                let id = *vertex_ids.entry((x, y)).or_insert_with(|| {
+                    vertices.push(x as f64 * tileSize.0.x);
+                    vertices.push(y as f64 * tileSize.0.y);
+                    (vertices.len() / 2 - 1) as u32
+                });
+                indices.push(id);
+            }
+        }
+
+        RawMeshData { vertices, indices }
+    }
+}
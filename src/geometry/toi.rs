@@ -1,11 +1,45 @@
+use crate::geometry::feature::IntoTypeValue;
+use crate::geometry::RawFeatureType;
 use crate::math::RawVector;
 use crate::utils::{self, FlatHandle};
-use rapier::geometry::{ColliderHandle, ShapeCastHit};
+use rapier::dynamics::RigidBodyHandle;
+use rapier::geometry::{ColliderHandle, Shape, ShapeCastHit};
+use rapier::math::{Isometry, Real};
+use rapier::parry::query::PointQuery;
+use rapier::prelude::FeatureId;
 use wasm_bindgen::prelude::*;
 
+/// Identifies the features of both shapes touching at the time of impact.
+///
+/// The witness points of `hit` must be expressed in the local-space of their shape.
+pub(crate) fn witness_features(
+    shape1: &dyn Shape,
+    shape2: &dyn Shape,
+    hit: &ShapeCastHit,
+) -> (FeatureId, FeatureId) {
+    (
+        shape1.project_local_point_and_get_feature(&hit.witness1).1,
+        shape2.project_local_point_and_get_feature(&hit.witness2).1,
+    )
+}
+
 #[wasm_bindgen]
 pub struct RawShapeCastHit {
     pub(crate) hit: ShapeCastHit,
+    pub(crate) feature1: FeatureId,
+    pub(crate) feature2: FeatureId,
+}
+
+impl RawShapeCastHit {
+    /// Wraps a hit with witnesses expressed in the local-space of the shapes.
+    pub(crate) fn new(shape1: &dyn Shape, shape2: &dyn Shape, hit: ShapeCastHit) -> Self {
+        let (feature1, feature2) = witness_features(shape1, shape2, &hit);
+        Self {
+            hit,
+            feature1,
+            feature2,
+        }
+    }
 }
 
 #[wasm_bindgen]
@@ -29,12 +63,105 @@ impl RawShapeCastHit {
     pub fn normal2(&self) -> RawVector {
         self.hit.normal2.into_inner().into()
     }
+
+    pub fn featureType1(&self) -> RawFeatureType {
+        self.feature1.into_type()
+    }
+
+    pub fn featureId1(&self) -> Option<u32> {
+        self.feature1.into_value()
+    }
+
+    pub fn featureType2(&self) -> RawFeatureType {
+        self.feature2.into_type()
+    }
+
+    pub fn featureId2(&self) -> Option<u32> {
+        self.feature2.into_value()
+    }
 }
 
 #[wasm_bindgen]
 pub struct RawColliderShapeCastHit {
     pub(crate) handle: ColliderHandle,
+    pub(crate) parent: Option<RigidBodyHandle>,
     pub(crate) hit: ShapeCastHit,
+    /// The same hit, with both witnesses and normals in the local-space of their shape.
+    pub(crate) local_hit: ShapeCastHit,
+    pub(crate) feature1: FeatureId,
+    pub(crate) feature2: FeatureId,
+}
+
+impl RawColliderShapeCastHit {
+    /// Wraps a hit with witnesses expressed in the local-space of the shapes.
+    pub(crate) fn new(
+        handle: ColliderHandle,
+        parent: Option<RigidBodyHandle>,
+        shape1: &dyn Shape,
+        shape2: &dyn Shape,
+        hit: ShapeCastHit,
+    ) -> Self {
+        let (feature1, feature2) = witness_features(shape1, shape2, &hit);
+        Self {
+            handle,
+            parent,
+            hit,
+            local_hit: hit,
+            feature1,
+            feature2,
+        }
+    }
+
+    /// Wraps a hit with witnesses expressed in the local-space of the shapes, where the first
+    /// shape is the collider `handle` at the position `pos1`.
+    ///
+    /// The first witness and normal of the world-space version of the hit are expressed in
+    /// world-space, like for the scene queries.
+    pub(crate) fn from_local_hit(
+        handle: ColliderHandle,
+        parent: Option<RigidBodyHandle>,
+        pos1: &Isometry<Real>,
+        shape1: &dyn Shape,
+        shape2: &dyn Shape,
+        local_hit: ShapeCastHit,
+    ) -> Self {
+        let mut hit = local_hit;
+        hit.witness1 = pos1 * local_hit.witness1;
+        hit.normal1 = pos1 * local_hit.normal1;
+        let (feature1, feature2) = witness_features(shape1, shape2, &local_hit);
+        Self {
+            handle,
+            parent,
+            hit,
+            local_hit,
+            feature1,
+            feature2,
+        }
+    }
+
+    /// Wraps a hit from a scene query, where the first witness and normal refer to the
+    /// collider `handle` at the position `pos1` and are expressed in world-space.
+    pub(crate) fn from_world_hit(
+        handle: ColliderHandle,
+        parent: Option<RigidBodyHandle>,
+        pos1: &Isometry<Real>,
+        shape1: &dyn Shape,
+        shape2: &dyn Shape,
+        hit: ShapeCastHit,
+    ) -> Self {
+        let mut local_hit = hit;
+        local_hit.witness1 = pos1.inverse_transform_point(&hit.witness1);
+        local_hit.normal1 = pos1.inverse_transform_unit_vector(&hit.normal1);
+        let (feature1, feature2) = witness_features(shape1, shape2, &local_hit);
+        Self {
+            handle,
+            parent,
+            hit,
+            local_hit,
+            feature1,
+            feature2,
+        }
+    }
 }
 
 #[wasm_bindgen]
@@ -43,6 +170,11 @@ impl RawColliderShapeCastHit {
         utils::flat_handle(self.handle.0)
     }
 
+    /// The handle of the rigid-body the hit collider is attached to, if any.
+    pub fn parentHandle(&self) -> Option<FlatHandle> {
+        self.parent.map(|h| utils::flat_handle(h.0))
+    }
+
     pub fn time_of_impact(&self) -> f64 {
         self.hit.time_of_impact
     }
@@ -62,4 +194,36 @@ impl RawColliderShapeCastHit {
     pub fn normal2(&self) -> RawVector {
         self.hit.normal2.into_inner().into()
     }
+
+    pub fn localWitness1(&self) -> RawVector {
+        self.local_hit.witness1.coords.into()
+    }
+
+    pub fn localWitness2(&self) -> RawVector {
+        self.local_hit.witness2.coords.into()
+    }
+
+    pub fn localNormal1(&self) -> RawVector {
+        self.local_hit.normal1.into_inner().into()
+    }
+
+    pub fn localNormal2(&self) -> RawVector {
+        self.local_hit.normal2.into_inner().into()
+    }
+
+    pub fn featureType1(&self) -> RawFeatureType {
+        self.feature1.into_type()
+    }
+
+    pub fn featureId1(&self) -> Option<u32> {
+        self.feature1.into_value()
+    }
+
+    pub fn featureType2(&self) -> RawFeatureType {
+        self.feature2.into_type()
+    }
+
+    pub fn featureId2(&self) -> Option<u32> {
+        self.feature2.into_value()
+    }
 }
@@ -0,0 +1,156 @@
+use crate::geometry::{RawColliderSet, RawNarrowPhase};
+use crate::utils::{self, FlatHandle};
+use rapier::geometry::{ColliderHandle, NarrowPhase};
+use std::collections::HashMap;
+use wasm_bindgen::prelude::*;
+
+/// The colliders overlapping a sensor, and how they changed during the last update.
+#[derive(Clone, Debug, Default)]
+struct TriggerState {
+    /// The colliders currently overlapping the sensor, sorted by handle.
+    overlapping: Vec<ColliderHandle>,
+    entered: Vec<ColliderHandle>,
+    exited: Vec<ColliderHandle>,
+    stayed: Vec<ColliderHandle>,
+}
+
+/// Does the given list of colliders, sorted by handle, contain `handle`?
+fn sorted_contains(sorted: &[ColliderHandle], handle: &ColliderHandle) -> bool {
+    sorted
+        .binary_search_by_key(&handle.into_raw_parts(), |h| h.into_raw_parts())
+        .is_ok()
+}
+
+/// The colliders intersecting (or in contact with) the given collider, sorted by handle.
+fn overlapping_colliders(
+    narrow_phase: &NarrowPhase,
+    handle: ColliderHandle,
+) -> Vec<ColliderHandle> {
+    let other = |h1: ColliderHandle, h2: ColliderHandle| if h1 == handle { h2 } else { h1 };
+    let mut overlapping: Vec<_> = narrow_phase
+        .intersection_pairs_with(handle)
+        .filter(|(_, _, intersecting)| *intersecting)
+        .map(|(h1, h2, _)| other(h1, h2))
+        .chain(
+            narrow_phase
+                .contact_pairs_with(handle)
+                .filter(|pair| pair.has_any_active_contact)
+                .map(|pair| other(pair.collider1, pair.collider2)),
+        )
+        .collect();
+    overlapping.sort_unstable_by_key(|h| h.into_raw_parts());
+    overlapping.dedup();
+    overlapping
+}
+
+fn flat_handles(handles: &[ColliderHandle]) -> Vec<FlatHandle> {
+    handles.iter().map(|h| utils::flat_handle(h.0)).collect()
+}
+
+/// A set of sensors tracking the colliders overlapping each of them.
+///
+/// After each call to `update`, the colliders that started overlapping a sensor, stopped
+/// overlapping it, or kept overlapping it since the previous update can be listed, without
+/// having to accumulate intersection events.
+#[wasm_bindgen]
+pub struct RawTriggerSet {
+    sensors: HashMap<ColliderHandle, TriggerState>,
+}
+
+#[wasm_bindgen]
+impl RawTriggerSet {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        RawTriggerSet {
+            sensors: HashMap::new(),
+        }
+    }
+
+    /// Registers a sensor (or any collider) to track. The colliders already overlapping it
+    /// are reported as entered at the next update.
+    pub fn addSensor(&mut self, handle: FlatHandle) {
+        let _ = self
+            .sensors
+            .entry(utils::collider_handle(handle))
+            .or_default();
+    }
+
+    pub fn removeSensor(&mut self, handle: FlatHandle) {
+        let _ = self.sensors.remove(&utils::collider_handle(handle));
+    }
+
+    pub fn contains(&self, handle: FlatHandle) -> bool {
+        self.sensors.contains_key(&utils::collider_handle(handle))
+    }
+
+    pub fn len(&self) -> usize {
+        self.sensors.len()
+    }
+
+    /// Updates the colliders overlapping each sensor from the intersections and active
+    /// contacts of the narrow-phase.
+    ///
+    /// The sensors removed from the collider set since the previous update report all their
+    /// colliders as exited, and are unregistered at the following update.
+    pub fn update(&mut self, narrow_phase: &RawNarrowPhase, colliders: &RawColliderSet) {
+        self.sensors.retain(|handle, state| {
+            colliders.0.get(*handle).is_some() || !state.overlapping.is_empty()
+        });
+
+        for (handle, state) in self.sensors.iter_mut() {
+            let current = if colliders.0.get(*handle).is_some() {
+                overlapping_colliders(&narrow_phase.0, *handle)
+            } else {
+                vec![]
+            };
+
+            state.entered.clear();
+            state.exited.clear();
+            state.stayed.clear();
+
+            for collider in &current {
+                if sorted_contains(&state.overlapping, collider) {
+                    state.stayed.push(*collider);
+                } else {
+                    state.entered.push(*collider);
+                }
+            }
+
+            for collider in &state.overlapping {
+                if !sorted_contains(&current, collider) {
+                    state.exited.push(*collider);
+                }
+            }
+
+            state.overlapping = current;
+        }
+    }
+
+    /// The colliders currently overlapping the given sensor.
+    pub fn overlapping(&self, sensor: FlatHandle) -> Vec<FlatHandle> {
+        self.sensors
+            .get(&utils::collider_handle(sensor))
+            .map_or_else(Vec::new, |state| flat_handles(&state.overlapping))
+    }
+
+    /// The colliders that started overlapping the given sensor during the last update.
+    pub fn entered(&self, sensor: FlatHandle) -> Vec<FlatHandle> {
+        self.sensors
+            .get(&utils::collider_handle(sensor))
+            .map_or_else(Vec::new, |state| flat_handles(&state.entered))
+    }
+
+    /// The colliders that stopped overlapping the given sensor during the last update.
+    pub fn exited(&self, sensor: FlatHandle) -> Vec<FlatHandle> {
+        self.sensors
+            .get(&utils::collider_handle(sensor))
+            .map_or_else(Vec::new, |state| flat_handles(&state.exited))
+    }
+
+    /// The colliders that kept overlapping the given sensor during the last update.
+    pub fn stayed(&self, sensor: FlatHandle) -> Vec<FlatHandle> {
+        self.sensors
+            .get(&utils::collider_handle(sensor))
+            .map_or_else(Vec::new, |state| flat_handles(&state.stayed))
+    }
+}
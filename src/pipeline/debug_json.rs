@@ -0,0 +1,421 @@
+//! Human-readable JSON description of a physics world, for debugging.
+
+use crate::utils;
+use rapier::dynamics::{
+    GenericJoint, ImpulseJointSet, IntegrationParameters, MultibodyJointSet, RigidBodySet,
+};
+use rapier::geometry::{Collider, ColliderSet, InteractionGroups};
+use rapier::math::{Isometry, Real, Vector, SPATIAL_DIM};
+use rapier::parry::shape::{Shape, TypedShape};
+use std::fmt::Write;
+
+/// Writes a JSON number. Non-finite numbers are written as `null`, which is the only
+/// valid JSON for them.
+///
+/// Very large and very small numbers (including the flat handles, which are subnormal
+/// numbers) are written in scientific notation to keep them short.
+fn number(out: &mut String, x: Real) {
+    if !x.is_finite() {
+        out.push_str("null");
+    } else if x != 0.0 && (x.abs() >= 1.0e16 || x.abs() < 1.0e-6) {
+        let _ = write!(out, "{:e}", x);
+    } else {
+        let _ = write!(out, "{}", x);
+    }
+}
+
+fn array(out: &mut String, values: impl IntoIterator<Item = Real>) {
+    out.push('[');
+    for (i, x) in values.into_iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        number(out, x);
+    }
+    out.push(']');
+}
+
+fn vector(out: &mut String, v: &Vector<Real>) {
+    array(out, v.iter().copied());
+}
+
+/// Writes a position as its translation and rotation. The rotation is written as an angle in
+/// 2D, and as a quaternion `[x, y, z, w]` in 3D.
+fn isometry(out: &mut String, pos: &Isometry<Real>) {
+    out.push_str("{\"translation\":");
+    vector(out, &pos.translation.vector);
+    out.push_str(",\"rotation\":");
+    #[cfg(feature = "dim2")]
+    number(out, pos.rotation.angle());
+    #[cfg(feature = "dim3")]
+    array(out, pos.rotation.coords.iter().copied());
+    out.push('}');
+}
+
+fn groups(groups: InteractionGroups) -> u32 {
+    crate::geometry::pack_interaction_groups(groups)
+}
+
+fn shape(out: &mut String, shape: &dyn Shape) {
+    let _ = write!(out, "{{\"type\":\"{:?}\"", shape.shape_type());
+
+    match shape.as_typed_shape() {
+        TypedShape::Ball(s) => {
+            out.push_str(",\"radius\":");
+            number(out, s.radius);
+        }
+        TypedShape::Cuboid(s) => {
+            out.push_str(",\"halfExtents\":");
+            vector(out, &s.half_extents);
+        }
+        TypedShape::RoundCuboid(s) => {
+            out.push_str(",\"halfExtents\":");
+            vector(out, &s.inner_shape.half_extents);
+            out.push_str(",\"borderRadius\":");
+            number(out, s.border_radius);
+        }
+        TypedShape::Capsule(s) => {
+            out.push_str(",\"a\":");
+            vector(out, &s.segment.a.coords);
+            out.push_str(",\"b\":");
+            vector(out, &s.segment.b.coords);
+            out.push_str(",\"radius\":");
+            number(out, s.radius);
+        }
+        TypedShape::Segment(s) => {
+            out.push_str(",\"a\":");
+            vector(out, &s.a.coords);
+            out.push_str(",\"b\":");
+            vector(out, &s.b.coords);
+        }
+        TypedShape::Triangle(s) => {
+            out.push_str(",\"vertices\":");
+            array(
+                out,
+                [s.a, s.b, s.c]
+                    .iter()
+                    .flat_map(|p| p.coords.iter().copied()),
+            );
+        }
+        TypedShape::HalfSpace(s) => {
+            out.push_str(",\"normal\":");
+            vector(out, &s.normal);
+        }
+        TypedShape::TriMesh(s) => {
+            out.push_str(",\"vertices\":");
+            array(
+                out,
+                s.vertices().iter().flat_map(|p| p.coords.iter().copied()),
+            );
+            out.push_str(",\"indices\":");
+            array(out, s.indices().iter().flatten().map(|i| *i as Real));
+        }
+        TypedShape::Polyline(s) => {
+            out.push_str(",\"vertices\":");
+            array(
+                out,
+                s.vertices().iter().flat_map(|p| p.coords.iter().copied()),
+            );
+            out.push_str(",\"indices\":");
+            array(out, s.indices().iter().flatten().map(|i| *i as Real));
+        }
+        TypedShape::HeightField(s) => {
+            out.push_str(",\"heights\":");
+            array(out, s.heights().iter().copied());
+            out.push_str(",\"scale\":");
+            vector(out, s.scale());
+        }
+        #[cfg(feature = "dim2")]
+        TypedShape::ConvexPolygon(s) => {
+            out.push_str(",\"vertices\":");
+            array(
+                out,
+                s.points().iter().flat_map(|p| p.coords.iter().copied()),
+            );
+        }
+        #[cfg(feature = "dim3")]
+        TypedShape::ConvexPolyhedron(s) => {
+            out.push_str(",\"vertices\":");
+            array(
+                out,
+                s.points().iter().flat_map(|p| p.coords.iter().copied()),
+            );
+        }
+        #[cfg(feature = "dim3")]
+        TypedShape::Cylinder(s) => {
+            out.push_str(",\"halfHeight\":");
+            number(out, s.half_height);
+            out.push_str(",\"radius\":");
+            number(out, s.radius);
+        }
+        #[cfg(feature = "dim3")]
+        TypedShape::Cone(s) => {
+            out.push_str(",\"halfHeight\":");
+            number(out, s.half_height);
+            out.push_str(",\"radius\":");
+            number(out, s.radius);
+        }
+        TypedShape::Compound(s) => {
+            out.push_str(",\"shapes\":[");
+            for (i, (pos, subshape)) in s.shapes().iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str("{\"position\":");
+                isometry(out, pos);
+                out.push_str(",\"shape\":");
+                self::shape(out, &**subshape);
+                out.push('}');
+            }
+            out.push(']');
+        }
+        _ => {}
+    }
+
+    out.push('}');
+}
+
+fn collider(out: &mut String, handle: f64, co: &Collider) {
+    out.push_str("{\"handle\":");
+    number(out, handle);
+    out.push_str(",\"parent\":");
+    match co.parent() {
+        Some(parent) => number(out, utils::flat_handle(parent.0)),
+        None => out.push_str("null"),
+    }
+    let _ = write!(out, ",\"enabled\":{}", co.is_enabled());
+    out.push_str(",\"position\":");
+    isometry(out, co.position());
+    if let Some(pos) = co.position_wrt_parent() {
+        out.push_str(",\"positionWrtParent\":");
+        isometry(out, pos);
+    }
+    out.push_str(",\"shape\":");
+    shape(out, co.shape());
+    out.push_str(",\"density\":");
+    number(out, co.density());
+    out.push_str(",\"mass\":");
+    number(out, co.mass());
+    out.push_str(",\"friction\":");
+    number(out, co.material().friction);
+    out.push_str(",\"restitution\":");
+    number(out, co.material().restitution);
+    let _ = write!(
+        out,
+        ",\"frictionCombineRule\":\"{:?}\",\"restitutionCombineRule\":\"{:?}\"",
+        co.friction_combine_rule(),
+        co.restitution_combine_rule()
+    );
+    let _ = write!(
+        out,
+        ",\"sensor\":{},\"collisionGroups\":{},\"solverGroups\":{}",
+        co.is_sensor(),
+        groups(co.collision_groups()),
+        groups(co.solver_groups())
+    );
+    let _ = write!(
+        out,
+        ",\"activeCollisionTypes\":{},\"activeEvents\":{},\"activeHooks\":{}",
+        co.active_collision_types().bits(),
+        co.active_events().bits(),
+        co.active_hooks().bits()
+    );
+    out.push_str(",\"contactSkin\":");
+    number(out, co.contact_skin());
+    let _ = write!(out, ",\"userData\":\"{}\"}}", co.user_data);
+}
+
+fn joint(out: &mut String, handle: f64, body1: f64, body2: f64, joint: &GenericJoint) {
+    out.push_str("{\"handle\":");
+    number(out, handle);
+    out.push_str(",\"body1\":");
+    number(out, body1);
+    out.push_str(",\"body2\":");
+    number(out, body2);
+    let _ = write!(out, ",\"enabled\":{}", joint.is_enabled());
+    out.push_str(",\"frame1\":");
+    isometry(out, &joint.local_frame1);
+    out.push_str(",\"frame2\":");
+    isometry(out, &joint.local_frame2);
+    let _ = write!(
+        out,
+        ",\"lockedAxes\":{},\"limitAxes\":{},\"motorAxes\":{},\"coupledAxes\":{}",
+        joint.locked_axes.bits(),
+        joint.limit_axes.bits(),
+        joint.motor_axes.bits(),
+        joint.coupled_axes.bits()
+    );
+    let _ = write!(out, ",\"contactsEnabled\":{}", joint.contacts_enabled);
+
+    out.push_str(",\"limits\":[");
+    let mut first = true;
+    for axis in 0..SPATIAL_DIM {
+        if joint.limit_axes.bits() & (1 << axis) != 0 {
+            if !first {
+                out.push(',');
+            }
+            first = false;
+            let _ = write!(out, "{{\"axis\":{},\"min\":", axis);
+            number(out, joint.limits[axis].min);
+            out.push_str(",\"max\":");
+            number(out, joint.limits[axis].max);
+            out.push('}');
+        }
+    }
+    out.push(']');
+
+    out.push_str(",\"motors\":[");
+    let mut first = true;
+    for axis in 0..SPATIAL_DIM {
+        if joint.motor_axes.bits() & (1 << axis) != 0 {
+            if !first {
+                out.push(',');
+            }
+            first = false;
+            let motor = &joint.motors[axis];
+            let _ = write!(out, "{{\"axis\":{},\"model\":\"{:?}\"", axis, motor.model);
+            out.push_str(",\"targetPos\":");
+            number(out, motor.target_pos);
+            out.push_str(",\"targetVel\":");
+            number(out, motor.target_vel);
+            out.push_str(",\"stiffness\":");
+            number(out, motor.stiffness);
+            out.push_str(",\"damping\":");
+            number(out, motor.damping);
+            out.push_str(",\"maxForce\":");
+            number(out, motor.max_force);
+            out.push('}');
+        }
+    }
+    out.push(']');
+
+    let _ = write!(out, ",\"userData\":\"{}\"}}", joint.user_data);
+}
+
+/// Describes the given world as a JSON string.
+///
+/// Only the user-facing state is described: the broad-phase, the narrow-phase, and the
+/// islands are ignored.
+pub(crate) fn world_to_debug_json(
+    gravity: &Vector<Real>,
+    params: &IntegrationParameters,
+    bodies: &RigidBodySet,
+    colliders: &ColliderSet,
+    impulse_joints: &ImpulseJointSet,
+    multibody_joints: &MultibodyJointSet,
+) -> String {
+    let mut out = String::new();
+
+    out.push_str("{\"gravity\":");
+    vector(&mut out, gravity);
+
+    out.push_str(",\"integrationParameters\":{\"dt\":");
+    number(&mut out, params.dt);
+    let _ = write!(
+        out,
+        ",\"numSolverIterations\":{},\"numInternalPgsIterations\":{}",
+        params.num_solver_iterations, params.num_internal_pgs_iterations
+    );
+    out.push_str(",\"normalizedAllowedLinearError\":");
+    number(&mut out, params.normalized_allowed_linear_error);
+    out.push_str(",\"normalizedPredictionDistance\":");
+    number(&mut out, params.normalized_prediction_distance);
+    out.push_str(",\"predictionDistance\":");
+    number(&mut out, params.prediction_distance());
+    out.push_str(",\"lengthUnit\":");
+    number(&mut out, params.length_unit);
+    out.push('}');
+
+    out.push_str(",\"bodies\":[");
+    for (i, (handle, rb)) in bodies.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push_str("{\"handle\":");
+        number(&mut out, utils::flat_handle(handle.0));
+        let _ = write!(
+            out,
+            ",\"type\":\"{:?}\",\"enabled\":{},\"sleeping\":{}",
+            rb.body_type(),
+            rb.is_enabled(),
+            rb.is_sleeping()
+        );
+        out.push_str(",\"position\":");
+        isometry(&mut out, rb.position());
+        out.push_str(",\"linvel\":");
+        vector(&mut out, rb.linvel());
+        out.push_str(",\"angvel\":");
+        #[cfg(feature = "dim2")]
+        number(&mut out, rb.angvel());
+        #[cfg(feature = "dim3")]
+        vector(&mut out, rb.angvel());
+        out.push_str(",\"mass\":");
+        number(&mut out, rb.mass());
+        out.push_str(",\"centerOfMass\":");
+        vector(&mut out, &rb.center_of_mass().coords);
+        out.push_str(",\"linearDamping\":");
+        number(&mut out, rb.linear_damping());
+        out.push_str(",\"angularDamping\":");
+        number(&mut out, rb.angular_damping());
+        out.push_str(",\"gravityScale\":");
+        number(&mut out, rb.gravity_scale());
+        let _ = write!(
+            out,
+            ",\"lockedAxes\":{},\"ccdEnabled\":{},\"dominanceGroup\":{}",
+            rb.locked_axes().bits(),
+            rb.is_ccd_enabled(),
+            rb.dominance_group()
+        );
+        let _ = write!(out, ",\"userData\":\"{}\"}}", rb.user_data);
+    }
+    out.push(']');
+
+    out.push_str(",\"colliders\":[");
+    for (i, (handle, co)) in colliders.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        collider(&mut out, utils::flat_handle(handle.0), co);
+    }
+    out.push(']');
+
+    out.push_str(",\"impulseJoints\":[");
+    for (i, (handle, j)) in impulse_joints.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        joint(
+            &mut out,
+            utils::flat_handle(handle.0),
+            utils::flat_handle(j.body1.0),
+            utils::flat_handle(j.body2.0),
+            &j.data,
+        );
+    }
+    out.push(']');
+
+    out.push_str(",\"multibodyJoints\":[");
+    let mut first = true;
+    for multibody in multibody_joints.multibodies() {
+        for link in multibody.links() {
+            let parent = link.parent_id().and_then(|id| multibody.link(id));
+            if let Some(parent) = parent {
+                if !first {
+                    out.push(',');
+                }
+                first = false;
+                joint(
+                    &mut out,
+                    utils::flat_handle(link.rigid_body_handle().0),
+                    utils::flat_handle(parent.rigid_body_handle().0),
+                    utils::flat_handle(link.rigid_body_handle().0),
+                    &link.joint.data,
+                );
+            }
+        }
+    }
+    out.push(']');
+
+    out.push('}');
+    out
+}
@@ -1,34 +1,141 @@
-use crate::dynamics::{RawImpulseJointSet, RawMultibodyJointSet, RawRigidBodySet};
+use crate::dynamics::{
+    RawImpulseJointSet, RawMultibodyJointSet, RawRigidBodySet, RawRigidBodyType,
+};
 use crate::geometry::{RawColliderSet, RawNarrowPhase};
 use js_sys::Float64Array;
 use palette::convert::IntoColorUnclamped;
 use palette::rgb::Rgba;
 use palette::Hsla;
-use rapier::dynamics::{RigidBody, RigidBodySet};
+use rapier::dynamics::{RigidBody, RigidBodySet, RigidBodyType};
 use rapier::geometry::ColliderSet;
 use rapier::math::Point;
-use rapier::pipeline::{DebugRenderBackend, DebugRenderObject, DebugRenderPipeline};
+use rapier::pipeline::{
+    DebugRenderBackend, DebugRenderMode, DebugRenderObject, DebugRenderPipeline,
+};
 use rapier::prelude::{QueryFilter, QueryFilterFlags};
 use wasm_bindgen::prelude::*;
 
+// NOTE: these must match the `DebugRenderCategory` enum on the TS side.
+const CATEGORY_COLLIDERS: u32 = 1 << 0;
+const CATEGORY_SENSORS: u32 = 1 << 1;
+const CATEGORY_JOINTS: u32 = 1 << 2;
+const CATEGORY_CONTACTS: u32 = 1 << 3;
+const CATEGORY_AABBS: u32 = 1 << 4;
+const CATEGORY_RIGID_BODY_AXES: u32 = 1 << 5;
+const CATEGORY_SOLVER_CONTACTS: u32 = 1 << 6;
+const DEFAULT_CATEGORIES: u32 =
+    CATEGORY_COLLIDERS | CATEGORY_SENSORS | CATEGORY_JOINTS | CATEGORY_RIGID_BODY_AXES;
+
+fn categories_to_mode(categories: u32) -> DebugRenderMode {
+    let mut mode = DebugRenderMode::empty();
+    mode.set(
+        DebugRenderMode::COLLIDER_SHAPES,
+        categories & (CATEGORY_COLLIDERS | CATEGORY_SENSORS) != 0,
+    );
+    mode.set(DebugRenderMode::JOINTS, categories & CATEGORY_JOINTS != 0);
+    mode.set(
+        DebugRenderMode::CONTACTS,
+        categories & CATEGORY_CONTACTS != 0,
+    );
+    mode.set(
+        DebugRenderMode::COLLIDER_AABBS,
+        categories & CATEGORY_AABBS != 0,
+    );
+    mode.set(
+        DebugRenderMode::RIGID_BODY_AXES,
+        categories & CATEGORY_RIGID_BODY_AXES != 0,
+    );
+    mode.set(
+        DebugRenderMode::SOLVER_CONTACTS,
+        categories & CATEGORY_SOLVER_CONTACTS != 0,
+    );
+    mode
+}
+
+/// The objects a style override applies to.
+#[derive(Copy, Clone, Debug)]
+enum StyleSelector {
+    /// The colliders member of at least one of the given collision groups.
+    Groups(u32),
+    /// The rigid-bodies of the given type, and the colliders attached to them.
+    BodyType(RigidBodyType),
+}
+
+/// A color replacing the default debug-render color of some objects.
+#[derive(Copy, Clone, Debug)]
+struct StyleOverride {
+    selector: StyleSelector,
+    /// The RGBA color.
+    color: [f64; 4],
+}
+
 #[wasm_bindgen]
 pub struct RawDebugRenderPipeline {
     pub(crate) raw: DebugRenderPipeline,
     vertices: Vec<f64>,
     colors: Vec<f64>,
+    categories: u32,
+    style_overrides: Vec<StyleOverride>,
 }
 
 #[wasm_bindgen]
 impl RawDebugRenderPipeline {
     #[wasm_bindgen(constructor)]
     pub fn new() -> Self {
+        let mut raw = DebugRenderPipeline::default();
+        raw.mode = categories_to_mode(DEFAULT_CATEGORIES);
         RawDebugRenderPipeline {
-            raw: DebugRenderPipeline::default(),
+            raw,
             vertices: vec![],
             colors: vec![],
+            categories: DEFAULT_CATEGORIES,
+            style_overrides: vec![],
         }
     }
 
+    /// The bit mask of the categories of objects being rendered.
+    pub fn categories(&self) -> u32 {
+        self.categories
+    }
+
+    pub fn setCategories(&mut self, categories: u32) {
+        self.categories = categories;
+        self.raw.mode = categories_to_mode(categories);
+    }
+
+    /// Renders the colliders member of at least one of the given collision groups, and their
+    /// AABBs, with the given RGBA color.
+    ///
+    /// Overrides are checked in insertion order, and the first matching one applies.
+    pub fn addStyleOverrideForGroups(&mut self, groups: u32, r: f64, g: f64, b: f64, a: f64) {
+        self.style_overrides.push(StyleOverride {
+            selector: StyleSelector::Groups(groups),
+            color: [r, g, b, a],
+        });
+    }
+
+    /// Renders the rigid-bodies of the given type, the colliders attached to them, and their
+    /// AABBs, with the given RGBA color.
+    ///
+    /// Overrides are checked in insertion order, and the first matching one applies.
+    pub fn addStyleOverrideForBodyType(
+        &mut self,
+        bodyType: RawRigidBodyType,
+        r: f64,
+        g: f64,
+        b: f64,
+        a: f64,
+    ) {
+        self.style_overrides.push(StyleOverride {
+            selector: StyleSelector::BodyType(bodyType.into()),
+            color: [r, g, b, a],
+        });
+    }
+
+    pub fn clearStyleOverrides(&mut self) {
+        self.style_overrides.clear();
+    }
+
     pub fn vertices(&self) -> Float64Array {
         let output = Float64Array::new_with_length(self.vertices.len() as u32);
         let f64_vertices: Vec<f64> = self.vertices.iter().map(|&v| v as f64).collect();
@@ -67,6 +174,9 @@ impl RawDebugRenderPipeline {
                 },
                 bodies: &bodies.0,
                 colliders: &colliders.0,
+                render_sensors: self.categories & CATEGORY_SENSORS != 0,
+                render_non_sensors: self.categories & CATEGORY_COLLIDERS != 0,
+                style_overrides: &self.style_overrides,
                 vertices: &mut self.vertices,
                 colors: &mut self.colors,
             };
@@ -87,10 +197,41 @@ struct CopyToBuffersBackend<'a> {
     filter: QueryFilter<'a>,
     bodies: &'a RigidBodySet,
     colliders: &'a ColliderSet,
+    render_sensors: bool,
+    render_non_sensors: bool,
+    style_overrides: &'a [StyleOverride],
     vertices: &'a mut Vec<f64>,
     colors: &'a mut Vec<f64>,
 }
 
+impl CopyToBuffersBackend<'_> {
+    /// The color of the first style override matching the given object, if any.
+    fn override_color(&self, object: &DebugRenderObject) -> Option<[f64; 4]> {
+        if self.style_overrides.is_empty() {
+            return None;
+        }
+
+        let (memberships, body_type) = match object {
+            DebugRenderObject::Collider(_, co) | DebugRenderObject::ColliderAabb(_, co, _) => (
+                co.collision_groups().memberships.bits(),
+                co.parent()
+                    .and_then(|h| self.bodies.get(h))
+                    .map(|rb| rb.body_type()),
+            ),
+            DebugRenderObject::RigidBody(_, rb) => (0, Some(rb.body_type())),
+            _ => return None,
+        };
+
+        self.style_overrides
+            .iter()
+            .find(|style| match style.selector {
+                StyleSelector::Groups(groups) => memberships & groups != 0,
+                StyleSelector::BodyType(ty) => body_type == Some(ty),
+            })
+            .map(|style| style.color)
+    }
+}
+
 impl<'a> DebugRenderBackend for CopyToBuffersBackend<'a> {
     fn filter_object(&self, object: DebugRenderObject) -> bool {
         let test_rigid_body = |rb: &RigidBody| {
@@ -103,8 +244,15 @@ impl<'a> DebugRenderBackend for CopyToBuffersBackend<'a> {
         };
 
         match object {
-            DebugRenderObject::Collider(handle, co)
-            | DebugRenderObject::ColliderAabb(handle, co, _) => {
+            DebugRenderObject::Collider(handle, co) => {
+                let category_enabled = if co.is_sensor() {
+                    self.render_sensors
+                } else {
+                    self.render_non_sensors
+                };
+                category_enabled && self.filter.test(self.bodies, handle, co)
+            }
+            DebugRenderObject::ColliderAabb(handle, co, _) => {
                 self.filter.test(self.bodies, handle, co)
             }
             DebugRenderObject::ContactPair(pair, co1, co2) => {
@@ -135,7 +283,7 @@ impl<'a> DebugRenderBackend for CopyToBuffersBackend<'a> {
     /// Note that this method can be called multiple time for the same `object`.
     fn draw_line(
         &mut self,
-        _object: DebugRenderObject,
+        object: DebugRenderObject,
         a: Point<f64>,
         b: Point<f64>,
         color: [f32; 4],
@@ -143,18 +291,18 @@ impl<'a> DebugRenderBackend for CopyToBuffersBackend<'a> {
         self.vertices.extend_from_slice(a.coords.as_slice());
         self.vertices.extend_from_slice(b.coords.as_slice());
 
-        // Convert to RGB which will be easier to handle in JS.
-        let hsl = Hsla::new(color[0], color[1], color[2], color[3]);
-        let rgb: Rgba<f32> = hsl.into_color_unclamped();
-        self.colors.extend_from_slice(&[
-            rgb.red as f64,
-            rgb.green as f64,
-            rgb.blue as f64,
-            rgb.alpha as f64,
-            rgb.red as f64,
-            rgb.green as f64,
-            rgb.blue as f64,
-            rgb.alpha as f64,
-        ]);
+        let [red, green, blue, alpha] = self.override_color(&object).unwrap_or_else(|| {
+            // Convert to RGB which will be easier to handle in JS.
+            let hsl = Hsla::new(color[0], color[1], color[2], color[3]);
+            let rgb: Rgba<f32> = hsl.into_color_unclamped();
+            [
+                rgb.red as f64,
+                rgb.green as f64,
+                rgb.blue as f64,
+                rgb.alpha as f64,
+            ]
+        });
+        self.colors
+            .extend_from_slice(&[red, green, blue, alpha, red, green, blue, alpha]);
     }
 }
@@ -1,9 +1,15 @@
+use crate::geometry::RawNarrowPhase;
 use crate::math::RawVector;
 use crate::utils;
 use crate::utils::FlatHandle;
-use rapier::geometry::{CollisionEvent, ContactForceEvent};
-use rapier::pipeline::ChannelEventCollector;
-use std::sync::mpsc::Receiver;
+use rapier::dynamics::RigidBodySet;
+use rapier::geometry::{
+    ColliderHandle, ColliderSet, CollisionEvent, ContactForceEvent, ContactPair,
+};
+use rapier::math::Real;
+use rapier::pipeline::{ChannelEventCollector, EventHandler};
+use std::collections::HashSet;
+use std::sync::mpsc::{self, Receiver, Sender};
 use wasm_bindgen::prelude::*;
 
 /// A structure responsible for collecting events generated
@@ -14,6 +20,209 @@ pub struct RawEventQueue {
     collision_events: Receiver<CollisionEvent>,
     contact_force_events: Receiver<ContactForceEvent>,
     pub(crate) auto_drain: bool,
+    interests: EventInterests,
+    force_thresholds: ContactForceThresholds,
+    substep_tags: Option<SubstepTags>,
+}
+
+/// The substep during which an event was generated.
+#[derive(Copy, Clone, Debug, Default)]
+pub(crate) struct SubstepTag {
+    /// The index of the substep in its step.
+    pub substep: u32,
+    /// The simulation time at the end of the substep.
+    pub time: Real,
+}
+
+impl SubstepTag {
+    /// The substep and time passed to the drain closures, `undefined` for untagged events.
+    fn args(tag: Option<SubstepTag>) -> (JsValue, JsValue) {
+        match tag {
+            Some(tag) => (JsValue::from(tag.substep), JsValue::from(tag.time)),
+            None => (JsValue::UNDEFINED, JsValue::UNDEFINED),
+        }
+    }
+}
+
+/// The channels of the substep tags, sent alongside each event reported to the collector.
+struct SubstepTags {
+    collision_sender: Sender<SubstepTag>,
+    collision: Receiver<SubstepTag>,
+    contact_force_sender: Sender<SubstepTag>,
+    contact_force: Receiver<SubstepTag>,
+}
+
+impl SubstepTags {
+    fn new() -> Self {
+        let collision_channel = mpsc::channel();
+        let contact_force_channel = mpsc::channel();
+        Self {
+            collision_sender: collision_channel.0,
+            collision: collision_channel.1,
+            contact_force_sender: contact_force_channel.0,
+            contact_force: contact_force_channel.1,
+        }
+    }
+}
+
+/// The colliders an event queue is interested in.
+///
+/// If no interest is registered, all the events are reported.
+#[derive(Clone, Debug, Default)]
+struct EventInterests {
+    colliders: HashSet<ColliderHandle>,
+    /// Bit mask of the collision groups of interest.
+    groups: u32,
+}
+
+impl EventInterests {
+    fn is_empty(&self) -> bool {
+        self.colliders.is_empty() && self.groups == 0
+    }
+
+    fn involves(&self, colliders: &ColliderSet, handle: ColliderHandle) -> bool {
+        self.colliders.contains(&handle)
+            || colliders
+                .get(handle)
+                .map(|co| co.collision_groups().memberships.bits() & self.groups != 0)
+                .unwrap_or(false)
+    }
+
+    fn accepts(
+        &self,
+        colliders: &ColliderSet,
+        handle1: ColliderHandle,
+        handle2: ColliderHandle,
+    ) -> bool {
+        self.is_empty() || self.involves(colliders, handle1) || self.involves(colliders, handle2)
+    }
+}
+
+/// A contact force event threshold applying to the pairs of colliders members of two sets of
+/// collision groups.
+#[derive(Copy, Clone, Debug)]
+struct ContactForceThreshold {
+    id: u32,
+    groups1: u32,
+    groups2: u32,
+    threshold: Real,
+}
+
+/// A table of contact force event thresholds per pair of collision groups.
+#[derive(Clone, Debug, Default)]
+struct ContactForceThresholds {
+    thresholds: Vec<ContactForceThreshold>,
+    next_id: u32,
+}
+
+impl ContactForceThresholds {
+    /// Checks if the force between two colliders reaches the thresholds of the pair.
+    ///
+    /// If several thresholds apply to the pair, the largest one is used.
+    fn accepts(
+        &self,
+        colliders: &ColliderSet,
+        handle1: ColliderHandle,
+        handle2: ColliderHandle,
+        total_force_magnitude: Real,
+    ) -> bool {
+        if self.thresholds.is_empty() {
+            return true;
+        }
+
+        let memberships = |handle: ColliderHandle| {
+            colliders
+                .get(handle)
+                .map_or(0, |co| co.collision_groups().memberships.bits())
+        };
+        let memberships1 = memberships(handle1);
+        let memberships2 = memberships(handle2);
+
+        self.thresholds
+            .iter()
+            .filter(|t| {
+                (memberships1 & t.groups1 != 0 && memberships2 & t.groups2 != 0)
+                    || (memberships1 & t.groups2 != 0 && memberships2 & t.groups1 != 0)
+            })
+            .all(|t| total_force_magnitude >= t.threshold)
+    }
+}
+
+/// An event handler forwarding to the event collector only the events involving
+/// colliders of interest.
+pub(crate) struct FilteredEventCollector<'a> {
+    collector: &'a ChannelEventCollector,
+    interests: &'a EventInterests,
+    force_thresholds: &'a ContactForceThresholds,
+    substep_tags: Option<&'a SubstepTags>,
+    tag: SubstepTag,
+}
+
+impl EventHandler for FilteredEventCollector<'_> {
+    fn handle_collision_event(
+        &self,
+        bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+        event: CollisionEvent,
+        contact_pair: Option<&ContactPair>,
+    ) {
+        if self
+            .interests
+            .accepts(colliders, event.collider1(), event.collider2())
+        {
+            self.collector
+                .handle_collision_event(bodies, colliders, event, contact_pair);
+            if let Some(tags) = self.substep_tags {
+                let _ = tags.collision_sender.send(self.tag);
+            }
+        }
+    }
+
+    fn handle_contact_force_event(
+        &self,
+        dt: Real,
+        bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+        contact_pair: &ContactPair,
+        total_force_magnitude: Real,
+    ) {
+        let (handle1, handle2) = (contact_pair.collider1, contact_pair.collider2);
+        if self.interests.accepts(colliders, handle1, handle2)
+            && self
+                .force_thresholds
+                .accepts(colliders, handle1, handle2, total_force_magnitude)
+        {
+            self.collector.handle_contact_force_event(
+                dt,
+                bodies,
+                colliders,
+                contact_pair,
+                total_force_magnitude,
+            );
+            if let Some(tags) = self.substep_tags {
+                let _ = tags.contact_force_sender.send(self.tag);
+            }
+        }
+    }
+}
+
+impl RawEventQueue {
+    /// The event handler to pass to the physics pipeline, for the substep identified by `tag`.
+    pub(crate) fn event_handler(&self, tag: SubstepTag) -> FilteredEventCollector<'_> {
+        FilteredEventCollector {
+            collector: &self.collector,
+            interests: &self.interests,
+            force_thresholds: &self.force_thresholds,
+            substep_tags: self.substep_tags.as_ref(),
+            tag,
+        }
+    }
+
+    fn next_collision_tag(&self) -> Option<SubstepTag> {
+        self.substep_tags
+            .as_ref()
+            .and_then(|tags| tags.collision.try_recv().ok())
+    }
 }
 
 #[wasm_bindgen]
@@ -87,9 +296,99 @@ impl RawEventQueue {
             collision_events: collision_channel.1,
             contact_force_events: contact_force_channel.1,
             auto_drain: autoDrain,
+            interests: EventInterests::default(),
+            force_thresholds: ContactForceThresholds::default(),
+            substep_tags: None,
         }
     }
 
+    /// Enables the tagging of the events with the substep during which they were generated
+    /// (disabled by default).
+    ///
+    /// When enabled, the closures given to the drain methods also receive the index of the
+    /// substep in its step, and the simulation time at the end of that substep. This allows
+    /// ordering the events of pairs of colliders that start and stop touching during the same
+    /// step. The events already in the queue are removed when this is changed.
+    pub fn setSubstepTagsEnabled(&mut self, enabled: bool) {
+        while self.collision_events.try_recv().is_ok() {}
+        while self.contact_force_events.try_recv().is_ok() {}
+        self.substep_tags = enabled.then(SubstepTags::new);
+    }
+
+    pub fn substepTagsEnabled(&self) -> bool {
+        self.substep_tags.is_some()
+    }
+
+    /// Only report the events involving the given collider, or the other registered interests.
+    ///
+    /// If no interest is registered, the events involving any collider are reported.
+    pub fn addInterestCollider(&mut self, handle: FlatHandle) {
+        let _ = self
+            .interests
+            .colliders
+            .insert(utils::collider_handle(handle));
+    }
+
+    pub fn removeInterestCollider(&mut self, handle: FlatHandle) {
+        let _ = self
+            .interests
+            .colliders
+            .remove(&utils::collider_handle(handle));
+    }
+
+    /// Only report the events involving a collider member of at least one of the given
+    /// collision groups, or the other registered interests.
+    ///
+    /// # Parameters
+    /// - `groups`: bit mask of the collision groups of interest, `0` to disable this interest.
+    pub fn setInterestGroups(&mut self, groups: u32) {
+        self.interests.groups = groups;
+    }
+
+    pub fn interestGroups(&self) -> u32 {
+        self.interests.groups
+    }
+
+    /// Removes all the registered interests, so that all the events are reported.
+    pub fn clearInterests(&mut self) {
+        self.interests = EventInterests::default();
+    }
+
+    /// Adds a contact force event threshold for the pairs of colliders members of `groups1` and
+    /// `groups2`, in addition to the thresholds of the colliders.
+    ///
+    /// The contact force events of these pairs are only reported if the total force magnitude
+    /// reaches `threshold`. The pipeline only generates contact force events above the smallest
+    /// threshold of the two colliders, so this can only make the pairs less sensitive, e.g., to
+    /// suppress noisy ground contacts while reporting the impacts between objects of the same
+    /// groups. If several thresholds apply to a pair, the largest one is used.
+    ///
+    /// Returns the id of the threshold.
+    pub fn addContactForceThreshold(&mut self, groups1: u32, groups2: u32, threshold: f64) -> u32 {
+        let thresholds = &mut self.force_thresholds;
+        let id = thresholds.next_id;
+        thresholds.next_id = thresholds.next_id.wrapping_add(1);
+        thresholds.thresholds.push(ContactForceThreshold {
+            id,
+            groups1,
+            groups2,
+            threshold,
+        });
+        id
+    }
+
+    pub fn removeContactForceThreshold(&mut self, id: u32) {
+        self.force_thresholds.thresholds.retain(|t| t.id != id);
+    }
+
+    pub fn clearContactForceThresholds(&mut self) {
+        self.force_thresholds.thresholds.clear();
+    }
+
+    pub fn numContactForceThresholds(&self) -> usize {
+        self.force_thresholds.thresholds.len()
+    }
+
     /// Applies the given javascript closure on each collision event of this collector, then clear
     /// the internal collision event buffer.
     ///
@@ -97,44 +396,98 @@ impl RawEventQueue {
     /// - `f(handle1, handle2, started)`:  JavaScript closure applied to each collision event. The
     /// closure should take three arguments: two integers representing the handles of the colliders
     /// involved in the collision, and a boolean indicating if the collision started (true) or stopped
-    /// (false).
+    /// (false). If substep tags are enabled, the closure also receives the index of the substep
+    /// and the simulation time at its end.
     pub fn drainCollisionEvents(&mut self, f: &js_sys::Function) {
         let this = JsValue::null();
         while let Ok(event) = self.collision_events.try_recv() {
-            match event {
-                CollisionEvent::Started(co1, co2, _) => {
-                    let h1 = utils::flat_handle(co1.0);
-                    let h2 = utils::flat_handle(co2.0);
-                    let _ = f.call3(
-                        &this,
-                        &JsValue::from(h1),
-                        &JsValue::from(h2),
-                        &JsValue::from_bool(true),
-                    );
-                }
-                CollisionEvent::Stopped(co1, co2, _) => {
-                    let h1 = utils::flat_handle(co1.0);
-                    let h2 = utils::flat_handle(co2.0);
-                    let _ = f.call3(
-                        &this,
-                        &JsValue::from(h1),
-                        &JsValue::from(h2),
-                        &JsValue::from_bool(false),
-                    );
-                }
+            let (co1, co2, started) = match event {
+                CollisionEvent::Started(co1, co2, _) => (co1, co2, true),
+                CollisionEvent::Stopped(co1, co2, _) => (co1, co2, false),
+            };
+            let h1 = JsValue::from(utils::flat_handle(co1.0));
+            let h2 = JsValue::from(utils::flat_handle(co2.0));
+            let started = JsValue::from_bool(started);
+
+            if self.substep_tags.is_some() {
+                let (substep, time) = SubstepTag::args(self.next_collision_tag());
+                let args = js_sys::Array::of5(&h1, &h2, &started, &substep, &time);
+                let _ = f.apply(&this, &args);
+            } else {
+                let _ = f.call3(&this, &h1, &h2, &started);
+            }
+        }
+    }
+
+    /// Applies the given javascript closure on each collision event of this collector, then clear
+    /// the internal collision event buffer.
+    ///
+    /// This is similar to `drainCollisionEvents` except that the closure also receives the stable
+    /// id of the pair of colliders, as tracked by the narrow-phase.
+    ///
+    /// # Parameters
+    /// - `f(handle1, handle2, started, pairId)`: JavaScript closure applied to each collision event.
+    /// `pairId` is `undefined` if contact pair ids are disabled on the narrow-phase. If substep
+    /// tags are enabled, the closure also receives the index of the substep and the simulation
+    /// time at its end.
+    pub fn drainCollisionEventsWithPairIds(
+        &mut self,
+        narrowPhase: &RawNarrowPhase,
+        f: &js_sys::Function,
+    ) {
+        let this = JsValue::null();
+        while let Ok(event) = self.collision_events.try_recv() {
+            let (co1, co2, started) = match event {
+                CollisionEvent::Started(co1, co2, _) => (co1, co2, true),
+                CollisionEvent::Stopped(co1, co2, _) => (co1, co2, false),
+            };
+            let pair_id = narrowPhase
+                .1
+                .pair_ids
+                .get(co1, co2)
+                .map(JsValue::from)
+                .unwrap_or(JsValue::UNDEFINED);
+            let args = js_sys::Array::of4(
+                &JsValue::from(utils::flat_handle(co1.0)),
+                &JsValue::from(utils::flat_handle(co2.0)),
+                &JsValue::from_bool(started),
+                &pair_id,
+            );
+            if self.substep_tags.is_some() {
+                let (substep, time) = SubstepTag::args(self.next_collision_tag());
+                let _ = args.push(&substep);
+                let _ = args.push(&time);
             }
+            let _ = f.apply(&this, &args);
         }
     }
 
+    /// Applies the given javascript closure on each contact force event of this collector,
+    /// then clear the internal contact force event buffer.
+    ///
+    /// If substep tags are enabled, the closure also receives the index of the substep and the
+    /// simulation time at its end.
     pub fn drainContactForceEvents(&mut self, f: &js_sys::Function) {
         let this = JsValue::null();
         while let Ok(event) = self.contact_force_events.try_recv() {
-            let _ = f.call1(&this, &JsValue::from(RawContactForceEvent(event)));
+            let event = JsValue::from(RawContactForceEvent(event));
+            match &self.substep_tags {
+                Some(tags) => {
+                    let (substep, time) = SubstepTag::args(tags.contact_force.try_recv().ok());
+                    let _ = f.call3(&this, &event, &substep, &time);
+                }
+                None => {
+                    let _ = f.call1(&this, &event);
+                }
+            }
         }
     }
 
     /// Removes all events contained by this collector.
     pub fn clear(&self) {
         while let Ok(_) = self.collision_events.try_recv() {}
+        if let Some(tags) = &self.substep_tags {
+            while tags.collision.try_recv().is_ok() {}
+        }
     }
 }
@@ -0,0 +1,366 @@
+//! Conversion of the snapshots taken by the f32 bindings into f64 worlds.
+
+use rapier::dynamics::{
+    CoefficientCombineRule, GenericJoint, ImpulseJointSet, IntegrationParameters, JointAxesMask,
+    JointLimits, JointMotor, LockedAxes, MotorModel, MultibodyJointSet, RigidBodyBuilder,
+    RigidBodyHandle, RigidBodySet, RigidBodyType,
+};
+use rapier::geometry::{
+    ActiveCollisionTypes, ActiveEvents, ActiveHooks, ColliderBuilder, ColliderSet,
+    InteractionGroups, SharedShape, TriMeshFlags,
+};
+use rapier::math::{Isometry, Point, Real, Vector, SPATIAL_DIM};
+use rapier::parry::mass_properties::MassProperties;
+use rapier::prelude::Group;
+use rapier3d as rapier_f32;
+use rapier_f32::parry::shape::TypedShape as TypedShapeF32;
+use std::collections::HashMap;
+
+/// The content of a snapshot taken by the f32 bindings.
+///
+/// This must match the `SerializableWorld` of the f32 bindings.
+#[derive(Deserialize)]
+struct DeserializableWorldF32 {
+    gravity: rapier_f32::math::Vector<f32>,
+    integration_parameters: rapier_f32::dynamics::IntegrationParameters,
+    #[allow(dead_code)]
+    islands: rapier_f32::dynamics::IslandManager,
+    #[allow(dead_code)]
+    broad_phase: rapier_f32::geometry::DefaultBroadPhase,
+    #[allow(dead_code)]
+    narrow_phase: rapier_f32::geometry::NarrowPhase,
+    bodies: rapier_f32::dynamics::RigidBodySet,
+    colliders: rapier_f32::geometry::ColliderSet,
+    impulse_joints: rapier_f32::dynamics::ImpulseJointSet,
+    multibody_joints: rapier_f32::dynamics::MultibodyJointSet,
+}
+
+/// The f64 version of a world deserialized from a f32 snapshot.
+///
+/// The broad-phase, narrow-phase, and islands are not converted: they are rebuilt
+/// during the next simulation step.
+pub(crate) struct ConvertedWorld {
+    pub gravity: Vector<Real>,
+    pub integration_parameters: IntegrationParameters,
+    pub bodies: RigidBodySet,
+    pub colliders: ColliderSet,
+    pub impulse_joints: ImpulseJointSet,
+    pub multibody_joints: MultibodyJointSet,
+}
+
+/// Deserializes a snapshot taken by the f32 bindings and converts it to f64.
+///
+/// The colliders with a shape that can’t be converted (voxels and custom shapes) are skipped.
+pub(crate) fn deserialize_f32_snapshot(data: &[u8]) -> Option<ConvertedWorld> {
+    let world: DeserializableWorldF32 = bincode::deserialize(data).ok()?;
+
+    let mut bodies = RigidBodySet::new();
+    let mut body_handles = HashMap::new();
+
+    for (handle, rb) in world.bodies.iter() {
+        let new_handle = bodies.insert(convert_body(rb));
+        let _ = body_handles.insert(handle.into_raw_parts(), new_handle);
+    }
+
+    let body_handle = |handle: rapier_f32::dynamics::RigidBodyHandle| -> Option<RigidBodyHandle> {
+        body_handles.get(&handle.into_raw_parts()).copied()
+    };
+
+    let mut colliders = ColliderSet::new();
+
+    for (_, co) in world.colliders.iter() {
+        if let Some(shape) = convert_shape(co.shape()) {
+            let collider = convert_collider(co, shape);
+
+            match co.parent().and_then(body_handle) {
+                Some(parent) => {
+                    let _ = colliders.insert_with_parent(collider, parent, &mut bodies);
+                }
+                None => {
+                    let _ = colliders.insert(collider);
+                }
+            }
+        }
+    }
+
+    let mut impulse_joints = ImpulseJointSet::new();
+
+    for (_, joint) in world.impulse_joints.iter() {
+        if let (Some(body1), Some(body2)) = (body_handle(joint.body1), body_handle(joint.body2)) {
+            let _ = impulse_joints.insert(body1, body2, convert_joint(&joint.data), false);
+        }
+    }
+
+    let mut multibody_joints = MultibodyJointSet::new();
+
+    for multibody in world.multibody_joints.multibodies() {
+        // The links are sorted such that parents always come before their children.
+        for link in multibody.links() {
+            let body1 = link
+                .parent_id()
+                .and_then(|parent| multibody.link(parent))
+                .and_then(|parent| body_handle(parent.rigid_body_handle()));
+            let body2 = body_handle(link.rigid_body_handle());
+
+            if let (Some(body1), Some(body2)) = (body1, body2) {
+                let joint = convert_joint(&link.joint.data);
+                let _ = multibody_joints.insert(body1, body2, joint, false);
+            }
+        }
+    }
+
+    Some(ConvertedWorld {
+        gravity: vector(&world.gravity),
+        integration_parameters: convert_integration_parameters(&world.integration_parameters),
+        bodies,
+        colliders,
+        impulse_joints,
+        multibody_joints,
+    })
+}
+
+fn vector(v: &rapier_f32::math::Vector<f32>) -> Vector<Real> {
+    v.cast::<Real>()
+}
+
+fn point(p: &rapier_f32::math::Point<f32>) -> Point<Real> {
+    p.cast::<Real>()
+}
+
+fn isometry(pos: &rapier_f32::math::Isometry<f32>) -> Isometry<Real> {
+    pos.cast::<Real>()
+}
+
+fn mass_properties(mprops: &rapier_f32::parry::mass_properties::MassProperties) -> MassProperties {
+    MassProperties::with_principal_inertia_frame(
+        point(&mprops.local_com),
+        mprops.mass() as Real,
+        vector(&mprops.principal_inertia()),
+        mprops.principal_inertia_local_frame.cast::<Real>(),
+    )
+}
+
+fn combine_rule(rule: rapier_f32::dynamics::CoefficientCombineRule) -> CoefficientCombineRule {
+    crate::geometry::combine_rule_from_u32(rule as u32)
+}
+
+fn interaction_groups(groups: rapier_f32::geometry::InteractionGroups) -> InteractionGroups {
+    InteractionGroups::new(
+        Group::from_bits_retain(groups.memberships.bits()),
+        Group::from_bits_retain(groups.filter.bits()),
+    )
+}
+
+fn convert_integration_parameters(
+    params: &rapier_f32::dynamics::IntegrationParameters,
+) -> IntegrationParameters {
+    IntegrationParameters {
+        dt: params.dt as Real,
+        min_ccd_dt: params.min_ccd_dt as Real,
+        contact_natural_frequency: params.contact_natural_frequency as Real,
+        joint_natural_frequency: params.joint_natural_frequency as Real,
+        joint_damping_ratio: params.joint_damping_ratio as Real,
+        normalized_allowed_linear_error: params.normalized_allowed_linear_error as Real,
+        normalized_prediction_distance: params.normalized_prediction_distance as Real,
+        num_solver_iterations: params.num_solver_iterations,
+        num_internal_pgs_iterations: params.num_internal_pgs_iterations,
+        min_island_size: params.min_island_size,
+        max_ccd_substeps: params.max_ccd_substeps,
+        length_unit: params.length_unit as Real,
+        ..IntegrationParameters::default()
+    }
+}
+
+fn convert_body(rb: &rapier_f32::dynamics::RigidBody) -> rapier::dynamics::RigidBody {
+    let body_type = match rb.body_type() {
+        rapier_f32::dynamics::RigidBodyType::Dynamic => RigidBodyType::Dynamic,
+        rapier_f32::dynamics::RigidBodyType::Fixed => RigidBodyType::Fixed,
+        rapier_f32::dynamics::RigidBodyType::KinematicPositionBased => {
+            RigidBodyType::KinematicPositionBased
+        }
+        rapier_f32::dynamics::RigidBodyType::KinematicVelocityBased => {
+            RigidBodyType::KinematicVelocityBased
+        }
+    };
+
+    let mut builder = RigidBodyBuilder::new(body_type)
+        .enabled(rb.is_enabled())
+        .pose(isometry(rb.position()))
+        .gravity_scale(rb.gravity_scale() as Real)
+        .locked_axes(LockedAxes::from_bits_truncate(rb.locked_axes().bits()))
+        .linvel(vector(rb.linvel()))
+        .angvel(vector(rb.angvel()))
+        .linear_damping(rb.linear_damping() as Real)
+        .angular_damping(rb.angular_damping() as Real)
+        .sleeping(rb.is_sleeping())
+        .ccd_enabled(rb.is_ccd_enabled())
+        .soft_ccd_prediction(rb.soft_ccd_prediction() as Real)
+        .dominance_group(rb.dominance_group())
+        .additional_solver_iterations(rb.additional_solver_iterations())
+        .user_data(rb.user_data);
+
+    if let Some(additional) = &rb.mass_properties().additional_local_mprops {
+        builder = match &**additional {
+            rapier_f32::dynamics::RigidBodyAdditionalMassProps::MassProps(mprops) => {
+                builder.additional_mass_properties(mass_properties(mprops))
+            }
+            rapier_f32::dynamics::RigidBodyAdditionalMassProps::Mass(mass) => {
+                builder.additional_mass(*mass as Real)
+            }
+        };
+    }
+
+    builder.build()
+}
+
+fn convert_collider(
+    co: &rapier_f32::geometry::Collider,
+    shape: SharedShape,
+) -> rapier::geometry::Collider {
+    let pos = co
+        .position_wrt_parent()
+        .map(isometry)
+        .unwrap_or_else(|| isometry(co.position()));
+
+    ColliderBuilder::new(shape)
+        .enabled(co.is_enabled())
+        .position(pos)
+        .mass_properties(mass_properties(&co.mass_properties()))
+        .friction(co.material().friction as Real)
+        .restitution(co.material().restitution as Real)
+        .friction_combine_rule(combine_rule(co.friction_combine_rule()))
+        .restitution_combine_rule(combine_rule(co.restitution_combine_rule()))
+        .sensor(co.is_sensor())
+        .collision_groups(interaction_groups(co.collision_groups()))
+        .solver_groups(interaction_groups(co.solver_groups()))
+        .active_collision_types(ActiveCollisionTypes::from_bits_truncate(
+            co.active_collision_types().bits(),
+        ))
+        .active_hooks(ActiveHooks::from_bits_truncate(co.active_hooks().bits()))
+        .active_events(ActiveEvents::from_bits_truncate(co.active_events().bits()))
+        .contact_force_event_threshold(co.contact_force_event_threshold() as Real)
+        .contact_skin(co.contact_skin() as Real)
+        .user_data(co.user_data)
+        .build()
+}
+
+fn convert_shape(shape: &dyn rapier_f32::parry::shape::Shape) -> Option<SharedShape> {
+    let points = |pts: &[rapier_f32::math::Point<f32>]| pts.iter().map(point).collect::<Vec<_>>();
+
+    match shape.as_typed_shape() {
+        TypedShapeF32::Ball(s) => Some(SharedShape::ball(s.radius as Real)),
+        TypedShapeF32::Cuboid(s) => {
+            let he = vector(&s.half_extents);
+            Some(SharedShape::cuboid(he.x, he.y, he.z))
+        }
+        TypedShapeF32::RoundCuboid(s) => {
+            let he = vector(&s.inner_shape.half_extents);
+            Some(SharedShape::round_cuboid(
+                he.x,
+                he.y,
+                he.z,
+                s.border_radius as Real,
+            ))
+        }
+        TypedShapeF32::Capsule(s) => Some(SharedShape::capsule(
+            point(&s.segment.a),
+            point(&s.segment.b),
+            s.radius as Real,
+        )),
+        TypedShapeF32::Segment(s) => Some(SharedShape::segment(point(&s.a), point(&s.b))),
+        TypedShapeF32::Triangle(s) => {
+            Some(SharedShape::triangle(point(&s.a), point(&s.b), point(&s.c)))
+        }
+        TypedShapeF32::RoundTriangle(s) => Some(SharedShape::round_triangle(
+            point(&s.inner_shape.a),
+            point(&s.inner_shape.b),
+            point(&s.inner_shape.c),
+            s.border_radius as Real,
+        )),
+        TypedShapeF32::TriMesh(s) => SharedShape::trimesh_with_flags(
+            points(s.vertices()),
+            s.indices().to_vec(),
+            TriMeshFlags::from_bits_truncate(s.flags().bits()),
+        )
+        .ok(),
+        TypedShapeF32::Polyline(s) => Some(SharedShape::polyline(
+            points(s.vertices()),
+            Some(s.indices().to_vec()),
+        )),
+        TypedShapeF32::HalfSpace(s) => Some(SharedShape::halfspace(
+            rapier::math::UnitVector::new_normalize(vector(&s.normal)),
+        )),
+        TypedShapeF32::HeightField(s) => Some(SharedShape::heightfield(
+            s.heights().map(|h| h as Real),
+            vector(s.scale()),
+        )),
+        TypedShapeF32::ConvexPolyhedron(s) => SharedShape::convex_hull(&points(s.points())),
+        TypedShapeF32::RoundConvexPolyhedron(s) => {
+            SharedShape::round_convex_hull(&points(s.inner_shape.points()), s.border_radius as Real)
+        }
+        TypedShapeF32::Cylinder(s) => Some(SharedShape::cylinder(
+            s.half_height as Real,
+            s.radius as Real,
+        )),
+        TypedShapeF32::RoundCylinder(s) => Some(SharedShape::round_cylinder(
+            s.inner_shape.half_height as Real,
+            s.inner_shape.radius as Real,
+            s.border_radius as Real,
+        )),
+        TypedShapeF32::Cone(s) => Some(SharedShape::cone(s.half_height as Real, s.radius as Real)),
+        TypedShapeF32::RoundCone(s) => Some(SharedShape::round_cone(
+            s.inner_shape.half_height as Real,
+            s.inner_shape.radius as Real,
+            s.border_radius as Real,
+        )),
+        TypedShapeF32::Compound(s) => {
+            let shapes = s
+                .shapes()
+                .iter()
+                .map(|(pos, shape)| Some((isometry(pos), convert_shape(&**shape)?)))
+                .collect::<Option<Vec<_>>>()?;
+            Some(SharedShape::compound(shapes))
+        }
+        _ => None,
+    }
+}
+
+fn convert_joint(joint: &rapier_f32::dynamics::GenericJoint) -> GenericJoint {
+    let mut result = GenericJoint::default();
+    result.local_frame1 = isometry(&joint.local_frame1);
+    result.local_frame2 = isometry(&joint.local_frame2);
+    result.locked_axes = JointAxesMask::from_bits_truncate(joint.locked_axes.bits());
+    result.limit_axes = JointAxesMask::from_bits_truncate(joint.limit_axes.bits());
+    result.motor_axes = JointAxesMask::from_bits_truncate(joint.motor_axes.bits());
+    result.coupled_axes = JointAxesMask::from_bits_truncate(joint.coupled_axes.bits());
+    result.contacts_enabled = joint.contacts_enabled;
+    result.user_data = joint.user_data;
+
+    for i in 0..SPATIAL_DIM {
+        let limits = &joint.limits[i];
+        result.limits[i] = JointLimits {
+            min: limits.min as Real,
+            max: limits.max as Real,
+            impulse: limits.impulse as Real,
+        };
+
+        let motor = &joint.motors[i];
+        result.motors[i] = JointMotor {
+            target_vel: motor.target_vel as Real,
+            target_pos: motor.target_pos as Real,
+            stiffness: motor.stiffness as Real,
+            damping: motor.damping as Real,
+            max_force: motor.max_force as Real,
+            impulse: motor.impulse as Real,
+            model: match motor.model {
+                rapier_f32::dynamics::MotorModel::AccelerationBased => {
+                    MotorModel::AccelerationBased
+                }
+                rapier_f32::dynamics::MotorModel::ForceBased => MotorModel::ForceBased,
+            },
+        };
+    }
+
+    result.set_enabled(joint.is_enabled());
+    result
+}
@@ -2,10 +2,21 @@ pub use self::debug_render_pipeline::*;
 pub use self::event_queue::*;
 pub use self::physics_hooks::*;
 pub use self::physics_pipeline::*;
+pub use self::scene_region::*;
 pub use self::serialization_pipeline::*;
+pub use self::substepping::*;
+pub use self::world_scaling::*;
 
+mod debug_json;
 mod debug_render_pipeline;
 mod event_queue;
+#[cfg(feature = "f32-snapshots")]
+mod f32_snapshot;
+mod network_state;
 mod physics_hooks;
 mod physics_pipeline;
+mod prefab;
+mod scene_region;
 mod serialization_pipeline;
+mod substepping;
+mod world_scaling;
@@ -0,0 +1,332 @@
+use crate::dynamics::RawRigidBodySet;
+use crate::pipeline::RawSerializationPipeline;
+use crate::utils::{self, FlatHandle};
+use half::f16;
+#[cfg(feature = "dim3")]
+use na::{Quaternion, Vector4};
+use rapier::dynamics::{RigidBodyHandle, RigidBodyVelocity};
+use rapier::math::{AngVector, Isometry, Real, Rotation, Vector};
+#[cfg(feature = "dim3")]
+use std::f64::consts::SQRT_2;
+#[cfg(feature = "dim2")]
+use std::f64::consts::{PI, TAU};
+use wasm_bindgen::prelude::*;
+
+/// The number of bits of each of the three smallest components of an encoded quaternion.
+#[cfg(feature = "dim3")]
+const QUAT_COMPONENT_BITS: u32 = 15;
+#[cfg(feature = "dim3")]
+const QUAT_COMPONENT_MAX: u64 = (1 << QUAT_COMPONENT_BITS) - 1;
+
+const FLAG_SLEEPING: u8 = 1;
+
+/// Can translations be quantized to multiples of the given precision?
+fn is_valid_precision(precision: Real) -> bool {
+    precision.is_finite() && precision > 0.0
+}
+
+/// The state of a rigid-body decoded from a network state buffer.
+struct BodyState {
+    handle: RigidBodyHandle,
+    sleeping: bool,
+    position: Isometry<Real>,
+    vels: RigidBodyVelocity,
+}
+
+struct StateWriter(Vec<u8>);
+
+impl StateWriter {
+    fn write_u8(&mut self, value: u8) {
+        self.0.push(value);
+    }
+
+    fn write_varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.0.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        self.0.push(value as u8);
+    }
+
+    /// Writes a signed integer with the zigzag encoding, so small magnitudes take few bytes.
+    fn write_signed_varint(&mut self, value: i64) {
+        self.write_varint(((value << 1) ^ (value >> 63)) as u64);
+    }
+
+    fn write_f16(&mut self, value: Real) {
+        self.0
+            .extend_from_slice(&f16::from_f64(value).to_bits().to_le_bytes());
+    }
+
+    fn write_f64(&mut self, value: Real) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    /// Writes the rotation angle, quantized on 16 bits.
+    #[cfg(feature = "dim2")]
+    fn write_rotation(&mut self, rotation: &Rotation<Real>) {
+        let turns = (rotation.angle() + PI) / TAU;
+        let quantized = (turns * u16::MAX as Real).round() as u16;
+        self.0.extend_from_slice(&quantized.to_le_bytes());
+    }
+
+    /// Writes the rotation with the smallest-three encoding: the index of the largest component
+    /// of the quaternion on 2 bits, followed by the three other components quantized on 15 bits
+    /// each. The largest component is recovered from the unit norm of the quaternion.
+    #[cfg(feature = "dim3")]
+    fn write_rotation(&mut self, rotation: &Rotation<Real>) {
+        let mut coords = rotation.coords;
+        let largest = coords.iamax();
+        if coords[largest] < 0.0 {
+            coords = -coords;
+        }
+
+        let mut packed = largest as u64;
+        for (i, c) in coords.iter().enumerate() {
+            if i != largest {
+                // The smallest components are in [-1 / sqrt(2), 1 / sqrt(2)].
+                let unit = (c * SQRT_2 + 1.0) * 0.5;
+                let quantized = (unit * QUAT_COMPONENT_MAX as Real).round() as u64;
+                packed = (packed << QUAT_COMPONENT_BITS) | quantized.min(QUAT_COMPONENT_MAX);
+            }
+        }
+
+        self.0.extend_from_slice(&packed.to_le_bytes()[..6]);
+    }
+
+    #[cfg(feature = "dim2")]
+    fn write_angvel(&mut self, angvel: AngVector<Real>) {
+        self.write_f16(angvel);
+    }
+
+    #[cfg(feature = "dim3")]
+    fn write_angvel(&mut self, angvel: AngVector<Real>) {
+        for c in angvel.iter() {
+            self.write_f16(*c);
+        }
+    }
+}
+
+struct StateReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> StateReader<'a> {
+    fn is_empty(&self) -> bool {
+        self.pos == self.data.len()
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        let bytes = self.data.get(self.pos..self.pos + len)?;
+        self.pos += len;
+        Some(bytes)
+    }
+
+    fn read_u8(&mut self) -> Option<u8> {
+        Some(self.read_bytes(1)?[0])
+    }
+
+    fn read_varint(&mut self) -> Option<u64> {
+        let mut value = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_u8()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    fn read_signed_varint(&mut self) -> Option<i64> {
+        let value = self.read_varint()?;
+        Some((value >> 1) as i64 ^ -((value & 1) as i64))
+    }
+
+    fn read_f16(&mut self) -> Option<Real> {
+        let mut bytes = [0; 2];
+        bytes.copy_from_slice(self.read_bytes(2)?);
+        Some(f16::from_bits(u16::from_le_bytes(bytes)).to_f64())
+    }
+
+    fn read_f64(&mut self) -> Option<Real> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.read_bytes(8)?);
+        Some(Real::from_le_bytes(bytes))
+    }
+
+    #[cfg(feature = "dim2")]
+    fn read_rotation(&mut self) -> Option<Rotation<Real>> {
+        let mut bytes = [0; 2];
+        bytes.copy_from_slice(self.read_bytes(2)?);
+        let turns = u16::from_le_bytes(bytes) as Real / u16::MAX as Real;
+        Some(Rotation::new(turns * TAU - PI))
+    }
+
+    #[cfg(feature = "dim3")]
+    fn read_rotation(&mut self) -> Option<Rotation<Real>> {
+        let mut bytes = [0; 8];
+        bytes[..6].copy_from_slice(self.read_bytes(6)?);
+        let packed = u64::from_le_bytes(bytes);
+
+        let largest = (packed >> (3 * QUAT_COMPONENT_BITS)) as usize;
+        if largest > 3 {
+            return None;
+        }
+
+        let mut coords = Vector4::zeros();
+        let mut shift = 3 * QUAT_COMPONENT_BITS;
+        let mut norm_squared = 0.0;
+        for (i, c) in coords.iter_mut().enumerate() {
+            if i != largest {
+                shift -= QUAT_COMPONENT_BITS;
+                let quantized = (packed >> shift) & QUAT_COMPONENT_MAX;
+                let unit = quantized as Real / QUAT_COMPONENT_MAX as Real;
+                *c = (unit * 2.0 - 1.0) / SQRT_2;
+                norm_squared += *c * *c;
+            }
+        }
+        coords[largest] = (1.0 - norm_squared).max(0.0).sqrt();
+
+        Some(Rotation::new_normalize(Quaternion::from(coords)))
+    }
+
+    #[cfg(feature = "dim2")]
+    fn read_angvel(&mut self) -> Option<AngVector<Real>> {
+        self.read_f16()
+    }
+
+    #[cfg(feature = "dim3")]
+    fn read_angvel(&mut self) -> Option<AngVector<Real>> {
+        Some(AngVector::new(
+            self.read_f16()?,
+            self.read_f16()?,
+            self.read_f16()?,
+        ))
+    }
+}
+
+fn decode_network_state(data: &[u8]) -> Option<Vec<BodyState>> {
+    let mut reader = StateReader { data, pos: 0 };
+    let precision = reader.read_f64()?;
+    if !is_valid_precision(precision) {
+        return None;
+    }
+    let mut states = vec![];
+
+    while !reader.is_empty() {
+        let index = reader.read_varint()? as u32;
+        let generation = reader.read_varint()? as u32;
+        let flags = reader.read_u8()?;
+
+        let mut translation = Vector::zeros();
+        for c in translation.iter_mut() {
+            *c = reader.read_signed_varint()? as Real * precision;
+        }
+        let rotation = reader.read_rotation()?;
+
+        let mut linvel = Vector::zeros();
+        for c in linvel.iter_mut() {
+            *c = reader.read_f16()?;
+        }
+        let angvel = reader.read_angvel()?;
+
+        states.push(BodyState {
+            handle: RigidBodyHandle::from_raw_parts(index, generation),
+            sleeping: flags & FLAG_SLEEPING != 0,
+            position: Isometry::from_parts(translation.into(), rotation),
+            vels: RigidBodyVelocity { linvel, angvel },
+        });
+    }
+
+    Some(states)
+}
+
+#[wasm_bindgen]
+impl RawSerializationPipeline {
+    /// Encodes the state of the given rigid-bodies into a compact buffer, for network
+    /// synchronization.
+    ///
+    /// The translations are quantized to multiples of `precision`, the rotations are encoded
+    /// on 6 bytes in 3D (2 bytes in 2D), and the velocities are encoded as half-floats. The
+    /// rigid-bodies that don’t exist are skipped. Returns `None` if `precision` isn’t a finite
+    /// positive number.
+    pub fn encodeNetworkState(
+        &self,
+        bodies: &RawRigidBodySet,
+        handles: Vec<FlatHandle>,
+        precision: f64,
+    ) -> Option<Vec<u8>> {
+        if !is_valid_precision(precision) {
+            return None;
+        }
+
+        let mut writer = StateWriter(vec![]);
+        writer.write_f64(precision);
+
+        for handle in handles {
+            let handle = utils::body_handle(handle);
+            let rb = match bodies.0.get(handle) {
+                Some(rb) => rb,
+                None => continue,
+            };
+
+            let (index, generation) = handle.0.into_raw_parts();
+            writer.write_varint(index as u64);
+            writer.write_varint(generation as u64);
+            writer.write_u8(if rb.is_sleeping() { FLAG_SLEEPING } else { 0 });
+
+            for c in rb.translation().iter() {
+                writer.write_signed_varint((c / precision).round() as i64);
+            }
+            writer.write_rotation(rb.rotation());
+
+            for c in rb.linvel().iter() {
+                writer.write_f16(*c);
+            }
+            writer.write_angvel(rb.vels().angvel);
+        }
+
+        Some(writer.0)
+    }
+
+    /// Applies a state encoded by `encodeNetworkState`.
+    ///
+    /// The rigid-bodies are moved by a fraction `1 - smoothing` of the way from their current
+    /// position to the decoded one: a `smoothing` of zero snaps them to the decoded position.
+    /// Their velocities and sleep state are set to the decoded ones. The rigid-bodies that
+    /// don’t exist are skipped. Returns `false` and applies nothing if `data` is invalid.
+    pub fn applyNetworkState(
+        &self,
+        bodies: &mut RawRigidBodySet,
+        data: Vec<u8>,
+        smoothing: f64,
+    ) -> bool {
+        let states = match decode_network_state(&data) {
+            Some(states) => states,
+            None => return false,
+        };
+
+        for state in states {
+            let rb = match bodies.0.get_mut(state.handle) {
+                Some(rb) => rb,
+                None => continue,
+            };
+
+            let position = rb
+                .position()
+                .lerp_slerp(&state.position, 1.0 - smoothing.clamp(0.0, 1.0));
+            rb.set_position(position, false);
+            rb.set_vels(state.vels, false);
+
+            if state.sleeping {
+                rb.sleep();
+            } else {
+                rb.wake_up(true);
+            }
+        }
+
+        true
+    }
+}
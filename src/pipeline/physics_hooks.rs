@@ -1,6 +1,10 @@
+use crate::geometry::{ColliderExt, RawColliderSet};
 use crate::utils;
-use rapier::geometry::SolverFlags;
-use rapier::pipeline::{ContactModificationContext, PairFilterContext, PhysicsHooks};
+use rapier::dynamics::{IntegrationParameters, RigidBodyHandle, RigidBodySet};
+use rapier::geometry::{ColliderHandle, ColliderSet, SolverFlags};
+use rapier::math::{Real, Vector};
+use rapier::pipeline::{ActiveHooks, ContactModificationContext, PairFilterContext, PhysicsHooks};
+use std::collections::{HashMap, HashSet};
 use wasm_bindgen::prelude::*;
 
 pub struct RawPhysicsHooks {
@@ -76,6 +80,438 @@ impl PhysicsHooks for RawPhysicsHooks {
     fn modify_solver_contacts(&self, _ctxt: &mut ContactModificationContext) {}
 }
 
+/// A rule preventing the colliders members of two sets of collision groups from
+/// interacting while their relative velocity is below a threshold.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct PairFilterRule {
+    pub id: u32,
+    pub groups1: u32,
+    pub groups2: u32,
+    pub velocity_threshold: Real,
+}
+
+/// A table of pair filtering rules, evaluated natively by the physics hooks.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct PairFilterRules {
+    rules: Vec<PairFilterRule>,
+    next_id: u32,
+    /// Pairs of colliders explicitly prevented from interacting, sorted by handle.
+    excluded_pairs: HashSet<(ColliderHandle, ColliderHandle)>,
+    /// Pairs of rigid-bodies (e.g. links of a multibody) whose colliders are prevented from
+    /// interacting, sorted by handle.
+    excluded_body_pairs: HashSet<(RigidBodyHandle, RigidBodyHandle)>,
+}
+
+/// The filtering active hooks set by `PairFilterRules::update_hooks` on the colliders which
+/// didn’t have them already.
+///
+/// These hooks aren’t considered as set by the user: the physics hooks given to the simulation
+/// step aren’t called for a pair of colliders which only have them because of a rule or an
+/// exclusion.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct AddedHooks(HashMap<ColliderHandle, ActiveHooks>);
+
+impl AddedHooks {
+    pub fn get(&self, handle: ColliderHandle) -> ActiveHooks {
+        self.0
+            .get(&handle)
+            .copied()
+            .unwrap_or_else(ActiveHooks::empty)
+    }
+
+    /// Forgets that the given hooks were set natively on a collider, e.g., because the user set
+    /// them explicitly.
+    pub fn remove(&mut self, handle: ColliderHandle, hooks: ActiveHooks) {
+        if let Some(added) = self.0.get_mut(&handle) {
+            *added -= hooks;
+            if added.is_empty() {
+                let _ = self.0.remove(&handle);
+            }
+        }
+    }
+}
+
+/// The active hooks needed for the pair filtering rules and exclusions to be evaluated.
+fn filter_hooks() -> ActiveHooks {
+    ActiveHooks::FILTER_CONTACT_PAIRS | ActiveHooks::FILTER_INTERSECTION_PAIR
+}
+
+fn sorted_pair(
+    handle1: ColliderHandle,
+    handle2: ColliderHandle,
+) -> (ColliderHandle, ColliderHandle) {
+    if handle1.into_raw_parts() <= handle2.into_raw_parts() {
+        (handle1, handle2)
+    } else {
+        (handle2, handle1)
+    }
+}
+
+fn sorted_body_pair(
+    handle1: RigidBodyHandle,
+    handle2: RigidBodyHandle,
+) -> (RigidBodyHandle, RigidBodyHandle) {
+    if handle1.into_raw_parts() <= handle2.into_raw_parts() {
+        (handle1, handle2)
+    } else {
+        (handle2, handle1)
+    }
+}
+
+impl PairFilterRules {
+    pub fn insert(&mut self, groups1: u32, groups2: u32, velocity_threshold: Real) -> u32 {
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        self.rules.push(PairFilterRule {
+            id,
+            groups1,
+            groups2,
+            velocity_threshold,
+        });
+        id
+    }
+
+    pub fn remove(&mut self, id: u32) {
+        self.rules.retain(|rule| rule.id != id);
+    }
+
+    pub fn clear(&mut self) {
+        self.rules.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn exclude_pair(&mut self, handle1: ColliderHandle, handle2: ColliderHandle) {
+        self.excluded_pairs.insert(sorted_pair(handle1, handle2));
+    }
+
+    pub fn remove_exclusion(&mut self, handle1: ColliderHandle, handle2: ColliderHandle) {
+        self.excluded_pairs.remove(&sorted_pair(handle1, handle2));
+    }
+
+    pub fn exclude_body_pair(&mut self, handle1: RigidBodyHandle, handle2: RigidBodyHandle) {
+        self.excluded_body_pairs
+            .insert(sorted_body_pair(handle1, handle2));
+    }
+
+    pub fn remove_body_exclusion(&mut self, handle1: RigidBodyHandle, handle2: RigidBodyHandle) {
+        self.excluded_body_pairs
+            .remove(&sorted_body_pair(handle1, handle2));
+    }
+
+    pub fn clear_exclusions(&mut self) {
+        self.excluded_pairs.clear();
+        self.excluded_body_pairs.clear();
+    }
+
+    pub fn is_pair_excluded(&self, handle1: ColliderHandle, handle2: ColliderHandle) -> bool {
+        self.excluded_pairs.contains(&sorted_pair(handle1, handle2))
+    }
+
+    pub fn is_body_pair_excluded(
+        &self,
+        handle1: RigidBodyHandle,
+        handle2: RigidBodyHandle,
+    ) -> bool {
+        self.excluded_body_pairs
+            .contains(&sorted_body_pair(handle1, handle2))
+    }
+
+    /// Sets the filtering active hooks on the colliders affected by a rule or an exclusion, and
+    /// removes them from the colliders no longer affected.
+    ///
+    /// Only the hooks set by this method, recorded in `ColliderExt::added_hooks`, are ever
+    /// removed, so the hooks set by the user are left untouched. This is called whenever the
+    /// rules or exclusions change, and before each simulation step to account for the colliders
+    /// added or whose collision groups changed.
+    pub fn update_hooks(&self, colliders: &mut RawColliderSet) {
+        let added = &mut colliders.1.added_hooks;
+        let colliders = &mut colliders.0;
+        if self.rules.is_empty()
+            && self.excluded_pairs.is_empty()
+            && self.excluded_body_pairs.is_empty()
+            && added.0.is_empty()
+        {
+            return;
+        }
+
+        let mut filtered = HashSet::new();
+        for (handle1, handle2) in &self.excluded_pairs {
+            filtered.insert(*handle1);
+            filtered.insert(*handle2);
+        }
+
+        if !self.rules.is_empty() || !self.excluded_body_pairs.is_empty() {
+            let groups = self
+                .rules
+                .iter()
+                .fold(0, |groups, rule| groups | rule.groups1 | rule.groups2);
+            let mut bodies = HashSet::new();
+            for (handle1, handle2) in &self.excluded_body_pairs {
+                bodies.insert(*handle1);
+                bodies.insert(*handle2);
+            }
+
+            for (handle, co) in colliders.iter() {
+                if co.collision_groups().memberships.bits() & groups != 0
+                    || co.parent().is_some_and(|parent| bodies.contains(&parent))
+                {
+                    filtered.insert(handle);
+                }
+            }
+        }
+
+        // Remove the hooks from the colliders no longer affected.
+        added.0.retain(|handle, added| {
+            if filtered.contains(handle) && colliders.get(*handle).is_some() {
+                return true;
+            }
+            if let Some(co) = colliders.get_mut(*handle) {
+                co.set_active_hooks(co.active_hooks() - *added);
+            }
+            false
+        });
+
+        for handle in filtered {
+            let missing = match colliders.get(handle) {
+                Some(co) => filter_hooks() - co.active_hooks(),
+                None => continue,
+            };
+            if missing.is_empty() {
+                continue;
+            }
+            if let Some(co) = colliders.get_mut(handle) {
+                co.set_active_hooks(co.active_hooks() | missing);
+                *added.0.entry(handle).or_insert_with(ActiveHooks::empty) |= missing;
+            }
+        }
+    }
+
+    /// Checks if a rule or an exclusion prevents the two colliders of the given context from
+    /// interacting.
+    pub fn rejects(&self, ctxt: &PairFilterContext) -> bool {
+        if self.is_pair_excluded(ctxt.collider1, ctxt.collider2) {
+            return true;
+        }
+
+        if let (Some(rb1), Some(rb2)) = (ctxt.rigid_body1, ctxt.rigid_body2) {
+            if self.is_body_pair_excluded(rb1, rb2) {
+                return true;
+            }
+        }
+
+        if self.rules.is_empty() {
+            return false;
+        }
+
+        let memberships =
+            |handle: ColliderHandle| ctxt.colliders[handle].collision_groups().memberships.bits();
+        let memberships1 = memberships(ctxt.collider1);
+        let memberships2 = memberships(ctxt.collider2);
+        let relative_velocity = || {
+            let linvel1 = ctxt.rigid_body1.map(|h| *ctxt.bodies[h].linvel());
+            let linvel2 = ctxt.rigid_body2.map(|h| *ctxt.bodies[h].linvel());
+            (linvel2.unwrap_or_else(Vector::zeros) - linvel1.unwrap_or_else(Vector::zeros)).norm()
+        };
+
+        self.rules.iter().any(|rule| {
+            let matches = (memberships1 & rule.groups1 != 0 && memberships2 & rule.groups2 != 0)
+                || (memberships1 & rule.groups2 != 0 && memberships2 & rule.groups1 != 0);
+            matches && relative_velocity() < rule.velocity_threshold
+        })
+    }
+}
+
+/// Warm-start impulses to discard at the next simulation step, e.g., after teleporting bodies.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct WarmstartResets {
+    pub all: bool,
+    bodies: HashSet<RigidBodyHandle>,
+    pairs: HashSet<(ColliderHandle, ColliderHandle)>,
+}
+
+impl WarmstartResets {
+    /// Sets the `MODIFY_SOLVER_CONTACTS` active hook on the colliders involved in a pending
+    /// reset, so their solver contacts can be modified by the next step.
+    ///
+    /// Returns the colliders which didn’t have the hook already, to be given to
+    /// `restore_hooks` once the step is done.
+    pub fn enable_hooks(
+        &self,
+        bodies: &RigidBodySet,
+        colliders: &mut ColliderSet,
+    ) -> Vec<ColliderHandle> {
+        let mut handles: Vec<ColliderHandle> = self
+            .bodies
+            .iter()
+            .filter_map(|handle| bodies.get(*handle))
+            .flat_map(|rb| rb.colliders().iter().copied())
+            .collect();
+        for (handle1, handle2) in &self.pairs {
+            handles.push(*handle1);
+            handles.push(*handle2);
+        }
+
+        let mut added = vec![];
+        for handle in handles {
+            let has_hook = match colliders.get(handle) {
+                Some(co) => co
+                    .active_hooks()
+                    .contains(ActiveHooks::MODIFY_SOLVER_CONTACTS),
+                None => continue,
+            };
+            if !has_hook {
+                if let Some(co) = colliders.get_mut(handle) {
+                    co.set_active_hooks(co.active_hooks() | ActiveHooks::MODIFY_SOLVER_CONTACTS);
+                    added.push(handle);
+                }
+            }
+        }
+        added
+    }
+
+    /// Removes the `MODIFY_SOLVER_CONTACTS` active hook set by `enable_hooks`.
+    pub fn restore_hooks(added: Vec<ColliderHandle>, colliders: &mut ColliderSet) {
+        for handle in added {
+            if let Some(co) = colliders.get_mut(handle) {
+                co.set_active_hooks(co.active_hooks() - ActiveHooks::MODIFY_SOLVER_CONTACTS);
+            }
+        }
+    }
+
+    pub fn reset_body(&mut self, handle: RigidBodyHandle) {
+        self.bodies.insert(handle);
+    }
+
+    pub fn reset_pair(&mut self, handle1: ColliderHandle, handle2: ColliderHandle) {
+        self.pairs.insert(sorted_pair(handle1, handle2));
+    }
+
+    /// Forgets all the pending resets, once they have been applied by a simulation step.
+    pub fn clear(&mut self) {
+        self.all = false;
+        self.bodies.clear();
+        self.pairs.clear();
+    }
+
+    /// The integration parameters to use for the next step: warm-starting is disabled
+    /// altogether if a global reset is pending.
+    pub fn integration_parameters(&self, params: &IntegrationParameters) -> IntegrationParameters {
+        let mut params = *params;
+        if self.all {
+            params.warmstart_coefficient = 0.0;
+        }
+        params
+    }
+
+    fn modify_solver_contacts(&self, ctxt: &mut ContactModificationContext) {
+        let reset_body = |handle: Option<RigidBodyHandle>| {
+            handle.is_some_and(|handle| self.bodies.contains(&handle))
+        };
+
+        if reset_body(ctxt.rigid_body1)
+            || reset_body(ctxt.rigid_body2)
+            || self
+                .pairs
+                .contains(&sorted_pair(ctxt.collider1, ctxt.collider2))
+        {
+            for contact in ctxt.solver_contacts.iter_mut() {
+                contact.warmstart_impulse = 0.0;
+                contact.warmstart_tangent_impulse.fill(0.0);
+            }
+        }
+    }
+}
+
+/// Physics hooks applying the behaviors implemented natively (e.g. per-triangle
+/// materials, pair filtering rules, warm-start resets) on top of another set of hooks.
+///
+/// The other set of hooks is only called for the pairs where at least one collider has the
+/// corresponding active hook set by the user, and not only natively.
+pub(crate) struct NativePhysicsHooks<'a> {
+    pub inner: &'a dyn PhysicsHooks,
+    pub colliders: &'a ColliderExt,
+    pub rules: &'a PairFilterRules,
+    pub warmstart_resets: &'a WarmstartResets,
+    /// The colliders on which the warm-start resets enabled `MODIFY_SOLVER_CONTACTS`
+    /// for this step.
+    pub warmstart_hooks: &'a [ColliderHandle],
+}
+
+impl NativePhysicsHooks<'_> {
+    /// The active hooks set natively on a collider, including for this step only.
+    fn native_hooks(&self, handle: ColliderHandle) -> ActiveHooks {
+        let mut hooks = self.colliders.native_hooks(handle);
+        if self.warmstart_hooks.contains(&handle) {
+            hooks |= ActiveHooks::MODIFY_SOLVER_CONTACTS;
+        }
+        hooks
+    }
+
+    /// Checks if the user set the given active hook on at least one of the two colliders.
+    fn has_user_hook(
+        &self,
+        colliders: &ColliderSet,
+        handle1: ColliderHandle,
+        handle2: ColliderHandle,
+        hook: ActiveHooks,
+    ) -> bool {
+        [handle1, handle2].iter().any(|handle| {
+            colliders
+                .get(*handle)
+                .is_some_and(|co| (co.active_hooks() - self.native_hooks(*handle)).contains(hook))
+        })
+    }
+}
+
+impl PhysicsHooks for NativePhysicsHooks<'_> {
+    fn filter_contact_pair(&self, ctxt: &PairFilterContext) -> Option<SolverFlags> {
+        if self.rules.rejects(ctxt) {
+            return None;
+        }
+
+        if self.has_user_hook(
+            ctxt.colliders,
+            ctxt.collider1,
+            ctxt.collider2,
+            ActiveHooks::FILTER_CONTACT_PAIRS,
+        ) {
+            self.inner.filter_contact_pair(ctxt)
+        } else {
+            Some(SolverFlags::default())
+        }
+    }
+
+    fn filter_intersection_pair(&self, ctxt: &PairFilterContext) -> bool {
+        if self.rules.rejects(ctxt) {
+            return false;
+        }
+
+        !self.has_user_hook(
+            ctxt.colliders,
+            ctxt.collider1,
+            ctxt.collider2,
+            ActiveHooks::FILTER_INTERSECTION_PAIR,
+        ) || self.inner.filter_intersection_pair(ctxt)
+    }
+
+    fn modify_solver_contacts(&self, ctxt: &mut ContactModificationContext) {
+        if self.has_user_hook(
+            ctxt.colliders,
+            ctxt.collider1,
+            ctxt.collider2,
+            ActiveHooks::MODIFY_SOLVER_CONTACTS,
+        ) {
+            self.inner.modify_solver_contacts(ctxt);
+        }
+        self.colliders.materials.modify_solver_contacts(ctxt);
+        self.warmstart_resets.modify_solver_contacts(ctxt);
+    }
+}
+
 /* NOTE: the following is an attempt to make contact modification work.
  *
 #[wasm_bindgen]
@@ -1,15 +1,43 @@
 use crate::dynamics::{
     RawCCDSolver, RawImpulseJointSet, RawIntegrationParameters, RawIslandManager,
-    RawMultibodyJointSet, RawRigidBodySet,
+    RawMultibodyJointSet, RawRigidBodySet, SimulationLayers,
 };
 use crate::geometry::{RawBroadPhase, RawColliderSet, RawNarrowPhase};
 use crate::math::RawVector;
-use crate::pipeline::{RawEventQueue, RawPhysicsHooks};
-use crate::rapier::pipeline::PhysicsPipeline;
+use crate::pipeline::{
+    AutoSubstepping, NativePhysicsHooks, PairFilterRules, PendingStep, RawEventQueue,
+    RawPhysicsHooks, SubstepTag, Substepping, WarmstartResets,
+};
+use crate::rapier::math::Real;
+use crate::rapier::pipeline::{EventHandler, PhysicsHooks, PhysicsPipeline};
+use crate::utils::{self, FlatHandle};
 use wasm_bindgen::prelude::*;
 
+/// The state attached to the physics pipeline by these bindings, on top of the physics pipeline
+/// of Rapier.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct PipelineExt {
+    pub rules: PairFilterRules,
+    pub warmstart_resets: WarmstartResets,
+    pub substepping: Substepping,
+    /// The active simulation layers.
+    pub active_layers: u32,
+}
+
+impl Default for PipelineExt {
+    fn default() -> Self {
+        Self {
+            rules: PairFilterRules::default(),
+            warmstart_resets: WarmstartResets::default(),
+            substepping: Substepping::default(),
+            active_layers: u32::MAX,
+        }
+    }
+}
+
 #[wasm_bindgen]
-pub struct RawPhysicsPipeline(pub(crate) PhysicsPipeline);
+pub struct RawPhysicsPipeline(pub(crate) PhysicsPipeline, pub(crate) PipelineExt);
 
 #[wasm_bindgen]
 impl RawPhysicsPipeline {
@@ -17,7 +45,287 @@ impl RawPhysicsPipeline {
     pub fn new() -> Self {
         let mut pipeline = PhysicsPipeline::new();
         pipeline.counters.disable(); // Disable perf counters by default.
-        RawPhysicsPipeline(pipeline)
+        RawPhysicsPipeline(pipeline, PipelineExt::default())
+    }
+
+    /// The ratio between the time simulated by a step and the timestep of the integration
+    /// parameters.
+    pub fn timeScale(&self) -> f64 {
+        self.1.substepping.time_scale
+    }
+
+    /// Sets the ratio between the time simulated by a step and the timestep of the integration
+    /// parameters, e.g., `0.2` for a slow-motion effect.
+    ///
+    /// The motor target velocities, damping coefficients and spring frequencies are all
+    /// expressed per second of simulated time, so they are slowed down consistently with the
+    /// rest of the simulation, unlike when velocities are scaled manually. The integration
+    /// parameters are left unchanged, so the time scale can be changed at any time without
+    /// affecting the solver settings. A time scale of 0 pauses the simulation. Negative values
+    /// are treated as 0.
+    pub fn setTimeScale(&mut self, factor: f64) {
+        self.1.substepping.time_scale = factor.max(0.0);
+    }
+
+    /// The number of substeps each simulation step is split into.
+    pub fn numSubsteps(&self) -> u32 {
+        self.1.substepping.num_substeps
+    }
+
+    /// Sets the number of substeps each simulation step is split into.
+    ///
+    /// Each substep runs a full pipeline step with a timestep equal to `dt / numSubsteps`.
+    /// The next kinematic positions of the position-based kinematic rigid-bodies are
+    /// interpolated across the substeps. Values smaller than 1 are treated as 1.
+    pub fn setNumSubsteps(&mut self, numSubsteps: u32) {
+        self.1.substepping.num_substeps = numSubsteps.max(1);
+    }
+
+    /// Enables the automatic increase of the number of substeps when a rigid-body moves fast
+    /// relative to its size.
+    ///
+    /// Before each step, the number of substeps is increased so that no awake dynamic
+    /// rigid-body travels, during a substep, more than `maxTravelRatio` times the smallest
+    /// extent of its colliders (assuming its current linear velocity). The number of substeps
+    /// never drops below `numSubsteps` nor exceeds `maxSubsteps`.
+    ///
+    /// Returns `false`, leaving the substepping unchanged, if `maxTravelRatio` isn’t positive
+    /// or if `maxSubsteps` is 0.
+    pub fn enableAutoSubstepping(&mut self, maxTravelRatio: f64, maxSubsteps: u32) -> bool {
+        if maxTravelRatio.is_nan() || maxTravelRatio <= 0.0 || maxSubsteps == 0 {
+            return false;
+        }
+
+        self.1.substepping.auto = Some(AutoSubstepping {
+            max_travel_ratio: maxTravelRatio,
+            max_substeps: maxSubsteps,
+        });
+        true
+    }
+
+    pub fn disableAutoSubstepping(&mut self) {
+        self.1.substepping.auto = None;
+    }
+
+    pub fn isAutoSubsteppingEnabled(&self) -> bool {
+        self.1.substepping.auto.is_some()
+    }
+
+    /// The bit mask of the active simulation layers.
+    pub fn activeLayers(&self) -> u32 {
+        self.1.active_layers
+    }
+
+    /// Sets the bit mask of the active simulation layers.
+    ///
+    /// Only the rigid-bodies with any of their simulation layers active are simulated. The other
+    /// rigid-bodies are frozen in place: they keep their velocities, which apply again once any
+    /// of their layers is reactivated, and act as static obstacles for the simulated
+    /// rigid-bodies. All the layers are active by default.
+    pub fn setActiveLayers(&mut self, mask: u32) {
+        self.1.active_layers = mask;
+    }
+
+    /// The number of substeps executed by the last simulation step.
+    pub fn lastNumSubsteps(&self) -> u32 {
+        self.1.substepping.last_num_substeps
+    }
+
+    /// The simulation time, i.e., the sum of the timesteps of all the substeps executed.
+    pub fn simulationTime(&self) -> f64 {
+        self.1.substepping.time
+    }
+
+    /// Sets the simulation time, e.g., to synchronize it with another clock.
+    pub fn setSimulationTime(&mut self, time: f64) {
+        self.1.substepping.time = time;
+    }
+
+    /// The number of substeps of the current step already executed, or 0 if no step was
+    /// partially executed by `step` with `numSubsteps`.
+    pub fn substepIndex(&self) -> u32 {
+        self.1
+            .substepping
+            .pending
+            .as_ref()
+            .map_or(0, |pending| pending.substep)
+    }
+
+    /// Sets the function called before each substep to compute forces applied during that
+    /// substep only, or removes it if `None`.
+    ///
+    /// The function is called as `f(substep, dt, states)` where `states` is a `Float64Array`
+    /// containing, for each awake dynamic rigid-body, its handle followed by its state with
+    /// the same layout as the rigid-body entries of `saveDynamicsState`. It may return an array
+    /// containing, for each rigid-body to push, its handle followed by the force and torque
+    /// to add to its user forces for the duration of the substep.
+    ///
+    /// The function must not access the physics world.
+    pub fn setSubstepCallback(&mut self, callback: Option<js_sys::Function>) {
+        self.1.substepping.force_callback = callback;
+    }
+
+    /// Adds a rule preventing the colliders members of `groups1` from generating contacts
+    /// (or intersections) with the colliders members of `groups2`, as long as the norm of the
+    /// relative linear velocity of their rigid-bodies is smaller than `velocityThreshold`.
+    ///
+    /// The `FILTER_CONTACT_PAIRS` and `FILTER_INTERSECTION_PAIR` active hooks are set on the
+    /// colliders members of `groups1` or `groups2`, including the ones added later, for the
+    /// rule to be evaluated. They are removed once no rule or exclusion applies to a collider,
+    /// unless they were already set before.
+    ///
+    /// # Parameters
+    /// - `groups1`, `groups2`: bit masks of collision groups.
+    /// - `velocityThreshold`: the rule no longer applies above this relative velocity. Set it
+    ///   to infinity for the rule to always apply.
+    ///
+    /// Returns the id of the rule.
+    pub fn addPairFilterRule(
+        &mut self,
+        colliders: &mut RawColliderSet,
+        groups1: u32,
+        groups2: u32,
+        velocityThreshold: f64,
+    ) -> u32 {
+        let id = self.1.rules.insert(groups1, groups2, velocityThreshold);
+        self.1.rules.update_hooks(colliders);
+        id
+    }
+
+    pub fn removePairFilterRule(&mut self, colliders: &mut RawColliderSet, id: u32) {
+        self.1.rules.remove(id);
+        self.1.rules.update_hooks(colliders);
+    }
+
+    pub fn clearPairFilterRules(&mut self, colliders: &mut RawColliderSet) {
+        self.1.rules.clear();
+        self.1.rules.update_hooks(colliders);
+    }
+
+    pub fn numPairFilterRules(&self) -> usize {
+        self.1.rules.len()
+    }
+
+    /// Prevents the two given colliders from generating contacts or intersections with each
+    /// other, until `removeExclusion` is called.
+    ///
+    /// Like with the pair filter rules, the `FILTER_CONTACT_PAIRS` and
+    /// `FILTER_INTERSECTION_PAIR` active hooks are set on both colliders, and removed by
+    /// `removeExclusion` unless they were already set before.
+    pub fn excludeCollisionPair(
+        &mut self,
+        colliders: &mut RawColliderSet,
+        handle1: FlatHandle,
+        handle2: FlatHandle,
+    ) {
+        self.1.rules.exclude_pair(
+            utils::collider_handle(handle1),
+            utils::collider_handle(handle2),
+        );
+        self.1.rules.update_hooks(colliders);
+    }
+
+    pub fn removeExclusion(
+        &mut self,
+        colliders: &mut RawColliderSet,
+        handle1: FlatHandle,
+        handle2: FlatHandle,
+    ) {
+        self.1.rules.remove_exclusion(
+            utils::collider_handle(handle1),
+            utils::collider_handle(handle2),
+        );
+        self.1.rules.update_hooks(colliders);
+    }
+
+    pub fn isCollisionPairExcluded(&self, handle1: FlatHandle, handle2: FlatHandle) -> bool {
+        self.1.rules.is_pair_excluded(
+            utils::collider_handle(handle1),
+            utils::collider_handle(handle2),
+        )
+    }
+
+    /// Prevents the colliders attached to the two given rigid-bodies from generating contacts
+    /// or intersections with each other, until `removeBodyExclusion` is called.
+    ///
+    /// Combined with the `contactsEnabled` flag of the joints, this allows selecting which
+    /// links of a multibody collide with each other. Like with `excludeCollisionPair`, the
+    /// filtering active hooks are set on the colliders of both rigid-bodies, including the ones
+    /// attached later, and removed by `removeBodyExclusion` unless they were already set.
+    pub fn excludeBodyCollisionPair(
+        &mut self,
+        colliders: &mut RawColliderSet,
+        handle1: FlatHandle,
+        handle2: FlatHandle,
+    ) {
+        self.1
+            .rules
+            .exclude_body_pair(utils::body_handle(handle1), utils::body_handle(handle2));
+        self.1.rules.update_hooks(colliders);
+    }
+
+    pub fn removeBodyExclusion(
+        &mut self,
+        colliders: &mut RawColliderSet,
+        handle1: FlatHandle,
+        handle2: FlatHandle,
+    ) {
+        self.1
+            .rules
+            .remove_body_exclusion(utils::body_handle(handle1), utils::body_handle(handle2));
+        self.1.rules.update_hooks(colliders);
+    }
+
+    pub fn isBodyCollisionPairExcluded(&self, handle1: FlatHandle, handle2: FlatHandle) -> bool {
+        self.1
+            .rules
+            .is_body_pair_excluded(utils::body_handle(handle1), utils::body_handle(handle2))
+    }
+
+    pub fn clearExclusions(&mut self, colliders: &mut RawColliderSet) {
+        self.1.rules.clear_exclusions();
+        self.1.rules.update_hooks(colliders);
+    }
+
+    /// Discards the warm-start impulses of all the contacts and joints at the next simulation
+    /// step, e.g., after large changes of the simulation state.
+    ///
+    /// Warm-starting reuses the impulses computed at the previous step to speed up the solver
+    /// convergence, but these impulses may cause visible pops if bodies were teleported.
+    pub fn resetWarmstart(&mut self) {
+        self.1.warmstart_resets.all = true;
+    }
+
+    /// Discards the warm-start impulses of the contacts and impulse joints involving the given
+    /// rigid-body, e.g., after teleporting it.
+    ///
+    /// The joint impulses are discarded immediately. The contact impulses are discarded at the
+    /// next simulation step: the `MODIFY_SOLVER_CONTACTS` active hook is set on the colliders
+    /// of the rigid-body for that step only, if they don’t have it already.
+    pub fn resetBodyWarmstart(
+        &mut self,
+        impulse_joints: &mut RawImpulseJointSet,
+        handle: FlatHandle,
+    ) {
+        let handle = utils::body_handle(handle);
+        for (_, joint) in impulse_joints.0.iter_mut() {
+            if joint.body1 == handle || joint.body2 == handle {
+                joint.impulses.fill(0.0);
+            }
+        }
+        self.1.warmstart_resets.reset_body(handle);
+    }
+
+    /// Discards, at the next simulation step, the warm-start impulses of the contacts between
+    /// the two given colliders.
+    ///
+    /// The `MODIFY_SOLVER_CONTACTS` active hook is set on both colliders for that step only, if
+    /// they don’t have it already.
+    pub fn resetPairWarmstart(&mut self, handle1: FlatHandle, handle2: FlatHandle) {
+        self.1.warmstart_resets.reset_pair(
+            utils::collider_handle(handle1),
+            utils::collider_handle(handle2),
+        )
     }
 
     pub fn set_profiler_enabled(&mut self, enabled: bool) {
@@ -96,6 +404,12 @@ impl RawPhysicsPipeline {
         self.0.counters.stages.user_changes.time_ms()
     }
 
+    /// Advances the simulation by one step.
+    ///
+    /// If `numSubsteps` is set, only that number of substeps is executed instead, starting new
+    /// steps as needed: the current step is then completed by the next calls. Without
+    /// `numSubsteps`, the remaining substeps of the current step are executed if it isn’t
+    /// complete.
     pub fn step(
         &mut self,
         gravity: &RawVector,
@@ -108,23 +422,26 @@ impl RawPhysicsPipeline {
         joints: &mut RawImpulseJointSet,
         articulations: &mut RawMultibodyJointSet,
         ccd_solver: &mut RawCCDSolver,
+        numSubsteps: Option<u32>,
     ) {
-        self.0.step(
-            &gravity.0,
-            &integrationParameters.0,
-            &mut islands.0,
-            &mut broadPhase.0,
-            &mut narrowPhase.0,
-            &mut bodies.0,
-            &mut colliders.0,
-            &mut joints.0,
-            &mut articulations.0,
-            &mut ccd_solver.0,
-            &(),
+        self.run_substeps(
+            gravity,
+            integrationParameters,
+            islands,
+            broadPhase,
+            narrowPhase,
+            bodies,
+            colliders,
+            joints,
+            articulations,
+            ccd_solver,
+            numSubsteps,
             &(),
+            None,
         );
     }
 
+    /// Like `step`, but with events and physics hooks.
     pub fn stepWithEvents(
         &mut self,
         gravity: &RawVector,
@@ -141,30 +458,257 @@ impl RawPhysicsPipeline {
         hookObject: js_sys::Object,
         hookFilterContactPair: js_sys::Function,
         hookFilterIntersectionPair: js_sys::Function,
+        numSubsteps: Option<u32>,
     ) {
         if eventQueue.auto_drain {
             eventQueue.clear();
         }
 
-        let hooks = RawPhysicsHooks {
-            this: hookObject,
-            filter_contact_pair: hookFilterContactPair,
-            filter_intersection_pair: hookFilterIntersectionPair,
+        let js_hooks;
+        let inner: &dyn PhysicsHooks = if hookObject.is_null() || hookObject.is_undefined() {
+            // Without JS hooks, only the native rules apply.
+            &()
+        } else {
+            js_hooks = RawPhysicsHooks {
+                this: hookObject,
+                filter_contact_pair: hookFilterContactPair,
+                filter_intersection_pair: hookFilterIntersectionPair,
+            };
+            &js_hooks
+        };
+
+        self.run_substeps(
+            gravity,
+            integrationParameters,
+            islands,
+            broadPhase,
+            narrowPhase,
+            bodies,
+            colliders,
+            joints,
+            articulations,
+            ccd_solver,
+            numSubsteps,
+            inner,
+            Some(&*eventQueue),
+        );
+    }
+}
+
+impl RawPhysicsPipeline {
+    /// Executes `num_substeps` substeps, starting new steps as needed, or if `None`, the
+    /// remaining substeps of the current step (or of a new step if none is in progress).
+    fn run_substeps(
+        &mut self,
+        gravity: &RawVector,
+        integration_parameters: &RawIntegrationParameters,
+        islands: &mut RawIslandManager,
+        broad_phase: &mut RawBroadPhase,
+        narrow_phase: &mut RawNarrowPhase,
+        bodies: &mut RawRigidBodySet,
+        colliders: &mut RawColliderSet,
+        joints: &mut RawImpulseJointSet,
+        articulations: &mut RawMultibodyJointSet,
+        ccd_solver: &mut RawCCDSolver,
+        num_substeps: Option<u32>,
+        inner_hooks: &dyn PhysicsHooks,
+        event_queue: Option<&RawEventQueue>,
+    ) {
+        let mut remaining = num_substeps.unwrap_or(u32::MAX);
+
+        // The kinematic targets may have been changed since the step was partially executed.
+        if let Some(pending) = &mut self.1.substepping.pending {
+            Substepping::resume_kinematic_motions(
+                &mut pending.kinematic_motions,
+                pending.substep,
+                &bodies.0,
+            );
+        }
+
+        while remaining > 0 {
+            if self.1.substepping.pending.is_none()
+                && !self.begin_step(integration_parameters, narrow_phase, bodies, colliders)
+            {
+                return;
+            }
+
+            self.run_substep(
+                gravity,
+                integration_parameters,
+                islands,
+                broad_phase,
+                narrow_phase,
+                bodies,
+                colliders,
+                joints,
+                articulations,
+                ccd_solver,
+                inner_hooks,
+                event_queue,
+            );
+            remaining -= 1;
+
+            if num_substeps.is_none() && self.1.substepping.pending.is_none() {
+                return;
+            }
+        }
+    }
+
+    /// Starts a new step, to be executed with `run_substep`.
+    ///
+    /// Returns `false` if the simulation is paused.
+    fn begin_step(
+        &mut self,
+        integration_parameters: &RawIntegrationParameters,
+        narrow_phase: &mut RawNarrowPhase,
+        bodies: &mut RawRigidBodySet,
+        colliders: &mut RawColliderSet,
+    ) -> bool {
+        let dt = integration_parameters.0.dt * self.1.substepping.time_scale;
+
+        narrow_phase.1.pair_ids.begin_step();
+        narrow_phase.1.impact_tracker.clear_impacts();
+        narrow_phase.1.proximity_tracker.clear_events();
+        narrow_phase.1.contact_loads.clear();
+
+        // The solver can’t handle a zero timestep: the simulation is paused.
+        if dt <= 0.0 {
+            self.1.substepping.last_num_substeps = 0;
+            return false;
+        }
+
+        // Account for the colliders added, or whose groups changed, since the last step.
+        self.1.rules.update_hooks(colliders);
+        bodies.1.remote_poses.advance(dt, &mut bodies.0);
+        let frozen = bodies
+            .1
+            .simulation_layers
+            .freeze(self.1.active_layers, &mut bodies.0);
+        let num_substeps = self
+            .1
+            .substepping
+            .substep_count(dt, &bodies.0, &colliders.0);
+        self.1.substepping.last_num_substeps = num_substeps;
+        self.1.substepping.pending = Some(PendingStep {
+            substep: 0,
+            num_substeps,
+            dt: dt / num_substeps as Real,
+            kinematic_motions: Substepping::kinematic_motions(num_substeps, &bodies.0),
+            frozen,
+        });
+        true
+    }
+
+    /// Executes the next substep of the current step, and completes the step if this was its
+    /// last substep.
+    fn run_substep(
+        &mut self,
+        gravity: &RawVector,
+        integration_parameters: &RawIntegrationParameters,
+        islands: &mut RawIslandManager,
+        broad_phase: &mut RawBroadPhase,
+        narrow_phase: &mut RawNarrowPhase,
+        bodies: &mut RawRigidBodySet,
+        colliders: &mut RawColliderSet,
+        joints: &mut RawImpulseJointSet,
+        articulations: &mut RawMultibodyJointSet,
+        ccd_solver: &mut RawCCDSolver,
+        inner_hooks: &dyn PhysicsHooks,
+        event_queue: Option<&RawEventQueue>,
+    ) {
+        let mut pending = match self.1.substepping.pending.take() {
+            Some(pending) => pending,
+            None => return,
+        };
+        let substep = pending.substep;
+        let mut substep_params = integration_parameters.0;
+        substep_params.dt = pending.dt;
+
+        Substepping::interpolate_kinematic_targets(
+            &mut pending.kinematic_motions,
+            substep,
+            pending.num_substeps,
+            &mut bodies.0,
+        );
+        let saved_forces = self
+            .3
+            .apply_forces(substep, substep_params.dt, &mut bodies.0);
+        bodies
+            .1
+            .forces
+            .apply(&mut bodies.0, &gravity.0, substep_params.dt);
+        joints.1.apply(&joints.0, &mut bodies.0, substep_params.dt);
+        colliders.1.materials.apply_rolling_resistance(
+            &narrow_phase.0,
+            &colliders.0,
+            &mut bodies.0,
+        );
+        colliders.1.materials.apply_aerodynamic_drag(
+            &colliders.0,
+            &mut bodies.0,
+            substep_params.dt,
+        );
+        // The warm-start resets only apply to the first substep.
+        let params = self
+            .1
+            .warmstart_resets
+            .integration_parameters(&substep_params);
+        let tag = SubstepTag {
+            substep,
+            time: self.1.substepping.time + pending.dt,
+        };
+        let event_handler = event_queue.map(|queue| queue.event_handler(tag));
+        let events: &dyn EventHandler = match &event_handler {
+            Some(event_handler) => event_handler,
+            None => &(),
+        };
+
+        narrow_phase.1.impact_tracker.begin_step(&bodies.0);
+        let warmstart_hooks = self
+            .1
+            .warmstart_resets
+            .enable_hooks(&bodies.0, &mut colliders.0);
+        let hooks = NativePhysicsHooks {
+            inner: inner_hooks,
+            colliders: &colliders.1,
+            rules: &self.1.rules,
+            warmstart_resets: &self.1.warmstart_resets,
+            warmstart_hooks: &warmstart_hooks,
         };
 
         self.0.step(
             &gravity.0,
-            &integrationParameters.0,
+            &params,
             &mut islands.0,
-            &mut broadPhase.0,
-            &mut narrowPhase.0,
+            &mut broad_phase.0,
+            &mut narrow_phase.0,
             &mut bodies.0,
             &mut colliders.0,
             &mut joints.0,
             &mut articulations.0,
             &mut ccd_solver.0,
             &hooks,
-            &eventQueue.collector,
+            events,
         );
+
+        narrow_phase.1.pair_ids.update(&narrow_phase.0);
+        narrow_phase.1.impact_tracker.update(&narrow_phase.0);
+        narrow_phase.1.proximity_tracker.update(&narrow_phase.0);
+        narrow_phase
+            .1
+            .intersection_durations
+            .update(&narrow_phase.0, substep_params.dt);
+        narrow_phase.1.contact_loads.update(&narrow_phase.0);
+        Substepping::restore_forces(saved_forces, &mut bodies.0);
+        WarmstartResets::restore_hooks(warmstart_hooks, &mut colliders.0);
+        self.1.warmstart_resets.clear();
+        self.1.substepping.time += pending.dt;
+
+        pending.substep += 1;
+        if pending.substep == pending.num_substeps {
+            SimulationLayers::unfreeze(pending.frozen, &mut bodies.0);
+        } else {
+            self.1.substepping.pending = Some(pending);
+        }
     }
 }
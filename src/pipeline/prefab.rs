@@ -0,0 +1,85 @@
+use crate::dynamics::{RawImpulseJointSet, RawRigidBodySet};
+use crate::geometry::RawColliderSet;
+use crate::math::{RawRotation, RawVector};
+use crate::pipeline::{RawHandleMapping, RawSerializationPipeline, SceneFragment};
+use crate::utils::{self, FlatHandle};
+use js_sys::Uint8Array;
+use rapier::dynamics::{ImpulseJointSet, RigidBodyHandle, RigidBodySet};
+use rapier::math::Isometry;
+use std::collections::HashSet;
+use wasm_bindgen::prelude::*;
+
+/// The given rigid-body, followed by the non-fixed rigid-bodies connected to it through impulse
+/// joints, directly or not.
+fn body_tree(
+    bodies: &RigidBodySet,
+    impulse_joints: &ImpulseJointSet,
+    root: RigidBodyHandle,
+) -> Vec<RigidBodyHandle> {
+    let mut tree = vec![root];
+    let mut visited = HashSet::new();
+    let _ = visited.insert(root);
+    let mut i = 0;
+
+    while i < tree.len() {
+        let handle = tree[i];
+        for (body1, body2, _, _) in impulse_joints.attached_joints(handle) {
+            let other = if body1 == handle { body2 } else { body1 };
+            let movable = bodies.get(other).map(|rb| !rb.is_fixed()) == Some(true);
+            if movable && visited.insert(other) {
+                tree.push(other);
+            }
+        }
+        i += 1;
+    }
+
+    tree
+}
+
+#[wasm_bindgen]
+impl RawSerializationPipeline {
+    /// Serializes a rigid-body with its colliders, along with the non-fixed rigid-bodies
+    /// connected to it through impulse joints, directly or not, their colliders, and the impulse
+    /// joints between all these rigid-bodies.
+    ///
+    /// The result can be instantiated any number of times with `instantiatePrefab`. Returns `None`
+    /// if the rigid-body doesn’t exist.
+    pub fn serializeBodyTree(
+        &self,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        impulseJoints: &RawImpulseJointSet,
+        handle: FlatHandle,
+    ) -> Option<Uint8Array> {
+        let root = utils::body_handle(handle);
+        let _ = bodies.0.get(root)?;
+        let fragment = SceneFragment::extract(
+            &bodies.0,
+            colliders,
+            &impulseJoints.0,
+            body_tree(&bodies.0, &impulseJoints.0, root),
+            std::iter::empty(),
+        );
+        let data = bincode::serialize(&fragment).ok()?;
+        Some(Uint8Array::from(&data[..]))
+    }
+
+    /// Inserts a copy of the rigid-bodies, colliders, and impulse joints serialized by
+    /// `serializeBodyTree`, moved so that the root rigid-body is at the given position.
+    ///
+    /// Returns the handles of the inserted elements, or `None` if the data is invalid.
+    pub fn instantiatePrefab(
+        &self,
+        data: Uint8Array,
+        bodies: &mut RawRigidBodySet,
+        colliders: &mut RawColliderSet,
+        impulseJoints: &mut RawImpulseJointSet,
+        translation: &RawVector,
+        rotation: &RawRotation,
+    ) -> Option<RawHandleMapping> {
+        let data = data.to_vec();
+        let mut fragment: SceneFragment = bincode::deserialize(&data[..]).ok()?;
+        fragment.move_root_to(Isometry::from_parts(translation.0.into(), rotation.0));
+        Some(fragment.insert(&mut bodies.0, &mut colliders.0, &mut impulseJoints.0))
+    }
+}
@@ -0,0 +1,297 @@
+use crate::dynamics::{RawImpulseJointSet, RawRigidBodySet};
+use crate::geometry::RawColliderSet;
+use crate::math::RawVector;
+use crate::pipeline::RawSerializationPipeline;
+use crate::utils::{self, FlatHandle};
+use js_sys::Uint8Array;
+use rapier::dynamics::{
+    GenericJoint, ImpulseJointHandle, ImpulseJointSet, RigidBody, RigidBodyHandle, RigidBodySet,
+};
+use rapier::geometry::{Aabb, Collider, ColliderHandle, ColliderSet};
+use rapier::math::{Isometry, Point, Real};
+use rapier::parry::bounding_volume::BoundingVolume;
+use std::collections::{HashMap, HashSet};
+use wasm_bindgen::prelude::*;
+
+/// A set of rigid-bodies, colliders, and impulse joints, detached from any physics world.
+#[derive(Serialize, Deserialize, Default)]
+pub(crate) struct SceneFragment {
+    bodies: Vec<(RigidBodyHandle, RigidBody)>,
+    /// The colliders with their parent, if any. The position of the colliders attached to a
+    /// rigid-body is relative to it.
+    colliders: Vec<(ColliderHandle, Option<RigidBodyHandle>, Collider)>,
+    impulse_joints: Vec<(
+        ImpulseJointHandle,
+        RigidBodyHandle,
+        RigidBodyHandle,
+        GenericJoint,
+    )>,
+}
+
+impl SceneFragment {
+    /// Copies the given rigid-bodies with their colliders, the given colliders without parent,
+    /// and the impulse joints between the given rigid-bodies.
+    ///
+    /// The active hooks set natively on the colliders (e.g. for the pair filter rules) are
+    /// removed from the copies, since the side tables tracking them aren't copied.
+    pub fn extract(
+        bodies: &RigidBodySet,
+        colliders: &RawColliderSet,
+        impulse_joints: &ImpulseJointSet,
+        body_handles: impl IntoIterator<Item = RigidBodyHandle>,
+        collider_handles: impl IntoIterator<Item = ColliderHandle>,
+    ) -> Self {
+        let mut fragment = SceneFragment::default();
+        let mut extracted = HashSet::new();
+        let copy_collider = |handle: ColliderHandle, co: &Collider| {
+            let mut co = co.clone();
+            co.set_active_hooks(co.active_hooks() - colliders.1.native_hooks(handle));
+            co
+        };
+
+        for handle in body_handles {
+            let rb = match bodies.get(handle) {
+                Some(rb) if extracted.insert(handle) => rb,
+                _ => continue,
+            };
+
+            for co_handle in rb.colliders() {
+                if let Some(co) = colliders.0.get(*co_handle) {
+                    let mut co = copy_collider(*co_handle, co);
+                    if let Some(pos_wrt_parent) = co.position_wrt_parent().copied() {
+                        co.set_position(pos_wrt_parent);
+                    }
+                    fragment.colliders.push((*co_handle, Some(handle), co));
+                }
+            }
+
+            fragment.bodies.push((handle, rb.clone()));
+        }
+
+        for handle in collider_handles {
+            match colliders.0.get(handle) {
+                Some(co) if co.parent().is_none() => {
+                    fragment
+                        .colliders
+                        .push((handle, None, copy_collider(handle, co)));
+                }
+                _ => {}
+            }
+        }
+
+        for (handle, joint) in impulse_joints.iter() {
+            if extracted.contains(&joint.body1) && extracted.contains(&joint.body2) {
+                fragment
+                    .impulse_joints
+                    .push((handle, joint.body1, joint.body2, joint.data));
+            }
+        }
+
+        fragment
+    }
+
+    /// Moves the content of this fragment rigidly, so that its first rigid-body ends up at the
+    /// given position. The velocities of the rigid-bodies are rotated accordingly.
+    pub fn move_root_to(&mut self, position: Isometry<Real>) {
+        let root = match self.bodies.first() {
+            Some((_, rb)) => *rb.position(),
+            None => return,
+        };
+        let delta = position * root.inverse();
+
+        for (_, rb) in &mut self.bodies {
+            let vels = rb.vels().transformed(&delta.rotation);
+            rb.set_position(delta * rb.position(), false);
+            rb.set_vels(vels, false);
+        }
+
+        for (_, parent, co) in &mut self.colliders {
+            if parent.is_none() {
+                co.set_position(delta * co.position());
+            }
+        }
+    }
+
+    /// Inserts the content of this fragment into the given sets, and returns the new handles.
+    pub fn insert(
+        self,
+        bodies: &mut RigidBodySet,
+        colliders: &mut ColliderSet,
+        impulse_joints: &mut ImpulseJointSet,
+    ) -> RawHandleMapping {
+        let mut mapping = RawHandleMapping::default();
+        let mut new_bodies = HashMap::new();
+
+        for (old, rb) in self.bodies {
+            let new = bodies.insert(rb);
+            let _ = new_bodies.insert(old, new);
+            mapping.bodies.push((old, new));
+        }
+
+        for (old, parent, co) in self.colliders {
+            let new = match parent {
+                Some(parent) => match new_bodies.get(&parent) {
+                    Some(parent) => colliders.insert_with_parent(co, *parent, bodies),
+                    None => continue,
+                },
+                None => colliders.insert(co),
+            };
+            mapping.colliders.push((old, new));
+        }
+
+        for (old, body1, body2, data) in self.impulse_joints {
+            if let (Some(body1), Some(body2)) = (new_bodies.get(&body1), new_bodies.get(&body2)) {
+                let new = impulse_joints.insert(*body1, *body2, data, true);
+                mapping.impulse_joints.push((old, new));
+            }
+        }
+
+        mapping
+    }
+}
+
+/// The handles given to the rigid-bodies, colliders, and impulse joints inserted into a
+/// physics world, along with their handles in the world they were taken from.
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct RawHandleMapping {
+    bodies: Vec<(RigidBodyHandle, RigidBodyHandle)>,
+    colliders: Vec<(ColliderHandle, ColliderHandle)>,
+    impulse_joints: Vec<(ImpulseJointHandle, ImpulseJointHandle)>,
+}
+
+#[wasm_bindgen]
+impl RawHandleMapping {
+    pub fn oldBodies(&self) -> Vec<FlatHandle> {
+        self.bodies
+            .iter()
+            .map(|(old, _)| utils::flat_handle(old.0))
+            .collect()
+    }
+
+    pub fn newBodies(&self) -> Vec<FlatHandle> {
+        self.bodies
+            .iter()
+            .map(|(_, new)| utils::flat_handle(new.0))
+            .collect()
+    }
+
+    pub fn oldColliders(&self) -> Vec<FlatHandle> {
+        self.colliders
+            .iter()
+            .map(|(old, _)| utils::flat_handle(old.0))
+            .collect()
+    }
+
+    pub fn newColliders(&self) -> Vec<FlatHandle> {
+        self.colliders
+            .iter()
+            .map(|(_, new)| utils::flat_handle(new.0))
+            .collect()
+    }
+
+    pub fn oldImpulseJoints(&self) -> Vec<FlatHandle> {
+        self.impulse_joints
+            .iter()
+            .map(|(old, _)| utils::flat_handle(old.0))
+            .collect()
+    }
+
+    pub fn newImpulseJoints(&self) -> Vec<FlatHandle> {
+        self.impulse_joints
+            .iter()
+            .map(|(_, new)| utils::flat_handle(new.0))
+            .collect()
+    }
+}
+
+#[wasm_bindgen]
+impl RawSerializationPipeline {
+    /// The rigid-bodies whose colliders are all contained in the given axis-aligned box.
+    ///
+    /// A rigid-body without collider is in the box if its origin is.
+    pub fn regionBodies(
+        &self,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        mins: &RawVector,
+        maxs: &RawVector,
+    ) -> Vec<FlatHandle> {
+        let region = Aabb::new(mins.0.into(), maxs.0.into());
+        bodies
+            .0
+            .iter()
+            .filter(|(_, rb)| {
+                let aabbs: Vec<_> = rb
+                    .colliders()
+                    .iter()
+                    .filter_map(|handle| colliders.0.get(*handle))
+                    .map(|co| co.compute_aabb())
+                    .collect();
+                if aabbs.is_empty() {
+                    let origin = Point::from(*rb.translation());
+                    region.contains(&Aabb::new(origin, origin))
+                } else {
+                    aabbs.iter().all(|aabb| region.contains(aabb))
+                }
+            })
+            .map(|(handle, _)| utils::flat_handle(handle.0))
+            .collect()
+    }
+
+    /// The colliders without parent contained in the given axis-aligned box.
+    pub fn regionColliders(
+        &self,
+        colliders: &RawColliderSet,
+        mins: &RawVector,
+        maxs: &RawVector,
+    ) -> Vec<FlatHandle> {
+        let region = Aabb::new(mins.0.into(), maxs.0.into());
+        colliders
+            .0
+            .iter()
+            .filter(|(_, co)| co.parent().is_none() && region.contains(&co.compute_aabb()))
+            .map(|(handle, _)| utils::flat_handle(handle.0))
+            .collect()
+    }
+
+    /// Serializes the given rigid-bodies with their colliders, the given colliders without
+    /// parent, and the impulse joints between the given rigid-bodies.
+    ///
+    /// The result can be inserted into any physics world with `insertRegion`. Only the state
+    /// managed by the physics engine is serialized, the other properties of the rigid-bodies,
+    /// colliders, and joints (e.g., rolling resistance or joint friction) are not.
+    pub fn serializeRegion(
+        &self,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        impulseJoints: &RawImpulseJointSet,
+        bodyHandles: Vec<FlatHandle>,
+        colliderHandles: Vec<FlatHandle>,
+    ) -> Option<Uint8Array> {
+        let fragment = SceneFragment::extract(
+            &bodies.0,
+            colliders,
+            &impulseJoints.0,
+            bodyHandles.into_iter().map(utils::body_handle),
+            colliderHandles.into_iter().map(utils::collider_handle),
+        );
+        let data = bincode::serialize(&fragment).ok()?;
+        Some(Uint8Array::from(&data[..]))
+    }
+
+    /// Inserts the rigid-bodies, colliders, and impulse joints serialized by `serializeRegion`.
+    ///
+    /// Returns the new handles of the inserted elements, or `None` if the data is invalid.
+    pub fn insertRegion(
+        &self,
+        data: Uint8Array,
+        bodies: &mut RawRigidBodySet,
+        colliders: &mut RawColliderSet,
+        impulseJoints: &mut RawImpulseJointSet,
+    ) -> Option<RawHandleMapping> {
+        let data = data.to_vec();
+        let fragment: SceneFragment = bincode::deserialize(&data[..]).ok()?;
+        Some(fragment.insert(&mut bodies.0, &mut colliders.0, &mut impulseJoints.0))
+    }
+}
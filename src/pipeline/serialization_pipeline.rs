@@ -1,15 +1,18 @@
 use crate::dynamics::{
-    RawImpulseJointSet, RawIntegrationParameters, RawIslandManager, RawMultibodyJointSet,
-    RawRigidBodySet,
+    JointFrictions, RawImpulseJointSet, RawIntegrationParameters, RawIslandManager,
+    RawMultibodyJointSet, RawRigidBodySet, RigidBodyExt, SimulationLayers,
 };
-use crate::geometry::{RawBroadPhase, RawColliderSet, RawNarrowPhase};
+use crate::geometry::{ColliderExt, NarrowPhaseExt, RawBroadPhase, RawColliderSet, RawNarrowPhase};
 use crate::math::RawVector;
+use crate::pipeline::{PipelineExt, RawHandleMapping, RawPhysicsPipeline, SceneFragment};
+use crate::utils;
 use js_sys::Uint8Array;
 use rapier::dynamics::{
-    ImpulseJointSet, IntegrationParameters, IslandManager, MultibodyJointSet, RigidBodySet,
+    ImpulseJointSet, IntegrationParameters, IslandManager, MultibodyJointSet, RigidBody,
+    RigidBodySet,
 };
 use rapier::geometry::{ColliderSet, DefaultBroadPhase, NarrowPhase};
-use rapier::math::Vector;
+use rapier::math::{Isometry, Vector, DIM, SPATIAL_DIM};
 use wasm_bindgen::prelude::*;
 
 #[derive(Serialize)]
@@ -38,6 +41,91 @@ struct DeserializableWorld {
     multibody_joints: MultibodyJointSet,
 }
 
+/// The version of the bindings state stored after the world in the snapshots.
+const BINDINGS_STATE_VERSION: u32 = 1;
+
+/// The state attached to the world by these bindings, on top of the Rapier structures.
+///
+/// It is stored after the world, prefixed by `BINDINGS_STATE_VERSION`, so the snapshots
+/// taken without it can still be read.
+#[derive(Serialize)]
+struct SerializableBindingsState<'a> {
+    narrow_phase: &'a NarrowPhaseExt,
+    bodies: &'a RigidBodyExt,
+    colliders: &'a ColliderExt,
+    impulse_joints: &'a JointFrictions,
+    pipeline: &'a PipelineExt,
+}
+
+#[derive(Deserialize, Default)]
+struct DeserializableBindingsState {
+    narrow_phase: NarrowPhaseExt,
+    bodies: RigidBodyExt,
+    colliders: ColliderExt,
+    impulse_joints: JointFrictions,
+    pipeline: PipelineExt,
+}
+
+impl DeserializableBindingsState {
+    /// Builds the raw world made of the given Rapier structures and of this bindings state.
+    fn into_raw(self, d: DeserializableWorld) -> RawDeserializedWorld {
+        let mut pipeline = RawPhysicsPipeline::new();
+        pipeline.1 = self.pipeline;
+
+        RawDeserializedWorld {
+            gravity: Some(RawVector(d.gravity)),
+            integrationParameters: Some(RawIntegrationParameters(d.integration_parameters)),
+            islands: Some(RawIslandManager(d.islands)),
+            broadPhase: Some(RawBroadPhase(d.broad_phase)),
+            narrowPhase: Some(RawNarrowPhase(d.narrow_phase, self.narrow_phase)),
+            bodies: Some(RawRigidBodySet(d.bodies, self.bodies)),
+            colliders: Some(RawColliderSet(d.colliders, self.colliders)),
+            impulse_joints: Some(RawImpulseJointSet(d.impulse_joints, self.impulse_joints)),
+            multibody_joints: Some(RawMultibodyJointSet(d.multibody_joints)),
+            physicsPipeline: Some(pipeline),
+        }
+    }
+}
+
+/// Reads the world and, if the snapshot contains it, the bindings state of a decoded snapshot.
+///
+/// The bindings state is left to its default value if the snapshot was taken without it.
+/// Returns `None` if the snapshot is invalid or if its bindings state has an unknown version.
+fn read_snapshot(data: &[u8]) -> Option<(DeserializableWorld, DeserializableBindingsState)> {
+    let mut reader = data;
+    let world: DeserializableWorld = bincode::deserialize_from(&mut reader).ok()?;
+
+    if reader.is_empty() {
+        return Some((world, Default::default()));
+    }
+
+    let version: u32 = bincode::deserialize_from(&mut reader).ok()?;
+    if version != BINDINGS_STATE_VERSION {
+        return None;
+    }
+
+    let state = bincode::deserialize(reader).ok()?;
+    Some((world, state))
+}
+
+/// Compresses a snapshot with LZ4 if `compressed` is `true`.
+fn encode_snapshot(snap: Vec<u8>, compressed: bool) -> Vec<u8> {
+    if compressed {
+        lz4_flex::compress_prepend_size(&snap)
+    } else {
+        snap
+    }
+}
+
+/// Decompresses a snapshot encoded by `encode_snapshot` with the same `compressed` flag.
+fn decode_snapshot(data: Vec<u8>, compressed: bool) -> Option<Vec<u8>> {
+    if compressed {
+        lz4_flex::decompress_size_prepended(&data).ok()
+    } else {
+        Some(data)
+    }
+}
+
 #[wasm_bindgen]
 pub struct RawDeserializedWorld {
     gravity: Option<RawVector>,
@@ -49,6 +137,7 @@ pub struct RawDeserializedWorld {
     colliders: Option<RawColliderSet>,
     impulse_joints: Option<RawImpulseJointSet>,
     multibody_joints: Option<RawMultibodyJointSet>,
+    physicsPipeline: Option<RawPhysicsPipeline>,
 }
 
 #[wasm_bindgen]
@@ -88,6 +177,10 @@ impl RawDeserializedWorld {
     pub fn takeMultibodyJoints(&mut self) -> Option<RawMultibodyJointSet> {
         self.multibody_joints.take()
     }
+
+    pub fn takePhysicsPipeline(&mut self) -> Option<RawPhysicsPipeline> {
+        self.physicsPipeline.take()
+    }
 }
 
 #[wasm_bindgen]
@@ -100,6 +193,10 @@ impl RawSerializationPipeline {
         RawSerializationPipeline
     }
 
+    /// Serializes the given world, followed by the state attached to it by these bindings.
+    ///
+    /// The state attached to the physics pipeline (e.g. the pair filtering rules) is saved with
+    /// its default value, see `serializeAllWithPipeline`.
     pub fn serializeAll(
         &self,
         gravity: &RawVector,
@@ -111,6 +208,38 @@ impl RawSerializationPipeline {
         colliders: &RawColliderSet,
         impulse_joints: &RawImpulseJointSet,
         multibody_joints: &RawMultibodyJointSet,
+        compressed: bool,
+    ) -> Option<Uint8Array> {
+        self.serializeAllWithPipeline(
+            gravity,
+            integrationParameters,
+            islands,
+            broadPhase,
+            narrowPhase,
+            bodies,
+            colliders,
+            impulse_joints,
+            multibody_joints,
+            &RawPhysicsPipeline::new(),
+            compressed,
+        )
+    }
+
+    /// Serializes the given world, followed by the state attached to it and to its physics
+    /// pipeline by these bindings.
+    pub fn serializeAllWithPipeline(
+        &self,
+        gravity: &RawVector,
+        integrationParameters: &RawIntegrationParameters,
+        islands: &RawIslandManager,
+        broadPhase: &RawBroadPhase,
+        narrowPhase: &RawNarrowPhase,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        impulse_joints: &RawImpulseJointSet,
+        multibody_joints: &RawMultibodyJointSet,
+        physicsPipeline: &RawPhysicsPipeline,
+        compressed: bool,
     ) -> Option<Uint8Array> {
         let to_serialize = SerializableWorld {
             gravity: &gravity.0,
@@ -123,23 +252,256 @@ impl RawSerializationPipeline {
             impulse_joints: &impulse_joints.0,
             multibody_joints: &multibody_joints.0,
         };
-        let snap = bincode::serialize(&to_serialize).ok()?;
+        let state = SerializableBindingsState {
+            narrow_phase: &narrowPhase.1,
+            bodies: &bodies.1,
+            colliders: &colliders.1,
+            impulse_joints: &impulse_joints.1,
+            pipeline: &physicsPipeline.1,
+        };
+        let mut snap = bincode::serialize(&to_serialize).ok()?;
+        bincode::serialize_into(&mut snap, &BINDINGS_STATE_VERSION).ok()?;
+        bincode::serialize_into(&mut snap, &state).ok()?;
+        let snap = encode_snapshot(snap, compressed);
         Some(Uint8Array::from(&snap[..]))
     }
 
-    pub fn deserializeAll(&self, data: Uint8Array) -> Option<RawDeserializedWorld> {
-        let data = data.to_vec();
-        let d: DeserializableWorld = bincode::deserialize(&data[..]).ok()?;
-        Some(RawDeserializedWorld {
-            gravity: Some(RawVector(d.gravity)),
-            integrationParameters: Some(RawIntegrationParameters(d.integration_parameters)),
-            islands: Some(RawIslandManager(d.islands)),
-            broadPhase: Some(RawBroadPhase(d.broad_phase)),
-            narrowPhase: Some(RawNarrowPhase(d.narrow_phase)),
-            bodies: Some(RawRigidBodySet(d.bodies)),
-            colliders: Some(RawColliderSet(d.colliders)),
-            impulse_joints: Some(RawImpulseJointSet(d.impulse_joints)),
-            multibody_joints: Some(RawMultibodyJointSet(d.multibody_joints)),
-        })
+    /// Deserializes a snapshot taken by `serializeAll`.
+    pub fn deserializeAll(
+        &self,
+        data: Uint8Array,
+        compressed: bool,
+    ) -> Option<RawDeserializedWorld> {
+        let data = decode_snapshot(data.to_vec(), compressed)?;
+        let (d, state) = read_snapshot(&data)?;
+        Some(state.into_raw(d))
+    }
+
+    /// Inserts the rigid-bodies, colliders, and impulse joints of a snapshot into the given sets,
+    /// alongside the elements they already contain.
+    ///
+    /// Returns the new handles of the inserted elements, or `None` if the snapshot is invalid.
+    /// The multibody joints of the snapshot are not inserted.
+    pub fn deserializeInto(
+        &self,
+        data: Uint8Array,
+        bodies: &mut RawRigidBodySet,
+        colliders: &mut RawColliderSet,
+        impulseJoints: &mut RawImpulseJointSet,
+        compressed: bool,
+    ) -> Option<RawHandleMapping> {
+        let data = decode_snapshot(data.to_vec(), compressed)?;
+        let (mut d, state) = read_snapshot(&data)?;
+        // The rigid-bodies frozen during a partially executed step are inserted unfrozen.
+        if let Some(pending) = state.pipeline.substepping.pending {
+            SimulationLayers::unfreeze(pending.frozen, &mut d.bodies);
+        }
+        let colliders_in = RawColliderSet(d.colliders, state.colliders);
+        let fragment = SceneFragment::extract(
+            &d.bodies,
+            &colliders_in,
+            &d.impulse_joints,
+            d.bodies.iter().map(|(handle, _)| handle),
+            colliders_in.0.iter().map(|(handle, _)| handle),
+        );
+        Some(fragment.insert(&mut bodies.0, &mut colliders.0, &mut impulseJoints.0))
+    }
+
+    /// Describes the given world as a human-readable JSON string, for debugging.
+    ///
+    /// The description includes the rigid-bodies, the colliders with their shape, and the joints
+    /// with their frames, limits, and motors.
+    pub fn worldToDebugJson(
+        &self,
+        gravity: &RawVector,
+        integrationParameters: &RawIntegrationParameters,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        impulse_joints: &RawImpulseJointSet,
+        multibody_joints: &RawMultibodyJointSet,
+    ) -> String {
+        super::debug_json::world_to_debug_json(
+            &gravity.0,
+            &integrationParameters.0,
+            &bodies.0,
+            &colliders.0,
+            &impulse_joints.0,
+            &multibody_joints.0,
+        )
+    }
+
+    /// Deserializes a snapshot taken by the f32 bindings, converting it to f64.
+    ///
+    /// This requires the `f32-snapshots` feature, and a snapshot taken by bindings based on the
+    /// same version of Rapier. Returns `None` if the feature is disabled or if the snapshot is
+    /// invalid. The f32 bindings don’t have the bindings state saved by `serializeAll`, so it is
+    /// left to its default value.
+    #[allow(unused_variables)]
+    pub fn deserializeAllF32(&self, data: Uint8Array) -> Option<RawDeserializedWorld> {
+        #[cfg(feature = "f32-snapshots")]
+        {
+            let d = super::f32_snapshot::deserialize_f32_snapshot(&data.to_vec())?;
+            let d = DeserializableWorld {
+                gravity: d.gravity,
+                integration_parameters: d.integration_parameters,
+                islands: IslandManager::new(),
+                broad_phase: DefaultBroadPhase::new(),
+                narrow_phase: NarrowPhase::new(),
+                bodies: d.bodies,
+                colliders: d.colliders,
+                impulse_joints: d.impulse_joints,
+                multibody_joints: d.multibody_joints,
+            };
+            Some(DeserializableBindingsState::default().into_raw(d))
+        }
+        #[cfg(not(feature = "f32-snapshots"))]
+        None
+    }
+
+    /// Saves the state of the rigid-bodies and impulse joints that changes during a simulation
+    /// step, i.e., the positions, velocities, and sleep state of the non-fixed rigid-bodies,
+    /// and the impulses of the impulse joints.
+    ///
+    /// Shapes, broad-phase, and narrow-phase are not saved. This is meant to be cheap enough to
+    /// be called at each simulation step for rollback. The multibody joints aren’t saved either,
+    /// so the coordinates and velocities of their links aren’t restored.
+    pub fn saveDynamicsState(
+        &self,
+        bodies: &RawRigidBodySet,
+        impulse_joints: &RawImpulseJointSet,
+    ) -> Vec<f64> {
+        let dynamic_bodies = bodies.0.iter().filter(|(_, rb)| !rb.is_fixed());
+        let num_bodies = dynamic_bodies.clone().count();
+        let num_joints = impulse_joints.0.iter().count();
+        let mut data =
+            Vec::with_capacity(2 + num_bodies * BODY_STATE_LEN + num_joints * JOINT_STATE_LEN);
+        data.push(num_bodies as f64);
+        data.push(num_joints as f64);
+
+        for (handle, rb) in dynamic_bodies {
+            data.push(utils::flat_handle(handle.0));
+            write_body_state(rb, &mut data);
+        }
+
+        for (handle, joint) in impulse_joints.0.iter() {
+            data.push(utils::flat_handle(handle.0));
+            data.extend_from_slice(joint.impulses.as_slice());
+        }
+
+        data
+    }
+
+    /// Restores a state saved by `saveDynamicsState`.
+    ///
+    /// The rigid-bodies and impulse joints removed since the state was saved are ignored.
+    ///
+    /// Returns `false` if `data` isn’t a valid dynamics state.
+    pub fn restoreDynamicsState(
+        &self,
+        data: &[f64],
+        bodies: &mut RawRigidBodySet,
+        impulse_joints: &mut RawImpulseJointSet,
+    ) -> bool {
+        if data.len() < 2 {
+            return false;
+        }
+
+        let num_bodies = data[0] as usize;
+        let num_joints = data[1] as usize;
+        // The counts are untrusted, so the lengths they imply may overflow.
+        let joints_start = match num_bodies
+            .checked_mul(BODY_STATE_LEN)
+            .and_then(|len| len.checked_add(2))
+        {
+            Some(joints_start) => joints_start,
+            None => return false,
+        };
+        let len = num_joints
+            .checked_mul(JOINT_STATE_LEN)
+            .and_then(|len| len.checked_add(joints_start));
+
+        if len != Some(data.len()) {
+            return false;
+        }
+
+        for state in data[2..joints_start].chunks_exact(BODY_STATE_LEN) {
+            if let Some(rb) = bodies.0.get_mut(utils::body_handle(state[0])) {
+                read_body_state(&state[1..], rb);
+            }
+        }
+
+        for state in data[joints_start..].chunks_exact(JOINT_STATE_LEN) {
+            let handle = utils::impulse_joint_handle(state[0]);
+            if let Some(joint) = impulse_joints.0.get_mut(handle, false) {
+                joint.impulses.copy_from_slice(&state[1..]);
+            }
+        }
+
+        true
+    }
+}
+
+#[cfg(feature = "dim2")]
+const ROTATION_LEN: usize = 2;
+#[cfg(feature = "dim3")]
+const ROTATION_LEN: usize = 4;
+#[cfg(feature = "dim2")]
+pub(crate) const ANGVEL_LEN: usize = 1;
+#[cfg(feature = "dim3")]
+pub(crate) const ANGVEL_LEN: usize = 3;
+
+/// Handle, translation, rotation, linear velocity, angular velocity, and sleep flag.
+const BODY_STATE_LEN: usize = 1 + DIM + ROTATION_LEN + DIM + ANGVEL_LEN + 1;
+/// Handle and impulses.
+const JOINT_STATE_LEN: usize = 1 + SPATIAL_DIM;
+
+pub(crate) fn write_body_state(rb: &RigidBody, data: &mut Vec<f64>) {
+    let pos = rb.position();
+    data.extend_from_slice(pos.translation.vector.as_slice());
+    #[cfg(feature = "dim2")]
+    data.extend_from_slice(&[pos.rotation.re, pos.rotation.im]);
+    #[cfg(feature = "dim3")]
+    data.extend_from_slice(pos.rotation.coords.as_slice());
+    data.extend_from_slice(rb.linvel().as_slice());
+    #[cfg(feature = "dim2")]
+    data.push(rb.angvel());
+    #[cfg(feature = "dim3")]
+    data.extend_from_slice(rb.angvel().as_slice());
+    data.push(if rb.is_sleeping() { 1.0 } else { 0.0 });
+}
+
+fn read_body_state(state: &[f64], rb: &mut RigidBody) {
+    let (translation, state) = state.split_at(DIM);
+    let (rotation, state) = state.split_at(ROTATION_LEN);
+    let (linvel, state) = state.split_at(DIM);
+    let (angvel, state) = state.split_at(ANGVEL_LEN);
+    let sleeping = state[0] != 0.0;
+
+    #[cfg(feature = "dim2")]
+    let rotation = na::UnitComplex::new_unchecked(na::Complex::new(rotation[0], rotation[1]));
+    #[cfg(feature = "dim3")]
+    let rotation = na::UnitQuaternion::new_unchecked(na::Quaternion::new(
+        rotation[3],
+        rotation[0],
+        rotation[1],
+        rotation[2],
+    ));
+    let translation = Vector::from_column_slice(translation);
+    rb.set_position(Isometry::from_parts(translation.into(), rotation), false);
+
+    if sleeping {
+        if !rb.is_sleeping() {
+            rb.sleep();
+        }
+    } else {
+        if rb.is_sleeping() {
+            rb.wake_up(true);
+        }
+
+        rb.set_linvel(Vector::from_column_slice(linvel), false);
+        #[cfg(feature = "dim2")]
+        rb.set_angvel(angvel[0], false);
+        #[cfg(feature = "dim3")]
+        rb.set_angvel(Vector::from_column_slice(angvel), false);
     }
 }
@@ -0,0 +1,279 @@
+use super::serialization_pipeline::{write_body_state, ANGVEL_LEN};
+use crate::dynamics::FrozenBodies;
+use crate::utils;
+use js_sys::Float64Array;
+use rapier::dynamics::{RigidBody, RigidBodyHandle, RigidBodySet, RigidBodyType};
+use rapier::geometry::ColliderSet;
+use rapier::math::{AngVector, Isometry, Real, Vector, DIM};
+use std::collections::HashSet;
+use wasm_bindgen::prelude::*;
+
+/// Handle, force, and torque.
+const SUBSTEP_FORCE_LEN: usize = 1 + DIM + ANGVEL_LEN;
+
+/// The user forces and torques of rigid-bodies, saved before being temporarily modified.
+pub(crate) type SavedForces = Vec<(RigidBodyHandle, Vector<Real>, AngVector<Real>)>;
+
+/// The motion of a position-based kinematic rigid-body toward its next kinematic position,
+/// split across the substeps of a step.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct KinematicMotion {
+    pub handle: RigidBodyHandle,
+    pub start: Isometry<Real>,
+    pub target: Isometry<Real>,
+    /// The substep the motion started at.
+    pub first_substep: u32,
+    /// The next kinematic position set for the last substep executed.
+    pub last_position: Isometry<Real>,
+}
+
+/// The motions of the moving position-based kinematic rigid-bodies.
+pub(crate) type KinematicMotions = Vec<KinematicMotion>;
+
+#[cfg(feature = "dim2")]
+fn ang_vector(data: &[f64]) -> AngVector<Real> {
+    data[0]
+}
+
+#[cfg(feature = "dim3")]
+fn ang_vector(data: &[f64]) -> AngVector<Real> {
+    Vector::from_column_slice(data)
+}
+
+/// Parameters of the automatic increase of the number of substeps for fast rigid-bodies.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct AutoSubstepping {
+    /// The maximum distance a rigid-body may travel during a substep, relative to the
+    /// smallest extent of its colliders.
+    pub max_travel_ratio: Real,
+    pub max_substeps: u32,
+}
+
+/// A simulation step executed substep by substep, see `RawPhysicsPipeline::step`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct PendingStep {
+    /// The index of the next substep to execute.
+    pub substep: u32,
+    pub num_substeps: u32,
+    /// The timestep of each substep.
+    pub dt: Real,
+    pub kinematic_motions: KinematicMotions,
+    /// The rigid-bodies frozen until the end of the step.
+    pub frozen: FrozenBodies,
+}
+
+/// How each simulation step is split into several smaller steps.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct Substepping {
+    /// The ratio between the simulated time and the timestep of the integration parameters.
+    pub time_scale: Real,
+    pub num_substeps: u32,
+    pub auto: Option<AutoSubstepping>,
+    /// The number of substeps actually executed by the last step.
+    pub last_num_substeps: u32,
+    /// Called before each substep to compute forces applied during that substep only.
+    ///
+    /// It isn’t part of the world snapshots.
+    #[serde(skip)]
+    pub force_callback: Option<js_sys::Function>,
+    /// The step whose substeps are being executed, if it isn’t complete.
+    pub pending: Option<PendingStep>,
+    /// The sum of the timesteps of all the substeps executed.
+    pub time: Real,
+}
+
+impl Default for Substepping {
+    fn default() -> Self {
+        Self {
+            time_scale: 1.0,
+            num_substeps: 1,
+            auto: None,
+            last_num_substeps: 1,
+            force_callback: None,
+            pending: None,
+            time: 0.0,
+        }
+    }
+}
+
+/// The smallest extent of the non-sensor colliders attached to a rigid-body.
+fn smallest_extent(rb: &RigidBody, colliders: &ColliderSet) -> Option<Real> {
+    rb.colliders()
+        .iter()
+        .filter_map(|handle| colliders.get(*handle))
+        .filter(|co| !co.is_sensor())
+        .map(|co| co.shape().compute_local_aabb().extents().min())
+        .filter(|extent| *extent > 0.0)
+        .reduce(Real::min)
+}
+
+impl Substepping {
+    /// Computes the number of substeps of the next step, of length `dt`.
+    ///
+    /// With automatic substepping, this is the number of substeps needed for every awake
+    /// dynamic rigid-body to travel, during a substep, less than the configured fraction of
+    /// the smallest extent of its colliders, assuming its current linear velocity.
+    pub fn substep_count(&self, dt: Real, bodies: &RigidBodySet, colliders: &ColliderSet) -> u32 {
+        let num_substeps = self.num_substeps.max(1);
+        let auto = match &self.auto {
+            Some(auto) => auto,
+            None => return num_substeps,
+        };
+        let max_substeps = auto.max_substeps.max(num_substeps);
+
+        let mut required = num_substeps;
+        for (_, rb) in bodies.iter() {
+            if !rb.is_dynamic() || rb.is_sleeping() {
+                continue;
+            }
+
+            if let Some(extent) = smallest_extent(rb, colliders) {
+                let travel = rb.linvel().norm() * dt;
+                let substeps = (travel / (extent * auto.max_travel_ratio)).ceil();
+                if substeps >= max_substeps as Real {
+                    return max_substeps;
+                }
+                required = required.max(substeps as u32);
+            }
+        }
+
+        required
+    }
+
+    /// Calls the force callback, if any, and adds the forces it returns to the user forces
+    /// of the rigid-bodies.
+    ///
+    /// The callback is called as `f(substep, dt, states)` where `states` contains, for each
+    /// awake dynamic rigid-body, its handle followed by its state with the same layout as the
+    /// rigid-body entries of `saveDynamicsState`. It returns an array containing, for each
+    /// rigid-body to push, its handle followed by the force and torque to apply.
+    ///
+    /// Returns the user forces to restore with `restore_forces` after the substep.
+    pub fn apply_forces(&self, substep: u32, dt: Real, bodies: &mut RigidBodySet) -> SavedForces {
+        let mut saved = vec![];
+        let callback = match &self.force_callback {
+            Some(callback) => callback,
+            None => return saved,
+        };
+
+        let mut states = vec![];
+        for (handle, rb) in bodies.iter() {
+            if rb.is_dynamic() && !rb.is_sleeping() {
+                states.push(utils::flat_handle(handle.0));
+                write_body_state(rb, &mut states);
+            }
+        }
+
+        let result = callback.call3(
+            &JsValue::null(),
+            &JsValue::from(substep),
+            &JsValue::from(dt),
+            &Float64Array::from(&states[..]),
+        );
+        let forces = match result {
+            Ok(forces) if !forces.is_null() && !forces.is_undefined() => {
+                Float64Array::new(&forces).to_vec()
+            }
+            _ => return saved,
+        };
+
+        for force in forces.chunks_exact(SUBSTEP_FORCE_LEN) {
+            let handle = utils::body_handle(force[0]);
+            if let Some(rb) = bodies.get_mut(handle) {
+                if rb.is_dynamic() {
+                    saved.push((handle, rb.user_force(), rb.user_torque()));
+                    rb.add_force(Vector::from_column_slice(&force[1..1 + DIM]), true);
+                    rb.add_torque(ang_vector(&force[1 + DIM..]), true);
+                }
+            }
+        }
+
+        saved
+    }
+
+    /// Restores the user forces modified by `apply_forces`.
+    pub fn restore_forces(saved: SavedForces, bodies: &mut RigidBodySet) {
+        // Restore in reverse order in case the same rigid-body was pushed several times.
+        for (handle, force, torque) in saved.into_iter().rev() {
+            if let Some(rb) = bodies.get_mut(handle) {
+                rb.reset_forces(false);
+                rb.reset_torques(false);
+                rb.add_force(force, false);
+                rb.add_torque(torque, false);
+            }
+        }
+    }
+
+    /// Collects the motions of the position-based kinematic rigid-bodies toward their next
+    /// kinematic positions, to be split across the substeps of a step.
+    pub fn kinematic_motions(num_substeps: u32, bodies: &RigidBodySet) -> KinematicMotions {
+        if num_substeps <= 1 {
+            return vec![];
+        }
+
+        Self::new_kinematic_motions(0, bodies, |_| true).collect()
+    }
+
+    /// The motions, starting at the given substep, of the position-based kinematic rigid-bodies
+    /// moving toward their next kinematic position and accepted by `filter`.
+    fn new_kinematic_motions<'a>(
+        substep: u32,
+        bodies: &'a RigidBodySet,
+        filter: impl Fn(RigidBodyHandle) -> bool + 'a,
+    ) -> impl Iterator<Item = KinematicMotion> + 'a {
+        bodies
+            .iter()
+            .filter(|(_, rb)| rb.body_type() == RigidBodyType::KinematicPositionBased)
+            .filter(|(_, rb)| rb.position() != rb.next_position())
+            .filter(move |(handle, _)| filter(*handle))
+            .map(move |(handle, rb)| KinematicMotion {
+                handle,
+                start: *rb.position(),
+                target: *rb.next_position(),
+                first_substep: substep,
+                last_position: *rb.position(),
+            })
+    }
+
+    /// Restarts the motions of the position-based kinematic rigid-bodies whose next kinematic
+    /// position was changed since the last substep, i.e., between two calls to `step` with
+    /// `numSubsteps`, so that they move toward their new target during the remaining substeps
+    /// instead of having it overwritten.
+    pub fn resume_kinematic_motions(
+        motions: &mut KinematicMotions,
+        substep: u32,
+        bodies: &RigidBodySet,
+    ) {
+        motions.retain(|motion| {
+            bodies
+                .get(motion.handle)
+                .map_or(false, |rb| *rb.next_position() == motion.last_position)
+        });
+
+        let unchanged: HashSet<_> = motions.iter().map(|motion| motion.handle).collect();
+        let restarted: Vec<_> =
+            Self::new_kinematic_motions(substep, bodies, |handle| !unchanged.contains(&handle))
+                .collect();
+        motions.extend(restarted);
+    }
+
+    /// Sets the next kinematic positions of the rigid-bodies to their interpolated positions
+    /// at the end of the given substep, so that they move at a constant velocity during the
+    /// whole step instead of reaching their target during the first substep.
+    pub fn interpolate_kinematic_targets(
+        motions: &mut KinematicMotions,
+        substep: u32,
+        num_substeps: u32,
+        bodies: &mut RigidBodySet,
+    ) {
+        for motion in motions {
+            if let Some(rb) = bodies.get_mut(motion.handle) {
+                let t = (substep + 1 - motion.first_substep) as Real
+                    / (num_substeps - motion.first_substep) as Real;
+                motion.last_position = motion.start.lerp_slerp(&motion.target, t);
+                rb.set_next_kinematic_position(motion.last_position);
+            }
+        }
+    }
+}
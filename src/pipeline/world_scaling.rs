@@ -0,0 +1,130 @@
+use crate::dynamics::{
+    RawImpulseJointSet, RawIntegrationParameters, RawMultibodyJointSet, RawRigidBodySet,
+};
+use crate::geometry::RawColliderSet;
+use rapier::dynamics::{GenericJoint, RigidBodyAdditionalMassProps};
+use rapier::geometry::SharedShape;
+use rapier::math::{Isometry, Real, Vector, DIM};
+use rapier::parry::mass_properties::MassProperties;
+use wasm_bindgen::prelude::*;
+
+/// The number of subdivisions used to approximate the shapes that can’t be scaled exactly.
+const SCALED_SHAPE_SUBDIVISIONS: u32 = 20;
+
+fn scale_isometry(pos: &Isometry<Real>, factor: Real) -> Isometry<Real> {
+    Isometry::from_parts((pos.translation.vector * factor).into(), pos.rotation)
+}
+
+/// Scales mass properties as if the density of the matter was preserved.
+#[cfg(feature = "dim2")]
+fn scale_mass_properties(mprops: &MassProperties, factor: Real) -> MassProperties {
+    let mass_factor = factor.powi(DIM as i32);
+    MassProperties::new(
+        mprops.local_com * factor,
+        mprops.mass() * mass_factor,
+        mprops.principal_inertia() * mass_factor * factor * factor,
+    )
+}
+
+/// Scales mass properties as if the density of the matter was preserved.
+#[cfg(feature = "dim3")]
+fn scale_mass_properties(mprops: &MassProperties, factor: Real) -> MassProperties {
+    let mass_factor = factor.powi(DIM as i32);
+    MassProperties::with_principal_inertia_frame(
+        mprops.local_com * factor,
+        mprops.mass() * mass_factor,
+        mprops.principal_inertia() * mass_factor * factor * factor,
+        mprops.principal_inertia_local_frame,
+    )
+}
+
+fn scale_joint(joint: &mut GenericJoint, factor: Real) {
+    joint.local_frame1 = scale_isometry(&joint.local_frame1, factor);
+    joint.local_frame2 = scale_isometry(&joint.local_frame2, factor);
+
+    // The first `DIM` axes are the linear ones.
+    for axis in 0..DIM {
+        joint.limits[axis].min *= factor;
+        joint.limits[axis].max *= factor;
+        joint.motors[axis].target_pos *= factor;
+        joint.motors[axis].target_vel *= factor;
+    }
+}
+
+/// Rescales all the lengths of a physics world by the given factor.
+///
+/// This scales the positions and linear velocities of the rigid-bodies, the shapes and
+/// relative positions of the colliders, the joint anchors and linear limits/motor targets,
+/// and the length unit of the integration parameters. Masses are scaled as if the densities
+/// were preserved: the mass of each collider is recomputed from its current density and
+/// scaled shape, and the additional masses of the rigid-bodies are scaled accordingly.
+///
+/// The gravity must be scaled separately.
+#[wasm_bindgen]
+pub fn scaleWorld(
+    factor: f64,
+    integrationParameters: &mut RawIntegrationParameters,
+    bodies: &mut RawRigidBodySet,
+    colliders: &mut RawColliderSet,
+    impulse_joints: &mut RawImpulseJointSet,
+    multibody_joints: &mut RawMultibodyJointSet,
+) {
+    integrationParameters.0.length_unit *= factor;
+
+    for (_, co) in colliders.0.iter_mut() {
+        let density = co.density();
+
+        if let Some(shape) = co
+            .shape()
+            .scale_dyn(&Vector::repeat(factor), SCALED_SHAPE_SUBDIVISIONS)
+        {
+            co.set_shape(SharedShape(shape.into()));
+        }
+
+        if density.is_finite() {
+            co.set_density(density);
+        }
+
+        match co.position_wrt_parent().copied() {
+            Some(pos) => co.set_position_wrt_parent(scale_isometry(&pos, factor)),
+            None => co.set_position(scale_isometry(co.position(), factor)),
+        }
+
+        co.set_contact_skin(co.contact_skin() * factor);
+    }
+
+    for (_, rb) in bodies.0.iter_mut() {
+        let additional_mprops = rb
+            .mass_properties()
+            .additional_local_mprops
+            .as_deref()
+            .cloned();
+        match additional_mprops {
+            Some(RigidBodyAdditionalMassProps::MassProps(mprops)) => {
+                rb.set_additional_mass_properties(scale_mass_properties(&mprops, factor), false)
+            }
+            Some(RigidBodyAdditionalMassProps::Mass(mass)) => {
+                rb.set_additional_mass(mass * factor.powi(DIM as i32), false)
+            }
+            None => {}
+        }
+
+        rb.set_position(scale_isometry(rb.position(), factor), false);
+        rb.set_linvel(rb.linvel() * factor, false);
+        rb.set_soft_ccd_prediction(rb.soft_ccd_prediction() * factor);
+        rb.recompute_mass_properties_from_colliders(&colliders.0);
+    }
+
+    for (_, joint) in impulse_joints.0.iter_mut() {
+        scale_joint(&mut joint.data, factor);
+    }
+
+    let multibody_handles: Vec<_> = multibody_joints.0.iter().map(|(h, ..)| h).collect();
+    for handle in multibody_handles {
+        if let Some((multibody, link_id)) = multibody_joints.0.get_mut(handle) {
+            if let Some(link) = multibody.link_mut(link_id) {
+                scale_joint(&mut link.joint.data, factor);
+            }
+        }
+    }
+}